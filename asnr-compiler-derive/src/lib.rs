@@ -1,7 +1,11 @@
 extern crate proc_macro;
 
-use asnr_compiler::Framework;
+use std::error::Error;
+
+use asnr_compiler::{Framework, ParserError};
 use proc_macro::TokenStream;
+use proc_macro2::{Group, TokenStream as TokenStream2, TokenTree};
+use quote::ToTokens;
 use syn::{Token, Path, parse::Parse, parse_macro_input, LitStr};
 
 const DUMMY_HEADER: &'static str = r#"DUMMY { dummy(999) header(999)}
@@ -45,28 +49,130 @@ pub fn asn1(input: TokenStream) -> TokenStream {
         }
     );
 
-    let literal_asn1 = match config.asn.value() {
-        v if v.contains("BEGIN") => v,
-        v => String::from(DUMMY_HEADER) + &v + DUMMY_FOOTER
+    let asn = config.asn.value();
+    let (literal_asn1, header_length) = if asn.contains("BEGIN") {
+        (asn.clone(), 0)
+    } else {
+        (String::from(DUMMY_HEADER) + &asn + DUMMY_FOOTER, DUMMY_HEADER.len())
     };
 
-    asnr_compiler::Asnr::new()
+    let crate_root = config.crate_root.map(|path| path.into_token_stream());
+    match asnr_compiler::Asnr::new()
         .add_asn_literal(literal_asn1)
         .framework(framework)
-        .compile_to_string()
-        .map(|(res,_)| { if let Some(path) = config.crate_root {
-            res.replace(
-                "asnr_transcoder", 
-                &path.segments
-                .into_iter()
-                .map(|seg| seg.ident.to_string())
-                .collect::<Vec<String>>()
-                .join("::")
-            )
-        } else {
-            res
-        }})
-        .unwrap()
-        .parse()
-        .unwrap()
-}
\ No newline at end of file
+        .cache_dir(None)
+        .compile_to_tokens_with_diagnostics()
+    {
+        Ok((tokens, _)) => match crate_root {
+            Some(path) => replace_crate_root(tokens, &path).into(),
+            None => tokens.into(),
+        },
+        Err(e) => compile_error(&config.asn, &asn, header_length, e)
+            .to_compile_error()
+            .into(),
+    }
+}
+
+/// Replaces every reference to the `asnr_transcoder` crate with the given path
+fn replace_crate_root(tokens: TokenStream2, crate_root: &TokenStream2) -> TokenStream2 {
+    tokens
+        .into_iter()
+        .flat_map(|token| match token {
+            TokenTree::Ident(ident) if ident == "asnr_transcoder" => crate_root.clone(),
+            TokenTree::Group(group) => {
+                let mut replaced = Group::new(
+                    group.delimiter(),
+                    replace_crate_root(group.stream(), crate_root),
+                );
+                replaced.set_span(group.span());
+                TokenTree::Group(replaced).into()
+            }
+            other => other.into(),
+        })
+        .collect()
+}
+
+/// Converts a compilation failure into an error attached to the ASN1 literal.
+/// Parsing errors point to the position of the failure within the literal,
+/// if the compiler supports locating spans inside of literals.
+fn compile_error(
+    literal: &LitStr,
+    asn: &str,
+    header_length: usize,
+    error: Box<dyn Error>,
+) -> syn::Error {
+    if let Some(parser_error) = error.downcast_ref::<ParserError>() {
+        let offset = parser_error
+            .offset
+            .map(|o| o.saturating_sub(header_length).min(asn.len()));
+        let located = ParserError {
+            offset,
+            ..parser_error.clone()
+        };
+        if let Some(snippet) = located.snippet(asn) {
+            let token = literal.token();
+            let span = offset
+                .and_then(|o| source_offset(&token.to_string(), asn, o))
+                .and_then(|o| token.subspan(o..o + 1))
+                .unwrap_or_else(|| literal.span());
+            return syn::Error::new(
+                span,
+                format!("Failed to parse ASN1 ({:?}) at {snippet}", parser_error.kind),
+            );
+        }
+    }
+    match error.downcast::<syn::Error>() {
+        Ok(syn_error) => *syn_error,
+        Err(e) => syn::Error::new(literal.span(), e),
+    }
+}
+
+/// Maps an offset into the value of a string literal to an offset into the literal's source,
+/// e.g. `r#"..."#`. Returns `None` if the literal contains escape sequences,
+/// since the value then differs from the literal's source.
+fn source_offset(source: &str, value: &str, offset: usize) -> Option<usize> {
+    let content_start = source.find('"')? + 1;
+    (source.get(content_start..content_start + value.len()) == Some(value))
+        .then_some(content_start + offset)
+}
+
+#[cfg(test)]
+mod tests {
+    use proc_macro2::Span;
+    use syn::LitStr;
+
+    use crate::{compile_error, source_offset, DUMMY_FOOTER, DUMMY_HEADER};
+
+    #[test]
+    fn points_to_parsing_failure_in_literal() {
+        let asn = "Valid ::= BOOLEAN\nInvalid ::= SEQUENCE { member }\n";
+        let error = asnr_compiler::Asnr::new()
+            .add_asn_literal(String::from(DUMMY_HEADER) + asn + DUMMY_FOOTER)
+            .compile_to_tokens_with_diagnostics()
+            .unwrap_err();
+        let message = compile_error(
+            &LitStr::new(asn, Span::call_site()),
+            asn,
+            DUMMY_HEADER.len(),
+            error,
+        )
+        .to_string();
+        assert!(message.ends_with(
+            "at line 2, column 1:\nInvalid ::= SEQUENCE { member }\n^"
+        ));
+    }
+
+    #[test]
+    fn maps_literal_offsets_to_the_source() {
+        let asn = "Flag ::= \"BOOLEAN";
+        assert_eq!(
+            source_offset(r##"r#"Flag ::= "BOOLEAN"#"##, asn, 10),
+            Some(13)
+        );
+        assert_eq!(source_offset(r#""Flag ::= \"BOOLEAN""#, asn, 10), None);
+        assert_eq!(
+            source_offset(r#""Flag ::= BOOLEAN""#, "Flag ::= BOOLEAN", 9),
+            Some(10)
+        );
+    }
+}
@@ -0,0 +1,177 @@
+//! The `cache` module stores the Rust representations generated for the top-level declarations
+//! of an ASN1 specification, so that subsequent compilations of the same specification
+//! can skip generating the declarations that did not change.
+//! The cache is written to a single `.asnr_cache.json` file in the cache directory.
+use std::{
+    collections::{hash_map::DefaultHasher, BTreeMap},
+    fs,
+    hash::{Hash, Hasher},
+    io,
+    path::{Path, PathBuf},
+};
+
+use serde_json::{json, Value};
+
+pub const CACHE_FILE_NAME: &str = ".asnr_cache.json";
+
+/// Rust representation generated for a single top-level declaration
+#[derive(Debug, Clone, PartialEq)]
+struct CachedDeclaration {
+    source_hash: String,
+    generated_code_hash: String,
+    generated_code: String,
+}
+
+/// Generated Rust representations keyed by the name of their top-level declaration
+#[derive(Debug, Clone, PartialEq)]
+pub struct GenerationCache {
+    path: PathBuf,
+    /// hash of the compiler settings the cached representations have been generated with
+    settings_hash: String,
+    previous: BTreeMap<String, CachedDeclaration>,
+    current: BTreeMap<String, CachedDeclaration>,
+}
+
+impl GenerationCache {
+    /// Loads the cache from the given directory.
+    /// Cached representations are discarded if they have been generated with different
+    /// settings, or if the cache file is missing or cannot be read.
+    /// * `dir` - the directory containing the cache file
+    /// * `settings` - description of every compiler setting that affects all declarations
+    pub fn load(dir: &Path, settings: &str) -> Self {
+        let path = dir.join(CACHE_FILE_NAME);
+        let settings_hash = hash(settings);
+        let previous = fs::read_to_string(&path)
+            .ok()
+            .and_then(|cache| serde_json::from_str::<Value>(&cache).ok())
+            .filter(|cache| cache["settings_hash"].as_str() == Some(&settings_hash))
+            .and_then(|cache| cache["declarations"].as_object().cloned())
+            .map(|declarations| {
+                declarations
+                    .into_iter()
+                    .filter_map(|(name, declaration)| {
+                        Some((
+                            name,
+                            CachedDeclaration {
+                                source_hash: declaration["source_hash"].as_str()?.to_owned(),
+                                generated_code_hash: declaration["generated_code_hash"]
+                                    .as_str()?
+                                    .to_owned(),
+                                generated_code: declaration["generated_code"].as_str()?.to_owned(),
+                            },
+                        ))
+                    })
+                    .collect()
+            })
+            .unwrap_or_default();
+        Self {
+            path,
+            settings_hash,
+            previous,
+            current: BTreeMap::new(),
+        }
+    }
+
+    /// Returns the cached Rust representation of a declaration,
+    /// if the declaration's source did not change since it has been cached.
+    /// * `name` - the name of the top-level declaration
+    /// * `source` - description of the declaration and its declaration-specific settings
+    pub fn get(&mut self, name: &str, source: &str) -> Option<String> {
+        let cached = self.previous.get(name)?;
+        if cached.source_hash != hash(source)
+            || cached.generated_code_hash != hash(&cached.generated_code)
+        {
+            return None;
+        }
+        self.current.insert(name.to_owned(), cached.clone());
+        Some(cached.generated_code.clone())
+    }
+
+    /// Records the Rust representation generated for a declaration
+    /// * `name` - the name of the top-level declaration
+    /// * `source` - description of the declaration and its declaration-specific settings
+    /// * `generated_code` - the generated Rust representation
+    pub fn insert(&mut self, name: &str, source: &str, generated_code: &str) {
+        self.current.insert(
+            name.to_owned(),
+            CachedDeclaration {
+                source_hash: hash(source),
+                generated_code_hash: hash(generated_code),
+                generated_code: generated_code.to_owned(),
+            },
+        );
+    }
+
+    /// Writes the representations that have been recorded or reused
+    /// during the current compilation to the cache file.
+    /// Representations of declarations that are no longer present are dropped.
+    pub fn write(&self) -> io::Result<()> {
+        let declarations = self
+            .current
+            .iter()
+            .map(|(name, declaration)| {
+                (
+                    name.clone(),
+                    json!({
+                        "source_hash": declaration.source_hash,
+                        "generated_code_hash": declaration.generated_code_hash,
+                        "generated_code": declaration.generated_code,
+                    }),
+                )
+            })
+            .collect::<serde_json::Map<String, Value>>();
+        if let Some(dir) = self.path.parent() {
+            fs::create_dir_all(dir)?;
+        }
+        fs::write(
+            &self.path,
+            json!({
+                "settings_hash": self.settings_hash,
+                "declarations": declarations,
+            })
+            .to_string(),
+        )
+    }
+}
+
+/// Hashes the input with the standard library's default hasher.
+/// The hash is only stable for a given compiler toolchain,
+/// which at worst causes cache misses after a toolchain update.
+fn hash(input: &str) -> String {
+    let mut hasher = DefaultHasher::new();
+    input.hash(&mut hasher);
+    format!("{:016x}", hasher.finish())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn reuses_unchanged_declarations() {
+        let dir = std::env::temp_dir().join("asnr_reuses_unchanged_declarations");
+        let _ = fs::remove_dir_all(&dir);
+        let mut cache = GenerationCache::load(&dir, "Asnr");
+        assert_eq!(cache.get("Test-Int", "INTEGER"), None);
+        cache.insert("Test-Int", "INTEGER", "pub struct TestInt(pub i128);");
+        cache.insert("Test-Bool", "BOOLEAN", "pub struct TestBool(pub bool);");
+        cache.write().unwrap();
+
+        let mut cache = GenerationCache::load(&dir, "Asnr");
+        assert_eq!(
+            cache.get("Test-Int", "INTEGER"),
+            Some(String::from("pub struct TestInt(pub i128);"))
+        );
+        assert_eq!(cache.get("Test-Bool", "BOOLEAN (TRUE)"), None);
+        cache.write().unwrap();
+
+        let mut cache = GenerationCache::load(&dir, "Asnr");
+        assert_eq!(cache.get("Test-Bool", "BOOLEAN"), None);
+        assert!(cache.get("Test-Int", "INTEGER").is_some());
+        assert_eq!(
+            GenerationCache::load(&dir, "Rasn").get("Test-Int", "INTEGER"),
+            None
+        );
+        fs::remove_dir_all(&dir).unwrap();
+    }
+}
@@ -27,6 +27,14 @@ pub enum GeneratorErrorType {
     MissingCustomSyntax,
     SyntaxMismatch,
     MissingClassKey,
+    MissingClassLink,
+    UnsupportedObjectSetKey,
+    IncompatibleDerives,
+    UnnumberedObjectIdentifierArc,
+    UnresolvedValueReference,
+    UnsupportedOutput,
+    UnsupportedConstruct,
+    MixedEncodings,
     Unidentified
 }
 
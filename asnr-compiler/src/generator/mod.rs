@@ -2,36 +2,447 @@
 //! decoding and encoding of the parsed and validated ASN1 data elements.
 //! The `generator` uses string templates for generating rust code. 
 
+use std::{collections::BTreeMap, fmt::Debug, rc::Rc};
+
 use crate::Framework;
-use asnr_grammar::{information_object::*, *};
+pub use asnr_grammar::utils::NamingStrategy;
+use asnr_grammar::{
+    constraints::Constraint,
+    encoding_rules::{bit_length, per_visible::per_visible_range_constraints},
+    information_object::*,
+    utils::to_rust_title_case,
+    *,
+};
 
 pub(crate) mod error;
 pub(crate) mod templates;
 use self::{
-    error::GeneratorError,
+    error::{GeneratorError, GeneratorErrorType},
     templates::{
-        asnr::{builder::AsnrGenerator, template::asnr_imports_and_generic_types},
-        rasn::{builder::RasnGenerator, template::rasn_imports_and_generic_types},
+        asnr::{
+            builder::AsnrGenerator,
+            declarations::Declare,
+            template::{
+                asnr_imports_and_generic_types, asnr_module_imports, tag_constant_template,
+                ASNR_BANNER,
+            },
+            util::format_comments,
+        },
+        generate_boolean_value, generate_string_value,
+        json_schema::JsonSchemaGenerator,
+        rasn::{
+            builder::RasnGenerator,
+            template::{rasn_imports_and_generic_types, rasn_module_imports},
+        },
     },
 };
 
-pub fn imports_and_generic_types(framework: &Framework, custom_derive: Option<&str>, no_std: bool, include_file_headers: bool) -> String {
+/// Generates the imports and generic types of the bindings.
+/// File headers are only generated if a `header` configuration is passed.
+/// * `sources` - the names of the input files listed in the file header
+pub fn imports_and_generic_types(
+    framework: &Framework,
+    custom_derive: Option<&str>,
+    no_std: bool,
+    header: Option<&HeaderConfig>,
+    sources: &[String],
+) -> String {
     match framework {
-        Framework::Asnr => asnr_imports_and_generic_types(custom_derive, no_std, include_file_headers),
-        Framework::Rasn => rasn_imports_and_generic_types(include_file_headers),
+        Framework::Asnr => asnr_imports_and_generic_types(
+            custom_derive,
+            no_std,
+            header.map(|h| h.comments(ASNR_BANNER, sources)),
+        ),
+        Framework::Rasn => {
+            rasn_imports_and_generic_types(no_std, header.map(|h| h.comments("", sources)))
+        }
+        Framework::JsonSchema => String::new(),
+        Framework::Custom(custom) => {
+            header.map_or(String::new(), |h| h.comments("", sources)) + &custom.0.prelude(no_std)
+        }
     }
 }
 
+/// Generates the imports of a module file whose generic types are declared
+/// in a shared sibling module
+pub fn module_imports(framework: &Framework, no_std: bool) -> String {
+    match framework {
+        Framework::Asnr => asnr_module_imports(no_std, true),
+        Framework::Rasn => rasn_module_imports(no_std),
+        Framework::JsonSchema => String::new(),
+        Framework::Custom(_) => String::from("use super::*;\n"),
+    }
+}
+
+/// Backend generating the bindings of a custom framework, e.g. for a different codec crate.
+/// See `Asnr::custom_framework`.
 pub trait Generator {
-    fn generate_choice_value(tld: ToplevelValueDeclaration) -> Result<String, GeneratorError>;
+    /// Generates the imports and generic types that precede the generated declarations
+    /// * `no_std` - whether the bindings need to support `no_std` environments
+    fn prelude(&self, no_std: bool) -> String;
+
+    /// Generates the representation of a validated top-level declaration
+    /// * `tld` - the top-level declaration
+    /// * `custom_derive` - comma-separated derives configured for the declaration, if any
+    /// * `options` - options that influence the generated representations
+    fn generate(
+        &self,
+        tld: ToplevelDeclaration,
+        custom_derive: Option<&str>,
+        options: &GeneratorOptions,
+    ) -> Result<String, GeneratorError>;
+}
+
+/// A custom `Generator` backend selected as framework.
+/// Two custom frameworks are equal if they share the same backend.
+#[derive(Clone)]
+pub struct CustomFramework(pub Rc<dyn Generator>);
+
+impl Debug for CustomFramework {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str("CustomFramework")
+    }
+}
+
+impl PartialEq for CustomFramework {
+    fn eq(&self, other: &Self) -> bool {
+        Rc::ptr_eq(&self.0, &other.0)
+    }
+}
+
+/// Options that influence the Rust representations generated for ASN1 types
+#[derive(Debug, Clone, PartialEq)]
+pub struct GeneratorOptions {
+    /// Represent every INTEGER with the framework's widest integer type,
+    /// regardless of its constraints
+    pub force_i128_integers: bool,
+    /// Implement `Default` for SEQUENCEs with DEFAULT members by populating
+    /// these members with their DEFAULT values
+    pub materialize_defaults: bool,
+    /// Represent inline primitive SEQUENCE members by plain Rust types
+    /// instead of inner newtypes
+    pub flatten_primitive_members: bool,
+    /// Generate accessors for the values contained in OCTET STRING members
+    /// with a CONTAINING constraint
+    pub contained_type_accessors: bool,
+    /// Mark the enums generated for extensible ENUMERATEDs and CHOICEs as `#[non_exhaustive]`
+    pub mark_extensible_as_non_exhaustive: bool,
+    /// Naming of the types generated for inline and anonymous ASN1 types
+    pub naming: NamingConfig,
+    /// UPER-encoded sizes in bits of the top-level types whose encodings have a fixed size.
+    /// See `fixed_encoded_sizes`.
+    pub encoded_sizes: BTreeMap<String, usize>,
+}
+
+impl Default for GeneratorOptions {
+    fn default() -> Self {
+        Self {
+            force_i128_integers: false,
+            materialize_defaults: false,
+            flatten_primitive_members: false,
+            contained_type_accessors: false,
+            mark_extensible_as_non_exhaustive: true,
+            naming: NamingConfig::default(),
+            encoded_sizes: BTreeMap::new(),
+        }
+    }
+}
+
+/// Controls the names of the types that the generator declares for
+/// inline types of SEQUENCE members and CHOICE options,
+/// and for the anonymous member types of SEQUENCE OFs
+#[derive(Debug, Clone, PartialEq)]
+pub struct NamingConfig {
+    /// Prefix of the types generated for inline types.
+    /// If `None`, the framework's default is used, i.e. `Inner` for `asnr` and no prefix for `rasn`.
+    pub inner_prefix: Option<String>,
+    /// Prefix of the types generated for anonymous SEQUENCE OF members
+    pub anonymous_prefix: String,
+    /// Abbreviate the name of the parent type to its capital letters and digits,
+    /// e.g. `InnerExtendedSequence` becomes `IES`.
+    /// Abbreviated names are suffixed with a hash of the full name to keep them unique.
+    pub abbreviate_parent_names: bool,
+    /// Maximum length of a generated type name.
+    /// Longer names are truncated and suffixed with a hash of the full name.
+    pub max_length: Option<usize>,
+    /// Naming strategy for the variants generated for ENUMERATED enumerals
+    pub enumerals: NamingStrategy,
+}
+
+impl Default for NamingConfig {
+    fn default() -> Self {
+        Self {
+            inner_prefix: None,
+            anonymous_prefix: String::from("Anonymous"),
+            abbreviate_parent_names: false,
+            max_length: None,
+            enumerals: NamingStrategy::default(),
+        }
+    }
+}
+
+/// Controls the comments at the top of generated files,
+/// e.g. to add a license banner or to list the ASN1 sources the bindings were generated from.
+/// By default, only the framework's banner is generated, so that the output is reproducible.
+#[derive(Debug, Clone, PartialEq, Default)]
+pub struct HeaderConfig {
+    /// Banner replacing the framework's default banner.
+    /// Lines that are not comments yet are emitted as line comments.
+    pub banner: Option<String>,
+    /// List the names of the input files
+    pub list_sources: bool,
+    /// Name the version of the compiler that generated the bindings
+    pub include_version: bool,
+    /// Add the time of generation in seconds since the UNIX epoch.
+    /// Timestamps make the generated output differ between otherwise identical builds.
+    pub include_timestamp: bool,
+}
+
+impl HeaderConfig {
+    /// Renders the header comments of a generated file
+    /// * `default_banner` - the framework's banner used if no banner is configured
+    /// * `sources` - the names of the input files
+    pub fn comments(&self, default_banner: &str, sources: &[String]) -> String {
+        let mut comments = match &self.banner {
+            Some(banner) => banner
+                .lines()
+                .map(|line| match line.trim_start().starts_with("//") {
+                    true => format!("{line}\n"),
+                    false => format!("// {line}\n"),
+                })
+                .collect::<String>(),
+            None => default_banner.to_owned(),
+        };
+        if self.list_sources && !sources.is_empty() {
+            comments += &format!("// Generated from: {}\n", sources.join(", "));
+        }
+        if self.include_version {
+            comments += &format!(
+                "// Generated by asnr-compiler {}\n",
+                env!("CARGO_PKG_VERSION")
+            );
+        }
+        if self.include_timestamp {
+            let seconds = std::time::SystemTime::now()
+                .duration_since(std::time::UNIX_EPOCH)
+                .map_or(0, |d| d.as_secs());
+            comments += &format!("// Generated at: {seconds}\n");
+        }
+        comments
+    }
+}
+
+/// Standard derives of the generated types that can be configured.
+/// Derives that the frameworks rely on, such as `Default` for `asnr`
+/// or `AsnType` for `rasn`, are always generated.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub enum Derive {
+    Debug,
+    Clone,
+    PartialEq,
+    Eq,
+    Hash,
+    PartialOrd,
+    Ord,
+}
+
+impl Derive {
+    pub const ALL: [Derive; 7] = [
+        Derive::Debug,
+        Derive::Clone,
+        Derive::PartialEq,
+        Derive::Eq,
+        Derive::Hash,
+        Derive::PartialOrd,
+        Derive::Ord,
+    ];
+
+    pub fn name(&self) -> &'static str {
+        match self {
+            Derive::Debug => "Debug",
+            Derive::Clone => "Clone",
+            Derive::PartialEq => "PartialEq",
+            Derive::Eq => "Eq",
+            Derive::Hash => "Hash",
+            Derive::PartialOrd => "PartialOrd",
+            Derive::Ord => "Ord",
+        }
+    }
+}
+
+/// Checks that a configured set of derives can be implemented by the generated types
+/// * `derives` - the configured derives
+/// * `framework` - the framework that the types are generated for
+/// * `tlds` - the top-level declarations that are generated
+pub fn validate_derives(
+    derives: &[Derive],
+    framework: &Framework,
+    tlds: &[ToplevelDeclaration],
+) -> Result<(), GeneratorError> {
+    let requires = |derive: Derive, required: Derive| {
+        if derives.contains(&derive) && !derives.contains(&required) {
+            Err(GeneratorError::new(
+                None,
+                &format!("Deriving {derive:?} requires deriving {required:?} as well"),
+                GeneratorErrorType::IncompatibleDerives,
+            ))
+        } else {
+            Ok(())
+        }
+    };
+    if !derives.contains(&Derive::Clone) {
+        return Err(GeneratorError::new(
+            None,
+            "The generated types must derive Clone",
+            GeneratorErrorType::IncompatibleDerives,
+        ));
+    }
+    if framework == &Framework::Asnr && !derives.contains(&Derive::Debug) {
+        return Err(GeneratorError::new(
+            None,
+            "The asnr encoders rely on Debug, so the generated types must derive Debug",
+            GeneratorErrorType::IncompatibleDerives,
+        ));
+    }
+    requires(Derive::Eq, Derive::PartialEq)?;
+    requires(Derive::PartialOrd, Derive::PartialEq)?;
+    requires(Derive::Ord, Derive::Eq)?;
+    requires(Derive::Ord, Derive::PartialOrd)?;
+    if let Some(derive) = [Derive::Eq, Derive::Hash, Derive::Ord]
+        .into_iter()
+        .find(|d| derives.contains(d))
+    {
+        if let Some(tld) = tlds.iter().find(|tld| match tld {
+            ToplevelDeclaration::Type(t) => contains_real(&t.r#type),
+            _ => false,
+        }) {
+            return Err(GeneratorError::new(
+                Some(tld.clone()),
+                &format!("REAL values are represented by f64, which cannot derive {derive:?}"),
+                GeneratorErrorType::IncompatibleDerives,
+            ));
+        }
+    }
+    Ok(())
+}
+
+/// Collects the UPER-encoded sizes in bits of the top-level types whose encodings have a fixed size.
+/// Since the sizes of referencing types depend on the sizes of the referenced types,
+/// the declarations are traversed until no further sizes can be determined.
+pub fn fixed_encoded_sizes(tlds: &[ToplevelDeclaration]) -> BTreeMap<String, usize> {
+    let mut sizes = BTreeMap::new();
+    loop {
+        let mut found_size = false;
+        for tld in tlds {
+            if let ToplevelDeclaration::Type(t) = tld {
+                if sizes.contains_key(&t.name) {
+                    continue;
+                }
+                if let Some(size) = fixed_encoded_size(&t.r#type, &sizes) {
+                    sizes.insert(t.name.clone(), size);
+                    found_size = true;
+                }
+            }
+        }
+        if !found_size {
+            return sizes;
+        }
+    }
+}
+
+/// Returns the UPER-encoded size in bits of a type, if every value of the type
+/// is encoded with the same number of bits.
+/// * `sizes` - the fixed sizes of the top-level types that have been determined so far
+fn fixed_encoded_size(r#type: &ASN1Type, sizes: &BTreeMap<String, usize>) -> Option<usize> {
+    let fixed_range = |signed: bool, constraints: &Vec<Constraint>| {
+        per_visible_range_constraints(signed, constraints)
+            .ok()
+            .filter(|range| !range.is_extensible())
+    };
+    let fixed_size = |constraints: &Vec<Constraint>| {
+        fixed_range(false, constraints)
+            .filter(|range| range.is_size_constraint())
+            .and_then(|range| range.min::<usize>().filter(|min| Some(*min) == range.max()))
+            // larger sizes are fragmented
+            .filter(|size| *size < 65536)
+    };
+    match r#type {
+        ASN1Type::Null => Some(0),
+        ASN1Type::Boolean => Some(1),
+        ASN1Type::Integer(i) => fixed_range(true, &i.constraints)?.bit_length(),
+        ASN1Type::Enumerated(e) if e.extensible.is_none() => {
+            Some(bit_length(0, e.members.len() as i128 - 1))
+        }
+        ASN1Type::BitString(b) => fixed_size(&b.constraints),
+        ASN1Type::OctetString(o) => fixed_size(&o.constraints).map(|size| 8 * size),
+        ASN1Type::Sequence(s) | ASN1Type::Set(s) if s.extensible.is_none() => s
+            .members
+            .iter()
+            .map(|m| {
+                if m.is_optional || m.default_value.is_some() || !m.constraints.is_empty() {
+                    None
+                } else {
+                    fixed_encoded_size(&m.r#type, sizes)
+                }
+            })
+            .sum(),
+        ASN1Type::SequenceOf(s) | ASN1Type::SetOf(s) => {
+            Some(fixed_size(&s.constraints)? * fixed_encoded_size(&s.r#type, sizes)?)
+        }
+        ASN1Type::ElsewhereDeclaredType(e) if e.constraints.is_empty() => {
+            sizes.get(&e.identifier).copied()
+        }
+        _ => None,
+    }
+}
+
+fn contains_real(r#type: &ASN1Type) -> bool {
+    match r#type {
+        ASN1Type::Real(_) => true,
+        ASN1Type::Sequence(s) | ASN1Type::Set(s) => s.all_members().any(|m| contains_real(&m.r#type)),
+        ASN1Type::Choice(c) => c.options.iter().any(|o| contains_real(&o.r#type)),
+        ASN1Type::SequenceOf(s) | ASN1Type::SetOf(s) => contains_real(&s.r#type),
+        _ => false,
+    }
+}
+
+/// Generates a type alias for a top-level type declaration
+/// whose Rust representation has been overridden
+/// * `tld` - the top-level type declaration to be overridden
+/// * `rust_type` - the Rust type replacing the generated representation
+pub fn generate_type_override(tld: &ToplevelTypeDeclaration, rust_type: &str) -> String {
+    format!(
+        "{}pub type {} = {rust_type};\n",
+        format_comments(&tld.comments),
+        to_rust_title_case(&tld.name)
+    )
+}
+
+pub trait DeclarationGenerator {
+    fn generate_choice_value(
+        tld: ToplevelValueDeclaration,
+        options: &GeneratorOptions,
+    ) -> Result<String, GeneratorError>;
     fn generate_octet_string<'a>(
         tld: ToplevelTypeDeclaration,
         custom_derive: Option<&'a str>,
     ) -> Result<String, GeneratorError>;
     fn generate_integer_value<'a>(tld: ToplevelValueDeclaration) -> Result<String, GeneratorError>;
+    fn generate_boolean_value(tld: ToplevelValueDeclaration) -> Result<String, GeneratorError>;
+    fn generate_bit_string_value(tld: ToplevelValueDeclaration) -> Result<String, GeneratorError>;
+    fn generate_string_value(tld: ToplevelValueDeclaration) -> Result<String, GeneratorError>;
+    fn generate_enumerated_value(
+        tld: ToplevelValueDeclaration,
+        options: &GeneratorOptions,
+    ) -> Result<String, GeneratorError>;
+    fn generate_real_value(tld: ToplevelValueDeclaration) -> Result<String, GeneratorError>;
+    fn generate_object_identifier_value(
+        tld: ToplevelValueDeclaration,
+    ) -> Result<String, GeneratorError>;
     fn generate_integer<'a>(
         tld: ToplevelTypeDeclaration,
         custom_derive: Option<&'a str>,
+        options: &GeneratorOptions,
     ) -> Result<String, GeneratorError>;
     fn generate_bit_string<'a>(
         tld: ToplevelTypeDeclaration,
@@ -54,79 +465,154 @@ pub trait Generator {
         tld: ToplevelTypeDeclaration,
         custom_derive: Option<&'a str>,
     ) -> Result<String, GeneratorError>;
+    fn generate_object_identifier(
+        tld: ToplevelTypeDeclaration,
+        custom_derive: Option<&str>,
+    ) -> Result<String, GeneratorError>;
+    fn generate_real(
+        tld: ToplevelTypeDeclaration,
+        custom_derive: Option<&str>,
+    ) -> Result<String, GeneratorError>;
     fn generate_enumerated<'a>(
         tld: ToplevelTypeDeclaration,
         custom_derive: Option<&'a str>,
+        options: &GeneratorOptions,
     ) -> Result<String, GeneratorError>;
     fn generate_choice<'a>(
         tld: ToplevelTypeDeclaration,
         custom_derive: Option<&'a str>,
+        options: &GeneratorOptions,
     ) -> Result<String, GeneratorError>;
     fn generate_information_object_class<'a>(
         tld: ToplevelInformationDeclaration,
     ) -> Result<String, GeneratorError>;
-    fn generate_sequence<'a>(
+    fn generate_information_object_set(
+        tld: ToplevelInformationDeclaration,
+        custom_derive: Option<&str>,
+        options: &GeneratorOptions,
+    ) -> Result<String, GeneratorError>;
+    fn generate_sequence_or_set(
         tld: ToplevelTypeDeclaration,
-        custom_derive: Option<&'a str>,
+        custom_derive: Option<&str>,
+        options: &GeneratorOptions,
     ) -> Result<String, GeneratorError>;
     fn generate_sequence_of<'a>(
         tld: ToplevelTypeDeclaration,
         custom_derive: Option<&'a str>,
+        options: &GeneratorOptions,
     ) -> Result<String, GeneratorError>;
     fn generate_sequence_value(tld: ToplevelValueDeclaration) -> Result<String, GeneratorError>;
 }
 
-pub fn generate<'a>(
+/// Generates the representation of a top-level declaration in the given framework.
+/// Errors that do not name a declaration yet are attributed to the generated declaration.
+pub fn generate(
     framework: &Framework,
     tld: ToplevelDeclaration,
     custom_derive: Option<&str>,
+    options: &GeneratorOptions,
+) -> Result<std::string::String, GeneratorError> {
+    let declaration = tld.clone();
+    generate_in_framework(framework, tld, custom_derive, options).map_err(|mut e| {
+        e.top_level_declaration.get_or_insert(declaration);
+        e
+    })
+}
+
+/// The built-in frameworks represent extension addition groups as members
+/// of the SEQUENCE or SET that they are nested in
+fn inline_extension_groups(r#type: &mut ASN1Type) {
+    match r#type {
+        ASN1Type::Sequence(s) | ASN1Type::Set(s) => {
+            s.inline_extension_groups();
+            s.members
+                .iter_mut()
+                .for_each(|m| inline_extension_groups(&mut m.r#type));
+        }
+        ASN1Type::Choice(c) => c
+            .options
+            .iter_mut()
+            .for_each(|o| inline_extension_groups(&mut o.r#type)),
+        ASN1Type::SequenceOf(s) | ASN1Type::SetOf(s) => inline_extension_groups(&mut s.r#type),
+        _ => (),
+    }
+}
+
+fn generate_in_framework(
+    framework: &Framework,
+    mut tld: ToplevelDeclaration,
+    custom_derive: Option<&str>,
+    options: &GeneratorOptions,
 ) -> Result<std::string::String, GeneratorError> {
+    match (framework, &mut tld) {
+        (Framework::Custom(_), _) => (),
+        (_, ToplevelDeclaration::Type(t)) => inline_extension_groups(&mut t.r#type),
+        (_, ToplevelDeclaration::Value(_) | ToplevelDeclaration::Information(_)) => (),
+    }
     match framework {
         Framework::Asnr => {
             match tld {
-                ToplevelDeclaration::Type(t) => match t.r#type {
-                    ASN1Type::Null => AsnrGenerator::generate_null(t, custom_derive),
-                    ASN1Type::Boolean => AsnrGenerator::generate_boolean(t, custom_derive),
-                    ASN1Type::Integer(_) => AsnrGenerator::generate_integer(t, custom_derive),
-                    ASN1Type::Enumerated(_) => AsnrGenerator::generate_enumerated(t, custom_derive),
-                    ASN1Type::BitString(_) => AsnrGenerator::generate_bit_string(t, custom_derive),
-                    ASN1Type::CharacterString(_) => {
-                        AsnrGenerator::generate_character_string(t, custom_derive)
-                    }
-                    ASN1Type::Sequence(_) => AsnrGenerator::generate_sequence(t, custom_derive),
-                    ASN1Type::SequenceOf(_) => {
-                        AsnrGenerator::generate_sequence_of(t, custom_derive)
-                    }
-                    ASN1Type::Choice(_) => AsnrGenerator::generate_choice(t, custom_derive),
-                    ASN1Type::ElsewhereDeclaredType(_) => {
-                        AsnrGenerator::generate_typealias(t, custom_derive)
+                ToplevelDeclaration::Type(t) => {
+                    let tag_constant = t.tag.as_ref().map_or(String::new(), |tag| {
+                        tag_constant_template(to_rust_title_case(&t.name), tag.declare())
+                    });
+                    match t.r#type {
+                        ASN1Type::Null => AsnrGenerator::generate_null(t, custom_derive),
+                        ASN1Type::Boolean => AsnrGenerator::generate_boolean(t, custom_derive),
+                        ASN1Type::Integer(_) => AsnrGenerator::generate_integer(t, custom_derive, options),
+                        ASN1Type::Enumerated(_) => AsnrGenerator::generate_enumerated(t, custom_derive, options),
+                        ASN1Type::BitString(_) => AsnrGenerator::generate_bit_string(t, custom_derive),
+                        ASN1Type::CharacterString(_) => {
+                            AsnrGenerator::generate_character_string(t, custom_derive)
+                        }
+                        ASN1Type::Sequence(_) | ASN1Type::Set(_) => AsnrGenerator::generate_sequence_or_set(t, custom_derive, options),
+                        ASN1Type::SequenceOf(_) => {
+                            AsnrGenerator::generate_sequence_of(t, custom_derive, options)
+                        }
+                        ASN1Type::Choice(_) => AsnrGenerator::generate_choice(t, custom_derive, options),
+                        ASN1Type::ElsewhereDeclaredType(_) => {
+                            AsnrGenerator::generate_typealias(t, custom_derive)
+                        }
+                        ASN1Type::OctetString(_) => {
+                            AsnrGenerator::generate_octet_string(t, custom_derive)
+                        }
+                        ASN1Type::ObjectIdentifier(_) => {
+                            AsnrGenerator::generate_object_identifier(t, custom_derive)
+                        }
+                        ASN1Type::Real(_) => AsnrGenerator::generate_real(t, custom_derive),
+                        _ => Err(unsupported_type(framework, t)),
                     }
-                    ASN1Type::OctetString(_) => {
-                        AsnrGenerator::generate_octet_string(t, custom_derive)
-                    }
-                    _ => Ok("".into()),
-                },
+                    .map(|rust| if rust.is_empty() { rust } else { rust + &tag_constant })
+                }
                 ToplevelDeclaration::Value(v) => match v.value {
                     ASN1Value::Null => AsnrGenerator::generate_null_value(v),
-                    ASN1Value::Boolean(_) => todo!(),
+                    ASN1Value::Boolean(_) => AsnrGenerator::generate_boolean_value(v),
+                    ASN1Value::BitString(_) => AsnrGenerator::generate_bit_string_value(v),
                     ASN1Value::Integer(_) => AsnrGenerator::generate_integer_value(v),
-                    ASN1Value::String(_) => todo!(),
-                    ASN1Value::BitString(_) => todo!(),
-                    ASN1Value::EnumeratedValue { enumerated: _, enumerable: _ } => todo!(),
-                    ASN1Value::ElsewhereDeclaredValue(_) => todo!(),
-                    ASN1Value::All => todo!(),
-                    ASN1Value::Choice(_, _) => AsnrGenerator::generate_choice_value(v),
+                    ASN1Value::String(_) => AsnrGenerator::generate_string_value(v),
+                    ASN1Value::EnumeratedValue { .. } => {
+                        AsnrGenerator::generate_enumerated_value(v, options)
+                    }
+                    ASN1Value::ElsewhereDeclaredValue(ref id) => Err(GeneratorError::new(
+                        Some(ToplevelDeclaration::Value(v.clone())),
+                        &format!("Failed to resolve the value reference {id} of {}", v.name),
+                        GeneratorErrorType::UnresolvedValueReference,
+                    )),
+                    ASN1Value::Choice(_, _) => AsnrGenerator::generate_choice_value(v, options),
                     ASN1Value::Sequence(_) => AsnrGenerator::generate_sequence_value(v),
-                    ASN1Value::Real(_) => todo!(),
-                    ASN1Value::ObjectIdentifier(_) => todo!()
+                    ASN1Value::Real(_) => AsnrGenerator::generate_real_value(v),
+                    ASN1Value::ObjectIdentifier(_) => {
+                        AsnrGenerator::generate_object_identifier_value(v)
+                    }
+                    _ => Err(unsupported_value(framework, v)),
                 },
                 ToplevelDeclaration::Information(i) => match i.value {
                     ASN1Information::ObjectClass(_) => {
                         AsnrGenerator::generate_information_object_class(i)
                     }
-                    // ASN1Information::ObjectSet(_) => {
-                    //   generate_information_object_set(i)
-                    // }
+                    ASN1Information::ObjectSet(_) => {
+                        AsnrGenerator::generate_information_object_set(i, custom_derive, options)
+                    }
                     _ => Ok("".into()),
                 },
             }
@@ -135,51 +621,100 @@ pub fn generate<'a>(
             ToplevelDeclaration::Type(t) => match t.r#type {
                 ASN1Type::Null => RasnGenerator::generate_null(t, custom_derive),
                 ASN1Type::Boolean => RasnGenerator::generate_boolean(t, custom_derive),
-                ASN1Type::Integer(_) => RasnGenerator::generate_integer(t, custom_derive),
-                ASN1Type::Enumerated(_) => RasnGenerator::generate_enumerated(t, custom_derive),
+                ASN1Type::Integer(_) => RasnGenerator::generate_integer(t, custom_derive, options),
+                ASN1Type::Enumerated(_) => RasnGenerator::generate_enumerated(t, custom_derive, options),
                 ASN1Type::BitString(_) => RasnGenerator::generate_bit_string(t, custom_derive),
                 ASN1Type::CharacterString(_) => {
                     RasnGenerator::generate_character_string(t, custom_derive)
                 }
                 ASN1Type::Sequence(_) | ASN1Type::Set(_) => {
-                    RasnGenerator::generate_sequence_or_set(t, custom_derive)
+                    RasnGenerator::generate_sequence_or_set(t, custom_derive, options)
                 }
-                ASN1Type::SequenceOf(_) => {
-                    RasnGenerator::generate_sequence_of(t, custom_derive)
+                ASN1Type::SequenceOf(_) | ASN1Type::SetOf(_) => {
+                    RasnGenerator::generate_sequence_of(t, custom_derive, options)
                 }
                 ASN1Type::ElsewhereDeclaredType(_) => {
                     RasnGenerator::generate_typealias(t, custom_derive)
                 }
-                ASN1Type::Choice(_) => RasnGenerator::generate_choice(t, custom_derive),
+                ASN1Type::Choice(_) => RasnGenerator::generate_choice(t, custom_derive, options),
                 ASN1Type::OctetString(_) => {
                     RasnGenerator::generate_octet_string(t, custom_derive)
                 }
-                _ => Ok("".into()),
+                _ => Err(unsupported_type(framework, t)),
             },
             ToplevelDeclaration::Value(v) => match v.value {
                 ASN1Value::Null => RasnGenerator::generate_null_value(v),
-                ASN1Value::Boolean(_) => todo!(),
+                ASN1Value::Boolean(_) => generate_boolean_value(v),
+                ASN1Value::BitString(_) => RasnGenerator::generate_bit_string_value(v),
                 ASN1Value::Integer(_) => RasnGenerator::generate_integer_value(v),
-                ASN1Value::String(_) => todo!(),
-                ASN1Value::BitString(_) => todo!(),
-                ASN1Value::EnumeratedValue { enumerated: _, enumerable: _ }  => todo!(),
-                ASN1Value::ElsewhereDeclaredValue(_) => todo!(),
-                ASN1Value::All => todo!(),
-                // ASN1Value::Choice(_, _) => RasnGenerator::generate_choice_value(v),
-                // ASN1Value::Sequence(_) => RasnGenerator::generate_sequence_value(v),
-                ASN1Value::Real(_) => todo!(),
+                // rasn's string types are converted from literals
+                ASN1Value::String(_) => generate_string_value(v, "try_into().unwrap()"),
+                ASN1Value::Choice(_, _) => RasnGenerator::generate_choice_value(v, options),
+                ASN1Value::Sequence(_) => RasnGenerator::generate_sequence_value(v, options),
                 ASN1Value::ObjectIdentifier(_) => RasnGenerator::generate_object_identifier_value(v),
-                _ => Ok("".into()),
+                _ => Err(unsupported_value(framework, v)),
             },
             ToplevelDeclaration::Information(i) => match i.value {
-                // ASN1Information::ObjectClass(_) => {
-                //     RasnGenerator::generate_information_object_class(i)
-                // }
-                // ASN1Information::ObjectSet(_) => {
-                //   generate_information_object_set(i)
-                // }
+                ASN1Information::ObjectSet(_) => {
+                    RasnGenerator::generate_information_object_set(i, custom_derive, options)
+                }
+                // classes and objects are represented by the object sets that they make up
                 _ => Ok("".into()),
             },
         },
+        Framework::JsonSchema => JsonSchemaGenerator::generate_definition(tld),
+        Framework::Custom(custom) => custom.0.generate(tld, custom_derive, options),
     }
 }
+
+/// Reports a type declaration that the framework cannot represent yet
+fn unsupported_type(framework: &Framework, tld: ToplevelTypeDeclaration) -> GeneratorError {
+    let construct = match &tld.r#type {
+        ASN1Type::Null => "NULL",
+        ASN1Type::Boolean => "BOOLEAN",
+        ASN1Type::Integer(_) => "INTEGER",
+        ASN1Type::Real(_) => "REAL",
+        ASN1Type::BitString(_) => "BIT STRING",
+        ASN1Type::OctetString(_) => "OCTET STRING",
+        ASN1Type::CharacterString(_) => "character string",
+        ASN1Type::Enumerated(_) => "ENUMERATED",
+        ASN1Type::Choice(_) => "CHOICE",
+        ASN1Type::Sequence(_) => "SEQUENCE",
+        ASN1Type::SequenceOf(_) => "SEQUENCE OF",
+        ASN1Type::Set(_) => "SET",
+        ASN1Type::SetOf(_) => "SET OF",
+        ASN1Type::ObjectIdentifier(_) => "OBJECT IDENTIFIER",
+        ASN1Type::ElsewhereDeclaredType(_) => "type reference",
+        ASN1Type::InformationObjectFieldReference(_) => "information object field reference",
+    };
+    GeneratorError::new(
+        Some(ToplevelDeclaration::Type(tld)),
+        &format!("The {framework:?} framework does not support {construct} types yet"),
+        GeneratorErrorType::UnsupportedConstruct,
+    )
+}
+
+/// Reports a value declaration that the framework cannot represent yet
+fn unsupported_value(framework: &Framework, tld: ToplevelValueDeclaration) -> GeneratorError {
+    let construct = match &tld.value {
+        ASN1Value::All => "ALL",
+        ASN1Value::Null => "NULL",
+        ASN1Value::Boolean(_) => "BOOLEAN",
+        ASN1Value::Choice(_, _) => "CHOICE",
+        ASN1Value::Sequence(_) => "SEQUENCE",
+        ASN1Value::Integer(_) => "INTEGER",
+        ASN1Value::Real(_) => "REAL",
+        ASN1Value::String(_) => "string",
+        ASN1Value::BitString(_) => "BIT STRING",
+        ASN1Value::EnumeratedValue { .. } => "ENUMERATED",
+        ASN1Value::ElsewhereDeclaredValue(_) => "value reference",
+        ASN1Value::ObjectIdentifier(_) => "OBJECT IDENTIFIER",
+        ASN1Value::LinkedNestedValue { .. } => "nested",
+        ASN1Value::Optional(_) => "OPTIONAL member",
+    };
+    GeneratorError::new(
+        Some(ToplevelDeclaration::Value(tld)),
+        &format!("The {framework:?} framework does not support {construct} values yet"),
+        GeneratorErrorType::UnsupportedConstruct,
+    )
+}
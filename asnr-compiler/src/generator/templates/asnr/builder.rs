@@ -1,8 +1,10 @@
 use crate::{
-    generator::Generator,
+    generator::DeclarationGenerator,
     generator::{
         error::{GeneratorError, GeneratorErrorType},
         generate,
+        templates::{self, anonymous_name, append_derives, derives_serde},
+        GeneratorOptions,
     },
     Framework,
 };
@@ -13,11 +15,106 @@ use super::{template::*, util::*, declarations::Declare};
 pub struct StringifiedNameType {
     pub name: String,
     pub r#type: String,
+    /// The primitive ASN1 type of a member that is represented by a plain Rust type
+    /// rather than an inner newtype
+    pub flattened: Option<ASN1Type>,
+    /// The information object set that the type of an open type member is looked up in,
+    /// and the expression of the lookup key relative to the SEQUENCE, e.g. `container_id.0`
+    pub object_set_lookup: Option<(String, String)>,
 }
 
 pub struct AsnrGenerator;
 
-impl Generator for AsnrGenerator {
+impl DeclarationGenerator for AsnrGenerator {
+    fn generate_object_identifier_value(
+        tld: ToplevelValueDeclaration,
+    ) -> Result<String, GeneratorError> {
+        if let ASN1Value::ObjectIdentifier(ref oid) = tld.value {
+            let arcs = oid
+                .0
+                .iter()
+                .map(|arc| arc.number.and_then(|n| u32::try_from(n).ok()))
+                .collect::<Option<Vec<u32>>>();
+            match arcs {
+                Some(arcs) => Ok(object_identifier_value_template(
+                    format_comments(&tld.comments),
+                    to_rust_const_case(&tld.name),
+                    arcs.iter()
+                        .map(u32::to_string)
+                        .collect::<Vec<String>>()
+                        .join(", "),
+                )),
+                None => Err(GeneratorError::new(
+                    Some(ToplevelDeclaration::Value(tld)),
+                    "Every arc of an OBJECT IDENTIFIER value must have a number that fits into u32",
+                    GeneratorErrorType::UnnumberedObjectIdentifierArc,
+                )),
+            }
+        } else {
+            Err(GeneratorError::new(
+                Some(ToplevelDeclaration::Value(tld)),
+                "Expected OBJECT IDENTIFIER top-level declaration",
+                GeneratorErrorType::Asn1TypeMismatch,
+            ))
+        }
+    }
+
+    fn generate_string_value(tld: ToplevelValueDeclaration) -> Result<String, GeneratorError> {
+        templates::generate_string_value(tld, "into()")
+    }
+
+    fn generate_bit_string_value(tld: ToplevelValueDeclaration) -> Result<String, GeneratorError> {
+        if let ASN1Value::BitString(ref bits) = tld.value {
+            // BIT STRING types are backed by a Vec<bool>,
+            // so values are declared as constant slices of their bits
+            Ok(bit_string_value_template(
+                format_comments(&tld.comments),
+                to_rust_const_case(&tld.name),
+                bits.iter()
+                    .map(bool::to_string)
+                    .collect::<Vec<String>>()
+                    .join(", "),
+            ))
+        } else {
+            Err(GeneratorError::new(
+                Some(ToplevelDeclaration::Value(tld)),
+                "Expected BIT STRING value top-level declaration",
+                GeneratorErrorType::Asn1TypeMismatch,
+            ))
+        }
+    }
+
+    fn generate_boolean_value(tld: ToplevelValueDeclaration) -> Result<String, GeneratorError> {
+        templates::generate_boolean_value(tld)
+    }
+
+    fn generate_real_value(tld: ToplevelValueDeclaration) -> Result<String, GeneratorError> {
+        if let ASN1Value::Real(r) = tld.value {
+            if tld.type_name == REAL {
+                Ok(real_value_template(
+                    format_comments(&tld.comments),
+                    to_rust_const_case(&tld.name),
+                    "f64",
+                    format!("{r:?}"),
+                ))
+            } else {
+                let type_name = to_rust_title_case(&tld.type_name);
+                Ok(real_value_template(
+                    format_comments(&tld.comments),
+                    to_rust_const_case(&tld.name),
+                    &type_name,
+                    format!("{type_name}({r:?})"),
+                ))
+            }
+        } else {
+            Err(GeneratorError::new(
+                Some(ToplevelDeclaration::Value(tld)),
+                "Expected REAL value top-level declaration",
+                GeneratorErrorType::Asn1TypeMismatch,
+            ))
+        }
+    }
+
     fn generate_integer_value(tld: ToplevelValueDeclaration) -> Result<String, GeneratorError> {
         if let ASN1Value::Integer(i) = tld.value {
             if tld.type_name == INTEGER {
@@ -47,16 +144,26 @@ impl Generator for AsnrGenerator {
     fn generate_integer<'a>(
         tld: ToplevelTypeDeclaration,
         custom_derive: Option<&'a str>,
+        options: &GeneratorOptions,
     ) -> Result<String, GeneratorError> {
         if let ASN1Type::Integer(ref int) = tld.r#type {
+            let integer_type = if options.force_i128_integers {
+                "i128".to_owned()
+            } else {
+                int.type_token()
+            };
+            let distinguished_values =
+                format_distinguished_int_values(&tld.name, int, &integer_type);
+            let name = to_rust_title_case(&tld.name);
+            let conversions = newtype_conversions_template(&name, &integer_type);
             Ok(integer_template(
                 format_comments(&tld.comments),
-                custom_derive.unwrap_or(DERIVE_DEFAULT),
-                to_rust_title_case(&tld.name),
-                int.type_token(),
-                format_distinguished_values(&tld),
+                &append_derives(DERIVE_DEFAULT, custom_derive),
+                name,
+                integer_type,
+                distinguished_values,
                 int.declare(),
-            ))
+            ) + &conversions)
         } else {
             Err(GeneratorError::new(
                 Some(ToplevelDeclaration::Type(tld)),
@@ -71,13 +178,15 @@ impl Generator for AsnrGenerator {
         custom_derive: Option<&'a str>,
     ) -> Result<String, GeneratorError> {
         if let ASN1Type::BitString(ref bitstr) = tld.r#type {
+            let name = to_rust_title_case(&tld.name);
+            let conversions = newtype_conversions_template(&name, "Vec<bool>");
             Ok(bit_string_template(
                 format_comments(&tld.comments),
-                custom_derive.unwrap_or(DERIVE_DEFAULT),
-                to_rust_title_case(&tld.name),
+                &append_derives(DERIVE_DEFAULT, custom_derive),
+                name,
                 format_distinguished_values(&tld),
                 bitstr.declare(),
-            ))
+            ) + &conversions)
         } else {
             Err(GeneratorError::new(
                 Some(ToplevelDeclaration::Type(tld)),
@@ -92,12 +201,14 @@ impl Generator for AsnrGenerator {
         custom_derive: Option<&'a str>,
     ) -> Result<String, GeneratorError> {
         if let ASN1Type::OctetString(ref oct_str) = tld.r#type {
+            let name = to_rust_title_case(&tld.name);
+            let conversions = newtype_conversions_template(&name, "Vec<u8>");
             Ok(octet_string_template(
                 format_comments(&tld.comments),
-                custom_derive.unwrap_or(DERIVE_DEFAULT),
-                to_rust_title_case(&tld.name),
+                &append_derives(DERIVE_DEFAULT, custom_derive),
+                name,
                 oct_str.declare(),
-            ))
+            ) + &conversions)
         } else {
             Err(GeneratorError::new(
                 Some(ToplevelDeclaration::Type(tld)),
@@ -112,12 +223,14 @@ impl Generator for AsnrGenerator {
         custom_derive: Option<&'a str>,
     ) -> Result<String, GeneratorError> {
         if let ASN1Type::CharacterString(ref char_str) = tld.r#type {
+            let name = to_rust_title_case(&tld.name);
+            let conversions = newtype_conversions_template(&name, "String");
             Ok(char_string_template(
                 format_comments(&tld.comments),
-                custom_derive.unwrap_or(DERIVE_DEFAULT),
-                to_rust_title_case(&tld.name),
+                &append_derives(DERIVE_DEFAULT, custom_derive),
+                name,
                 char_str.declare(),
-            ))
+            ) + &conversions)
         } else {
             Err(GeneratorError::new(
                 Some(ToplevelDeclaration::Type(tld)),
@@ -132,15 +245,59 @@ impl Generator for AsnrGenerator {
         custom_derive: Option<&'a str>,
     ) -> Result<String, GeneratorError> {
         if let ASN1Type::Boolean = tld.r#type {
+            let name = to_rust_title_case(&tld.name);
+            let conversions = newtype_conversions_template(&name, "bool");
             Ok(boolean_template(
                 format_comments(&tld.comments),
-                custom_derive.unwrap_or(DERIVE_DEFAULT),
+                &append_derives(DERIVE_DEFAULT, custom_derive),
+                name,
+            ) + &conversions)
+        } else {
+            Err(GeneratorError::new(
+                Some(ToplevelDeclaration::Type(tld)),
+                "Expected BOOLEAN top-level declaration",
+                GeneratorErrorType::Asn1TypeMismatch,
+            ))
+        }
+    }
+
+    fn generate_object_identifier(
+        tld: ToplevelTypeDeclaration,
+        custom_derive: Option<&str>,
+    ) -> Result<String, GeneratorError> {
+        if let ASN1Type::ObjectIdentifier(_) = tld.r#type {
+            Ok(object_identifier_template(
+                format_comments(&tld.comments),
+                &append_derives(DERIVE_DEFAULT, custom_derive),
                 to_rust_title_case(&tld.name),
+                tld.r#type.declare(),
             ))
         } else {
             Err(GeneratorError::new(
                 Some(ToplevelDeclaration::Type(tld)),
-                "Expected BOOLEAN top-level declaration",
+                "Expected OBJECT IDENTIFIER top-level declaration",
+                GeneratorErrorType::Asn1TypeMismatch,
+            ))
+        }
+    }
+
+    fn generate_real(
+        tld: ToplevelTypeDeclaration,
+        custom_derive: Option<&str>,
+    ) -> Result<String, GeneratorError> {
+        if let ASN1Type::Real(_) = tld.r#type {
+            let name = to_rust_title_case(&tld.name);
+            let conversions = newtype_conversions_template(&name, "f64");
+            Ok(real_template(
+                format_comments(&tld.comments),
+                &append_derives(DERIVE_DEFAULT, custom_derive),
+                name,
+                tld.r#type.declare(),
+            ) + &conversions)
+        } else {
+            Err(GeneratorError::new(
+                Some(ToplevelDeclaration::Type(tld)),
+                "Expected REAL top-level declaration",
                 GeneratorErrorType::Asn1TypeMismatch,
             ))
         }
@@ -153,7 +310,7 @@ impl Generator for AsnrGenerator {
         if let ASN1Type::ElsewhereDeclaredType(dec) = &tld.r#type {
             Ok(typealias_template(
                 format_comments(&tld.comments),
-                custom_derive.unwrap_or(DERIVE_DEFAULT),
+                &append_derives(DERIVE_DEFAULT, custom_derive),
                 to_rust_title_case(&tld.name),
                 to_rust_title_case(&dec.identifier),
                 tld.r#type.declare(),
@@ -189,7 +346,7 @@ impl Generator for AsnrGenerator {
         if let ASN1Type::Null = tld.r#type {
             Ok(null_template(
                 format_comments(&tld.comments),
-                custom_derive.unwrap_or(DERIVE_DEFAULT),
+                &append_derives(DERIVE_DEFAULT, custom_derive),
                 to_rust_title_case(&tld.name),
             ))
         } else {
@@ -204,6 +361,7 @@ impl Generator for AsnrGenerator {
     fn generate_enumerated<'a>(
         mut tld: ToplevelTypeDeclaration,
         custom_derive: Option<&'a str>,
+        options: &GeneratorOptions,
     ) -> Result<String, GeneratorError> {
         if let ASN1Type::Enumerated(ref mut enumerated) = tld.r#type {
             enumerated.members.sort_by(|a, b| a.index.cmp(&b.index));
@@ -211,7 +369,14 @@ impl Generator for AsnrGenerator {
             let mut enumerals = enumerated
                 .members
                 .iter()
-                .fold(String::from("\t"), format_enumeral);
+                .fold(String::from("\t"), |acc, e| {
+                    format_enumeral(
+                        acc,
+                        e,
+                        &options.naming.enumerals,
+                        derives_serde(custom_derive),
+                    )
+                });
             if enumerated.extensible.is_some() {
                 enumerals.push_str("\n\tUnknownExtension")
             }
@@ -231,14 +396,30 @@ impl Generator for AsnrGenerator {
             let enumerals_from_int = enumerated
                 .members
                 .iter()
-                .fold(String::new(), format_enumeral_from_int);
+                .fold(String::new(), |acc, e| {
+                    format_enumeral_from_int(acc, e, &options.naming.enumerals)
+                });
+            let string_conversions = format_enumeral_string_conversions(
+                &name,
+                &enumerated.members,
+                enumerated.extensible.is_some(),
+                &options.naming.enumerals,
+            );
+            let extensible =
+                if enumerated.extensible.is_some() && options.mark_extensible_as_non_exhaustive {
+                    "\n#[non_exhaustive]"
+                } else {
+                    ""
+                };
             Ok(enumerated_template(
                 format_comments(&tld.comments),
-                custom_derive.unwrap_or(DERIVE_DEFAULT),
+                &append_derives(DERIVE_DEFAULT, custom_derive),
+                extensible,
                 name,
                 enumerals,
                 enumerals_from_int,
                 unknown_index_case,
+                string_conversions,
                 enumerated.declare(),
             ))
         } else {
@@ -250,15 +431,41 @@ impl Generator for AsnrGenerator {
         }
     }
 
-    fn generate_choice_value(tld: ToplevelValueDeclaration) -> Result<String, GeneratorError> {
-        if let ASN1Value::Choice(id, val) = tld.value {
-            let type_name = to_rust_snake_case(&tld.type_name);
+    fn generate_enumerated_value(
+        tld: ToplevelValueDeclaration,
+        options: &GeneratorOptions,
+    ) -> Result<String, GeneratorError> {
+        if let ASN1Value::EnumeratedValue {
+            ref enumerated,
+            ref enumerable,
+        } = tld.value
+        {
+            Ok(enumerated_value_template(
+                format_comments(&tld.comments),
+                to_rust_const_case(&tld.name),
+                &to_rust_title_case(enumerated),
+                options.naming.enumerals.apply(enumerable),
+            ))
+        } else {
+            Err(GeneratorError::new(
+                Some(ToplevelDeclaration::Value(tld)),
+                "Expected ENUMERATED value top-level declaration",
+                GeneratorErrorType::Asn1TypeMismatch,
+            ))
+        }
+    }
+
+    fn generate_choice_value(
+        tld: ToplevelValueDeclaration,
+        options: &GeneratorOptions,
+    ) -> Result<String, GeneratorError> {
+        if let ASN1Value::Choice(ref option, ref val) = tld.value {
+            let type_name = to_rust_title_case(&tld.type_name);
             Ok(choice_value_template(
                 format_comments(&tld.comments),
-                to_rust_snake_case(&tld.name),
+                to_rust_const_case(&tld.name),
                 &type_name,
-                id,
-                val.value_as_string(Some(&type_name))?,
+                format_choice_value(&type_name, option, val, &options.naming)?,
             ))
         } else {
             Err(GeneratorError::new(
@@ -272,12 +479,14 @@ impl Generator for AsnrGenerator {
     fn generate_choice<'a>(
         mut tld: ToplevelTypeDeclaration,
         custom_derive: Option<&'a str>,
+        options: &GeneratorOptions,
     ) -> Result<String, GeneratorError> {
         if let ASN1Type::Choice(ref mut choice) = tld.r#type {
             handle_duplicate_options(&mut choice.options);
             let name = to_rust_title_case(&tld.name);
-            let inner_options = flatten_nested_choice_options(&choice.options, &name).join("\n");
-            let options = extract_choice_options(&choice.options, &name);
+            let inner_options = flatten_nested_choice_options(&choice.options, &name, custom_derive, options).join("\n");
+            let non_exhaustive = options.mark_extensible_as_non_exhaustive;
+            let options = extract_choice_options(&choice.options, &name, &options.naming);
             let mut options_declaration = format_option_declaration(&options);
             if choice.extensible.is_some() {
                 options_declaration.push_str("\n\tUnknownChoiceValue(Vec<u8>)");
@@ -316,9 +525,15 @@ impl Generator for AsnrGenerator {
                 .map(format_option_encoder_from_int)
                 .collect::<Vec<String>>()
                 .join("\n\t\t  ");
+            let extensible = if choice.extensible.is_some() && non_exhaustive {
+                "\n#[non_exhaustive]"
+            } else {
+                ""
+            };
             Ok(choice_template(
                 format_comments(&tld.comments),
-                custom_derive.unwrap_or("#[derive(Debug, Clone, PartialEq)]"),
+                &append_derives("#[derive(Debug, Clone, PartialEq)]", custom_derive),
+                extensible,
                 name,
                 inner_options,
                 default_option,
@@ -355,6 +570,125 @@ impl Generator for AsnrGenerator {
         }
     }
 
+    fn generate_information_object_set(
+        tld: ToplevelInformationDeclaration,
+        custom_derive: Option<&str>,
+        options: &GeneratorOptions,
+    ) -> Result<String, GeneratorError> {
+        let (set, class) = match (&tld.value, &tld.class) {
+            (ASN1Information::ObjectSet(set), Some(ClassLink::ByReference(class))) => (set, class),
+            (ASN1Information::ObjectSet(_), _) => {
+                return Err(GeneratorError::new(
+                    Some(ToplevelDeclaration::Information(tld)),
+                    "Missing class link in information object set",
+                    GeneratorErrorType::MissingClassLink,
+                ))
+            }
+            _ => {
+                return Err(GeneratorError::new(
+                    Some(ToplevelDeclaration::Information(tld)),
+                    "Expected information object set top-level declaration",
+                    GeneratorErrorType::Asn1TypeMismatch,
+                ))
+            }
+        };
+        let name = to_rust_title_case(&tld.name);
+        let objects = match extract_object_set_options(set, class, &name, &options.naming) {
+            Ok(objects) => objects,
+            Err(mut e) => {
+                e.top_level_declaration = Some(ToplevelDeclaration::Information(tld));
+                return Err(e);
+            }
+        };
+        let default_option = match objects.first() {
+            Some((_, o, _)) => default_choice(o),
+            None => {
+                return Err(GeneratorError::new(
+                    Some(ToplevelDeclaration::Information(tld)),
+                    "Empty information object sets are not yet supported!",
+                    GeneratorErrorType::EmptyChoiceType,
+                ))
+            }
+        };
+        let inner_types = objects
+            .iter()
+            .filter_map(|(_, o, inner_type)| {
+                inner_type.as_ref().map(|t| {
+                    generate(
+                        &Framework::Asnr,
+                        ToplevelDeclaration::Type(ToplevelTypeDeclaration {
+                            parameterization: None,
+                            comments: " Inner type ".into(),
+                            name: o.r#type.clone(),
+                            r#type: t.clone(),
+                            tag: None,
+                        }),
+                        custom_derive,
+                        options,
+                    )
+                })
+            })
+            .collect::<Result<Vec<String>, GeneratorError>>()?
+            .join("\n");
+        let mut options_declaration = objects
+            .iter()
+            .map(|(_, o, _)| format!("{}({}),", o.name, o.r#type))
+            .collect::<Vec<String>>()
+            .join("\n  ");
+        let (unknown_key_decoder, unknown_key_encoder) = if set.extensible.is_some() {
+            options_declaration.push_str("\n  UnknownClassImplementation(Vec<u8>),");
+            (
+                r#"_ => Ok(|input| D::decode_open_type(input).map(|(r, v)| (r, Self::UnknownClassImplementation(v))))"#.to_owned(),
+                r#"_ => Ok(|encodable, output| {
+        if let Self::UnknownClassImplementation(inner) = encodable {
+          E::encode_open_type(inner, output)
+        } else {
+          Err(EncodingError { details: format!("Unknown key does not correspond to object {encodable:?}!") })
+        }
+      })"#.to_owned(),
+            )
+        } else {
+            (
+                format!(
+                    r#"x => Err(
+  DecodingError::new(
+    &format!("Invalid key decoding {name}. Received {{x}}"),
+    DecodingErrorType::InvalidObjectSetKey
+  )
+)"#
+                ),
+                format!(
+                    r#"x => Err(EncodingError {{ details: format!("Invalid key encoding {name}. Received {{x}}") }})"#
+                ),
+            )
+        };
+        let members = format_object_set_members(
+            &name,
+            &objects.iter().map(|(_, o, _)| o).collect::<Vec<_>>(),
+        );
+        Ok(information_object_set_template(
+            format_comments(&tld.comments),
+            &append_derives("#[derive(Debug, Clone, PartialEq)]", custom_derive),
+            inner_types,
+            name,
+            options_declaration,
+            default_option,
+            objects
+                .iter()
+                .map(|(key, o, _)| format_object_decoder_for_key(*key, o))
+                .collect::<Vec<String>>()
+                .join("\n      "),
+            unknown_key_decoder,
+            objects
+                .iter()
+                .map(|(key, o, _)| format_object_encoder_for_key(*key, o))
+                .collect::<Vec<String>>()
+                .join("\n      "),
+            unknown_key_encoder,
+            members,
+        ))
+    }
+
     fn generate_sequence_value(tld: ToplevelValueDeclaration) -> Result<String, GeneratorError> {
         if let ASN1Value::Sequence(_) = tld.value {
             let type_name = to_rust_snake_case(&tld.type_name);
@@ -373,31 +707,55 @@ impl Generator for AsnrGenerator {
         }
     }
 
-    fn generate_sequence<'a>(
+    /// Generates the Rust representation of a SEQUENCE or a SET.
+    /// Since the packed encoding rules encode the members of a SET in the same order
+    /// as the members of a SEQUENCE, both share the same representation.
+    fn generate_sequence_or_set(
         tld: ToplevelTypeDeclaration,
-        custom_derive: Option<&'a str>,
+        custom_derive: Option<&str>,
+        options: &GeneratorOptions,
     ) -> Result<String, GeneratorError> {
-        if let ASN1Type::Sequence(ref seq) = tld.r#type {
+        if let ASN1Type::Sequence(ref seq) | ASN1Type::Set(ref seq) = tld.r#type {
             let name = to_rust_title_case(&tld.name);
-            let members = extract_sequence_members(&seq.members, &name, seq.extensible);
+            let members = extract_sequence_members(&seq.members, &name, seq.extensible, options);
             let extension_decoder = format_extensible_sequence(&name, seq.extensible.is_some());
+            let default_methods = format_default_methods(&seq.members, &members, &name, options);
+            let constructor = format_constructor(&seq.members, &members, &name, options);
+            let contained_type_accessors =
+                format_contained_type_accessors(&seq.members, &members, &name, options);
+            let extension_group_accessors =
+                format_extension_group_accessors(&seq.members, &members, &name, options);
+            let derive = if options.materialize_defaults && !default_methods.is_empty() {
+                "#[derive(Debug, Clone, PartialEq)]"
+            } else {
+                DERIVE_DEFAULT
+            };
+
+            let encoded_size = options
+                .encoded_sizes
+                .get(&tld.name)
+                .map_or(String::new(), |bits| encoded_size_template(&name, *bits));
 
             Ok(sequence_template(
                 format_comments(&tld.comments),
-                custom_derive.unwrap_or(DERIVE_DEFAULT),
-                flatten_nested_sequence_members(&seq.members, &name)?.join("\n"),
+                &append_derives(derive, custom_derive),
+                flatten_nested_sequence_members(&seq.members, &name, custom_derive, options)?.join("\n"),
                 name,
                 format_member_declaration(&members),
+                default_methods,
+                constructor,
                 format_decode_member_body(&members),
                 format_encoder_member_body(&members),
                 format_has_optional_body(&members),
                 extension_decoder,
                 seq.declare(),
-            ))
+            ) + &contained_type_accessors
+                + &extension_group_accessors
+                + &encoded_size)
         } else {
             Err(GeneratorError::new(
                 Some(ToplevelDeclaration::Type(tld)),
-                "Expected SEQUENCE top-level declaration",
+                "Expected SEQUENCE or SET top-level declaration",
                 GeneratorErrorType::Asn1TypeMismatch,
             ))
         }
@@ -406,6 +764,7 @@ impl Generator for AsnrGenerator {
     fn generate_sequence_of<'a>(
         tld: ToplevelTypeDeclaration,
         custom_derive: Option<&'a str>,
+        options: &GeneratorOptions,
     ) -> Result<String, GeneratorError> {
         if let ASN1Type::SequenceOf(ref seq_of) = tld.r#type {
             let name = to_rust_title_case(&tld.name);
@@ -416,20 +775,21 @@ impl Generator for AsnrGenerator {
                     ToplevelDeclaration::Type(ToplevelTypeDeclaration {
                         parameterization: None,
                         comments: " Anonymous SEQUENCE OF member ".into(),
-                        name: String::from("Anonymous") + &name,
+                        name: anonymous_name(&name, &options.naming),
                         r#type: n.clone(),
                         tag: None,
                     }),
-                    None,
+                    custom_derive,
+                    options,
                 )?),
             }.unwrap_or_default();
             let member_type = match seq_of.r#type.as_ref() {
                 ASN1Type::ElsewhereDeclaredType(d) => to_rust_title_case(&d.identifier),
-                _ => String::from("Anonymous") + &name,
+                _ => anonymous_name(&name, &options.naming),
             };
             Ok(sequence_of_template(
                 format_comments(&tld.comments),
-                custom_derive.unwrap_or(DERIVE_DEFAULT),
+                &append_derives(DERIVE_DEFAULT, custom_derive),
                 name,
                 anonymous_item,
                 member_type,
@@ -483,7 +843,7 @@ mod tests {
         };
         println!(
             "{}",
-            AsnrGenerator::generate_enumerated(enum_tld, None).unwrap()
+            AsnrGenerator::generate_enumerated(enum_tld, None, &GeneratorOptions::default()).unwrap()
         )
     }
 
@@ -561,7 +921,7 @@ mod tests {
         };
         println!(
             "{}",
-            AsnrGenerator::generate_integer(int_tld, None).unwrap()
+            AsnrGenerator::generate_integer(int_tld, None, &GeneratorOptions::default()).unwrap()
         )
     }
 
@@ -591,6 +951,7 @@ mod tests {
                                 default_value: None,
                                 is_optional: false,
                                 constraints: vec![],
+                                is_recursive: false,
                             },
                             SequenceOrSetMember {
                                 name: "this-is-annoying".into(),
@@ -599,6 +960,7 @@ mod tests {
                                 default_value: Some(ASN1Value::Boolean(true)),
                                 is_optional: true,
                                 constraints: vec![],
+                                is_recursive: false,
                             },
                             SequenceOrSetMember {
                                 name: "another".into(),
@@ -636,24 +998,65 @@ mod tests {
                                         default_value: Some(ASN1Value::String("0".into())),
                                         is_optional: true,
                                         constraints: vec![],
+                                        is_recursive: false,
                                     }],
+                                    extension_groups: vec![],
                                 }),
                                 default_value: None,
                                 is_optional: true,
                                 constraints: vec![],
+                                is_recursive: false,
                             },
                         ],
+                        extension_groups: vec![],
                     }),
                     default_value: None,
                     is_optional: false,
                     constraints: vec![],
+                    is_recursive: false,
                 }],
+                extension_groups: vec![],
             }),
             tag: None,
         };
         println!(
             "{}",
-            AsnrGenerator::generate_sequence(seq_tld, None).unwrap()
+            AsnrGenerator::generate_sequence_or_set(seq_tld, None, &GeneratorOptions::default())
+                .unwrap()
         )
     }
+
+    #[test]
+    fn generates_choice_value_from_template() {
+        let choice_value_tld = ToplevelValueDeclaration {
+            comments: "".into(),
+            name: "selected".into(),
+            type_name: "MyChoice".into(),
+            value: ASN1Value::Choice("first".into(), Box::new(ASN1Value::Boolean(true))),
+        };
+        assert_eq!(
+            AsnrGenerator::generate_choice_value(choice_value_tld, &GeneratorOptions::default())
+                .unwrap()
+                .trim(),
+            "pub const SELECTED: MyChoice = MyChoice::First(InnerMyChoiceFirst(true));"
+        );
+        let nested_value_tld = ToplevelValueDeclaration {
+            comments: "".into(),
+            name: "nested".into(),
+            type_name: "MyChoice".into(),
+            value: ASN1Value::Choice(
+                "inner".into(),
+                Box::new(ASN1Value::Choice(
+                    "content".into(),
+                    Box::new(ASN1Value::Integer(42)),
+                )),
+            ),
+        };
+        assert_eq!(
+            AsnrGenerator::generate_choice_value(nested_value_tld, &GeneratorOptions::default())
+                .unwrap()
+                .trim(),
+            "pub const NESTED: MyChoice = MyChoice::Inner(InnerMyChoiceInner::Content(InnerInnerMyChoiceInnerContent(42)));"
+        );
+    }
 }
@@ -22,6 +22,7 @@ impl Declare for ASN1Type {
             ASN1Type::SequenceOf(s) => format!("ASN1Type::SequenceOf({})", s.declare()),
             ASN1Type::Sequence(s) => format!("ASN1Type::Sequence({})", s.declare()),
             ASN1Type::Set(s) => format!("ASN1Type::Set({})", s.declare()),
+            ASN1Type::SetOf(s) => format!("ASN1Type::SetOf({})", s.declare()),
             ASN1Type::Choice(c) => format!("ASN1Type::Choice({})", c.declare()),
             ASN1Type::ElsewhereDeclaredType(els) => {
                 format!("ASN1Type::ElsewhereDeclaredType({})", els.declare())
@@ -39,16 +40,16 @@ impl Declare for ASN1Value {
         match self {
             ASN1Value::All => String::from("ASN1Value::All"),
             ASN1Value::Null => String::from("ASN1Value::Null"),
-            ASN1Value::Real(r) => format!("ASN1Value::Real({})", r),
+            ASN1Value::Real(r) => format!("ASN1Value::Real({})", declare_f64(*r)),
             ASN1Value::Boolean(b) => format!("ASN1Value::Boolean({})", b),
             ASN1Value::Integer(i) => format!("ASN1Value::Integer({})", i),
             ASN1Value::String(s) => format!("ASN1Value::String(\"{}\".into())", s),
-            ASN1Value::Choice(i, v) => format!("ASN1Value::Choice({i}, Box::new({}))", v.declare()),
+            ASN1Value::Choice(i, v) => format!("ASN1Value::Choice(\"{i}\".into(), Box::new({}))", v.declare()),
             ASN1Value::Sequence(fields) => format!(
                 "ASN1Value::Sequence(vec![{}])",
                 fields
                     .iter()
-                    .map(|(id, val)| format!("({id}, Box::new({}))", val.declare()))
+                    .map(|(id, val)| format!("(\"{id}\".into(), Box::new({}))", val.declare()))
                     .collect::<Vec<String>>()
                     .join(", ")
             ),
@@ -77,10 +78,31 @@ impl Declare for ASN1Value {
             ASN1Value::ObjectIdentifier(oid) => {
                 format!("ASN1Value::ObjectIdentifier({})", oid.declare())
             }
+            ASN1Value::LinkedNestedValue { r#type, value } => format!(
+                "ASN1Value::LinkedNestedValue {{ r#type: Box::new({}), value: Box::new({}) }}",
+                r#type.declare(),
+                value.declare()
+            ),
+            ASN1Value::Optional(Some(v)) => {
+                format!("ASN1Value::Optional(Some(Box::new({})))", v.declare())
+            }
+            ASN1Value::Optional(None) => String::from("ASN1Value::Optional(None)"),
         }
     }
 }
 
+/// Declares a float literal that keeps its type regardless of its value,
+/// e.g. `100_f64` instead of `100`
+fn declare_f64(value: f64) -> String {
+    if value.is_nan() {
+        String::from("f64::NAN")
+    } else if value.is_infinite() {
+        String::from(if value > 0. { "f64::INFINITY" } else { "f64::NEG_INFINITY" })
+    } else {
+        format!("{value:?}_f64")
+    }
+}
+
 impl Declare for DeclarationElsewhere {
     fn declare(&self) -> String {
         format!(
@@ -238,7 +260,7 @@ impl Declare for SequenceOf {
 impl Declare for SequenceOrSet {
     fn declare(&self) -> String {
         format!(
-            "SequenceOrSet {{ constraints: vec![{}], extensible: {}, members: vec![{}] }}",
+            "SequenceOrSet {{ constraints: vec![{}], extensible: {}, members: vec![{}], extension_groups: vec![] }}",
             self.constraints
                 .iter()
                 .map(|c| c.declare())
@@ -259,7 +281,7 @@ impl Declare for SequenceOrSet {
 impl Declare for SequenceOrSetMember {
     fn declare(&self) -> String {
         format!(
-          "SequenceOrSetMember {{ name: \"{}\".into(), tag: {}, is_optional: {}, r#type: {}, default_value: {}, constraints: vec![{}] }}",
+          "SequenceOrSetMember {{ name: \"{}\".into(), tag: {}, is_optional: {}, r#type: {}, default_value: {}, constraints: vec![{}], is_recursive: {} }}",
           self.name,
           self.tag.as_ref().map_or(String::from("None"), |t| {
             String::from("Some(") + &t.declare() + ")"
@@ -274,6 +296,7 @@ impl Declare for SequenceOrSetMember {
           .map(|c| c.declare())
           .collect::<Vec<String>>()
           .join(", "),
+          self.is_recursive,
       )
     }
 }
@@ -302,7 +325,7 @@ impl Declare for Choice {
 impl Declare for ChoiceOption {
     fn declare(&self) -> String {
         format!(
-            "ChoiceOption {{ name: \"{}\".into(), tag: {}, r#type: {}, constraints: vec![{}] }}",
+            "ChoiceOption {{ name: \"{}\".into(), tag: {}, r#type: {}, constraints: vec![{}], is_recursive: {} }}",
             self.name,
             self.tag.as_ref().map_or(String::from("None"), |t| {
                 String::from("Some(") + &t.declare() + ")"
@@ -313,6 +336,7 @@ impl Declare for ChoiceOption {
                 .map(|c| c.declare())
                 .collect::<Vec<String>>()
                 .join(", "),
+            self.is_recursive,
         )
     }
 }
@@ -371,6 +395,9 @@ impl Declare for Constraint {
             Constraint::TableConstraint(t) => {
                 format!("Constraint::TableConstraint({})", t.declare())
             }
+            Constraint::ContentConstraint(c) => {
+                format!("Constraint::ContentConstraint({})", c.declare())
+            }
             Constraint::Parameter(params) => {
                 format!(
                     "Constraint::Parameter(vec![{}])",
@@ -463,6 +490,20 @@ impl Declare for ValueConstraint {
     }
 }
 
+impl Declare for ContentConstraint {
+    fn declare(&self) -> String {
+        format!(
+            "ContentConstraint {{ containing: {}, encoded_by: {} }}",
+            self.containing
+                .as_ref()
+                .map_or("None".to_owned(), |t| format!("Some({})", t.declare())),
+            self.encoded_by
+                .as_ref()
+                .map_or("None".to_owned(), |v| format!("Some({})", v.declare())),
+        )
+    }
+}
+
 impl Declare for TableConstraint {
     fn declare(&self) -> String {
         format!(
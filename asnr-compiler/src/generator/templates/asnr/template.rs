@@ -1,9 +1,11 @@
+use crate::generator::templates::append_derives;
+
 use super::builder::StringifiedNameType;
 
 pub fn asnr_imports_and_generic_types(
     derive: Option<&str>,
     no_std: bool,
-    include_file_headers: bool,
+    file_header: Option<String>,
 ) -> String {
     format!(
         r#"{}
@@ -41,23 +43,49 @@ impl<T, O: Extend<T> + Debug + 'static> Encode<T, O> for Asn1Open {{
   }}
 }}
 "#,
-        if include_file_headers {
-            r#"// This file has been auto-generated by ASNR
-#![allow(dead_code)]
+        file_header_attributes(file_header),
+        std_imports(no_std),
+        append_derives(DERIVE_DEFAULT, derive)
+    )
+}
+
+/// Imports of a generated module file that shares the generic types
+/// declared in `asnr_imports_and_generic_types` with its sibling modules
+pub fn asnr_module_imports(no_std: bool, include_file_headers: bool) -> String {
+    format!(
+        r#"{}
+{}
+use asnr_grammar::{{*, types::*, constraints::*, information_object::*}};
+use asnr_transcoder::{{*, error::*}};
+use super::*;
+"#,
+        file_header_attributes(include_file_headers.then(|| ASNR_BANNER.to_owned())),
+        std_imports(no_std),
+    )
+}
+
+pub const ASNR_BANNER: &str = "// This file has been auto-generated by ASNR\n";
+
+/// Appends the attributes silencing lints in generated code to the comments of a file header
+fn file_header_attributes(file_header: Option<String>) -> String {
+    match file_header {
+        Some(comments) => format!(
+            r#"{comments}#![allow(dead_code)]
 #![allow(unused_mut)]
 #![allow(unused_variables)]
 "#
-        } else {
-            ""
-        },
-        if no_std {
-            r#"use core::{any::Any, fmt::Debug};
+        ),
+        None => String::new(),
+    }
+}
+
+fn std_imports(no_std: bool) -> &'static str {
+    if no_std {
+        r#"use core::{any::Any, fmt::Debug};
 use alloc::{{format, vec, vec::Vec, string::String, boxed::Box}};"#
-        } else {
-            "use std::{any::Any, fmt::Debug};"
-        },
-        derive.unwrap_or(DERIVE_DEFAULT)
-    )
+    } else {
+        "use std::{any::Any, fmt::Debug};"
+    }
 }
 
 pub const DERIVE_DEFAULT: &str = "#[derive(Debug, Clone, PartialEq, Default)]";
@@ -122,7 +150,7 @@ pub fn typealias_template(
 
       {DECODER_SIGNATURE}
       {{
-        let mut inner_decoder = {alias}::decoder::<D>()?;
+        let inner_decoder = {alias}::decoder::<D>()?;
         Ok(Box::new(move |input| (*inner_decoder)(input).map(|(r, v)|(r, Self(v)))))
       }}
     }}
@@ -135,7 +163,7 @@ pub fn typealias_template(
     
       {ENCODER_SIGNATURE}
       {{
-        let mut inner_encoder = {alias}::encoder::<E>()?;
+        let inner_encoder = {alias}::encoder::<E>()?;
         Ok(Box::new(move |encodable, output| (*inner_encoder)(encodable.0, output)))
       }}
     }}
@@ -143,6 +171,90 @@ pub fn typealias_template(
     )
 }
 
+pub fn tag_constant_template(name: String, tag: String) -> String {
+    format!(
+        r#"
+impl {name} {{
+    pub const TAG: AsnTag = {tag};
+}}
+"#
+    )
+}
+
+pub fn encoded_size_template(name: &str, bits: usize) -> String {
+    format!(
+        r#"
+impl {name} {{
+    /// Size of the UPER encoding in bits
+    pub const ENCODED_SIZE_BITS: usize = {bits};
+}}
+"#
+    )
+}
+
+/// Conversions between a newtype and the Rust type it wraps,
+/// so that values can be constructed without spelling out the newtype
+pub fn newtype_conversions_template(name: &str, inner_type: &str) -> String {
+    let str_conversions = if inner_type == "String" {
+        format!(
+            r#"
+impl From<&str> for {name} {{
+    fn from(value: &str) -> Self {{
+        Self(value.into())
+    }}
+}}
+
+impl AsRef<str> for {name} {{
+    fn as_ref(&self) -> &str {{
+        &self.0
+    }}
+}}
+"#
+        )
+    } else {
+        String::new()
+    };
+    format!(
+        r#"
+impl From<{inner_type}> for {name} {{
+    fn from(value: {inner_type}) -> Self {{
+        Self(value)
+    }}
+}}
+
+impl From<{name}> for {inner_type} {{
+    fn from(value: {name}) -> Self {{
+        value.0
+    }}
+}}
+
+impl core::ops::Deref for {name} {{
+    type Target = {inner_type};
+
+    fn deref(&self) -> &Self::Target {{
+        &self.0
+    }}
+}}
+{str_conversions}"#
+    )
+}
+
+pub fn object_identifier_value_template(comments: String, name: String, arcs: String) -> String {
+    format!(
+        r#"{comments}
+pub const {name}: &[u32] = &[{arcs}];
+"#
+    )
+}
+
+pub fn bit_string_value_template(comments: String, name: String, bits: String) -> String {
+    format!(
+        r#"{comments}
+pub const {name}: &[bool] = &[{bits}];
+"#
+    )
+}
+
 pub fn integer_value_template(
     comments: String,
     name: String,
@@ -177,7 +289,7 @@ impl<'a, I: AsBytes + Debug + 'a> Decode<'a, I> for {name} {{
 
   {DECODER_SIGNATURE}
   {{
-    let mut int_decoder = D::decode_integer({int_descriptor})?;
+    let int_decoder = D::decode_integer({int_descriptor})?;
     Ok(Box::new(move |input| (*int_decoder)(input).map(|(remaining, res)| (remaining, Self(res)))))
   }}
 }}
@@ -190,7 +302,7 @@ impl<T, O: Extend<T> + Debug + 'static> Encode<T, O> for {name} {{
 
   {ENCODER_SIGNATURE}
   {{
-    let mut int_encoder = E::encode_integer::<{integer_type}>({int_descriptor})?;
+    let int_encoder = E::encode_integer::<{integer_type}>({int_descriptor})?;
     Ok(Box::new(move |encodable, output| (*int_encoder)(encodable.0, output)))
   }}
 }}
@@ -218,7 +330,7 @@ impl<'a, I: AsBytes + Debug + 'a> Decode<'a, I> for {name} {{
 
   {DECODER_SIGNATURE}
   {{
-    let mut bitstring_decoder = D::decode_bit_string({bitstr_descriptor})?;
+    let bitstring_decoder = D::decode_bit_string({bitstr_descriptor})?;
     Ok(Box::new(move |input| (*bitstring_decoder)(input).map(|(remaining, res)| (remaining, Self(res)))))
   }}
 }}
@@ -232,7 +344,7 @@ impl<T, O: Extend<T> + Debug + 'static> Encode<T, O> for {name} {{
 
   {ENCODER_SIGNATURE}
   {{
-    let mut bit_string_encoder = E::encode_bit_string({bitstr_descriptor})?;
+    let bit_string_encoder = E::encode_bit_string({bitstr_descriptor})?;
     Ok(Box::new(move |encodable, output| (*bit_string_encoder)(encodable.0, output)))
   }}
 }}
@@ -260,7 +372,7 @@ impl<'a, I: AsBytes + Debug + 'a> Decode<'a, I> for {name} {{
 
 {DECODER_SIGNATURE}
 {{
-  let mut octet_string_decoder = D::decode_octet_string({octetstr_descriptor})?;
+  let octet_string_decoder = D::decode_octet_string({octetstr_descriptor})?;
   Ok(Box::new(move |input| (*octet_string_decoder)(input).map(|(remaining, res)| (remaining, Self(res)))))
 }}
 }}
@@ -274,7 +386,7 @@ impl<T, O: Extend<T> + Debug + 'static> Encode<T, O> for {name} {{
 
 {ENCODER_SIGNATURE}
 {{
-  let mut octet_string_encoder = E::encode_octet_string({octetstr_descriptor})?;
+  let octet_string_encoder = E::encode_octet_string({octetstr_descriptor})?;
   Ok(Box::new(move |encodable, output| (*octet_string_encoder)(&encodable.0, output)))
 }}
 }}
@@ -302,7 +414,7 @@ impl<'a, I: AsBytes + Debug + 'a> Decode<'a, I> for {name} {{
 
   {DECODER_SIGNATURE}
   {{
-    let mut charstring_decoder = D::decode_character_string({charstr_descriptor})?;
+    let charstring_decoder = D::decode_character_string({charstr_descriptor})?;
     Ok(Box::new(move |input| (*charstring_decoder)(input).map(|(remaining, res)| (remaining, Self(res)))))
   }}
 }}
@@ -315,7 +427,7 @@ impl<T, O: Extend<T> + Debug + 'static> Encode<T, O> for {name} {{
 
   {ENCODER_SIGNATURE}
   {{
-    let mut char_string_encoder = E::encode_character_string({charstr_descriptor})?;
+    let char_string_encoder = E::encode_character_string({charstr_descriptor})?;
     Ok(Box::new(move |encodable, output| (*char_string_encoder)(encodable.0.as_str(), output)))
   }}
 }}
@@ -356,6 +468,97 @@ impl<T, O: Extend<T> + Debug + 'static> Encode<T, O> for {name} {{
     )
 }
 
+pub fn real_value_template(comments: String, name: String, vtype: &str, value: String) -> String {
+    format!(
+        r#"{comments}
+pub const {name}: {vtype} = {value};
+"#
+    )
+}
+
+pub fn real_template(comments: String, derive: &str, name: String, descriptor: String) -> String {
+    format!(
+        r#"
+{comments}{derive}
+pub struct {name}(pub f64);
+
+impl Describe for {name} {{
+  fn describe() -> ASN1Type {{
+    {descriptor}
+  }}
+}}
+
+impl<'a, I: AsBytes + Debug + 'a> Decode<'a, I> for {name} {{
+  {DECODE_SIGNATURE}
+  {{
+    {name}::decoder::<D>()?(input)
+  }}
+
+  {DECODER_SIGNATURE}
+  {{
+    Ok(Box::new(|input| D::decode_real(input).map(|(remaining, res)| (remaining, Self(res)))))
+  }}
+}}
+
+impl<T, O: Extend<T> + Debug + 'static> Encode<T, O> for {name} {{
+  {ENCODE_SIGNATURE}
+  {{
+    {name}::encoder::<E>()?(encodable, output)
+  }}
+
+  {ENCODER_SIGNATURE}
+  {{
+    Ok(Box::new(move |encodable, output| E::encode_real(encodable.0, output)))
+  }}
+}}
+"#
+    )
+}
+
+pub fn object_identifier_template(
+    comments: String,
+    derive: &str,
+    name: String,
+    descriptor: String,
+) -> String {
+    format!(
+        r#"
+{comments}{derive}
+pub struct {name}(pub Vec<u32>);
+
+impl Describe for {name} {{
+  fn describe() -> ASN1Type {{
+    {descriptor}
+  }}
+}}
+
+impl<'a, I: AsBytes + Debug + 'a> Decode<'a, I> for {name} {{
+  {DECODE_SIGNATURE}
+  {{
+    {name}::decoder::<D>()?(input)
+  }}
+
+  {DECODER_SIGNATURE}
+  {{
+    Ok(Box::new(|input| D::decode_object_identifier(input).map(|(remaining, res)| (remaining, Self(res)))))
+  }}
+}}
+
+impl<T, O: Extend<T> + Debug + 'static> Encode<T, O> for {name} {{
+  {ENCODE_SIGNATURE}
+  {{
+    {name}::encoder::<E>()?(encodable, output)
+  }}
+
+  {ENCODER_SIGNATURE}
+  {{
+    Ok(Box::new(move |encodable, output| E::encode_object_identifier(&encodable.0, output)))
+  }}
+}}
+"#
+    )
+}
+
 pub fn null_value_template(comments: String, name: String) -> String {
     format!(
         r#"{comments}
@@ -400,15 +603,17 @@ impl<T, O: Extend<T> + Debug + 'static> Encode<T, O> for {name} {{
 pub fn enumerated_template(
     comments: String,
     derive: &str,
+    extensible: &str,
     name: String,
     enumerals: String,
     enumerals_from_int: String,
     unknown_index_case: String,
+    string_conversions: String,
     enum_descriptor: String,
 ) -> String {
     format!(
         r#"
-  {comments}{derive}
+  {comments}{derive}{extensible}
   pub enum {name} {{
     #[default]
     {enumerals}
@@ -424,6 +629,7 @@ pub fn enumerated_template(
       }}
     }}
   }}
+  {string_conversions}
   
   impl<'a, I: AsBytes + Debug + 'a> Decode<'a, I> for {name} {{
     {DECODE_SIGNATURE}
@@ -445,7 +651,7 @@ pub fn enumerated_template(
   
     {ENCODER_SIGNATURE}
     {{
-      let mut enumerated_encoder = E::encode_enumerated({enum_descriptor})?;
+      let enumerated_encoder = E::encode_enumerated({enum_descriptor})?;
       Ok(Box::new(move |encodable, output| (*enumerated_encoder)(encodable, output)))
     }}
   }}
@@ -472,6 +678,8 @@ pub fn sequence_template(
     inner_members: String,
     name: String,
     member_declaration: String,
+    default_methods: String,
+    constructor: String,
     decode_member_body: String,
     encoder_member_body: String,
     has_optional_body: String,
@@ -486,7 +694,8 @@ pub fn sequence_template(
   pub struct {name} {{
     {member_declaration}
   }}
-  
+  {default_methods}
+  {constructor}
   impl<'a, I: AsBytes + Debug + 'a> DecodeMember<'a, I> for {name} {{
     fn decode_member_at_index<D>(&mut self, index: usize, input: I) -> Result<I, DecodingError<I>>
       where
@@ -543,7 +752,7 @@ pub fn sequence_template(
   
     {ENCODER_SIGNATURE}
     {{
-      let mut sequence_encoder = E::encode_sequence({seq_descriptor})?;
+      let sequence_encoder = E::encode_sequence({seq_descriptor})?;
       Ok(Box::new(move |encodable, output| (*sequence_encoder)(encodable, output)))
     }}
   }}
@@ -573,7 +782,7 @@ impl<'a, I: AsBytes + Debug + 'a> Decode<'a, I> for {name} {{
 
   {DECODER_SIGNATURE}
   {{
-    let mut seq_of_decoder = D::decode_sequence_of({seq_of_descriptor}, {member_type}::decode::<D>)?;
+    let seq_of_decoder = D::decode_sequence_of({seq_of_descriptor}, {member_type}::decoder::<D>()?)?;
     Ok(Box::new(move |input| (*seq_of_decoder)(input).map(|(remaining, res)| (remaining, Self(res)))))
   }}
 }}
@@ -586,7 +795,7 @@ impl<T, O: Extend<T> + Debug + 'static> Encode<T, O> for {name} {{
 
   {ENCODER_SIGNATURE}
   {{
-    let mut sequence_of_encoder = E::encode_sequence_of({seq_of_descriptor})?;
+    let sequence_of_encoder = E::encode_sequence_of({seq_of_descriptor})?;
     Ok(Box::new(move |encodable, output| (*sequence_of_encoder)(encodable.0, output)))
   }}
 }}
@@ -602,16 +811,28 @@ pub fn default_choice(option: &StringifiedNameType) -> String {
     )
 }
 
+pub fn enumerated_value_template(
+    comments: String,
+    name: String,
+    ty: &String,
+    enumeral: String,
+) -> String {
+    format!(
+        r#"{comments}
+pub const {name}: {ty} = {ty}::{enumeral};
+"#
+    )
+}
+
 pub fn choice_value_template(
     comments: String,
     name: String,
     ty: &String,
-    option_name: String,
-    inner: String,
+    value: String,
 ) -> String {
     format!(
         r#"{comments}
-  pub const {name}: &'static {ty} = &{ty}::{option_name}({inner});
+  pub const {name}: {ty} = {value};
   "#
     )
 }
@@ -619,6 +840,7 @@ pub fn choice_value_template(
 pub fn choice_template(
     comments: String,
     derive: &str,
+    extensible: &str,
     name: String,
     anonymous_option: String,
     default_option: String,
@@ -631,7 +853,7 @@ pub fn choice_template(
     format!(
         r#"{anonymous_option}
 
-{comments}{derive}
+{comments}{derive}{extensible}
 pub enum {name} {{
   {options}
 }}
@@ -683,7 +905,7 @@ impl<T, O: Extend<T> + Debug + 'static> Encode<T, O> for {name} {{
 
   {ENCODER_SIGNATURE}
   {{
-    let mut choice_encoder = E::encode_choice({choice_descriptor})?;
+    let choice_encoder = E::encode_choice({choice_descriptor})?;
     Ok(Box::new(move |encodable, output| (*choice_encoder)(encodable, output)))
   }}
 }}
@@ -763,30 +985,56 @@ impl<'a, I: AsBytes + Debug + 'a> Decode<'a, I> for {name} {{
     )
 }
 
-pub fn _information_object_set_template(
+pub fn information_object_set_template(
     comments: String,
+    derive: &str,
+    inner_types: String,
     name: String,
     options: String,
-    key_type: String,
-    for_key_branches: String,
+    default_option: String,
+    decoder_for_key_branches: String,
+    unknown_key_decoder: String,
+    encoder_for_key_branches: String,
+    unknown_key_encoder: String,
+    members: String,
 ) -> String {
     format!(
-        r#"{comments}
+        r#"{inner_types}
+
+{comments}{derive}
 pub enum {name} {{
   {options}
 }}
 
-impl<I: AsBytes + Debug> DecoderForKey<I, {key_type}> for {name} {{
-  fn decoder_for_key<I, D>(
-    key: {key_type},
-  ) -> Result<fn(I) -> IResult<I, Self>, DecodingError>
+{members}
+
+impl Default for {name} {{
+  fn default() -> Self {{
+    {default_option}
+  }}
+}}
+
+impl<'a, I: AsBytes + Debug + 'a> DecoderForKey<'a, I, i128> for {name} {{
+  fn decoder_for_key<D>(key: i128) -> Result<fn(I) -> IResult<I, Self>, DecodingError<I>>
   where
-    D: Decoder,
-    T: PartialEq,
-    Self: Sized, 
-  {{
+      D: Decoder<'a, I>,
+      i128: PartialEq,
+      Self: Sized {{
+    match key {{
+      {decoder_for_key_branches}
+      {unknown_key_decoder}
+    }}
+  }}
+}}
+
+impl<T, O: Extend<T> + Debug + 'static> EncoderForIndex<T, O> for {name} {{
+  fn encoder_for_index<E>(key: i128) -> Result<fn(&Self, O) -> Result<O, EncodingError>, EncodingError>
+  where
+      E: Encoder<T, O>,
+      Self: Sized {{
     match key {{
-      {for_key_branches}
+      {encoder_for_key_branches}
+      {unknown_key_encoder}
     }}
   }}
 }}
@@ -1,6 +1,8 @@
 use asnr_grammar::{
+    constraints::{Constraint, ContentConstraint, RelationalConstraint},
     information_object::{
-        InformationObjectClass, ObjectFieldIdentifier, SyntaxApplication, SyntaxExpression,
+        InformationObjectClass, InformationObjectField, InformationObjectFields,
+        ObjectFieldIdentifier, ObjectSet, ObjectSetValue, SyntaxApplication, SyntaxExpression,
         SyntaxToken,
     },
     types::*,
@@ -12,14 +14,15 @@ use crate::{
     generator::{
         error::{GeneratorError, GeneratorErrorType},
         generate,
+        templates::{compose_type_name, fits_integer_type, is_extension_group},
+        GeneratorOptions, NamingConfig,
     },
     Framework,
 };
 
-use super::builder::StringifiedNameType;
+use super::{builder::StringifiedNameType, declarations::Declare};
 
 /// Resolves the custom syntax declared in an information object class' WITH SYNTAX clause
-#[allow(dead_code)]
 pub fn resolve_syntax(
     class: &InformationObjectClass,
     application: &Vec<SyntaxApplication>,
@@ -45,7 +48,14 @@ pub fn resolve_syntax(
         if let Some(expr) = appl_iter.next() {
             if compare_tokens(&token, expr) {
                 match expr {
-                    SyntaxApplication::ObjectSetDeclaration(_) => todo!(),
+                    SyntaxApplication::ObjectSetDeclaration(_) => {
+                        return Err(GeneratorError {
+                            top_level_declaration: None,
+                            details: "Object sets within information objects are not supported yet"
+                                .into(),
+                            kind: GeneratorErrorType::UnsupportedConstruct,
+                        })
+                    }
                     SyntaxApplication::TypeReference(t) => {
                         if let Some(index) = class.fields.iter().enumerate().find_map(|(i, v)| {
                             (v.identifier
@@ -101,6 +111,46 @@ pub fn resolve_syntax(
     }
 }
 
+/// Resolves the fields of an information object that is declared in the default syntax
+pub fn resolve_default_syntax(
+    class: &InformationObjectClass,
+    fields: &Vec<InformationObjectField>,
+) -> Result<(ASN1Value, Vec<ASN1Type>), GeneratorError> {
+    let key = class.fields.iter().find(|f| f.is_unique).and_then(|unique| {
+        fields.iter().find_map(|f| match f {
+            InformationObjectField::FixedValueField(v)
+                if v.identifier == unique.identifier.identifier() =>
+            {
+                Some(v.value.clone())
+            }
+            _ => None,
+        })
+    });
+    // the types are ordered like the class fields that they are declared for
+    let types = class
+        .fields
+        .iter()
+        .filter_map(|class_field| {
+            fields.iter().find_map(|f| match f {
+                InformationObjectField::TypeField(t)
+                    if t.identifier == class_field.identifier.identifier() =>
+                {
+                    Some(t.r#type.clone())
+                }
+                _ => None,
+            })
+        })
+        .collect();
+    match key {
+        Some(k) => Ok((k, types)),
+        None => Err(GeneratorError {
+            top_level_declaration: None,
+            details: "Could not find class key!".into(),
+            kind: GeneratorErrorType::MissingClassKey,
+        }),
+    }
+}
+
 fn flatten_tokens(expressions: &Vec<SyntaxExpression>) -> Vec<(bool, SyntaxToken)> {
     iter_expressions(expressions, false)
         .into_iter()
@@ -152,18 +202,31 @@ pub fn format_comments(comments: &String) -> String {
     }
 }
 
-pub fn format_enumeral(acc: String, enumeral: &Enumeral) -> String {
-    let rust_name = to_rust_title_case(&enumeral.name);
+/// Formats an enumeral as a variant of the enum generated for its ENUMERATED.
+/// If serde is derived, variants that are not named like their enumeral are renamed to it.
+pub fn format_enumeral(
+    acc: String,
+    enumeral: &Enumeral,
+    naming: &NamingStrategy,
+    serde: bool,
+) -> String {
+    let rust_name = naming.apply(&enumeral.name);
     let name = if acc.contains(&format!("\t{} = ", &rust_name)) {
         enumeral.name.replace("-", "_")
     } else {
         rust_name
     };
+    let rename = if serde && name != enumeral.name {
+        format!("#[serde(rename = \"{}\")]\n\t", enumeral.name)
+    } else {
+        String::new()
+    };
     acc + "\n\t" + &enumeral
         .description
         .as_ref()
         .map(|desc| "/// ".to_owned() + desc + "\n\t")
         .unwrap_or_default()
+        + &rename
         + &name
         + " = "
         + &enumeral.index.to_string()
@@ -207,33 +270,185 @@ pub fn handle_duplicate_options(input: &mut Vec<ChoiceOption>) {
 }
 
 pub fn format_option_from_int(args: (usize, &StringifiedNameType)) -> String {
+    let (t, value) = match unwrap_box(&args.1.r#type) {
+        Some(boxed) => (boxed, "Box::new(v)"),
+        None => (args.1.r#type.as_str(), "v"),
+    };
     format!(
-        r#"x if x == {index} => Ok(|input| {{
-    {t}::decode::<D>(input).map(|(r, v)|(r, Self::{name}(v)))
+        r#"{index} => Ok(|input| {{
+    {t}::decode::<D>(input).map(|(r, v)|(r, Self::{name}({value})))
   }}),"#,
         index = args.0,
         name = args.1.name,
-        t = args.1.r#type
     )
 }
 
 pub fn format_option_encoder_from_int(args: (usize, &StringifiedNameType)) -> String {
+    let (t, value) = match unwrap_box(&args.1.r#type) {
+        Some(boxed) => (boxed, "*inner.clone()"),
+        None => (args.1.r#type.as_str(), "inner.clone()"),
+    };
     format!(
-        r#"x if x == {index} => Ok(|encodable, output| {{
+        r#"{index} => Ok(|encodable, output| {{
         if let Self::{name}(inner) = encodable {{
-          {t}::encode::<E>(inner.clone(), output)
+          {t}::encode::<E>({value}, output)
         }} else {{
-          Err(EncodingError {{ details: format!("Index {index} does not correspond to Choice option {name}!") }})
+          Err(EncodingError {{ details: "Index {index} does not correspond to Choice option {name}!".into() }})
         }}
       }}),"#,
         index = args.0,
         name = args.1.name,
-        t = args.1.r#type
     )
 }
 
-pub fn format_enumeral_from_int(acc: String, enumeral: &Enumeral) -> String {
-    let rust_name = to_rust_title_case(&enumeral.name);
+/// Resolves the objects of an information object set to their INTEGER keys and the type
+/// of the first type field of the class that they declare.
+/// Variants are named after the types they hold. Types that are declared inline are named
+/// after their key and generated as inner types, which are returned alongside.
+pub fn extract_object_set_options(
+    set: &ObjectSet,
+    class: &InformationObjectClass,
+    parent_name: &str,
+    naming: &NamingConfig,
+) -> Result<Vec<(i128, StringifiedNameType, Option<ASN1Type>)>, GeneratorError> {
+    let key_name = |key: i128| {
+        if key < 0 {
+            format!("KeyMinus{}", key.unsigned_abs())
+        } else {
+            format!("Key{key}")
+        }
+    };
+    let mut options = Vec::<(i128, StringifiedNameType, Option<ASN1Type>)>::new();
+    for value in &set.values {
+        let (key, types) = match value {
+            ObjectSetValue::Inline(InformationObjectFields::CustomSyntax(s)) => {
+                resolve_syntax(class, s)?
+            }
+            ObjectSetValue::Inline(InformationObjectFields::DefaultSyntax(f)) => {
+                resolve_default_syntax(class, f)?
+            }
+            ObjectSetValue::Reference(r) => {
+                return Err(GeneratorError::new(
+                    None,
+                    &format!("Unresolved reference {r} in information object set"),
+                    GeneratorErrorType::MissingClassLink,
+                ))
+            }
+        };
+        let key = match key {
+            ASN1Value::Integer(i) => i,
+            k => {
+                return Err(GeneratorError::new(
+                    None,
+                    &format!("Information object sets with key {k:?} are not yet supported"),
+                    GeneratorErrorType::UnsupportedObjectSetKey,
+                ))
+            }
+        };
+        let (mut name, rtype, inner_type) = match types.into_iter().next() {
+            Some(ASN1Type::ElsewhereDeclaredType(d)) => (
+                to_rust_title_case(&d.identifier),
+                to_rust_title_case(&d.identifier),
+                None,
+            ),
+            Some(t) => (
+                key_name(key),
+                inner_name(&key_name(key), parent_name, naming),
+                Some(t),
+            ),
+            None => {
+                return Err(GeneratorError::new(
+                    None,
+                    &format!("Information object with key {key} declares no type"),
+                    GeneratorErrorType::SyntaxMismatch,
+                ))
+            }
+        };
+        if options.iter().any(|(_, o, _)| o.name == name) {
+            name += &key_name(key);
+        }
+        options.push((
+            key,
+            StringifiedNameType {
+                name,
+                r#type: rtype,
+                flattened: None,
+                object_set_lookup: None,
+            },
+            inner_type,
+        ));
+    }
+    Ok(options)
+}
+
+/// Formats a sealed trait that is implemented by the types of the objects in an information
+/// object set, along with conversions of the member types into the set.
+/// Types that are held by more than one object are left out, since their key is ambiguous.
+pub fn format_object_set_members(set_name: &str, options: &[&StringifiedNameType]) -> String {
+    let sealed = to_rust_snake_case(&format!("{set_name}Member"));
+    let members = options
+        .iter()
+        .filter(|o| {
+            options
+                .iter()
+                .filter(|other| other.r#type == o.r#type)
+                .count()
+                == 1
+        })
+        .map(|o| {
+            format!(
+                r#"impl {sealed}::Sealed for {t} {{}}
+impl {set_name}Member for {t} {{}}
+
+impl From<{t}> for {set_name} {{
+  fn from(value: {t}) -> Self {{
+    Self::{name}(value)
+  }}
+}}"#,
+                t = o.r#type,
+                name = o.name,
+            )
+        })
+        .collect::<Vec<String>>()
+        .join("\n\n");
+    format!(
+        r#"/// Implemented by the types of the objects in [{set_name}]
+pub trait {set_name}Member: {sealed}::Sealed + Into<{set_name}> {{}}
+
+mod {sealed} {{
+  pub trait Sealed {{}}
+}}
+
+{members}"#
+    )
+}
+
+pub fn format_object_decoder_for_key(key: i128, option: &StringifiedNameType) -> String {
+    format!(
+        r#"{key} => Ok(|input| {{
+    D::decode_open_type_with(input, {t}::decode::<D>).map(|(r, v)| (r, Self::{name}(v)))
+  }}),"#,
+        t = option.r#type,
+        name = option.name,
+    )
+}
+
+pub fn format_object_encoder_for_key(key: i128, option: &StringifiedNameType) -> String {
+    format!(
+        r#"{key} => Ok(|encodable, output| {{
+        if let Self::{name}(inner) = encodable {{
+          E::encode_open_type_with(inner, |inner, output| {t}::encode::<E>(inner.clone(), output), output)
+        }} else {{
+          Err(EncodingError {{ details: "Key {key} does not correspond to object {name}!".into() }})
+        }}
+      }}),"#,
+        t = option.r#type,
+        name = option.name,
+    )
+}
+
+pub fn format_enumeral_from_int(acc: String, enumeral: &Enumeral, naming: &NamingStrategy) -> String {
+    let rust_name = naming.apply(&enumeral.name);
     let name = if acc.contains(&format!("x if x == Self::{rust_name} as i128 => Ok(Self::{rust_name}),")) {
         enumeral.name.replace("-","_")
     } else {
@@ -242,14 +457,87 @@ pub fn format_enumeral_from_int(acc: String, enumeral: &Enumeral) -> String {
     acc + "\n\t\t  " + &format!("x if x == Self::{name} as i128 => Ok(Self::{name}),")
 }
 
+/// Formats `Display`, `FromStr`, and `TryFrom<&str>` implementations for an ENUMERATED.
+/// Enumerals are displayed by their ASN1 names and parsed from both their ASN1 and Rust names.
+pub fn format_enumeral_string_conversions(
+    name: &str,
+    members: &[Enumeral],
+    extensible: bool,
+    naming: &NamingStrategy,
+) -> String {
+    let mut variants = Vec::<(String, &String)>::new();
+    for enumeral in members {
+        let rust_name = naming.apply(&enumeral.name);
+        let variant = if variants.iter().any(|(v, _)| v == &rust_name) {
+            enumeral.name.replace("-", "_")
+        } else {
+            rust_name
+        };
+        variants.push((variant, &enumeral.name));
+    }
+    let mut display_arms = variants
+        .iter()
+        .map(|(variant, asn_name)| format!(r#"Self::{variant} => write!(f, "{asn_name}"),"#))
+        .collect::<Vec<String>>();
+    if extensible {
+        display_arms.push(r#"Self::UnknownExtension => write!(f, "UnknownExtension"),"#.into());
+    }
+    let from_str_arms = variants
+        .iter()
+        .map(|(variant, asn_name)| {
+            if variant == *asn_name {
+                format!(r#""{asn_name}" => Ok(Self::{variant}),"#)
+            } else {
+                format!(r#""{asn_name}" | "{variant}" => Ok(Self::{variant}),"#)
+            }
+        })
+        .collect::<Vec<String>>();
+    format!(
+        r#"
+impl core::fmt::Display for {name} {{
+  fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {{
+    match self {{
+      {display_arms}
+    }}
+  }}
+}}
+
+impl core::str::FromStr for {name} {{
+  type Err = DecodingError<[u8;0]>;
+
+  fn from_str(s: &str) -> Result<Self, Self::Err> {{
+    match s {{
+      {from_str_arms}
+      _ => Err(DecodingError {{
+        details: format!("Invalid enumeral {{s}} of {name}"),
+        kind: DecodingErrorType::GenericParsingError,
+        input: None
+      }}),
+    }}
+  }}
+}}
+
+impl TryFrom<&str> for {name} {{
+  type Error = DecodingError<[u8;0]>;
+
+  fn try_from(s: &str) -> Result<Self, Self::Error> {{
+    s.parse()
+  }}
+}}
+"#,
+        display_arms = display_arms.join("\n      "),
+        from_str_arms = from_str_arms.join("\n      "),
+    )
+}
+
 pub fn format_distinguished_values(tld: &ToplevelTypeDeclaration) -> String {
     let name = &to_rust_title_case(&tld.name);
     match &tld.r#type {
-        asnr_grammar::ASN1Type::Integer(i) => match &i.distinguished_values {
+        asnr_grammar::ASN1Type::BitString(b) => match &b.distinguished_values {
             Some(d) => {
                 let d_vals = d
                     .iter()
-                    .map(format_distinguished_int_value)
+                    .map(format_distinguished_bit_value)
                     .collect::<Vec<String>>()
                     .join("\n  ");
                 format!(
@@ -262,31 +550,60 @@ impl {name} {{
             }
             None => "".into(),
         },
-        asnr_grammar::ASN1Type::BitString(b) => match &b.distinguished_values {
-            Some(d) => {
-                let d_vals = d
-                    .iter()
-                    .map(format_distinguished_bit_value)
-                    .collect::<Vec<String>>()
-                    .join("\n  ");
-                format!(
-                    r#"
+        _ => "".into(),
+    }
+}
+
+/// Formats the distinguished values of an INTEGER as associated constants and predicates.
+/// Constants are only declared for values that fit the generated `integer_type`.
+pub fn format_distinguished_int_values(name: &String, integer: &Integer, integer_type: &str) -> String {
+    match &integer.distinguished_values {
+        Some(d) => {
+            let d_vals = d
+                .iter()
+                .filter(|v| fits_integer_type(v.value, integer_type))
+                .map(format_distinguished_int_const)
+                .chain(d.iter().map(format_distinguished_int_value))
+                .collect::<Vec<String>>()
+                .join("\n  ");
+            let name = to_rust_title_case(name);
+            format!(
+                r#"
 
 impl {name} {{
   {d_vals}
 }}"#
-                )
-            }
-            None => "".into(),
-        },
-        _ => "".into(),
+            )
+        }
+        None => "".into(),
     }
 }
 
+/// Formats the index constant, getter, predicate and setter of a named bit.
+/// Setting a bit beyond the current length grows the bit string.
 pub fn format_distinguished_bit_value(value: &DistinguishedValue) -> String {
     let name = &to_rust_snake_case(&value.name);
+    let index = to_rust_const_case(&value.name);
     let i = value.value;
-    format!("pub fn is_{name}(&self) -> bool {{ *self.0.get({i}).unwrap_or(&false) }}")
+    format!(
+        r#"pub const {index}: usize = {i};
+
+  pub fn {name}(&self) -> bool {{ *self.0.get(Self::{index}).unwrap_or(&false) }}
+
+  pub fn is_{name}(&self) -> bool {{ self.{name}() }}
+
+  pub fn set_{name}(&mut self, value: bool) {{
+    let len = self.0.len().max(Self::{index} + 1);
+    self.0.resize(len, false);
+    self.0[Self::{index}] = value;
+  }}"#
+    )
+}
+
+pub fn format_distinguished_int_const(value: &DistinguishedValue) -> String {
+    let name = to_rust_const_case(&value.name);
+    let i = value.value;
+    format!("pub const {name}: Self = Self({i});")
 }
 
 pub fn format_distinguished_int_value(value: &DistinguishedValue) -> String {
@@ -297,21 +614,26 @@ pub fn format_distinguished_int_value(value: &DistinguishedValue) -> String {
 
 pub fn flatten_nested_sequence_members(
     members: &Vec<SequenceOrSetMember>,
-    parent_name: &String,
+    parent_name: &str,
+    custom_derive: Option<&str>,
+    options: &GeneratorOptions,
 ) -> Result<Vec<String>, GeneratorError> {
     members
         .iter()
         .filter_map(|i| match i.r#type {
             ASN1Type::ElsewhereDeclaredType(_) => None,
             ASN1Type::InformationObjectFieldReference(_) => None,
-            _ => Some(declare_inner_sequence_member(i, parent_name)),
+            _ if flattened_member_type(&i.r#type, options).is_some() => None,
+            _ => Some(declare_inner_sequence_member(i, parent_name, custom_derive, options)),
         })
         .collect::<Result<Vec<String>, GeneratorError>>()
 }
 
 pub fn flatten_nested_choice_options(
     options: &Vec<ChoiceOption>,
-    parent_name: &String,
+    parent_name: &str,
+    custom_derive: Option<&str>,
+    generator_options: &GeneratorOptions,
 ) -> Vec<String> {
     options
         .iter()
@@ -319,13 +641,14 @@ pub fn flatten_nested_choice_options(
             ASN1Type::ElsewhereDeclaredType(_) => false,
             _ => true,
         })
-        .map(|i| declare_inner_choice_option(i, parent_name).unwrap())
+        .map(|i| declare_inner_choice_option(i, parent_name, custom_derive, generator_options).unwrap())
         .collect::<Vec<String>>()
 }
 
 pub fn extract_choice_options(
     options: &Vec<ChoiceOption>,
-    parent_name: &String,
+    parent_name: &str,
+    naming: &NamingConfig,
 ) -> Vec<StringifiedNameType> {
     options
         .iter()
@@ -336,18 +659,56 @@ pub fn extract_choice_options(
             } else {
                 rust_name
             };
-            let rtype = match &m.r#type {
+            let mut rtype = match &m.r#type {
                 ASN1Type::ElsewhereDeclaredType(d) => to_rust_title_case(&d.identifier),
-                _ => inner_name(&m.name, parent_name),
+                _ => inner_name(&m.name, parent_name, naming),
             };
+            if m.is_recursive {
+                rtype = format!("Box<{rtype}>");
+            }
             acc.push(StringifiedNameType {
                 name,
                 r#type: rtype,
+                flattened: None,
+                object_set_lookup: None,
             });
             acc
         })
 }
 
+/// Formats a CHOICE value as the construction of the chosen alternative.
+/// Alternatives of anonymous types are wrapped in the inner types declared by the
+/// type generator, alternatives of referenced types in the referenced types.
+/// Referenced types are only known for values that the validator has linked to their types.
+pub fn format_choice_value(
+    parent_name: &String,
+    option: &String,
+    value: &ASN1Value,
+    naming: &NamingConfig,
+) -> Result<String, GeneratorError> {
+    let type_name = match value {
+        ASN1Value::LinkedNestedValue { r#type, .. } => match r#type.as_ref() {
+            ASN1Type::ElsewhereDeclaredType(e) => to_rust_title_case(&e.identifier),
+            _ => inner_name(option, parent_name, naming),
+        },
+        _ => inner_name(option, parent_name, naming),
+    };
+    let mut value = value;
+    while let ASN1Value::LinkedNestedValue { value: linked, .. } = value {
+        value = linked.as_ref();
+    }
+    let inner_value = match value {
+        ASN1Value::Choice(inner_option, inner_value) => {
+            format_choice_value(&type_name, inner_option, inner_value, naming)?
+        }
+        v => format!("{type_name}({})", v.value_as_string(None)?),
+    };
+    Ok(format!(
+        "{parent_name}::{}({inner_value})",
+        to_rust_title_case(option)
+    ))
+}
+
 pub fn format_option_declaration(members: &Vec<StringifiedNameType>) -> String {
     members
         .iter()
@@ -358,30 +719,142 @@ pub fn format_option_declaration(members: &Vec<StringifiedNameType>) -> String {
 
 pub fn extract_sequence_members(
     members: &Vec<SequenceOrSetMember>,
-    parent_name: &String,
+    parent_name: &str,
     index_of_first_extension: Option<usize>,
+    options: &GeneratorOptions,
 ) -> Vec<StringifiedNameType> {
     members
         .iter()
         .enumerate()
         .map(|(index, m)| {
             let name = to_rust_snake_case(&m.name);
-            let mut rtype = match &m.r#type {
-                ASN1Type::ElsewhereDeclaredType(d) => to_rust_title_case(&d.identifier),
-                ASN1Type::InformationObjectFieldReference(_) => "Asn1Open".to_string(),
-                _ => inner_name(&m.name, parent_name),
+            let flattened_type = flattened_member_type(&m.r#type, options);
+            let flattened = flattened_type.is_some().then(|| m.r#type.clone());
+            let object_set_lookup = object_set_lookup(m, members, options);
+            let mut rtype = match (&m.r#type, flattened_type, &object_set_lookup) {
+                (_, Some(primitive), _) => primitive,
+                (ASN1Type::ElsewhereDeclaredType(d), ..) => to_rust_title_case(&d.identifier),
+                (ASN1Type::InformationObjectFieldReference(_), _, Some((set, _))) => set.clone(),
+                (ASN1Type::InformationObjectFieldReference(_), ..) => "Asn1Open".to_string(),
+                _ => inner_name(&m.name, parent_name, &options.naming),
             };
+            if m.is_recursive {
+                rtype = format!("Box<{rtype}>");
+            }
             if m.is_optional || index >= index_of_first_extension.unwrap_or(usize::MAX) {
                 rtype = String::from("Option<") + &rtype + ">"
             }
             StringifiedNameType {
                 name,
                 r#type: rtype,
+                flattened,
+                object_set_lookup,
             }
         })
         .collect()
 }
 
+/// Returns the name of the information object set that the type of an open type member
+/// is looked up in, and the expression of the linked key member's value.
+/// The validator only keeps the relational constraints of table constraints that support a lookup.
+fn object_set_lookup(
+    member: &SequenceOrSetMember,
+    members: &Vec<SequenceOrSetMember>,
+    options: &GeneratorOptions,
+) -> Option<(String, String)> {
+    let ASN1Type::InformationObjectFieldReference(reference) = &member.r#type else {
+        return None;
+    };
+    reference.constraints.iter().find_map(|c| match c {
+        Constraint::TableConstraint(table) => {
+            match (&table.object_set.values[..], &table.linked_fields[..]) {
+                (
+                    [ObjectSetValue::Reference(set)],
+                    [RelationalConstraint {
+                        field_name,
+                        level: 0,
+                    }],
+                ) => {
+                    let key_member = members.iter().find(|m| &m.name == field_name)?;
+                    let key = to_rust_snake_case(&key_member.name);
+                    if flattened_member_type(&key_member.r#type, options).is_some() {
+                        Some((to_rust_title_case(set), key))
+                    } else {
+                        Some((to_rust_title_case(set), key + ".0"))
+                    }
+                }
+                _ => None,
+            }
+        }
+        _ => None,
+    })
+}
+
+/// Returns the plain Rust type of an inline primitive SEQUENCE member type,
+/// if the generator is configured to flatten primitive members
+fn flattened_member_type(r#type: &ASN1Type, options: &GeneratorOptions) -> Option<String> {
+    if !options.flatten_primitive_members {
+        return None;
+    }
+    primitive_member_type(r#type, options)
+}
+
+/// Returns the plain Rust type of an inline primitive SEQUENCE member type
+fn primitive_member_type(r#type: &ASN1Type, options: &GeneratorOptions) -> Option<String> {
+    match r#type {
+        ASN1Type::Integer(_) if options.force_i128_integers => Some("i128".into()),
+        ASN1Type::Integer(i) => Some(i.type_token()),
+        ASN1Type::Boolean => Some("bool".into()),
+        ASN1Type::CharacterString(_) => Some("String".into()),
+        ASN1Type::OctetString(_) => Some("Vec<u8>".into()),
+        ASN1Type::BitString(_) => Some("Vec<bool>".into()),
+        _ => None,
+    }
+}
+
+/// Formats the decoding of a flattened primitive member.
+/// Since the member's Rust type does not implement `Decode`, the decoder is called directly
+/// with the member type's descriptor, so that its constraints are honored.
+fn format_flattened_decoder(r#type: &ASN1Type, rtype: &str) -> String {
+    match r#type {
+        ASN1Type::Integer(i) => format!("D::decode_integer::<{rtype}>({})?(input)", i.declare()),
+        ASN1Type::CharacterString(c) => {
+            format!("D::decode_character_string({})?(input)", c.declare())
+        }
+        ASN1Type::OctetString(o) => format!("D::decode_octet_string({})?(input)", o.declare()),
+        ASN1Type::BitString(b) => format!("D::decode_bit_string({})?(input)", b.declare()),
+        _ => "D::decode_boolean(input)".into(),
+    }
+}
+
+/// Formats the encoding of the flattened primitive member `value`, which is a reference
+/// to the member's value. See `format_flattened_decoder`.
+fn format_flattened_encoder(r#type: &ASN1Type, rtype: &str, value: &str) -> String {
+    // `value` is a reference, which is dereferenced for `Copy` types
+    let copied = value
+        .strip_prefix('&')
+        .map_or(format!("*{value}"), str::to_owned);
+    match r#type {
+        ASN1Type::Integer(i) => format!(
+            "E::encode_integer::<{rtype}>({})?({copied}, output)",
+            i.declare()
+        ),
+        ASN1Type::CharacterString(c) => format!(
+            "E::encode_character_string({})?({value}.as_str(), output)",
+            c.declare()
+        ),
+        ASN1Type::OctetString(o) => format!(
+            "E::encode_octet_string({})?({value}.as_slice(), output)",
+            o.declare()
+        ),
+        ASN1Type::BitString(b) => format!(
+            "E::encode_bit_string({})?({value}.clone(), output)",
+            b.declare()
+        ),
+        _ => format!("E::encode_boolean({copied}, output)"),
+    }
+}
+
 pub fn format_member_declaration(members: &Vec<StringifiedNameType>) -> String {
     members
         .iter()
@@ -411,7 +884,38 @@ pub fn format_decode_member_body(members: &Vec<StringifiedNameType>) -> String {
         .iter()
         .enumerate()
         .map(|(i, m)| {
-            if m.r#type.starts_with("Option<") {
+            if let Some((set, key)) = &m.object_set_lookup {
+                let wrap = if m.r#type.starts_with("Option<") {
+                    ".map(|(i, v)| (i, Some(v)))"
+                } else {
+                    ""
+                };
+                format!(
+                    "{i} => {{ (input, self.{name}) = {set}::decoder_for_key::<D>(self.{key} as i128)?(input){wrap}? }},",
+                    name = to_rust_snake_case(&m.name)
+                )
+            } else if let Some(flattened) = &m.flattened {
+                let decoder = format_flattened_decoder(flattened, unwrap_option(&m.r#type));
+                let wrap = if m.r#type.starts_with("Option<") {
+                    ".map(|(i, v)| (i, Some(v)))"
+                } else {
+                    ""
+                };
+                format!(
+                    "{i} => {{ (input, self.{name}) = {decoder}{wrap}? }},",
+                    name = to_rust_snake_case(&m.name)
+                )
+            } else if let Some(boxed) = unwrap_box(unwrap_option(&m.r#type)) {
+                let value = if m.r#type.starts_with("Option<") {
+                    "Some(Box::new(v))"
+                } else {
+                    "Box::new(v)"
+                };
+                format!(
+                    "{i} => {{ (input, self.{name}) = {boxed}::decode::<D>(input).map(|(i, v)| (i, {value}))? }},",
+                    name = to_rust_snake_case(&m.name)
+                )
+            } else if m.r#type.starts_with("Option<") {
                 format!(
                     "{i} => {{ (input, self.{name}) = {t}::decode::<D>(input).map(|(i, v)| (i, Some(v)))? }},",
                     t = &m.r#type[7..m.r#type.len() - 1],
@@ -434,23 +938,66 @@ pub fn format_encoder_member_body(members: &Vec<StringifiedNameType>) -> String
         .iter()
         .enumerate()
         .map(|(i, m)| {
-            if m.r#type.starts_with("Option<") {
+            if let Some((set, key)) = &m.object_set_lookup {
+                let name = to_rust_snake_case(&m.name);
+                if m.r#type.starts_with("Option<") {
+                    format!(
+                        r#"{i} => Ok(|parent, output| {{
+                        if let Some(value) = &parent.{name} {{
+                        {set}::encoder_for_index::<E>(parent.{key} as i128)?(value, output)
+                    }} else {{
+                        Ok(output)
+                    }}
+                }}),"#
+                    )
+                } else {
+                    format!(
+                        "{i} => Ok(|parent, output| {set}::encoder_for_index::<E>(parent.{key} as i128)?(&parent.{name}, output)),"
+                    )
+                }
+            } else if let Some(flattened) = &m.flattened {
+                let name = to_rust_snake_case(&m.name);
+                let rtype = unwrap_option(&m.r#type);
+                if m.r#type.starts_with("Option<") {
+                    format!(
+                        r#"{i} => Ok(|parent, output| {{
+                        if let Some(value) = &parent.{name} {{
+                        {}
+                    }} else {{
+                        Ok(output)
+                    }}
+                }}),"#,
+                        format_flattened_encoder(flattened, rtype, "value")
+                    )
+                } else {
+                    format!(
+                        "{i} => Ok(|parent, output| {}),",
+                        format_flattened_encoder(flattened, rtype, &format!("&parent.{name}"))
+                    )
+                }
+            } else if m.r#type.starts_with("Option<") {
+                let (t, value) = match unwrap_box(unwrap_option(&m.r#type)) {
+                    Some(boxed) => (boxed, "*value"),
+                    None => (unwrap_option(&m.r#type), "value"),
+                };
                 format!(
                     r#"{i} => Ok(|parent, output| {{
                         if let Some(value) = parent.{name}.clone() {{
-                        {t}::encode::<E>(value, output)
+                        {t}::encode::<E>({value}, output)
                     }} else {{
-                        return Ok(output);
+                        Ok(output)
                     }}
                 }}),"#,
                     name = to_rust_snake_case(&m.name),
-                    t = &m.r#type[7..m.r#type.len() - 1],
                 )
             } else {
+                let (t, deref) = match unwrap_box(&m.r#type) {
+                    Some(boxed) => (boxed, "*"),
+                    None => (m.r#type.as_str(), ""),
+                };
                 format!(
-                    "{i} => Ok(|parent, output| {t}::encode::<E>(parent.{name}.clone(), output)),",
+                    "{i} => Ok(|parent, output| {t}::encode::<E>({deref}parent.{name}.clone(), output)),",
                     name = to_rust_snake_case(&m.name),
-                    t = m.r#type,
                 )
             }
         })
@@ -465,7 +1012,7 @@ pub fn format_has_optional_body(members: &Vec<StringifiedNameType>) -> String {
         .map(|(i, m)| {
             if m.r#type.starts_with("Option<") {
                 format!(
-                    r#"{i} => self.{name} != None,"#,
+                    r#"{i} => self.{name}.is_some(),"#,
                     name = to_rust_snake_case(&m.name),
                 )
             } else {
@@ -476,45 +1023,582 @@ pub fn format_has_optional_body(members: &Vec<StringifiedNameType>) -> String {
         .join("\n      ")
 }
 
+/// Formats the `default_<member>` methods of a SEQUENCE's DEFAULT members, getters that
+/// return a DEFAULT member's value or its DEFAULT value if it is absent, and a
+/// `new_with_defaults` constructor that populates them. If `materialize_defaults` is set,
+/// `Default` is implemented by means of `new_with_defaults`, so that absent DEFAULT members
+/// are filled in when decoding. Returns an empty string if no DEFAULT value can be represented.
+pub fn format_default_methods(
+    members: &[SequenceOrSetMember],
+    stringified_members: &[StringifiedNameType],
+    name: &String,
+    options: &GeneratorOptions,
+) -> String {
+    let default_methods = members
+        .iter()
+        .zip(stringified_members)
+        .filter_map(|(m, s)| {
+            let rtype = unwrap_option(&s.r#type);
+            let value = format_default_value(m.default_value.as_ref()?, s, &m.r#type, options)?;
+            let getter_type = match primitive_member_type(&m.r#type, options) {
+                Some(primitive) if s.flattened.is_none() => Some(primitive),
+                _ => None,
+            };
+            // flattened booleans and numbers are `Copy`
+            let clone = match m.r#type {
+                ASN1Type::Boolean | ASN1Type::Integer(_) | ASN1Type::Real(_)
+                    if s.flattened.is_some() =>
+                {
+                    ""
+                }
+                _ => ".clone()",
+            };
+            Some((to_rust_snake_case(&m.name), rtype, value, getter_type, clone))
+        })
+        .collect::<Vec<_>>();
+    if default_methods.is_empty() {
+        return String::new();
+    }
+    let initializers = stringified_members
+        .iter()
+        .map(|s| {
+            let member = to_rust_snake_case(&s.name);
+            if default_methods.iter().any(|(name, ..)| name == &member) {
+                format!("{member}: Some(Self::default_{member}()),")
+            } else {
+                format!("{member}: Default::default(),")
+            }
+        })
+        .collect::<Vec<String>>()
+        .join("\n      ");
+    let methods = default_methods
+        .iter()
+        .map(|(member, rtype, value, getter_type, clone)| {
+            let (getter_type, inner) = match getter_type {
+                Some(primitive) => (primitive.as_str(), ".0"),
+                None => (*rtype, ""),
+            };
+            format!(
+                r#"pub fn default_{member}() -> {rtype} {{
+    {value}
+  }}
+
+  /// Returns the value of `{member}`, or its DEFAULT value if `{member}` is absent
+  pub fn {member}(&self) -> {getter_type} {{
+    self.{member}{clone}.unwrap_or_else(Self::default_{member}){inner}
+  }}"#
+            )
+        })
+        .collect::<Vec<String>>()
+        .join("\n\n  ");
+    let default_impl = if options.materialize_defaults {
+        format!(
+            r#"
+
+impl Default for {name} {{
+  fn default() -> Self {{
+    Self::new_with_defaults()
+  }}
+}}"#
+        )
+    } else {
+        String::new()
+    };
+    format!(
+        r#"
+impl {name} {{
+  {methods}
+
+  pub fn new_with_defaults() -> Self {{
+    Self {{
+      {initializers}
+    }}
+  }}
+}}{default_impl}
+"#
+    )
+}
+
+/// Formats accessors that decode and encode the values contained in
+/// OCTET STRING members with a CONTAINING constraint on a type reference.
+/// The accessors use the codec's `decode_contents` and `encode_contents`.
+pub fn format_contained_type_accessors(
+    members: &[SequenceOrSetMember],
+    stringified_members: &[StringifiedNameType],
+    name: &String,
+    options: &GeneratorOptions,
+) -> String {
+    if !options.contained_type_accessors {
+        return String::new();
+    }
+    let accessors = members
+        .iter()
+        .zip(stringified_members)
+        .filter_map(|(m, s)| {
+            let ASN1Type::OctetString(octet_string) = &m.r#type else {
+                return None;
+            };
+            let contained = octet_string
+                .constraints
+                .iter()
+                .chain(&m.constraints)
+                .find_map(|c| match c {
+                    Constraint::ContentConstraint(ContentConstraint {
+                        containing: Some(ASN1Type::ElsewhereDeclaredType(d)),
+                        ..
+                    }) => Some(to_rust_title_case(&d.identifier)),
+                    _ => None,
+                })?;
+            let member = to_rust_snake_case(&m.name);
+            let is_optional = s.r#type.starts_with("Option<");
+            let (bytes, wrapped) = match s.flattened {
+                Some(_) => ("", "E::encode_contents(value)?".to_owned()),
+                None => (
+                    ".0",
+                    format!("{}(E::encode_contents(value)?)", unwrap_option(&s.r#type)),
+                ),
+            };
+            let optional_bytes = match s.flattened {
+                Some(_) => member.clone(),
+                None => format!("&{member}.0"),
+            };
+            let (decoded_type, decoded, wrapped) = if is_optional {
+                (
+                    format!("Option<{contained}>"),
+                    format!(
+                        "self.{member}.as_ref().map(|{member}| D::decode_contents({optional_bytes})).transpose()"
+                    ),
+                    format!("Some({wrapped})"),
+                )
+            } else {
+                (
+                    contained.clone(),
+                    format!("D::decode_contents(&self.{member}{bytes})"),
+                    wrapped,
+                )
+            };
+            Some(format!(
+                r#"/// Decodes the `{contained}` that `{member}` contains
+  pub fn decoded_{member}<'a, I: AsBytes + Debug + 'a, D: Decoder<'a, I>>(&'a self) -> Result<{decoded_type}, DecodingError<I>> {{
+    {decoded}
+  }}
+
+  /// Encodes `value` as the contents of `{member}`
+  pub fn set_{member}_from<T, O: Extend<T> + Debug + 'static, E: Encoder<T, O>>(&mut self, value: {contained}) -> Result<(), EncodingError> {{
+    self.{member} = {wrapped};
+    Ok(())
+  }}"#
+            ))
+        })
+        .collect::<Vec<String>>();
+    if accessors.is_empty() {
+        return String::new();
+    }
+    format!(
+        r#"
+impl {name} {{
+  {}
+}}
+"#,
+        accessors.join("\n\n  ")
+    )
+}
+
+/// Formats accessors for the members of a SEQUENCE's extension addition groups,
+/// which return `None` if the group is absent
+pub fn format_extension_group_accessors(
+    members: &[SequenceOrSetMember],
+    stringified_members: &[StringifiedNameType],
+    name: &String,
+    options: &GeneratorOptions,
+) -> String {
+    let accessors = members
+        .iter()
+        .zip(stringified_members)
+        .filter_map(|(m, s)| match &m.r#type {
+            ASN1Type::Sequence(group) if is_extension_group(&m.name) => Some((group, s)),
+            _ => None,
+        })
+        .flat_map(|(group, s)| {
+            let group_type = unwrap_option(&s.r#type);
+            extract_sequence_members(&group.members, group_type, None, options)
+                .into_iter()
+                .map(|inner| {
+                    let (member, group_member) = (&inner.name, &s.name);
+                    let (rtype, access) = if inner.r#type.starts_with("Option<") {
+                        (
+                            unwrap_option(&inner.r#type),
+                            format!("and_then(|group| group.{member}.as_ref())"),
+                        )
+                    } else {
+                        (inner.r#type.as_str(), format!("map(|group| &group.{member})"))
+                    };
+                    format!(
+                        r#"/// Returns `{member}` of the extension addition group `{group_member}`, if the group is present
+  pub fn {member}(&self) -> Option<&{rtype}> {{
+    self.{group_member}.as_ref().{access}
+  }}"#
+                    )
+                })
+                .collect::<Vec<String>>()
+        })
+        .collect::<Vec<String>>();
+    if accessors.is_empty() {
+        return String::new();
+    }
+    format!(
+        r#"
+impl {name} {{
+  {}
+}}
+"#,
+        accessors.join("\n\n  ")
+    )
+}
+
+/// SEQUENCEs with more members than this get a builder instead of a constructor
+const MAX_CONSTRUCTOR_MEMBERS: usize = 5;
+
+/// Formats a constructor for a SEQUENCE, or a builder if the SEQUENCE has many members.
+/// The constructor takes the required members, OPTIONAL and extension members
+/// are initialized with `None`, DEFAULT members with their DEFAULT values.
+pub fn format_constructor(
+    members: &[SequenceOrSetMember],
+    stringified_members: &[StringifiedNameType],
+    name: &String,
+    options: &GeneratorOptions,
+) -> String {
+    let defaults = members
+        .iter()
+        .zip(stringified_members)
+        .map(|(m, s)| {
+            m.default_value
+                .as_ref()
+                .and_then(|v| format_default_value(v, s, &m.r#type, options))
+                .map(|_| format!("Some({name}::default_{}())", s.name))
+        })
+        .collect::<Vec<Option<String>>>();
+    if members.len() > MAX_CONSTRUCTOR_MEMBERS {
+        return format_builder(members, stringified_members, &defaults, name, options);
+    }
+    let mut parameters = vec![];
+    let initializers = members
+        .iter()
+        .zip(stringified_members)
+        .zip(&defaults)
+        .map(|((m, s), default)| {
+            let member = &s.name;
+            if let Some(default) = default {
+                format!("{member}: {default},")
+            } else if s.r#type.starts_with("Option<") {
+                format!("{member}: None,")
+            } else {
+                let (parameter_type, value) = format_member_parameter(m, s, options);
+                parameters.push(format!("{member}: {parameter_type}"));
+                if &value == member {
+                    format!("{member},")
+                } else {
+                    format!("{member}: {value},")
+                }
+            }
+        })
+        .collect::<Vec<String>>()
+        .join("\n      ");
+    format!(
+        r#"
+impl {name} {{
+  pub fn new({parameters}) -> Self {{
+    Self {{
+      {initializers}
+    }}
+  }}
+}}
+"#,
+        parameters = parameters.join(", ")
+    )
+}
+
+/// Formats a builder for a SEQUENCE with many members. See `format_constructor`.
+/// Building fails with `None` if a required member has not been set.
+fn format_builder(
+    members: &[SequenceOrSetMember],
+    stringified_members: &[StringifiedNameType],
+    defaults: &[Option<String>],
+    name: &String,
+    options: &GeneratorOptions,
+) -> String {
+    let fields = stringified_members
+        .iter()
+        .map(|s| format!("{}: Option<{}>,", s.name, unwrap_option(&s.r#type)))
+        .collect::<Vec<String>>()
+        .join("\n  ");
+    let setters = members
+        .iter()
+        .zip(stringified_members)
+        .map(|(m, s)| {
+            let member = &s.name;
+            let (parameter_type, value) = format_member_parameter(m, s, options);
+            format!(
+                r#"pub fn {member}(mut self, {member}: {parameter_type}) -> Self {{
+    self.{member} = Some({value});
+    self
+  }}"#
+            )
+        })
+        .collect::<Vec<String>>()
+        .join("\n\n  ");
+    let initializers = stringified_members
+        .iter()
+        .zip(defaults)
+        .map(|(s, default)| {
+            let member = &s.name;
+            match default {
+                Some(default) => format!("{member}: self.{member}.or_else(|| {default}),"),
+                None if s.r#type.starts_with("Option<") => format!("{member}: self.{member},"),
+                None => format!("{member}: self.{member}?,"),
+            }
+        })
+        .collect::<Vec<String>>()
+        .join("\n      ");
+    format!(
+        r#"
+impl {name} {{
+  pub fn builder() -> {name}Builder {{
+    {name}Builder::default()
+  }}
+}}
+
+#[derive(Default)]
+pub struct {name}Builder {{
+  {fields}
+}}
+
+impl {name}Builder {{
+  {setters}
+
+  pub fn build(self) -> Option<{name}> {{
+    Some({name} {{
+      {initializers}
+    }})
+  }}
+}}
+"#
+    )
+}
+
+/// Returns the parameter type that a constructor or builder takes for `member`,
+/// along with the conversion of the parameter into the member's Rust type.
+/// Inline primitive members are passed as their plain Rust types and
+/// wrapped in their inner newtypes, recursive members are passed unboxed.
+fn format_member_parameter(
+    member: &SequenceOrSetMember,
+    stringified: &StringifiedNameType,
+    options: &GeneratorOptions,
+) -> (String, String) {
+    let parameter = &stringified.name;
+    let rtype = unwrap_option(&stringified.r#type);
+    if let Some(boxed) = unwrap_box(rtype) {
+        return (boxed.to_owned(), format!("Box::new({parameter})"));
+    }
+    match primitive_member_type(&member.r#type, options) {
+        Some(primitive) => {
+            let (parameter_type, value) = if primitive == "String" {
+                ("impl Into<String>".to_owned(), format!("{parameter}.into()"))
+            } else {
+                (primitive, parameter.clone())
+            };
+            if stringified.flattened.is_some() {
+                (parameter_type, value)
+            } else {
+                (parameter_type, format!("{rtype}({value})"))
+            }
+        }
+        None => (rtype.to_owned(), parameter.clone()),
+    }
+}
+
+/// Formats an ASN1 value as an expression of the generated Rust type of `member`.
+/// Returns `None` if the value cannot be represented, e.g. because the
+/// representation of an elsewhere declared type is unknown.
+fn format_default_value(
+    value: &ASN1Value,
+    member: &StringifiedNameType,
+    r#type: &ASN1Type,
+    options: &GeneratorOptions,
+) -> Option<String> {
+    let rtype = unwrap_option(&member.r#type);
+    if unwrap_box(rtype).is_some() {
+        return None;
+    }
+    if let ASN1Value::LinkedNestedValue {
+        r#type: linked_type,
+        value,
+    } = value
+    {
+        // the validator links composite values to the declarations of referenced types
+        let r#type = match r#type {
+            ASN1Type::ElsewhereDeclaredType(_) => linked_type.as_ref(),
+            r#type => r#type,
+        };
+        return format_default_value(value, member, r#type, options);
+    }
+    match (r#type, value) {
+        (ASN1Type::Null, ASN1Value::Null) => Some(rtype.to_owned()),
+        (ASN1Type::ElsewhereDeclaredType(_), ASN1Value::Integer(i)) => {
+            Some(format!("{rtype}({i})"))
+        }
+        (ASN1Type::ElsewhereDeclaredType(_), ASN1Value::Boolean(b)) => {
+            Some(format!("{rtype}({b})"))
+        }
+        (ASN1Type::ElsewhereDeclaredType(_), ASN1Value::String(s)) => {
+            Some(format!("{rtype}({s:?}.into())"))
+        }
+        (
+            ASN1Type::Enumerated(_) | ASN1Type::ElsewhereDeclaredType(_),
+            ASN1Value::EnumeratedValue { enumerable, .. },
+        ) => {
+            Some(format!("{rtype}::{}", options.naming.enumerals.apply(enumerable)))
+        }
+        (ASN1Type::Sequence(s) | ASN1Type::Set(s), ASN1Value::Sequence(fields)) => {
+            let stringified_members = extract_sequence_members(
+                &s.members,
+                rtype,
+                s.extensible,
+                options,
+            );
+            let initializers = s
+                .members
+                .iter()
+                .zip(&stringified_members)
+                .map(|(m, stringified)| {
+                    let member = to_rust_snake_case(&m.name);
+                    let field = fields.iter().find(|(name, _)| name == &m.name);
+                    let is_optional = stringified.r#type.starts_with("Option<");
+                    let field = match field.map(|(_, v)| v.as_ref()) {
+                        Some(ASN1Value::Optional(None)) => None,
+                        Some(ASN1Value::Optional(Some(v))) => Some(v.as_ref()),
+                        v => v,
+                    };
+                    match field {
+                        Some(v) => {
+                            let inner = format_default_value(v, stringified, &m.r#type, options)?;
+                            if is_optional {
+                                Some(format!("{member}: Some({inner})"))
+                            } else {
+                                Some(format!("{member}: {inner}"))
+                            }
+                        }
+                        None if is_optional => Some(format!("{member}: None")),
+                        None => None,
+                    }
+                })
+                .collect::<Option<Vec<String>>>()?
+                .join(", ");
+            Some(format!("{rtype} {{ {initializers} }}"))
+        }
+        _ => {
+            let primitive = format_primitive_value(value, r#type)?;
+            if member.flattened.is_some() {
+                Some(primitive)
+            } else {
+                Some(format!("{rtype}({primitive})"))
+            }
+        }
+    }
+}
+
+/// Formats an ASN1 value of a primitive type as an expression of its plain Rust type
+fn format_primitive_value(value: &ASN1Value, r#type: &ASN1Type) -> Option<String> {
+    match (r#type, value) {
+        (ASN1Type::Integer(_), ASN1Value::Integer(i)) => Some(i.to_string()),
+        (ASN1Type::Boolean, ASN1Value::Boolean(b)) => Some(b.to_string()),
+        (ASN1Type::CharacterString(_), ASN1Value::String(s)) => Some(format!("{s:?}.into()")),
+        (ASN1Type::OctetString(_), ASN1Value::BitString(bits)) if bits.len() % 8 == 0 => {
+            let bytes = bits
+                .chunks(8)
+                .map(|byte| {
+                    let octet = byte.iter().fold(0u8, |acc, bit| (acc << 1) | *bit as u8);
+                    format!("{octet:#04X}")
+                })
+                .collect::<Vec<String>>()
+                .join(", ");
+            Some(format!("vec![{bytes}]"))
+        }
+        (ASN1Type::BitString(_), ASN1Value::BitString(_)) => value.value_as_string(None).ok(),
+        _ => None,
+    }
+}
+
+fn unwrap_option(rtype: &str) -> &str {
+    rtype
+        .strip_prefix("Option<")
+        .and_then(|t| t.strip_suffix('>'))
+        .unwrap_or(rtype)
+}
+
+/// Returns the boxed type of a recursive member's Rust type, e.g. `Node` for `Box<Node>`
+fn unwrap_box(rtype: &str) -> Option<&str> {
+    rtype.strip_prefix("Box<").and_then(|t| t.strip_suffix('>'))
+}
+
 fn declare_inner_sequence_member(
     member: &SequenceOrSetMember,
-    parent_name: &String,
+    parent_name: &str,
+    custom_derive: Option<&str>,
+    options: &GeneratorOptions,
 ) -> Result<String, GeneratorError> {
     generate(
         &Framework::Asnr,
         ToplevelDeclaration::Type(ToplevelTypeDeclaration {
             parameterization: None,
             comments: " Inner type ".into(),
-            name: inner_name(&member.name, parent_name),
+            name: inner_name(&member.name, parent_name, &options.naming),
             r#type: member.r#type.clone(),
             tag: None,
         }),
-        None,
+        custom_derive,
+        options,
     )
 }
 
 fn declare_inner_choice_option(
     option: &ChoiceOption,
-    parent_name: &String,
+    parent_name: &str,
+    custom_derive: Option<&str>,
+    options: &GeneratorOptions,
 ) -> Result<String, GeneratorError> {
     generate(
         &Framework::Asnr,
         ToplevelDeclaration::Type(ToplevelTypeDeclaration {
             parameterization: None,
             comments: " Inner type ".into(),
-            name: inner_name(&option.name, parent_name),
+            name: inner_name(&option.name, parent_name, &options.naming),
             r#type: option.r#type.clone(),
             tag: None,
         }),
-        None,
+        custom_derive,
+        options,
     )
 }
 
-fn inner_name(name: &String, parent_name: &String) -> String {
+fn inner_name(name: &String, parent_name: &str, naming: &NamingConfig) -> String {
+    if is_extension_group(name) {
+        return compose_type_name(
+            naming.inner_prefix.as_deref().unwrap_or("Inner"),
+            parent_name,
+            &to_rust_title_case(name),
+            naming,
+        );
+    }
     let mut type_name = name.replace("-", "").replace("_", "");
     let mut name_chars = type_name.chars();
     if let Some(initial) = name_chars.next() {
         type_name = initial.to_uppercase().collect::<String>() + name_chars.as_str();
     }
-    format!("Inner{}{}", parent_name, type_name)
+    compose_type_name(
+        naming.inner_prefix.as_deref().unwrap_or("Inner"),
+        parent_name,
+        &type_name,
+        naming,
+    )
 }
@@ -0,0 +1,261 @@
+//! The `json_schema` module maps validated ASN1 type declarations onto
+//! JSON Schema (Draft 7) definitions. Every top-level type is declared
+//! in the `$defs` section of the schema document, so that types that are
+//! referenced by other types are only defined once.
+
+use asnr_grammar::{
+    constraints::Constraint, encoding_rules::per_visible::per_visible_range_constraints, *,
+};
+
+use crate::generator::error::GeneratorError;
+
+pub const JSON_SCHEMA_DRAFT_7: &str = "http://json-schema.org/draft-07/schema#";
+
+pub struct JsonSchemaGenerator;
+
+impl JsonSchemaGenerator {
+    /// Generates the `$defs` entry of a top-level declaration.
+    /// Values and information objects have no JSON Schema representation,
+    /// so an empty string is returned for them.
+    pub fn generate_definition(tld: ToplevelDeclaration) -> Result<String, GeneratorError> {
+        match tld {
+            ToplevelDeclaration::Type(t) => Ok(format!(
+                "{}:{}",
+                json_string(&t.name),
+                type_schema(&t.r#type)?
+            )),
+            _ => Ok(String::new()),
+        }
+    }
+}
+
+/// Wraps the generated definitions in a pretty-printed JSON Schema document
+/// * `definitions` - the `$defs` entries generated by the `JsonSchemaGenerator`
+pub fn json_schema_document(definitions: &[String]) -> String {
+    pretty_print_json(&format!(
+        r#"{{"$schema":{},"$defs":{{{}}}}}"#,
+        json_string(JSON_SCHEMA_DRAFT_7),
+        definitions
+            .iter()
+            .map(|d| d.trim())
+            .filter(|d| !d.is_empty())
+            .collect::<Vec<&str>>()
+            .join(",")
+    ))
+}
+
+fn type_schema(r#type: &ASN1Type) -> Result<String, GeneratorError> {
+    Ok(match r#type {
+        ASN1Type::Null => r#"{"type":"null"}"#.into(),
+        ASN1Type::Boolean => r#"{"type":"boolean"}"#.into(),
+        ASN1Type::Integer(i) => with_bounds(
+            r#""type":"integer""#.into(),
+            &i.constraints,
+            ("minimum", "maximum"),
+            1,
+        )?,
+        ASN1Type::Real(_) => r#"{"type":"number"}"#.into(),
+        ASN1Type::BitString(b) => with_bounds(
+            r#""type":"string","pattern":"^[01]*$""#.into(),
+            &b.constraints,
+            ("minLength", "maxLength"),
+            1,
+        )?,
+        ASN1Type::OctetString(o) => with_bounds(
+            r#""type":"string","pattern":"^([0-9A-Fa-f]{2})*$""#.into(),
+            &o.constraints,
+            ("minLength", "maxLength"),
+            2,
+        )?,
+        ASN1Type::CharacterString(c) => with_bounds(
+            r#""type":"string""#.into(),
+            &c.constraints,
+            ("minLength", "maxLength"),
+            1,
+        )?,
+        ASN1Type::Enumerated(e) => format!(
+            r#"{{"enum":[{}]}}"#,
+            e.members
+                .iter()
+                .map(|m| json_string(&m.name))
+                .collect::<Vec<String>>()
+                .join(",")
+        ),
+        ASN1Type::Choice(c) => format!(
+            r#"{{"oneOf":[{}]}}"#,
+            c.options
+                .iter()
+                .map(|o| {
+                    Ok(format!(
+                        r#"{{"type":"object","properties":{{{}:{}}},"required":[{}],"additionalProperties":false}}"#,
+                        json_string(&o.name),
+                        type_schema(&o.r#type)?,
+                        json_string(&o.name)
+                    ))
+                })
+                .collect::<Result<Vec<String>, GeneratorError>>()?
+                .join(",")
+        ),
+        ASN1Type::Sequence(s) | ASN1Type::Set(s) => {
+            let properties = s
+                .members
+                .iter()
+                .map(|m| Ok(format!("{}:{}", json_string(&m.name), type_schema(&m.r#type)?)))
+                .collect::<Result<Vec<String>, GeneratorError>>()?
+                .join(",");
+            let required = s
+                .members
+                .iter()
+                .filter(|m| !m.is_optional && m.default_value.is_none())
+                .map(|m| json_string(&m.name))
+                .collect::<Vec<String>>();
+            if required.is_empty() {
+                format!(r#"{{"type":"object","properties":{{{properties}}}}}"#)
+            } else {
+                format!(
+                    r#"{{"type":"object","properties":{{{properties}}},"required":[{}]}}"#,
+                    required.join(",")
+                )
+            }
+        }
+        ASN1Type::SequenceOf(s) | ASN1Type::SetOf(s) => with_bounds(
+            format!(r#""type":"array","items":{}"#, type_schema(&s.r#type)?),
+            &s.constraints,
+            ("minItems", "maxItems"),
+            1,
+        )?,
+        ASN1Type::ObjectIdentifier(_) => {
+            r#"{"type":"string","pattern":"^[0-9]+(\\.[0-9]+)*$"}"#.into()
+        }
+        ASN1Type::ElsewhereDeclaredType(e) => {
+            format!(r#"{{"$ref":{}}}"#, json_string(&format!("#/$defs/{}", e.identifier)))
+        }
+        ASN1Type::InformationObjectFieldReference(_) => "{}".into(),
+    })
+}
+
+/// Appends the bounds of the PER-visible range or size constraints to the keywords of a schema.
+/// Extensible constraints do not bound the permitted values, so no bounds are appended for them.
+/// * `keywords` - the keywords of the schema without surrounding braces
+/// * `constraints` - the constraints of the ASN1 type
+/// * `bound_keywords` - the keywords of the lower and upper bound
+/// * `factor` - the number of JSON units representing a constrained ASN1 unit,
+///   e.g. two hex digits per octet
+fn with_bounds(
+    keywords: String,
+    constraints: &Vec<Constraint>,
+    bound_keywords: (&str, &str),
+    factor: i128,
+) -> Result<String, GeneratorError> {
+    let mut schema = keywords;
+    if !constraints.is_empty() {
+        let range = per_visible_range_constraints(true, constraints)?;
+        if !range.is_extensible() {
+            if let Some(min) = range.min::<i128>() {
+                schema += &format!(r#","{}":{}"#, bound_keywords.0, min * factor);
+            }
+            if let Some(max) = range.max::<i128>() {
+                schema += &format!(r#","{}":{}"#, bound_keywords.1, max * factor);
+            }
+        }
+    }
+    Ok(format!("{{{schema}}}"))
+}
+
+/// Quotes and escapes a string as a JSON string literal
+fn json_string(value: &str) -> String {
+    let mut quoted = String::from("\"");
+    for c in value.chars() {
+        match c {
+            '"' => quoted.push_str("\\\""),
+            '\\' => quoted.push_str("\\\\"),
+            c if (c as u32) < 0x20 => quoted.push_str(&format!("\\u{:04x}", c as u32)),
+            c => quoted.push(c),
+        }
+    }
+    quoted.push('"');
+    quoted
+}
+
+/// Indents compact JSON by two spaces per nesting level.
+/// Empty objects and arrays are kept on a single line.
+fn pretty_print_json(json: &str) -> String {
+    let mut pretty = String::with_capacity(json.len() * 2);
+    let mut depth = 0;
+    let mut in_string = false;
+    let mut escaped = false;
+    let mut chars = json.chars().peekable();
+    let newline = |pretty: &mut String, depth: usize| {
+        pretty.push('\n');
+        pretty.push_str(&"  ".repeat(depth));
+    };
+    while let Some(c) = chars.next() {
+        if in_string {
+            pretty.push(c);
+            match c {
+                _ if escaped => escaped = false,
+                '\\' => escaped = true,
+                '"' => in_string = false,
+                _ => (),
+            }
+            continue;
+        }
+        match c {
+            '"' => {
+                in_string = true;
+                pretty.push(c);
+            }
+            '{' | '[' => {
+                pretty.push(c);
+                if matches!(chars.peek(), Some('}') | Some(']')) {
+                    pretty.push(chars.next().unwrap_or_default());
+                } else {
+                    depth += 1;
+                    newline(&mut pretty, depth);
+                }
+            }
+            '}' | ']' => {
+                depth = depth.saturating_sub(1);
+                newline(&mut pretty, depth);
+                pretty.push(c);
+            }
+            ',' => {
+                pretty.push(c);
+                newline(&mut pretty, depth);
+            }
+            ':' => pretty.push_str(": "),
+            c => pretty.push(c),
+        }
+    }
+    pretty.push('\n');
+    pretty
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn pretty_prints_json() {
+        assert_eq!(
+            pretty_print_json(r#"{"a":{"b":[1,2]},"c":{},"d":"{,:\"}"}"#),
+            r#"{
+  "a": {
+    "b": [
+      1,
+      2
+    ]
+  },
+  "c": {},
+  "d": "{,:\"}"
+}
+"#
+        );
+    }
+
+    #[test]
+    fn escapes_json_strings() {
+        assert_eq!(json_string(r#"a"b\c"#), r#""a\"b\\c""#);
+        assert_eq!(json_string("\n"), r#""\u000a""#);
+    }
+}
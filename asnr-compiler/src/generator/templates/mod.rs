@@ -1,8 +1,374 @@
-use asnr_grammar::utils::to_rust_title_case;
+use asnr_grammar::{
+    utils::{to_rust_const_case, to_rust_snake_case, to_rust_title_case},
+    ASN1Value, ToplevelDeclaration, ToplevelValueDeclaration, BOOLEAN, CHARACTER_STRING_TYPES,
+};
+
+use self::asnr::util::format_comments;
+
+use super::{
+    error::{GeneratorError, GeneratorErrorType},
+    Derive, NamingConfig,
+};
 
 pub(crate) mod asnr;
+pub(crate) mod json_schema;
 pub(crate) mod rasn;
 
-pub fn inner_name(name: &String, parent_name: &String) -> String {
-    format!("{}{}", parent_name, to_rust_title_case(&name))
-}
\ No newline at end of file
+/// Generates the constant of a BOOLEAN value.
+/// Since the asnr and the rasn framework both represent BOOLEAN types by `bool`s,
+/// they declare BOOLEAN values alike.
+pub fn generate_boolean_value(tld: ToplevelValueDeclaration) -> Result<String, GeneratorError> {
+    if let ASN1Value::Boolean(b) = tld.value {
+        let (value_type, value) = if tld.type_name == BOOLEAN {
+            ("bool".to_string(), b.to_string())
+        } else {
+            let type_name = to_rust_title_case(&tld.type_name);
+            let value = format!("{type_name}({b})");
+            (type_name, value)
+        };
+        Ok(const_value_template(
+            format_comments(&tld.comments),
+            to_rust_const_case(&tld.name),
+            &value_type,
+            value,
+        ))
+    } else {
+        Err(GeneratorError::new(
+            Some(ToplevelDeclaration::Value(tld)),
+            "Expected BOOLEAN value top-level declaration",
+            GeneratorErrorType::Asn1TypeMismatch,
+        ))
+    }
+}
+
+/// Generates a character string value.
+/// Values of the built-in character string types are declared as string slices.
+/// Declared character string types wrap owned strings, which cannot be constructed
+/// in constants, so their values are returned by functions.
+/// * `conversion` - method call that converts a string literal into the framework's string type
+pub fn generate_string_value(
+    tld: ToplevelValueDeclaration,
+    conversion: &str,
+) -> Result<String, GeneratorError> {
+    if let ASN1Value::String(ref s) = tld.value {
+        if CHARACTER_STRING_TYPES.contains(&tld.type_name.as_str()) {
+            Ok(const_value_template(
+                format_comments(&tld.comments),
+                to_rust_const_case(&tld.name),
+                "&str",
+                format!("{s:?}"),
+            ))
+        } else {
+            let type_name = to_rust_title_case(&tld.type_name);
+            let value = format!("{type_name}({s:?}.{conversion})");
+            Ok(value_function_template(
+                format_comments(&tld.comments),
+                to_rust_snake_case(&tld.name),
+                &type_name,
+                value,
+            ))
+        }
+    } else {
+        Err(GeneratorError::new(
+            Some(ToplevelDeclaration::Value(tld)),
+            "Expected character string value top-level declaration",
+            GeneratorErrorType::Asn1TypeMismatch,
+        ))
+    }
+}
+
+pub fn const_value_template(
+    comments: String,
+    name: String,
+    value_type: &str,
+    value: String,
+) -> String {
+    format!(
+        r#"{comments}
+pub const {name}: {value_type} = {value};
+"#
+    )
+}
+
+pub fn value_function_template(
+    comments: String,
+    name: String,
+    value_type: &str,
+    value: String,
+) -> String {
+    format!(
+        r#"{comments}
+pub fn {name}() -> {value_type} {{
+    {value}
+}}
+"#
+    )
+}
+
+/// Checks whether a SEQUENCE member represents an extension addition group,
+/// which the parser names `ext_group_<index>`
+pub fn is_extension_group(member_name: &str) -> bool {
+    member_name.starts_with("ext_group_")
+}
+
+pub fn inner_name(name: &String, parent_name: &str, naming: &NamingConfig) -> String {
+    compose_type_name(
+        naming.inner_prefix.as_deref().unwrap_or(""),
+        parent_name,
+        &to_rust_title_case(name),
+        naming,
+    )
+}
+
+/// Formats the name of the type generated for the anonymous member type of a SEQUENCE OF
+pub fn anonymous_name(parent_name: &str, naming: &NamingConfig) -> String {
+    compose_type_name(&naming.anonymous_prefix, parent_name, "", naming)
+}
+
+/// Composes the name of a generated type from a prefix, the name of its parent type, and its own name.
+/// If the parent name is abbreviated or the composed name exceeds the maximum length,
+/// the name is shortened and suffixed with a hash of the full name.
+/// Since the hash only depends on the full name, shortened names are disambiguated deterministically.
+pub fn compose_type_name(
+    prefix: &str,
+    parent_name: &str,
+    name: &str,
+    naming: &NamingConfig,
+) -> String {
+    let full_name = format!("{prefix}{parent_name}{name}");
+    let mut type_name = if naming.abbreviate_parent_names {
+        let abbreviation = parent_name
+            .chars()
+            .filter(|c| c.is_ascii_uppercase() || c.is_ascii_digit())
+            .collect::<String>();
+        format!("{prefix}{abbreviation}{name}")
+    } else {
+        full_name.clone()
+    };
+    let max_length = naming.max_length.unwrap_or(usize::MAX);
+    if type_name != full_name || type_name.len() > max_length {
+        let hash = format!("{:08X}", fnv1a_hash(&full_name));
+        type_name = type_name
+            .chars()
+            .take(max_length.saturating_sub(hash.len()).max(1))
+            .collect::<String>()
+            + &hash;
+    }
+    type_name
+}
+
+/// 32-bit FNV-1a hash, which, unlike the std hashers, is stable across Rust releases
+fn fnv1a_hash(input: &str) -> u32 {
+    input.bytes().fold(0x811c9dc5, |hash, byte| {
+        (hash ^ byte as u32).wrapping_mul(0x01000193)
+    })
+}
+/// Checks whether a value can be represented by the generated Rust integer type.
+/// Types other than Rust's primitive integers are assumed to be of arbitrary precision.
+pub fn fits_integer_type(value: i128, integer_type: &str) -> bool {
+    match integer_type {
+        "u8" => u8::try_from(value).is_ok(),
+        "u16" => u16::try_from(value).is_ok(),
+        "u32" => u32::try_from(value).is_ok(),
+        "u64" => u64::try_from(value).is_ok(),
+        "u128" => u128::try_from(value).is_ok(),
+        "i8" => i8::try_from(value).is_ok(),
+        "i16" => i16::try_from(value).is_ok(),
+        "i32" => i32::try_from(value).is_ok(),
+        "i64" => i64::try_from(value).is_ok(),
+        _ => true,
+    }
+}
+
+/// Appends the custom derives to a default derive attribute
+/// * `default_derive` - default derive attribute, e.g. `#[derive(Debug, Clone)]`
+/// * `custom_derive` - comma-separated list of additional derives, e.g. `Serialize, Deserialize`
+pub fn append_derives(default_derive: &str, custom_derive: Option<&str>) -> String {
+    match custom_derive {
+        Some(custom) if !custom.is_empty() => format!(
+            "{}, {custom})]",
+            default_derive.trim_end().trim_end_matches(")]")
+        ),
+        _ => default_derive.to_owned(),
+    }
+}
+
+/// Returns whether the custom derives include serde's `Serialize` or `Deserialize`
+/// * `custom_derive` - comma-separated list of additional derives
+pub fn derives_serde(custom_derive: Option<&str>) -> bool {
+    custom_derive.is_some_and(|custom| {
+        custom.split(',').any(|derive| {
+            matches!(
+                derive.trim().rsplit("::").next(),
+                Some("Serialize" | "Deserialize")
+            )
+        })
+    })
+}
+
+/// Replaces the configurable derives of every derive attribute in the generated Rust
+/// with the configured derives. Framework derives and custom derives are kept.
+/// * `rust` - the generated Rust code
+/// * `derives` - the configured derives
+/// * `custom_derives` - names of the custom derive macros
+pub fn apply_derives(rust: &str, derives: &[Derive], custom_derives: &[String]) -> String {
+    let mut result = String::with_capacity(rust.len());
+    let mut rest = rust;
+    while let Some(start) = rest.find("#[derive(") {
+        let (before, attribute) = rest.split_at(start + "#[derive(".len());
+        result.push_str(before);
+        let end = match attribute.find(")]") {
+            Some(end) => end,
+            None => {
+                rest = attribute;
+                break;
+            }
+        };
+        let entries = attribute[..end]
+            .split(',')
+            .map(str::trim)
+            .filter(|entry| !entry.is_empty())
+            .collect::<Vec<&str>>();
+        let is_custom = |entry: &str| custom_derives.iter().any(|c| c == entry);
+        let mut applied = entries
+            .iter()
+            .filter(|e| !is_custom(e) && !Derive::ALL.iter().any(|d| d.name() == **e))
+            .copied()
+            .collect::<Vec<&str>>();
+        applied.extend(
+            derives
+                .iter()
+                .map(|d| d.name())
+                .filter(|d| !entries.iter().any(|e| e == d && is_custom(e))),
+        );
+        applied.extend(entries.iter().copied().filter(|e| is_custom(e)));
+        result.push_str(&applied.join(", "));
+        rest = &attribute[end..];
+    }
+    result.push_str(rest);
+    result
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn checks_whether_value_fits_integer_type() {
+        assert!(fits_integer_type(255, "u8"));
+        assert!(!fits_integer_type(-1, "u8"));
+        assert!(!fits_integer_type(16898, "i8"));
+        assert!(fits_integer_type(-16898, "i128"));
+        assert!(fits_integer_type(-16898, "Integer"));
+    }
+
+    #[test]
+    fn composes_type_names() {
+        let naming = NamingConfig::default();
+        assert_eq!(
+            inner_name(&"member-1".into(), "ExampleSequence", &naming),
+            "ExampleSequenceMember1"
+        );
+        assert_eq!(
+            anonymous_name("TestSequenceOf", &naming),
+            "AnonymousTestSequenceOf"
+        );
+        let naming = NamingConfig {
+            inner_prefix: Some("Nested".into()),
+            anonymous_prefix: "Item".into(),
+            ..Default::default()
+        };
+        assert_eq!(
+            inner_name(&"member-1".into(), "ExampleSequence", &naming),
+            "NestedExampleSequenceMember1"
+        );
+        assert_eq!(
+            anonymous_name("TestSequenceOf", &naming),
+            "ItemTestSequenceOf"
+        );
+    }
+
+    #[test]
+    fn abbreviates_parent_names() {
+        let naming = NamingConfig {
+            abbreviate_parent_names: true,
+            ..Default::default()
+        };
+        let abbreviated = compose_type_name("Inner", "InnerExtendedSequence", "Item", &naming);
+        assert!(abbreviated.starts_with("InnerIESItem"));
+        assert_eq!(abbreviated.len(), "InnerIESItem".len() + 8);
+        assert_ne!(
+            abbreviated,
+            compose_type_name("Inner", "InnerExampleSet", "Item", &naming)
+        );
+        assert_eq!(compose_type_name("Inner", "IES", "Item", &naming), "InnerIESItem");
+    }
+
+    #[test]
+    fn truncates_long_names_deterministically() {
+        let naming = NamingConfig {
+            max_length: Some(24),
+            ..Default::default()
+        };
+        let first = compose_type_name(
+            "Inner",
+            "InnerExtendedSequenceExtgroupalternateitemcode",
+            "Alternateitemcode",
+            &naming,
+        );
+        let second = compose_type_name(
+            "Inner",
+            "InnerExtendedSequenceExtgroupalternateitemcode",
+            "Alternateitemname",
+            &naming,
+        );
+        assert_eq!(first.len(), 24);
+        assert!(first.starts_with("InnerInnerExtend"));
+        assert_ne!(first, second);
+        assert_eq!(
+            first,
+            compose_type_name(
+                "Inner",
+                "InnerExtendedSequenceExtgroupalternateitemcode",
+                "Alternateitemcode",
+                &naming,
+            )
+        );
+        assert_eq!(
+            compose_type_name("Inner", "Short", "Name", &naming),
+            "InnerShortName"
+        );
+    }
+
+    #[test]
+    fn applies_configured_derives() {
+        assert_eq!(
+            apply_derives(
+                "#[derive(AsnType, Debug, Clone, Copy, Decode, Encode, PartialEq, Serialize)]\npub struct A;\n#[derive(Debug, Clone, PartialEq, Default)]",
+                &[Derive::Clone, Derive::PartialEq, Derive::Eq, Derive::Hash],
+                &["Serialize".into()],
+            ),
+            "#[derive(AsnType, Copy, Decode, Encode, Clone, PartialEq, Eq, Hash, Serialize)]\npub struct A;\n#[derive(Default, Clone, PartialEq, Eq, Hash)]"
+        );
+        assert_eq!(
+            apply_derives(
+                "#[derive(Debug, Clone, Hash)]",
+                &[Derive::Clone],
+                &["Hash".into()]
+            ),
+            "#[derive(Clone, Hash)]"
+        );
+    }
+
+    #[test]
+    fn appends_custom_derives() {
+        assert_eq!(
+            append_derives("#[derive(Debug, Clone)]", Some("Serialize, Hash")),
+            "#[derive(Debug, Clone, Serialize, Hash)]"
+        );
+        assert_eq!(
+            append_derives("#[derive(Debug, Clone)]", None),
+            "#[derive(Debug, Clone)]"
+        );
+    }
+}
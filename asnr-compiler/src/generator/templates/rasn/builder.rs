@@ -1,28 +1,32 @@
 use asnr_grammar::{
-    utils::{to_rust_const_case, to_rust_title_case}, ASN1Type, ASN1Value, ToplevelDeclaration, ToplevelTypeDeclaration,
-    ToplevelValueDeclaration, INTEGER,
+    information_object::{ASN1Information, ClassLink, ToplevelInformationDeclaration},
+    utils::{to_rust_const_case, to_rust_snake_case, to_rust_title_case}, ASN1Type, ASN1Value, ToplevelDeclaration, ToplevelTypeDeclaration,
+    ToplevelValueDeclaration, BIT_STRING, INTEGER,
 };
 
 use crate::{
     generator::{
         error::{GeneratorError, GeneratorErrorType},
         generate,
-        templates::asnr::util::format_comments,
+        templates::{anonymous_name, append_derives, asnr::util::format_comments, derives_serde},
+        GeneratorOptions,
     },
     Framework,
 };
 
 use super::{
     template::{
-        bit_string_template, boolean_template, char_string_template, choice_template,
-        enumerated_template, integer_template, integer_value_template, null_template,
+        bit_string_template, bit_string_value_template, boolean_template, char_string_template, choice_template,
+        choice_value_template, sequence_value_template,
+        enumerated_template, information_object_set_template, integer_template, integer_value_template, null_template, ObjectSetEnum,
         null_value_template, object_identifier_value_template, octet_string_template,
         sequence_of_template, sequence_or_set_template, typealias_template,
     },
     utils::{
-        format_alphabet_annotations, format_choice_options, format_default_methods,
-        format_enum_members, format_nested_choice_options, format_nested_sequence_members,
-        format_range_annotations, format_sequence_or_set_members, format_tag, string_type, int_type_token,
+        format_alphabet_annotations, format_choice_options, format_choice_value, format_default_methods,
+        format_distinguished_values, format_sequence_value, extract_object_set_options, join_annotations,
+        format_enum_members, format_enum_try_from_str_arms, format_named_bits, format_nested_choice_options, format_nested_sequence_members,
+        format_range_annotations, format_sequence_or_set_members, format_tag, string_type, int_type_token, integer_type,
     },
 };
 
@@ -31,7 +35,7 @@ pub struct RasnGenerator;
 impl RasnGenerator {
     pub fn generate_typealias<'a>(
         tld: ToplevelTypeDeclaration,
-        _custom_derive: Option<&'a str>,
+        custom_derive: Option<&'a str>,
     ) -> Result<String, GeneratorError> {
         if let ASN1Type::ElsewhereDeclaredType(dec) = &tld.r#type {
             Ok(typealias_template(
@@ -40,6 +44,7 @@ impl RasnGenerator {
                 to_rust_title_case(&dec.identifier),
                 format_tag(tld.tag.as_ref()),
                 format_range_annotations(true, &dec.constraints)?,
+                custom_derive,
             ))
         } else {
             Err(GeneratorError::new(
@@ -78,20 +83,19 @@ impl RasnGenerator {
 
     pub fn generate_integer<'a>(
         tld: ToplevelTypeDeclaration,
-        _custom_derive: Option<&'a str>,
+        custom_derive: Option<&'a str>,
+        options: &GeneratorOptions,
     ) -> Result<String, GeneratorError> {
         if let ASN1Type::Integer(ref int) = tld.r#type {
-            let mut int_type = int.type_token();
-            if int_type == "i128" {
-                // rasn only supports integers of up to 64 bits length
-                int_type = "i64".to_owned();
-            }
+            let integer_type = integer_type(int, options);
             Ok(integer_template(
                 format_comments(&tld.comments),
                 to_rust_title_case(&tld.name),
                 format_range_annotations(true, &int.constraints)?,
                 format_tag(tld.tag.as_ref()),
-                int_type,
+                format_distinguished_values(int, &integer_type),
+                integer_type,
+                custom_derive,
             ))
         } else {
             Err(GeneratorError::new(
@@ -104,7 +108,7 @@ impl RasnGenerator {
 
     pub fn generate_bit_string<'a>(
         tld: ToplevelTypeDeclaration,
-        _custom_derive: Option<&'a str>,
+        custom_derive: Option<&'a str>,
     ) -> Result<String, GeneratorError> {
         if let ASN1Type::BitString(ref bitstr) = tld.r#type {
             Ok(bit_string_template(
@@ -112,6 +116,8 @@ impl RasnGenerator {
                 to_rust_title_case(&tld.name),
                 format_range_annotations(true, &bitstr.constraints)?,
                 format_tag(tld.tag.as_ref()),
+                format_named_bits(bitstr),
+                custom_derive,
             ))
         } else {
             Err(GeneratorError::new(
@@ -124,7 +130,7 @@ impl RasnGenerator {
 
     pub fn generate_octet_string<'a>(
         tld: ToplevelTypeDeclaration,
-        _custom_derive: Option<&'a str>,
+        custom_derive: Option<&'a str>,
     ) -> Result<String, GeneratorError> {
         if let ASN1Type::OctetString(ref oct_str) = tld.r#type {
             Ok(octet_string_template(
@@ -132,6 +138,7 @@ impl RasnGenerator {
                 to_rust_title_case(&tld.name),
                 format_range_annotations(true, &oct_str.constraints)?,
                 format_tag(tld.tag.as_ref()),
+                custom_derive,
             ))
         } else {
             Err(GeneratorError::new(
@@ -144,16 +151,17 @@ impl RasnGenerator {
 
     pub fn generate_character_string<'a>(
         tld: ToplevelTypeDeclaration,
-        _custom_derive: Option<&'a str>,
+        custom_derive: Option<&'a str>,
     ) -> Result<String, GeneratorError> {
         if let ASN1Type::CharacterString(ref char_str) = tld.r#type {
             Ok(char_string_template(
                 format_comments(&tld.comments),
                 to_rust_title_case(&tld.name),
-                string_type(&char_str.r#type),
+                string_type(&char_str.r#type)?,
                 format_range_annotations(false, &char_str.constraints)?,
                 format_alphabet_annotations(char_str.r#type, &char_str.constraints)?,
                 format_tag(tld.tag.as_ref()),
+                custom_derive,
             ))
         } else {
             Err(GeneratorError::new(
@@ -166,13 +174,14 @@ impl RasnGenerator {
 
     pub fn generate_boolean<'a>(
         tld: ToplevelTypeDeclaration,
-        _custom_derive: Option<&'a str>,
+        custom_derive: Option<&'a str>,
     ) -> Result<String, GeneratorError> {
         if let ASN1Type::Boolean = tld.r#type {
             Ok(boolean_template(
                 format_comments(&tld.comments),
                 to_rust_title_case(&tld.name),
                 format_tag(tld.tag.as_ref()),
+                custom_derive,
             ))
         } else {
             Err(GeneratorError::new(
@@ -204,6 +213,34 @@ impl RasnGenerator {
     //         }
     //     }
 
+    pub fn generate_bit_string_value(
+        tld: ToplevelValueDeclaration,
+    ) -> Result<String, GeneratorError> {
+        if let ASN1Value::BitString(_) = tld.value {
+            // rasn's BitString is heap-allocated, so BIT STRING values are declared as functions
+            let bits = format!("{}.iter().collect()", tld.value.value_as_string(None)?);
+            let (type_name, value) = if tld.type_name == BIT_STRING {
+                (String::from("BitString"), bits)
+            } else {
+                let type_name = to_rust_title_case(&tld.type_name);
+                let value = format!("{type_name}({bits})");
+                (type_name, value)
+            };
+            Ok(bit_string_value_template(
+                format_comments(&tld.comments),
+                to_rust_snake_case(&tld.name),
+                type_name,
+                value,
+            ))
+        } else {
+            Err(GeneratorError::new(
+                Some(ToplevelDeclaration::Value(tld)),
+                "Expected BIT STRING value top-level declaration",
+                GeneratorErrorType::Asn1TypeMismatch,
+            ))
+        }
+    }
+
     pub fn generate_null_value(tld: ToplevelValueDeclaration) -> Result<String, GeneratorError> {
         if let ASN1Value::Null = tld.value {
             Ok(null_value_template(
@@ -221,13 +258,14 @@ impl RasnGenerator {
 
     pub fn generate_null<'a>(
         tld: ToplevelTypeDeclaration,
-        _custom_derive: Option<&'a str>,
+        custom_derive: Option<&'a str>,
     ) -> Result<String, GeneratorError> {
         if let ASN1Type::Null = tld.r#type {
             Ok(null_template(
                 format_comments(&tld.comments),
                 to_rust_title_case(&tld.name),
                 format_tag(tld.tag.as_ref()),
+                custom_derive,
             ))
         } else {
             Err(GeneratorError::new(
@@ -240,7 +278,8 @@ impl RasnGenerator {
 
     pub fn generate_enumerated<'a>(
         tld: ToplevelTypeDeclaration,
-        _custom_derive: Option<&'a str>,
+        custom_derive: Option<&'a str>,
+        options: &GeneratorOptions,
     ) -> Result<String, GeneratorError> {
         if let ASN1Type::Enumerated(ref enumerated) = tld.r#type {
             let extensible = if enumerated.extensible.is_some() {
@@ -253,8 +292,14 @@ impl RasnGenerator {
                 format_comments(&tld.comments),
                 to_rust_title_case(&tld.name),
                 extensible,
-                format_enum_members(enumerated),
+                format_enum_members(
+                    enumerated,
+                    &options.naming.enumerals,
+                    derives_serde(custom_derive),
+                ),
+                format_enum_try_from_str_arms(enumerated, &options.naming.enumerals),
                 format_tag(tld.tag.as_ref()),
+                custom_derive,
             ))
         } else {
             Err(GeneratorError::new(
@@ -265,13 +310,37 @@ impl RasnGenerator {
         }
     }
 
+    pub fn generate_choice_value(
+        tld: ToplevelValueDeclaration,
+        options: &GeneratorOptions,
+    ) -> Result<String, GeneratorError> {
+        if let ASN1Value::Choice(ref option, ref value) = tld.value {
+            // rasn's types cannot generally be constructed in a const context,
+            // so CHOICE values are declared as functions
+            let type_name = to_rust_title_case(&tld.type_name);
+            Ok(choice_value_template(
+                format_comments(&tld.comments),
+                to_rust_snake_case(&tld.name),
+                type_name.clone(),
+                format_choice_value(&type_name, option, value, options)?,
+            ))
+        } else {
+            Err(GeneratorError::new(
+                Some(ToplevelDeclaration::Value(tld)),
+                "Expected CHOICE value top-level declaration",
+                GeneratorErrorType::Asn1TypeMismatch,
+            ))
+        }
+    }
+
     pub fn generate_choice<'a>(
         tld: ToplevelTypeDeclaration,
-        _custom_derive: Option<&'a str>,
+        custom_derive: Option<&'a str>,
+        options: &GeneratorOptions,
     ) -> Result<String, GeneratorError> {
         if let ASN1Type::Choice(ref choice) = tld.r#type {
             let name = to_rust_title_case(&tld.name);
-            let inner_options = format_nested_choice_options(&choice, &name)?;
+            let inner_options = format_nested_choice_options(choice, &name, custom_derive, options)?;
             let extensible = if choice.extensible.is_some() {
                 r#"
                 #[non_exhaustive]"#
@@ -283,9 +352,10 @@ impl RasnGenerator {
                 format_comments(&tld.comments),
                 name.clone(),
                 extensible,
-                format_choice_options(&choice, &name)?,
+                format_choice_options(choice, &name, options)?,
                 inner_options,
                 format_tag(tld.tag.as_ref()),
+                custom_derive,
             ))
         } else {
             Err(GeneratorError::new(
@@ -332,9 +402,38 @@ impl RasnGenerator {
         }
     }
 
+    pub fn generate_sequence_value(
+        tld: ToplevelValueDeclaration,
+        options: &GeneratorOptions,
+    ) -> Result<String, GeneratorError> {
+        if let ASN1Value::Sequence(_) = tld.value {
+            // like CHOICE values, SEQUENCE values are declared as functions
+            let type_name = to_rust_title_case(&tld.type_name);
+            let value =
+                format_sequence_value(&type_name, &tld.value, options).map_err(|mut e| {
+                    e.top_level_declaration
+                        .get_or_insert_with(|| ToplevelDeclaration::Value(tld.clone()));
+                    e
+                })?;
+            Ok(sequence_value_template(
+                format_comments(&tld.comments),
+                to_rust_snake_case(&tld.name),
+                type_name,
+                value,
+            ))
+        } else {
+            Err(GeneratorError::new(
+                Some(ToplevelDeclaration::Value(tld)),
+                "Expected SEQUENCE value top-level declaration",
+                GeneratorErrorType::Asn1TypeMismatch,
+            ))
+        }
+    }
+
     pub fn generate_sequence_or_set<'a>(
         tld: ToplevelTypeDeclaration,
-        _custom_derive: Option<&'a str>,
+        custom_derive: Option<&'a str>,
+        options: &GeneratorOptions,
     ) -> Result<String, GeneratorError> {
         match tld.r#type {
             ASN1Type::Sequence(ref seq) | ASN1Type::Set(ref seq) => {
@@ -354,11 +453,12 @@ impl RasnGenerator {
                     format_comments(&tld.comments),
                     name.clone(),
                     extensible,
-                    format_sequence_or_set_members(seq, &name)?,
-                    format_nested_sequence_members(seq, &name)?,
+                    format_sequence_or_set_members(seq, &name, options)?,
+                    format_nested_sequence_members(seq, &name, custom_derive, options)?,
                     format_tag(tld.tag.as_ref()),
                     set_annotation.into(),
-                    format_default_methods(&seq.members, &name)?,
+                    format_default_methods(&seq.members, &name, options)?,
+                    custom_derive,
                 ))
             }
             _ => Err(GeneratorError::new(
@@ -371,9 +471,11 @@ impl RasnGenerator {
 
     pub fn generate_sequence_of<'a>(
         tld: ToplevelTypeDeclaration,
-        _custom_derive: Option<&'a str>,
+        custom_derive: Option<&'a str>,
+        options: &GeneratorOptions,
     ) -> Result<String, GeneratorError> {
-        if let ASN1Type::SequenceOf(ref seq_of) = tld.r#type {
+        if let ASN1Type::SequenceOf(ref seq_of) | ASN1Type::SetOf(ref seq_of) = tld.r#type {
+            let is_set_of = matches!(tld.r#type, ASN1Type::SetOf(_));
             let name = to_rust_title_case(&tld.name);
             let anonymous_item = match seq_of.r#type.as_ref() {
                 ASN1Type::ElsewhereDeclaredType(_) => None,
@@ -381,18 +483,29 @@ impl RasnGenerator {
                     &Framework::Rasn,
                     ToplevelDeclaration::Type(ToplevelTypeDeclaration {
                         parameterization: None,
-                        comments: " Anonymous SEQUENCE OF member ".into(),
-                        name: String::from("Anonymous") + &name,
+                        comments: if is_set_of {
+                            " Anonymous SET OF member ".into()
+                        } else {
+                            " Anonymous SEQUENCE OF member ".into()
+                        },
+                        name: anonymous_name(&name, &options.naming),
                         r#type: n.clone(),
                         tag: None,
                     }),
-                    None,
+                    custom_derive,
+                    options,
                 )?),
             }
             .unwrap_or_default();
             let member_type = match seq_of.r#type.as_ref() {
                 ASN1Type::ElsewhereDeclaredType(d) => to_rust_title_case(&d.identifier),
-                _ => String::from("Anonymous") + &name,
+                _ => anonymous_name(&name, &options.naming),
+            };
+            // rasn's `SetOf` requires its elements to be ordered, so SET OFs are represented
+            // like SEQUENCE OFs that carry the universal SET tag
+            let tag_annotations = match tld.tag.as_ref() {
+                None if is_set_of => String::from("tag(universal, 17)"),
+                tag => format_tag(tag),
             };
             Ok(sequence_of_template(
                 format_comments(&tld.comments),
@@ -400,104 +513,139 @@ impl RasnGenerator {
                 anonymous_item,
                 member_type,
                 format_range_annotations(true, &seq_of.constraints)?,
-                format_tag(tld.tag.as_ref()),
+                tag_annotations,
+                custom_derive,
             ))
         } else {
             Err(GeneratorError::new(
                 Some(ToplevelDeclaration::Type(tld)),
-                "Expected SEQUENCE OF top-level declaration",
+                "Expected SEQUENCE OF or SET OF top-level declaration",
                 GeneratorErrorType::Asn1TypeMismatch,
             ))
         }
     }
 
-    //     fn generate_information_object_set<'a>(
-    //         tld: ToplevelInformationDeclaration,
-    //     ) -> Result<String, GeneratorError> {
-    //         if let ASN1Information::ObjectSet(o) = &tld.value {
-    //             let class: &InformationObjectClass = match tld.class {
-    //                 Some(ClassLink::ByReference(ref c)) => c,
-    //                 _ => {
-    //                     return Err(GeneratorError::new(
-    //                         None,
-    //                         "Missing class link in Information Object Set",
-    //                         GeneratorErrorType::MissingClassLink,
-    //                     ))
-    //                 }
-    //             };
-    //             let keys_to_types = o
-    //                 .values
-    //                 .iter()
-    //                 .map(|v| {
-    //                     match v {
-    //                         ObjectSetValue::Reference(_) => todo!(),
-    //                         // basically, an information object specifies a sequence implementing a class. So we sould treat information objects like sequences
-    //                         ObjectSetValue::Inline(InformationObjectFields::CustomSyntax(s)) => {
-    //                             resolve_syntax(class, s)
-    //                         }
-    //                         ObjectSetValue::Inline(InformationObjectFields::DefaultSyntax(_s)) => {
-    //                             todo!()
-    //                         }
-    //                     }
-    //                 })
-    //                 .collect::<Result<Vec<(ASN1Value, Vec<ASN1Type>)>, GeneratorError>>()?;
-    //             let mut options = keys_to_types
-    //                 .iter()
-    //                 .map(|(k, types)| {
-    //                     format!(
-    //                         "_{}({})",
-    //                         k.to_string(),
-    //                         types
-    //                             .iter()
-    //                             .map(|t| format!("pub {}", t.to_string()))
-    //                             .collect::<Vec<String>>()
-    //                             .join(", ")
-    //                     )
-    //                 })
-    //                 .collect::<Vec<String>>()
-    //                 .join(",\n\t");
-    //             if o.extensible.is_some() {
-    //                 options.push_str(",\n\tUnknownClassImplementation(pub Vec<u8>)");
-    //             }
-    //             let key_type = match class
-    //                 .fields
-    //                 .iter()
-    //                 .find_map(|f| {
-    //                     f.is_unique
-    //                         .then(|| f.r#type.as_ref().map(|t| t.to_string()))
-    //                 })
-    //                 .flatten()
-    //             {
-    //                 Some(key_type) => key_type,
-    //                 None => {
-    //                     return Err(GeneratorError::new(
-    //                         None,
-    //                         "Could not determine class key type!",
-    //                         GeneratorErrorType::MissingClassKey,
-    //                     ))
-    //                 }
-    //             };
-    //             let mut branches = keys_to_types
-    //                 .iter()
-    //                 .map(|(k, _)| format!("{} => todo!()", k.to_string(),))
-    //                 .collect::<Vec<String>>()
-    //                 .join(",\n\t");
-    //             if o.extensible.is_some() {
-    //                 branches.push_str(",\n\t_ => todo!()");
-    //             }
-    //             Ok(information_object_set_template(
-    //                 format_comments(&tld.comments),
-    //                 rustify_name(&tld.name),
-    //                 options,
-    //                 key_type,
-    //                 branches,
-    //             ))
-    //         } else {
-    //             Err(GeneratorError::new(
-    //                 Some(ToplevelDeclaration::Information(tld)),
-    //                 "Expected Object Set top-level declaration",
-    //                 GeneratorErrorType::Asn1TypeMismatch,
-    //             ))
-    //         }
-    //     }
+    pub fn generate_information_object_set(
+        tld: ToplevelInformationDeclaration,
+        custom_derive: Option<&str>,
+        options: &GeneratorOptions,
+    ) -> Result<String, GeneratorError> {
+        let (set, class) = match (&tld.value, &tld.class) {
+            (ASN1Information::ObjectSet(set), Some(ClassLink::ByReference(class))) => (set, class),
+            (ASN1Information::ObjectSet(_), _) => {
+                return Err(GeneratorError::new(
+                    Some(ToplevelDeclaration::Information(tld)),
+                    "Missing class link in information object set",
+                    GeneratorErrorType::MissingClassLink,
+                ))
+            }
+            _ => {
+                return Err(GeneratorError::new(
+                    Some(ToplevelDeclaration::Information(tld)),
+                    "Expected information object set top-level declaration",
+                    GeneratorErrorType::Asn1TypeMismatch,
+                ))
+            }
+        };
+        let name = to_rust_title_case(&tld.name);
+        let objects = match extract_object_set_options(set, class, &name, options) {
+            Ok(objects) if !objects.is_empty() => objects,
+            Ok(_) => {
+                return Err(GeneratorError::new(
+                    Some(ToplevelDeclaration::Information(tld)),
+                    "Empty information object sets are not yet supported!",
+                    GeneratorErrorType::EmptyChoiceType,
+                ))
+            }
+            Err(mut e) => {
+                e.top_level_declaration = Some(ToplevelDeclaration::Information(tld));
+                return Err(e);
+            }
+        };
+        let inner_types = objects
+            .iter()
+            .filter_map(|o| {
+                o.inner_type.as_ref().map(|t| {
+                    generate(
+                        &Framework::Rasn,
+                        ToplevelDeclaration::Type(ToplevelTypeDeclaration {
+                            parameterization: None,
+                            comments: " Inner type ".into(),
+                            name: o.type_name.clone(),
+                            r#type: t.clone(),
+                            tag: None,
+                        }),
+                        custom_derive,
+                        options,
+                    )
+                })
+            })
+            .collect::<Result<Vec<String>, GeneratorError>>()?
+            .join("\n");
+        let mut variants = objects
+            .iter()
+            .map(|o| format!("{}({}),", o.variant, o.type_name))
+            .collect::<Vec<String>>();
+        let key_constants = objects
+            .iter()
+            .map(|o| {
+                format!(
+                    "pub const {}: i128 = {};",
+                    to_rust_const_case(&o.variant),
+                    o.key
+                )
+            })
+            .collect::<Vec<String>>()
+            .join("\n    ");
+        // the contents of a closed set are distinguished like the alternatives of a CHOICE,
+        // while contents of an extensible set may belong to unknown objects
+        let (derive, rasn_annotations, key_type, key_arms) = if set.extensible.is_some() {
+            variants.push("UnknownClassImplementation(Any),".into());
+            let mut key_arms = objects
+                .iter()
+                .map(|o| {
+                    format!(
+                        "Self::{}(_) => Some(Self::{}),",
+                        o.variant,
+                        to_rust_const_case(&o.variant)
+                    )
+                })
+                .collect::<Vec<String>>();
+            key_arms.push("Self::UnknownClassImplementation(_) => None,".into());
+            (
+                "#[derive(Debug, Clone, PartialEq)]",
+                String::new(),
+                "Option<i128>",
+                key_arms,
+            )
+        } else {
+            let key_arms = objects
+                .iter()
+                .map(|o| {
+                    format!(
+                        "Self::{}(_) => Self::{},",
+                        o.variant,
+                        to_rust_const_case(&o.variant)
+                    )
+                })
+                .collect::<Vec<String>>();
+            (
+                "#[derive(AsnType, Debug, Clone, Decode, Encode, PartialEq)]",
+                join_annotations(vec!["choice".into()]),
+                "i128",
+                key_arms,
+            )
+        };
+        Ok(information_object_set_template(ObjectSetEnum {
+            comments: format_comments(&tld.comments),
+            derive: append_derives(derive, custom_derive),
+            rasn_annotations,
+            inner_types,
+            name,
+            options: variants.join("\n    "),
+            key_constants,
+            key_type: key_type.into(),
+            key_arms: key_arms.join("\n            "),
+        }))
+    }
 }
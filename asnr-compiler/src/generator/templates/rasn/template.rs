@@ -1,29 +1,52 @@
-use crate::generator::templates::rasn::utils::join_annotations;
+use crate::generator::templates::{append_derives, rasn::utils::join_annotations};
 
-pub fn rasn_imports_and_generic_types(
-    include_file_headers: bool
-) -> String {
+pub fn rasn_imports_and_generic_types(no_std: bool, file_header: Option<String>) -> String {
     format!(
         r#"{}
+{}
+use rasn::prelude::*;"#,
+        match file_header {
+            Some(comments) if no_std => comments + "#![no_std]",
+            Some(comments) => comments,
+            None => String::new(),
+        },
+        alloc_imports(no_std)
+    )
+}
 
-        extern crate alloc;
-        
-        use rasn::prelude::*;"#,
-        if include_file_headers {
-            "#![no_std]"
-        } else {
-            ""
-        }
+/// Imports of a generated module file that is declared alongside sibling modules
+pub fn rasn_module_imports(no_std: bool) -> String {
+    format!(
+        r#"{}
+use rasn::prelude::*;
+use super::*;
+"#,
+        alloc_imports(no_std)
     )
 }
 
+/// Imports of the heap-allocated types that the generated code uses besides rasn's prelude
+fn alloc_imports(no_std: bool) -> &'static str {
+    if no_std {
+        r#"extern crate alloc;
+use alloc::{string::String, vec, vec::Vec};"#
+    } else {
+        ""
+    }
+}
+
 pub fn typealias_template(
     comments: String,
     name: String,
     alias: String,
     tag_annotations: String,
     constraint_annotations: String,
+    custom_derive: Option<&str>,
 ) -> String {
+    let derive = append_derives(
+        "#[derive(AsnType, Debug, Clone, Decode, Encode, PartialEq)]",
+        custom_derive,
+    );
     let rasn_annotations: String = join_annotations(vec![
         "delegate".into(),
         tag_annotations,
@@ -32,7 +55,7 @@ pub fn typealias_template(
     format!(
         r#"
 {comments}
-#[derive(AsnType, Debug, Clone, Decode, Encode, PartialEq)]
+{derive}
 {rasn_annotations}pub struct {name}(pub {alias});
 "#
     )
@@ -59,24 +82,76 @@ pub const {name}: {vtype} = {value};
     )
 }
 
+pub fn choice_value_template(comments: String, name: String, ty: String, value: String) -> String {
+    format!(
+        r#"{comments}
+pub fn {name}() -> {ty} {{
+    {value}
+}}
+"#
+    )
+}
+
+pub fn bit_string_value_template(
+    comments: String,
+    name: String,
+    ty: String,
+    value: String,
+) -> String {
+    format!(
+        r#"{comments}
+pub fn {name}() -> {ty} {{
+    {value}
+}}
+"#
+    )
+}
+
+pub fn sequence_value_template(comments: String, name: String, ty: String, value: String) -> String {
+    format!(
+        r#"{comments}
+pub fn {name}() -> {ty} {{
+    {value}
+}}
+"#
+    )
+}
+
 pub fn integer_template(
     comments: String,
     name: String,
     constraint_annotations: String,
     tag_annotations: String,
+    distinguished_values: String,
     integer_type: String,
+    custom_derive: Option<&str>,
 ) -> String {
+    let derive = append_derives(
+        "#[derive(AsnType, Debug, Clone, Decode, Encode, PartialEq, PartialOrd, Eq, Ord, Hash)]",
+        custom_derive,
+    );
     let rasn_annotations: String = join_annotations(vec![
         "delegate".into(),
         tag_annotations,
         constraint_annotations,
     ]);
+    let distinguished_values = if distinguished_values.is_empty() {
+        distinguished_values
+    } else {
+        format!(
+            r#"
+impl {name} {{
+    {distinguished_values}
+}}
+"#
+        )
+    };
     format!(
         r#"
 {comments}
-#[derive(AsnType, Debug, Clone, Decode, Encode, PartialEq, PartialOrd, Eq, Ord, Hash)]
+{derive}
 {rasn_annotations}pub struct {name}(pub {integer_type});
-"#
+{distinguished_values}"#
     )
 }
 
@@ -85,18 +160,35 @@ pub fn bit_string_template(
     name: String,
     constraint_annotations: String,
     tag_annotations: String,
+    named_bits: String,
+    custom_derive: Option<&str>,
 ) -> String {
+    let derive = append_derives(
+        "#[derive(AsnType, Debug, Clone, Decode, Encode, PartialEq)]",
+        custom_derive,
+    );
     let rasn_annotations: String = join_annotations(vec![
         "delegate".into(),
         tag_annotations,
         constraint_annotations,
     ]);
+    let named_bits = if named_bits.is_empty() {
+        named_bits
+    } else {
+        format!(
+            r#"
+impl {name} {{
+    {named_bits}
+}}
+"#
+        )
+    };
     format!(
         r#"
 {comments}
-#[derive(AsnType, Debug, Clone, Decode, Encode, PartialEq)]
+{derive}
 {rasn_annotations}pub struct {name}(pub BitString);
-"#
+{named_bits}"#
     )
 }
 
@@ -105,7 +197,12 @@ pub fn octet_string_template(
     name: String,
     constraint_annotations: String,
     tag_annotations: String,
+    custom_derive: Option<&str>,
 ) -> String {
+    let derive = append_derives(
+        "#[derive(AsnType, Debug, Clone, Decode, Encode, PartialEq)]",
+        custom_derive,
+    );
     let rasn_annotations: String = join_annotations(vec![
         "delegate".into(),
         tag_annotations,
@@ -114,7 +211,7 @@ pub fn octet_string_template(
     format!(
         r#"
 {comments}
-#[derive(AsnType, Debug, Clone, Decode, Encode, PartialEq)]
+{derive}
 {rasn_annotations}pub struct {name}(pub OctetString);
 "#
     )
@@ -127,7 +224,12 @@ pub fn char_string_template(
     constraint_annotations: String,
     alphabet_annotations: String,
     tag_annotations: String,
+    custom_derive: Option<&str>,
 ) -> String {
+    let derive = append_derives(
+        "#[derive(AsnType, Debug, Clone, Decode, Encode, PartialEq)]",
+        custom_derive,
+    );
     let rasn_annotations: String = join_annotations(vec![
         "delegate".into(),
         tag_annotations,
@@ -137,7 +239,7 @@ pub fn char_string_template(
     format!(
         r#"
 {comments}
-#[derive(AsnType, Debug, Clone, Decode, Encode, PartialEq)]
+{derive}
 {rasn_annotations}pub struct {name}(pub {string_type});
 "#
     )
@@ -147,13 +249,18 @@ pub fn boolean_template(
     comments: String,
     name: String,
     tag_annotations: String,
+    custom_derive: Option<&str>,
 ) -> String {
+    let derive = append_derives(
+        "#[derive(AsnType, Debug, Clone, Copy, Decode, Encode, PartialEq)]",
+        custom_derive,
+    );
     let rasn_annotations: String =
         join_annotations(vec!["delegate".into(), tag_annotations]);
     format!(
         r#"
 {comments}
-#[derive(AsnType, Debug, Clone, Copy, Decode, Encode, PartialEq)]
+{derive}
 {rasn_annotations}pub struct {name}(pub bool);
 "#
     )
@@ -171,13 +278,18 @@ pub fn null_template(
     comments: String,
     name: String,
     tag_annotations: String,
+    custom_derive: Option<&str>,
 ) -> String {
+    let derive = append_derives(
+        "#[derive(AsnType, Debug, Clone, Copy, Decode, Encode, PartialEq)]",
+        custom_derive,
+    );
     let rasn_annotations: String =
         join_annotations(vec!["delegate".into(), tag_annotations]);
     format!(
         r#"
 {comments}
-#[derive(AsnType, Debug, Clone, Copy, Decode, Encode, PartialEq)]
+{derive}
 {rasn_annotations}pub struct {name}(());
 "#
     )
@@ -188,17 +300,34 @@ pub fn enumerated_template(
     name: String,
     extensible: &str,
     enum_members: String,
+    try_from_str_arms: String,
     tag_annotations: String,
+    custom_derive: Option<&str>,
 ) -> String {
+    let derive = append_derives(
+        "#[derive(AsnType, Debug, Clone, Copy, Decode, Encode, PartialEq, PartialOrd, Eq, Ord, Hash)]",
+        custom_derive,
+    );
     let rasn_annotations = join_annotations(vec!["enumerated".into(), tag_annotations]);
     format!(
         r#"
 {comments}
-#[derive(AsnType, Debug, Clone, Copy, Decode, Encode, PartialEq, PartialOrd, Eq, Ord, Hash)]
+{derive}
 {rasn_annotations}{extensible}
 pub enum {name} {{
     {enum_members}
 }}
+
+impl TryFrom<&str> for {name} {{
+    type Error = &'static str;
+
+    fn try_from(s: &str) -> Result<Self, Self::Error> {{
+        match s {{
+            {try_from_str_arms}
+            _ => Err("Invalid enumeral of {name}"),
+        }}
+    }}
+}}
 "#
     )
 }
@@ -212,13 +341,18 @@ pub fn sequence_or_set_template(
     tag_annotations: String,
     set_annotation: String,
     default_methods: String,
+    custom_derive: Option<&str>,
 ) -> String {
+    let derive = append_derives(
+        "#[derive(AsnType, Debug, Clone, Decode, Encode, PartialEq)]",
+        custom_derive,
+    );
     let rasn_annotations = join_annotations(vec![set_annotation, tag_annotations]);
     format!(
         r#"
         {nested_members}
         {comments}
-        #[derive(AsnType, Debug, Clone, Decode, Encode, PartialEq)]
+        {derive}
         {rasn_annotations}{extensible}pub struct {name} {{
             {members}
         }}
@@ -234,7 +368,12 @@ pub fn sequence_of_template(
     member_type: String,
     constraint_annotations: String,
     tag_annotations: String,
+    custom_derive: Option<&str>,
 ) -> String {
+    let derive = append_derives(
+        "#[derive(AsnType, Debug, Clone, Decode, Encode, PartialEq)]",
+        custom_derive,
+    );
     let rasn_annotations: String = join_annotations(vec![
         "delegate".into(),
         tag_annotations,
@@ -244,7 +383,7 @@ pub fn sequence_of_template(
         r#"
         {anonymous_item}
 {comments}
-#[derive(AsnType, Debug, Clone, Decode, Encode, PartialEq)]
+{derive}
 {rasn_annotations}pub struct {name}(pub SequenceOf<{member_type}>);
 "#
     )
@@ -257,12 +396,17 @@ pub fn choice_template(
     options: String,
     nested_options: String,
     tag_annotations: String,
+    custom_derive: Option<&str>,
 ) -> String {
+    let derive = append_derives(
+        "#[derive(AsnType, Debug, Clone, Decode, Encode, PartialEq)]",
+        custom_derive,
+    );
     let rasn_annotations = join_annotations(vec!["choice".into(), tag_annotations]);
     format!(
         r#"{nested_options}
 {comments}
-#[derive(AsnType, Debug, Clone, Decode, Encode, PartialEq)]
+{derive}
 {rasn_annotations}{extensible}pub enum {name} {{
   {options}
 }}
@@ -342,33 +486,50 @@ pub fn choice_template(
 //     )
 // }
 
-// pub fn information_object_set_template(
-//     comments: String,
-//     name: String,
-//     options: String,
-//     key_type: String,
-//     for_key_branches: String,
-// ) -> String {
-//     format!(
-//         r#"{comments}
-// pub enum {name} {{
-//   {options}
-// }}
+/// Formatted parts of the enum that holds the contents of an information object set
+pub struct ObjectSetEnum {
+    pub comments: String,
+    pub derive: String,
+    pub rasn_annotations: String,
+    pub inner_types: String,
+    pub name: String,
+    pub options: String,
+    pub key_constants: String,
+    pub key_type: String,
+    pub key_arms: String,
+}
 
-// impl<I: AsBytes + Debug> DecoderForKey<I, {key_type}> for {name} {{
-//   fn decoder_for_key<I, D>(
-//     key: {key_type},
-//   ) -> Result<fn(I) -> IResult<I, Self>, DecodingError>
-//   where
-//     D: Decoder,
-//     T: PartialEq,
-//     Self: Sized,
-//   {{
-//     match key {{
-//       {for_key_branches}
-//     }}
-//   }}
-// }}
-// "#
-//     )
-// }
+pub fn information_object_set_template(object_set: ObjectSetEnum) -> String {
+    let ObjectSetEnum {
+        comments,
+        derive,
+        rasn_annotations,
+        inner_types,
+        name,
+        options,
+        key_constants,
+        key_type,
+        key_arms,
+    } = object_set;
+    format!(
+        r#"
+{inner_types}
+{comments}
+{derive}
+{rasn_annotations}pub enum {name} {{
+    {options}
+}}
+
+impl {name} {{
+    {key_constants}
+
+    /// Returns the key of the information object that the content belongs to
+    pub fn key(&self) -> {key_type} {{
+        match self {{
+            {key_arms}
+        }}
+    }}
+}}
+"#
+    )
+}
@@ -1,15 +1,29 @@
 use asnr_grammar::{
-    constraints::Constraint,
+    constraints::{Constraint, ContentConstraint},
     encoding_rules::per_visible::{
         per_visible_range_constraints, CharsetSubset, PerVisibleAlphabetConstraints,
     },
-    types::{Choice, ChoiceOption, Enumerated, SequenceOrSet, SequenceOrSetMember},
-    utils::{to_rust_snake_case, to_rust_title_case},
+    error::GrammarError,
+    information_object::{
+        InformationObjectClass, InformationObjectFields, ObjectSet, ObjectSetValue,
+    },
+    types::{
+        BitString, Choice, ChoiceOption, Enumerated, Integer, SequenceOrSet, SequenceOrSetMember,
+    },
+    utils::{to_rust_const_case, to_rust_snake_case, to_rust_title_case, NamingStrategy},
     ASN1Type, ASN1Value, AsnTag, CharacterStringType, TagClass, TaggingEnvironment,
     ToplevelDeclaration, ToplevelTypeDeclaration,
 };
 
-use crate::generator::{error::GeneratorError, generate, templates::inner_name, Framework};
+use crate::generator::{
+    error::{GeneratorError, GeneratorErrorType},
+    generate,
+    templates::{
+        asnr::util::{resolve_default_syntax, resolve_syntax},
+        fits_integer_type, inner_name, is_extension_group,
+    },
+    Framework, GeneratorOptions, NamingConfig,
+};
 
 pub fn int_type_token(opt_min: Option<i128>, opt_max: Option<i128>) -> &'static str {
     if let (Some(min), Some(max)) = (opt_min, opt_max) {
@@ -24,6 +38,72 @@ pub fn int_type_token(opt_min: Option<i128>, opt_max: Option<i128>) -> &'static
     }
 }
 
+/// Returns the narrowest Rust type supported by rasn for an INTEGER.
+/// Unconstrained, extensible, and wider integers are represented by rasn's `Integer`.
+pub fn integer_type(integer: &Integer, options: &GeneratorOptions) -> String {
+    let token = integer.type_token();
+    if options.force_i128_integers || token.contains("128") {
+        "Integer".into()
+    } else {
+        token
+    }
+}
+
+/// Formats the distinguished values of an INTEGER as associated items of its delegate type.
+/// Since rasn's arbitrary-precision `Integer` cannot be constructed in a const context,
+/// its distinguished values are exposed as associated functions instead of constants.
+/// Values that do not fit the generated `integer_type` are skipped.
+pub fn format_distinguished_values(integer: &Integer, integer_type: &str) -> String {
+    let Some(distinguished_values) = &integer.distinguished_values else {
+        return String::new();
+    };
+    distinguished_values
+        .iter()
+        .filter(|d| fits_integer_type(d.value, integer_type))
+        .map(|d| {
+            let value = d.value;
+            if integer_type == "Integer" {
+                let name = to_rust_snake_case(&d.name);
+                format!("pub fn {name}() -> Self {{ Self(Integer::from({value})) }}")
+            } else {
+                let name = to_rust_const_case(&d.name);
+                format!("pub const {name}: Self = Self({value});")
+            }
+        })
+        .collect::<Vec<String>>()
+        .join("\n    ")
+}
+
+/// Formats the index constants, getters and setters of a BIT STRING's named bits.
+/// Setting a bit beyond the current length grows the bit string.
+pub fn format_named_bits(bit_string: &BitString) -> String {
+    let Some(named_bits) = &bit_string.distinguished_values else {
+        return String::new();
+    };
+    named_bits
+        .iter()
+        .map(|bit| {
+            let name = to_rust_snake_case(&bit.name);
+            let index = to_rust_const_case(&bit.name);
+            let i = bit.value;
+            format!(
+                r#"pub const {index}: usize = {i};
+
+    pub fn {name}(&self) -> bool {{
+        self.0.get(Self::{index}).is_some_and(|bit| *bit)
+    }}
+
+    pub fn set_{name}(&mut self, value: bool) {{
+        let len = self.0.len().max(Self::{index} + 1);
+        self.0.resize(len, false);
+        self.0.set(Self::{index}, value);
+    }}"#
+            )
+        })
+        .collect::<Vec<String>>()
+        .join("\n\n    ")
+}
+
 pub fn format_range_annotations(
     signed: bool,
     constraints: &Vec<Constraint>,
@@ -37,10 +117,15 @@ pub fn format_range_annotations(
     } else {
         "value"
     };
-    // handle default size constraints
-    if per_constraints.is_size_constraint()
-        && !per_constraints.is_extensible()
-        && per_constraints.min::<i128>() == Some(0)
+    // handle default size constraints and constraints without PER-visible bounds,
+    // e.g. the permitted alphabet constraints of character strings
+    let default_min = if signed && !per_constraints.is_size_constraint() {
+        None
+    } else {
+        Some(0)
+    };
+    if !per_constraints.is_extensible()
+        && per_constraints.min::<i128>() == default_min
         && per_constraints.max::<i128>().is_none()
     {
         return Ok(String::new());
@@ -84,46 +169,94 @@ pub fn format_alphabet_annotations(
             .map(|mut p| permitted_alphabet += &mut p);
     }
     permitted_alphabet.finalize();
-    let alphabet_unicode: Vec<String> = permitted_alphabet
+    let alphabet = permitted_alphabet
         .charset_subsets()
         .iter()
         .map(|subset| match subset {
-            CharsetSubset::Single(c) => format!("{}", c.escape_unicode()),
-            CharsetSubset::Range { from, to } => format!(
-                "{}..{}",
-                from.map_or(String::from(""), |c| format!("{}", c.escape_unicode())),
-                to.map_or(String::from(""), |c| format!("{}", c.escape_unicode()))
-            ),
+            CharsetSubset::Single(c) => Some(format!(r#""{}""#, c.escape_default())),
+            CharsetSubset::Range {
+                from: Some(from),
+                to: Some(to),
+            } if from == to => Some(format!(r#""{}""#, from.escape_default())),
+            CharsetSubset::Range {
+                from: Some(from),
+                to: Some(to),
+            } => Some(format!(
+                r#""{}".."{}""#,
+                from.escape_default(),
+                to.escape_default()
+            )),
+            // ranges without known bounds cannot be expressed as a permitted alphabet
+            CharsetSubset::Range { .. } => None,
         })
-        .collect();
-    Ok(if alphabet_unicode.is_empty() {
+        .collect::<Option<Vec<String>>>()
+        .unwrap_or_default();
+    Ok(if alphabet.is_empty() {
         "".into()
     } else {
-        String::from(", from(") + &alphabet_unicode.join(", ") + ")"
+        format!("from({})", alphabet.join(", "))
     })
 }
 
-pub fn format_enum_members(enumerated: &Enumerated) -> String {
+/// Formats the variants of an ENUMERATED's enum.
+/// If serde is derived, variants that are not named like their enumeral are renamed to it.
+pub fn format_enum_members(
+    enumerated: &Enumerated,
+    naming: &NamingStrategy,
+    serde: bool,
+) -> String {
     let first_extension_index = enumerated.extensible;
-    enumerated.members.iter().fold(String::new(), |acc, e| {
-        let rust_name = to_rust_title_case(&e.name);
-        let name = if acc.contains(&format!(r#" {rust_name} = "#)) {
-            e.name.replace("-", "_")
-        } else {
-            rust_name
-        };
-        let index = e.index;
-        let extension = if index >= first_extension_index.map_or(i128::MAX, |x| x as i128) {
-            r#"#[rasn(extension_addition)]
+    enum_variants(enumerated, naming)
+        .into_iter()
+        .zip(&enumerated.members)
+        .fold(String::new(), |acc, (name, e)| {
+            let index = e.index;
+            let extension = if index >= first_extension_index.map_or(i128::MAX, |x| x as i128) {
+                r#"#[rasn(extension_addition)]
             "#
-        } else {
-            ""
-        };
-        acc + &format!(
-            r#"{extension} {name} = {index},
+            } else {
+                ""
+            };
+            let rename = if serde && name != e.name {
+                format!(
+                    r#"#[serde(rename = "{}")]
+            "#,
+                    e.name
+                )
+            } else {
+                String::new()
+            };
+            acc + &format!(
+                r#"{extension}{rename} {name} = {index},
                 "#
-        )
-    })
+            )
+        })
+}
+
+/// Formats the arms of an ENUMERATED's `TryFrom<&str>` implementation,
+/// which matches the enumerals' ASN1 names
+pub fn format_enum_try_from_str_arms(enumerated: &Enumerated, naming: &NamingStrategy) -> String {
+    enum_variants(enumerated, naming)
+        .into_iter()
+        .zip(&enumerated.members)
+        .map(|(name, e)| format!(r#""{}" => Ok(Self::{name}),"#, e.name))
+        .collect::<Vec<String>>()
+        .join("\n            ")
+}
+
+/// Returns the names of the variants of an ENUMERATED's enum.
+/// Enumerals whose names collide after applying the naming strategy keep their ASN1 names.
+fn enum_variants(enumerated: &Enumerated, naming: &NamingStrategy) -> Vec<String> {
+    let mut variants = Vec::<String>::new();
+    for e in &enumerated.members {
+        let rust_name = naming.apply(&e.name);
+        if variants.contains(&rust_name) {
+            variants.push(e.name.replace("-", "_"));
+        } else {
+            variants.push(rust_name);
+        }
+    }
+    variants
 }
 
 pub fn format_tag(tag: Option<&AsnTag>) -> String {
@@ -149,6 +282,7 @@ pub fn format_tag(tag: Option<&AsnTag>) -> String {
 pub fn format_sequence_or_set_members(
     sequence_or_set: &SequenceOrSet,
     parent_name: &String,
+    options: &GeneratorOptions,
 ) -> Result<String, GeneratorError> {
     let first_extension_index = sequence_or_set.extensible;
     Ok(sequence_or_set
@@ -157,7 +291,7 @@ pub fn format_sequence_or_set_members(
         .enumerate()
         .map(|(i, m)| {
             let extension_annotation = if i >= first_extension_index.unwrap_or(usize::MAX)
-                && m.name.starts_with("ext_group_")
+                && is_extension_group(&m.name)
             {
                 "extension_addition_group"
             } else if i >= first_extension_index.unwrap_or(usize::MAX) {
@@ -165,7 +299,7 @@ pub fn format_sequence_or_set_members(
             } else {
                 ""
             };
-            format_sequence_member(m, parent_name, extension_annotation)
+            format_sequence_member(m, parent_name, extension_annotation, options)
         })
         .collect::<Result<Vec<String>, _>>()?
         .join(
@@ -178,34 +312,33 @@ fn format_sequence_member(
     member: &SequenceOrSetMember,
     parent_name: &String,
     extension_annotation: &str,
+    options: &GeneratorOptions,
 ) -> Result<String, GeneratorError> {
     let name = to_rust_snake_case(&member.name);
     let (mut all_constraints, mut formatted_type_name) = match &member.r#type {
         ASN1Type::Null => (vec![], "()".into()),
         ASN1Type::Boolean => (vec![], "bool".into()),
-        ASN1Type::Integer(i) => {
-            let per_constraints = per_visible_range_constraints(true, &i.constraints)?;
-            (
-                i.constraints.clone(),
-                int_type_token(per_constraints.min(), per_constraints.max()).into(),
-            )
-        }
+        ASN1Type::Integer(i) => (i.constraints.clone(), integer_type(i, options)),
         ASN1Type::Real(_) => (vec![], "f64".into()),
         ASN1Type::BitString(b) => (b.constraints.clone(), "BitString".into()),
         ASN1Type::ObjectIdentifier(b) => (b.constraints.clone(), "Oid".into()),
         ASN1Type::OctetString(o) => (o.constraints.clone(), "OctetString".into()),
-        ASN1Type::CharacterString(c) => (c.constraints.clone(), string_type(&c.r#type)),
+        ASN1Type::CharacterString(c) => (c.constraints.clone(), string_type(&c.r#type)?),
         ASN1Type::Enumerated(_)
         | ASN1Type::Choice(_)
         | ASN1Type::Sequence(_)
         | ASN1Type::SequenceOf(_)
-        | ASN1Type::Set(_) => (vec![], inner_name(&member.name, parent_name)),
+        | ASN1Type::Set(_)
+        | ASN1Type::SetOf(_) => (vec![], inner_name(&member.name, parent_name, &options.naming)),
         ASN1Type::ElsewhereDeclaredType(e) => {
             (e.constraints.clone(), to_rust_title_case(&e.identifier))
         }
         ASN1Type::InformationObjectFieldReference(_) => (vec![], "Any".into()),
     };
     all_constraints.append(&mut member.constraints.clone());
+    if member.is_recursive {
+        formatted_type_name = format!("Box<{formatted_type_name}>");
+    }
     if member.is_optional && member.default_value.is_none() {
         formatted_type_name = String::from("Option<") + &formatted_type_name + ">";
     }
@@ -234,12 +367,38 @@ fn format_sequence_member(
         tag,
         default_annotation,
     ]);
-    Ok(format!(r#"{annotations}{name}: {formatted_type_name}"#))
+    let contents_doc = if matches!(member.r#type, ASN1Type::OctetString(_)) {
+        format_contained_type_doc(&all_constraints)
+    } else {
+        String::new()
+    };
+    Ok(format!(
+        r#"{contents_doc}{annotations}{name}: {formatted_type_name}"#
+    ))
+}
+
+/// Documents the type whose encoded values an OCTET STRING contains
+/// according to its CONTAINING constraint
+fn format_contained_type_doc(constraints: &[Constraint]) -> String {
+    constraints
+        .iter()
+        .find_map(|c| match c {
+            Constraint::ContentConstraint(ContentConstraint {
+                containing: Some(ASN1Type::ElsewhereDeclaredType(d)),
+                ..
+            }) => Some(format!(
+                "/// Contains an encoded [{}]\n    ",
+                to_rust_title_case(&d.identifier)
+            )),
+            _ => None,
+        })
+        .unwrap_or_default()
 }
 
 pub fn format_choice_options(
     choice: &Choice,
-    parent_name: &String,
+    parent_name: &str,
+    options: &GeneratorOptions,
 ) -> Result<String, GeneratorError> {
     let first_extension_index = choice.extensible;
     choice
@@ -248,7 +407,7 @@ pub fn format_choice_options(
         .enumerate()
         .try_fold(String::new(), |acc, (i, o)| {
             let extension_annotation = if i >= first_extension_index.unwrap_or(usize::MAX)
-                && o.name.starts_with("ext_group_")
+                && is_extension_group(&o.name)
             {
                 "extension_addition_group"
             } else if i >= first_extension_index.unwrap_or(usize::MAX) {
@@ -262,42 +421,41 @@ pub fn format_choice_options(
             } else {
                 rust_name
             };
-            format_choice_option(name, o, parent_name, extension_annotation).map(|opt| acc + &opt)
+            format_choice_option(name, o, parent_name, extension_annotation, options).map(|opt| acc + &opt)
         })
 }
 
 fn format_choice_option(
     name: String,
     member: &ChoiceOption,
-    parent_name: &String,
+    parent_name: &str,
     extension_annotation: &str,
+    options: &GeneratorOptions,
 ) -> Result<String, GeneratorError> {
-    let (mut all_constraints, formatted_type_name) = match &member.r#type {
+    let (mut all_constraints, mut formatted_type_name) = match &member.r#type {
         ASN1Type::Null => (vec![], "()".into()),
         ASN1Type::Boolean => (vec![], "bool".into()),
-        ASN1Type::Integer(i) => {
-            let per_constraints = per_visible_range_constraints(true, &i.constraints)?;
-            (
-                i.constraints.clone(),
-                int_type_token(per_constraints.min(), per_constraints.max()).into(),
-            )
-        }
+        ASN1Type::Integer(i) => (i.constraints.clone(), integer_type(i, options)),
         ASN1Type::ObjectIdentifier(b) => (b.constraints.clone(), "Oid".into()),
         ASN1Type::Real(_) => (vec![], "f64".into()),
         ASN1Type::BitString(b) => (b.constraints.clone(), "BitString".into()),
         ASN1Type::OctetString(o) => (o.constraints.clone(), "OctetString".into()),
-        ASN1Type::CharacterString(c) => (c.constraints.clone(), string_type(&c.r#type)),
+        ASN1Type::CharacterString(c) => (c.constraints.clone(), string_type(&c.r#type)?),
         ASN1Type::Enumerated(_)
         | ASN1Type::Choice(_)
         | ASN1Type::Sequence(_)
         | ASN1Type::SequenceOf(_)
-        | ASN1Type::Set(_) => (vec![], inner_name(&member.name, parent_name)),
+        | ASN1Type::Set(_)
+        | ASN1Type::SetOf(_) => (vec![], inner_name(&member.name, parent_name, &options.naming)),
         ASN1Type::ElsewhereDeclaredType(e) => {
             (e.constraints.clone(), to_rust_title_case(&e.identifier))
         }
         ASN1Type::InformationObjectFieldReference(_) => (vec![], "Any".into()),
     };
     all_constraints.append(&mut member.constraints.clone());
+    if member.is_recursive {
+        formatted_type_name = format!("Box<{formatted_type_name}>");
+    }
     let range_annotations = format_range_annotations(
         matches!(member.r#type, ASN1Type::Integer(_)),
         &all_constraints,
@@ -320,19 +478,45 @@ fn format_choice_option(
     ))
 }
 
-pub fn string_type(c_type: &CharacterStringType) -> String {
+/// Names the type returned by the method providing a member's DEFAULT value.
+/// Constructed inline types are named after the inner types generated for them.
+fn default_type_name(
+    member: &SequenceOrSetMember,
+    parent_name: &str,
+    naming: &NamingConfig,
+) -> String {
+    match &member.r#type {
+        ASN1Type::Enumerated(_)
+        | ASN1Type::Choice(_)
+        | ASN1Type::Sequence(_)
+        | ASN1Type::SequenceOf(_)
+        | ASN1Type::Set(_)
+        | ASN1Type::SetOf(_)
+        | ASN1Type::ObjectIdentifier(_)
+        | ASN1Type::InformationObjectFieldReference(_) => {
+            inner_name(&member.name, parent_name, naming)
+        }
+        ty => ty.to_string(),
+    }
+}
+
+pub fn string_type(c_type: &CharacterStringType) -> Result<String, GeneratorError> {
     match c_type {
-        CharacterStringType::NumericString => "NumericString".into(),
-        CharacterStringType::VisibleString => "VisibleString".into(),
-        CharacterStringType::IA5String => "Ia5String".into(),
-        CharacterStringType::TeletexString => "TeletexString".into(),
-        CharacterStringType::VideotexString => todo!(),
-        CharacterStringType::GraphicString => todo!(),
-        CharacterStringType::GeneralString => "GeneralString".into(),
-        CharacterStringType::UniversalString => todo!(),
-        CharacterStringType::UTF8String => "Utf8String".into(),
-        CharacterStringType::BMPString => "BmpString".into(),
-        CharacterStringType::PrintableString => "PrintableString".into(),
+        CharacterStringType::NumericString => Ok("NumericString".into()),
+        CharacterStringType::VisibleString => Ok("VisibleString".into()),
+        CharacterStringType::IA5String => Ok("Ia5String".into()),
+        CharacterStringType::TeletexString => Ok("TeletexString".into()),
+        CharacterStringType::GeneralString => Ok("GeneralString".into()),
+        CharacterStringType::UTF8String => Ok("Utf8String".into()),
+        CharacterStringType::BMPString => Ok("BmpString".into()),
+        CharacterStringType::PrintableString => Ok("PrintableString".into()),
+        CharacterStringType::VideotexString
+        | CharacterStringType::GraphicString
+        | CharacterStringType::UniversalString => Err(GeneratorError::new(
+            None,
+            &format!("The Rasn framework does not support {c_type:?}s yet"),
+            GeneratorErrorType::UnsupportedConstruct,
+        )),
     }
 }
 
@@ -353,6 +537,235 @@ pub fn join_annotations(strings: Vec<String>) -> String {
     }
 }
 
+/// Formats an enumerated value, naming the enumeral according to the `enumerals` strategy
+fn format_enumerated_value(
+    value: &ASN1Value,
+    enumerals: &NamingStrategy,
+) -> Result<String, GrammarError> {
+    match value {
+        ASN1Value::EnumeratedValue {
+            enumerated,
+            enumerable,
+        } => Ok(format!(
+            "{}::{}",
+            to_rust_title_case(enumerated),
+            enumerals.apply(enumerable)
+        )),
+        v => v.value_as_string(None),
+    }
+}
+
+/// Formats a CHOICE value as the construction of the chosen alternative.
+/// Inline constructed alternatives and members are named like the inner types
+/// declared by the type generator. Referenced types are only known for values
+/// that the validator has linked to their types.
+pub fn format_choice_value(
+    parent_name: &str,
+    option: &String,
+    value: &ASN1Value,
+    options: &GeneratorOptions,
+) -> Result<String, GeneratorError> {
+    Ok(format!(
+        "{parent_name}::{}({})",
+        to_rust_title_case(option),
+        format_nested_value(
+            &inner_name(option, parent_name, &options.naming),
+            value,
+            options
+        )?
+    ))
+}
+
+/// Formats a SEQUENCE value as the initialization of its struct.
+/// The validator links the value to the members of its type, so that
+/// all members are present and OPTIONAL members are wrapped in `Option`s.
+pub fn format_sequence_value(
+    type_name: &str,
+    value: &ASN1Value,
+    options: &GeneratorOptions,
+) -> Result<String, GeneratorError> {
+    format_nested_value(type_name, value, options)
+}
+
+fn format_nested_value(
+    type_name: &str,
+    value: &ASN1Value,
+    options: &GeneratorOptions,
+) -> Result<String, GeneratorError> {
+    Ok(match value {
+        ASN1Value::Choice(option, inner) => format_choice_value(type_name, option, inner, options)?,
+        ASN1Value::Sequence(members) => format!(
+            "{type_name} {{ {} }}",
+            members
+                .iter()
+                .map(|(name, v)| {
+                    if let Some(id) = unresolved_reference(v) {
+                        return Err(GeneratorError::new(
+                            None,
+                            &format!("Failed to resolve the value reference {id} of member {name}"),
+                            GeneratorErrorType::UnresolvedValueReference,
+                        ));
+                    }
+                    format_nested_value(&inner_name(name, type_name, &options.naming), v, options)
+                        .map(|v| format!("{}: {v}", to_rust_snake_case(name)))
+                })
+                .collect::<Result<Vec<String>, _>>()?
+                .join(", ")
+        ),
+        ASN1Value::LinkedNestedValue { r#type, value } => {
+            format_linked_value(type_name, r#type, value, options)?
+        }
+        ASN1Value::Optional(Some(v)) => {
+            format!("Some({})", format_nested_value(type_name, v, options)?)
+        }
+        ASN1Value::Optional(None) => "None".into(),
+        ASN1Value::Null => "()".into(),
+        // rasn's integer and string types are converted from literals
+        ASN1Value::Integer(i) => format!("{i}.into()"),
+        ASN1Value::String(s) => format!("{s:?}.try_into().unwrap()"),
+        ASN1Value::Real(r) => format!("{r:?}"),
+        ASN1Value::BitString(_) => format!("{}.iter().collect()", value.value_as_string(None)?),
+        ASN1Value::EnumeratedValue { .. } => {
+            format_enumerated_value(value, &options.naming.enumerals)?
+        }
+        v => v.value_as_string(None)?,
+    })
+}
+
+/// Formats a value that the validator has linked to its type
+/// * `type_name` - the name of the type if it is declared inline
+fn format_linked_value(
+    type_name: &str,
+    r#type: &ASN1Type,
+    value: &ASN1Value,
+    options: &GeneratorOptions,
+) -> Result<String, GeneratorError> {
+    Ok(match (r#type, value) {
+        (ASN1Type::ElsewhereDeclaredType(e), value) => {
+            let name = to_rust_title_case(&e.identifier);
+            match value {
+                ASN1Value::LinkedNestedValue {
+                    r#type: inner_type,
+                    value: inner,
+                } if matches!(
+                    inner_type.as_ref(),
+                    ASN1Type::Sequence(_)
+                        | ASN1Type::Set(_)
+                        | ASN1Type::Choice(_)
+                        | ASN1Type::Enumerated(_)
+                ) =>
+                {
+                    format_linked_value(&name, inner_type, inner, options)?
+                }
+                // other referenced types are generated as newtypes
+                v => format!("{name}({})", format_nested_value(&name, v, options)?),
+            }
+        }
+        (ASN1Type::Enumerated(_), ASN1Value::EnumeratedValue { enumerable, .. }) => {
+            format!(
+                "{type_name}::{}",
+                options.naming.enumerals.apply(enumerable)
+            )
+        }
+        (ASN1Type::Integer(i), ASN1Value::Integer(v)) if integer_type(i, options) != "Integer" => {
+            v.to_string()
+        }
+        (_, value) => format_nested_value(type_name, value, options)?,
+    })
+}
+
+/// Returns the identifier of a value reference that the validator could not resolve
+fn unresolved_reference(value: &ASN1Value) -> Option<&String> {
+    match value {
+        ASN1Value::ElsewhereDeclaredValue(id) => Some(id),
+        ASN1Value::LinkedNestedValue { value, .. } => unresolved_reference(value),
+        ASN1Value::Optional(Some(v)) => unresolved_reference(v),
+        _ => None,
+    }
+}
+
+/// An object of an information object set, resolved to the variant of the generated enum
+pub struct ObjectSetOption {
+    pub key: i128,
+    pub variant: String,
+    pub type_name: String,
+    /// The type of the object if it is declared inline
+    pub inner_type: Option<ASN1Type>,
+}
+
+/// Resolves the objects of an information object set to their INTEGER keys and the type
+/// of the first type field of the class that they declare.
+/// Variants are named after the types they hold. Types that are declared inline are named
+/// after their key and generated as inner types.
+pub fn extract_object_set_options(
+    set: &ObjectSet,
+    class: &InformationObjectClass,
+    parent_name: &str,
+    options: &GeneratorOptions,
+) -> Result<Vec<ObjectSetOption>, GeneratorError> {
+    let key_name = |key: i128| {
+        if key < 0 {
+            format!("KeyMinus{}", key.unsigned_abs())
+        } else {
+            format!("Key{key}")
+        }
+    };
+    let mut objects = Vec::<ObjectSetOption>::new();
+    for value in &set.values {
+        let (key, types) = match value {
+            ObjectSetValue::Inline(InformationObjectFields::CustomSyntax(s)) => {
+                resolve_syntax(class, s)?
+            }
+            ObjectSetValue::Inline(InformationObjectFields::DefaultSyntax(f)) => {
+                resolve_default_syntax(class, f)?
+            }
+            ObjectSetValue::Reference(r) => {
+                return Err(GeneratorError::new(
+                    None,
+                    &format!("Unresolved reference {r} in information object set"),
+                    GeneratorErrorType::MissingClassLink,
+                ))
+            }
+        };
+        let ASN1Value::Integer(key) = key else {
+            return Err(GeneratorError::new(
+                None,
+                &format!("Information object sets with key {key:?} are not yet supported"),
+                GeneratorErrorType::UnsupportedObjectSetKey,
+            ));
+        };
+        let (mut variant, type_name, inner_type) = match types.into_iter().next() {
+            Some(ASN1Type::ElsewhereDeclaredType(d)) => (
+                to_rust_title_case(&d.identifier),
+                to_rust_title_case(&d.identifier),
+                None,
+            ),
+            Some(t) => (
+                key_name(key),
+                inner_name(&key_name(key), parent_name, &options.naming),
+                Some(t),
+            ),
+            None => {
+                return Err(GeneratorError::new(
+                    None,
+                    &format!("Information object with key {key} declares no type"),
+                    GeneratorErrorType::SyntaxMismatch,
+                ))
+            }
+        };
+        if objects.iter().any(|o| o.variant == variant) {
+            variant += &key_name(key);
+        }
+        objects.push(ObjectSetOption {
+            key,
+            variant,
+            type_name,
+            inner_type,
+        });
+    }
+    Ok(objects)
+}
+
 pub fn default_method_name(parent_name: &String, field_name: &String) -> String {
     format!(
         "{}_{}_default",
@@ -364,11 +777,31 @@ pub fn default_method_name(parent_name: &String, field_name: &String) -> String
 pub fn format_default_methods(
     members: &Vec<SequenceOrSetMember>,
     parent_name: &String,
+    options: &GeneratorOptions,
 ) -> Result<String, GeneratorError> {
+    let naming = &options.naming;
     let mut output = String::new();
     for member in members {
         if let Some(value) = member.default_value.as_ref() {
             let (value_as_string, type_as_string) = match &member.r#type {
+                _ if matches!(value, ASN1Value::EnumeratedValue { .. }) => (
+                    format_enumerated_value(value, &naming.enumerals)?,
+                    default_type_name(member, parent_name, naming),
+                ),
+                // SEQUENCE and CHOICE values have been linked to their types by the validator
+                _ if matches!(
+                    value,
+                    ASN1Value::Sequence(_)
+                        | ASN1Value::Choice(_, _)
+                        | ASN1Value::LinkedNestedValue { .. }
+                ) =>
+                {
+                    let type_name = default_type_name(member, parent_name, naming);
+                    (
+                        format_linked_value(&type_name, &member.r#type, value, options)?,
+                        type_name,
+                    )
+                }
                 ASN1Type::BitString(_) => (
                     format!("{}.iter().collect()", value.value_as_string(None)?),
                     "BitString".into(),
@@ -388,10 +821,13 @@ pub fn format_default_methods(
                         stringified_type,
                     )
                 }
-                ty => (
-                    value.value_as_string(Some(&to_rust_title_case(&ty.to_string())))?,
-                    ty.to_string(),
-                ),
+                _ => {
+                    let type_name = default_type_name(member, parent_name, naming);
+                    (
+                        value.value_as_string(Some(&to_rust_title_case(&type_name)))?,
+                        type_name,
+                    )
+                }
             };
             let method_name = default_method_name(parent_name, &member.name);
             output.push_str(&format!(
@@ -408,7 +844,9 @@ pub fn format_default_methods(
 
 pub fn format_nested_sequence_members(
     sequence_or_set: &SequenceOrSet,
-    parent_name: &String,
+    parent_name: &str,
+    custom_derive: Option<&str>,
+    options: &GeneratorOptions,
 ) -> Result<String, GeneratorError> {
     Ok(sequence_or_set
         .members
@@ -421,6 +859,7 @@ pub fn format_nested_sequence_members(
                     | ASN1Type::Sequence(_)
                     | ASN1Type::SequenceOf(_)
                     | ASN1Type::Set(_)
+                    | ASN1Type::SetOf(_)
             )
         })
         .map(|m| {
@@ -429,11 +868,12 @@ pub fn format_nested_sequence_members(
                 ToplevelDeclaration::Type(ToplevelTypeDeclaration {
                     parameterization: None,
                     comments: " Inner type ".into(),
-                    name: inner_name(&m.name, parent_name),
+                    name: inner_name(&m.name, parent_name, &options.naming),
                     r#type: m.r#type.clone(),
                     tag: None,
                 }),
-                None,
+                custom_derive,
+                options,
             )
         })
         .collect::<Result<Vec<String>, _>>()?
@@ -446,7 +886,9 @@ pub fn format_nested_sequence_members(
 
 pub fn format_nested_choice_options(
     choice: &Choice,
-    parent_name: &String,
+    parent_name: &str,
+    custom_derive: Option<&str>,
+    options: &GeneratorOptions,
 ) -> Result<String, GeneratorError> {
     Ok(choice
         .options
@@ -459,6 +901,7 @@ pub fn format_nested_choice_options(
                     | ASN1Type::Sequence(_)
                     | ASN1Type::SequenceOf(_)
                     | ASN1Type::Set(_)
+                    | ASN1Type::SetOf(_)
             )
         })
         .map(|m| {
@@ -467,11 +910,12 @@ pub fn format_nested_choice_options(
                 ToplevelDeclaration::Type(ToplevelTypeDeclaration {
                     parameterization: None,
                     comments: " Inner type ".into(),
-                    name: inner_name(&m.name, parent_name),
+                    name: inner_name(&m.name, parent_name, &options.naming),
                     r#type: m.r#type.clone(),
                     tag: None,
                 }),
-                None,
+                custom_derive,
+                options,
             )
         })
         .collect::<Result<Vec<String>, _>>()?
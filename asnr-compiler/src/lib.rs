@@ -23,6 +23,8 @@
 //!         PathBuf::from("spec_2.asn"),
 //!         PathBuf::from("spec_3.asn"),
 //!     ].iter())
+//!     // add the .asn and .asn1 files of a directory
+//!     .add_asn_sources_by_dir(PathBuf::from("specs"))
 //!     // set an output path for the generated rust code
 //!     .set_output_path(PathBuf::from("./asn/generated.rs"))
 //!     // you may also compile literal ASN1 snippets
@@ -35,24 +37,45 @@
 //!   }
 //! }
 //! ```
+mod cache;
 mod generator;
 mod parser;
 mod validator;
 
 use std::{
+    collections::{BTreeMap, BTreeSet},
     env::{self},
     error::Error,
     fs::{self, read_to_string},
-    io::{self, Write},
-    path::PathBuf,
+    io::{self, Read, Write},
+    path::{Path, PathBuf},
     process::{Command, Stdio},
     vec,
 };
 
-use asnr_grammar::ToplevelDeclaration;
-use generator::{generate, imports_and_generic_types};
-use parser::asn_spec;
-use validator::Validator;
+use asnr_grammar::{information_object::ASN1Information, EncodingReferenceDefault};
+pub use asnr_grammar::{
+    utils::{to_rust_const_case, to_rust_snake_case, to_rust_title_case},
+    ModuleReference, ToplevelDeclaration,
+};
+use cache::GenerationCache;
+pub use generator::{
+    error::{GeneratorError, GeneratorErrorType},
+    CustomFramework, Derive, Generator, GeneratorOptions, HeaderConfig, NamingConfig,
+    NamingStrategy,
+};
+use generator::{
+    fixed_encoded_sizes, generate, generate_type_override, imports_and_generic_types,
+    module_imports,
+    templates::{apply_derives, json_schema::json_schema_document},
+    validate_derives,
+};
+use parser::{asn_spec, asn_spec_with_recovery, declaration_sources};
+pub use parser::error::{ParserError, ParserErrorType};
+use proc_macro2::{Span, TokenStream};
+use quote::ToTokens;
+use syn::{parse::Parser, Attribute, Item};
+use validator::{topological_sort, Validator};
 
 /// The ASNR compiler
 #[derive(Debug, PartialEq)]
@@ -60,61 +83,434 @@ pub struct Asnr<S: AsnrState> {
     state: S,
 }
 
-/// Typestate representing compiler with missing parameters
-pub struct AsnrMissingParams {
+/// Compiler configuration shared by all typestates
+#[derive(Debug, PartialEq)]
+struct AsnrConfig {
     no_std: bool,
-    framework: Framework,
+    framework: Option<Framework>,
+    merge_duplicate_declarations: bool,
+    use_rustfmt: bool,
+    force_i128_integers: bool,
+    materialize_defaults: bool,
+    suppress_lints: bool,
+    skip_unparsable_declarations: bool,
+    flatten_primitive_members: bool,
+    contained_type_accessors: bool,
+    mark_extensible_as_non_exhaustive: bool,
+    type_prefix: String,
+    type_suffix: String,
+    recursive_source_dirs: bool,
+    source_dir_extensions: Vec<String>,
+    naming: NamingConfig,
+    header: HeaderConfig,
+    embed_asn1_definitions: bool,
+    custom_derives: Vec<String>,
+    derives: Option<Vec<Derive>>,
+    type_overrides: BTreeMap<String, String>,
+    derive_overrides: BTreeMap<String, Vec<String>>,
+    cache_dir: Option<PathBuf>,
+    emit_rerun_directives: bool,
 }
 
-impl Default for AsnrMissingParams {
+impl Default for AsnrConfig {
     fn default() -> Self {
         Self {
             no_std: false,
-            framework: Framework::Asnr,
+            framework: None,
+            merge_duplicate_declarations: true,
+            use_rustfmt: false,
+            force_i128_integers: false,
+            materialize_defaults: false,
+            suppress_lints: false,
+            skip_unparsable_declarations: false,
+            flatten_primitive_members: false,
+            contained_type_accessors: false,
+            mark_extensible_as_non_exhaustive: true,
+            type_prefix: String::new(),
+            type_suffix: String::new(),
+            recursive_source_dirs: false,
+            source_dir_extensions: vec![String::from("asn"), String::from("asn1")],
+            naming: NamingConfig::default(),
+            header: HeaderConfig::default(),
+            embed_asn1_definitions: false,
+            custom_derives: vec![],
+            derives: None,
+            type_overrides: BTreeMap::new(),
+            derive_overrides: BTreeMap::new(),
+            cache_dir: env::var_os("OUT_DIR").map(PathBuf::from),
+            emit_rerun_directives: env::var_os("OUT_DIR").is_some(),
         }
     }
 }
 
-#[derive(Debug, PartialEq, Default)]
+/// Typestate representing compiler with missing parameters
+#[derive(Default)]
+pub struct AsnrMissingParams {
+    config: AsnrConfig,
+}
+
+#[derive(Debug, Clone, PartialEq, Default)]
 pub enum Framework {
     #[default]
     Asnr,
     Rasn,
+    /// Generates a JSON Schema (Draft 7) document instead of Rust bindings
+    JsonSchema,
+    /// Generates bindings with a custom `Generator` backend, see `Asnr::custom_framework`
+    Custom(CustomFramework),
 }
 
 /// Typestate representing compiler that is ready to compile
 pub struct AsnrCompileReady {
     sources: Vec<AsnSource>,
-    output_path: PathBuf,
-    no_std: bool,
-    framework: Framework,
+    output: AsnrOutput,
+    config: AsnrConfig,
 }
 
 /// Typestate representing compiler that has the output path set, but is missing ASN1 sources
 pub struct AsnrOutputSet {
-    output_path: PathBuf,
-    no_std: bool,
-    framework: Framework,
+    output: AsnrOutput,
+    config: AsnrConfig,
 }
 
 /// Typestate representing compiler that knows about ASN1 sources, but doesn't have an output path set
 pub struct AsnrSourcesSet {
     sources: Vec<AsnSource>,
-    no_std: bool,
-    framework: Framework,
+    config: AsnrConfig,
+}
+
+/// Result of a compilation, containing the compiler output and the warnings raised during compilation
+pub type CompileResult<T> = Result<(T, Vec<Box<dyn Error>>), Box<dyn Error>>;
+
+/// Output target of the compiler
+#[derive(Debug, PartialEq)]
+enum AsnrOutput {
+    /// Single file containing the Rust representations of all ASN1 modules
+    File(PathBuf),
+    /// Directory containing one file per ASN1 module
+    Directory(PathBuf),
 }
 
 /// State of the Asnr compiler
-pub trait AsnrState {}
+pub trait AsnrState: sealed::Configured {}
 impl AsnrState for AsnrCompileReady {}
 impl AsnrState for AsnrOutputSet {}
 impl AsnrState for AsnrSourcesSet {}
 impl AsnrState for AsnrMissingParams {}
 
-#[derive(Debug, PartialEq)]
+mod sealed {
+    // The trait cannot be named outside of this crate, so exposing the
+    // private configuration through it does not leak into the public API.
+    #![allow(private_interfaces)]
+    use super::AsnrConfig;
+
+    /// Access to the compiler configuration that every typestate carries
+    pub trait Configured {
+        fn config_mut(&mut self) -> &mut AsnrConfig;
+    }
+
+    impl Configured for super::AsnrMissingParams {
+        fn config_mut(&mut self) -> &mut AsnrConfig {
+            &mut self.config
+        }
+    }
+
+    impl Configured for super::AsnrOutputSet {
+        fn config_mut(&mut self) -> &mut AsnrConfig {
+            &mut self.config
+        }
+    }
+
+    impl Configured for super::AsnrSourcesSet {
+        fn config_mut(&mut self) -> &mut AsnrConfig {
+            &mut self.config
+        }
+    }
+
+    impl Configured for super::AsnrCompileReady {
+        fn config_mut(&mut self) -> &mut AsnrConfig {
+            &mut self.config
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
 enum AsnSource {
     Path(PathBuf),
     Literal(String),
+    /// Directory whose ASN1 files are added when compiling
+    Directory(PathBuf),
+    /// Standard input, which is read when compiling
+    Stdin,
+}
+
+impl<S: AsnrState> Asnr<S> {
+    /// Generate Rust representations compatible with an environment without the standard library
+    /// * `is_supporting` - whether the generated Rust should comply with no_std
+    pub fn no_std(mut self, is_supporting: bool) -> Self {
+        self.state.config_mut().no_std = is_supporting;
+        self
+    }
+
+    /// Select the framework used to represent the ASN1 data elements in Rust.
+    /// If no framework is selected, the framework is chosen by the encoding reference default
+    /// of the ASN1 modules, e.g. `asnr` for `PER INSTRUCTIONS`. Otherwise, `asnr` is used.
+    /// The ASNR compiler can generate code for two frameworks:
+    /// 1. the `asnr-transcoder` crate, that supports UPER only
+    /// 2. the `rasn` crate, that supports BER, CER, DER, UPER, and PER
+    /// * `framework` - which framework the ASNR compiler should generate code for
+    pub fn framework(mut self, framework: Framework) -> Self {
+        self.state.config_mut().framework = Some(framework);
+        self
+    }
+
+    /// Generate the bindings with a custom backend instead of one of the built-in frameworks,
+    /// e.g. to target a different codec crate. The backend's `prelude` replaces the imports
+    /// and generic types that the built-in frameworks generate.
+    /// * `generator` - the backend generating the bindings
+    pub fn custom_framework(mut self, generator: Box<dyn Generator>) -> Self {
+        self.state.config_mut().framework =
+            Some(Framework::Custom(CustomFramework(generator.into())));
+        self
+    }
+
+    /// Merge top-level declarations that are declared identically in several ASN1 sources.
+    /// If disabled, or if the duplicate declarations differ, the compilation fails.
+    /// Merging is enabled by default.
+    /// * `merge` - whether identical duplicate declarations should be merged
+    pub fn merge_duplicate_declarations(mut self, merge: bool) -> Self {
+        self.state.config_mut().merge_duplicate_declarations = merge;
+        self
+    }
+
+    /// Format the generated bindings with the external `rustfmt` binary located in `$CARGO_HOME/bin`
+    /// instead of the built-in formatter.
+    /// By default, bindings are formatted in-process if the `prettyplease` feature is enabled.
+    /// * `use_rustfmt` - whether the generated bindings should be formatted with `rustfmt`
+    pub fn format_with_rustfmt(mut self, use_rustfmt: bool) -> Self {
+        self.state.config_mut().use_rustfmt = use_rustfmt;
+        self
+    }
+
+    /// Represent every INTEGER as `i128`, regardless of its constraints.
+    /// By default, the narrowest Rust integer type that fits the constrained range is generated.
+    /// The `rasn` framework uses its arbitrary-precision `Integer` instead of `i128`.
+    /// * `force` - whether integers should be generated as `i128`
+    pub fn force_i128_integers(mut self, force: bool) -> Self {
+        self.state.config_mut().force_i128_integers = force;
+        self
+    }
+
+    /// Implement `Default` for generated SEQUENCEs by populating their DEFAULT members
+    /// with the DEFAULT values declared in the ASN1 specification.
+    /// When decoding, absent DEFAULT members are then filled in rather than left `None`.
+    /// Regardless of this setting, the `asnr` framework generates `default_<member>()`
+    /// helpers and a `new_with_defaults()` constructor.
+    /// * `materialize` - whether DEFAULT values should be materialized
+    pub fn materialize_defaults(mut self, materialize: bool) -> Self {
+        self.state.config_mut().materialize_defaults = materialize;
+        self
+    }
+
+    /// Attach an `#[allow(...)]` attribute to the items of the generated Rust files that silences
+    /// the lints which the generated bindings may trigger, e.g. for acronyms in type names
+    /// such as `CAM`. The attributes are outer attributes, so the generated files
+    /// can be declared as modules as well as `include!`d.
+    /// * `suppress` - whether lints of the generated bindings should be suppressed
+    pub fn suppress_lints(mut self, suppress: bool) -> Self {
+        self.state.config_mut().suppress_lints = suppress;
+        self
+    }
+
+    /// Represent inline INTEGER, BOOLEAN, character string, OCTET STRING, and BIT STRING
+    /// members of a SEQUENCE by plain Rust types (e.g. `String`) rather than generated newtypes
+    /// (e.g. `InnerExampleSequenceMember1`). The members' constraints are still honored when
+    /// encoding and decoding. Only applies to the `asnr` framework.
+    /// * `flatten` - whether primitive members should be flattened
+    pub fn flatten_primitive_members(mut self, flatten: bool) -> Self {
+        self.state.config_mut().flatten_primitive_members = flatten;
+        self
+    }
+
+    /// Generate accessors for the values that OCTET STRING members of a SEQUENCE contain
+    /// according to a CONTAINING constraint, e.g. `payload OCTET STRING (CONTAINING Data)`.
+    /// The SEQUENCE then gets a `decoded_payload` method that decodes the `Data` from
+    /// the member's octets, and a `set_payload_from` method that encodes a `Data` into them.
+    /// Only applies to the `asnr` framework.
+    /// * `generate` - whether the accessors should be generated
+    pub fn contained_type_accessors(mut self, generate: bool) -> Self {
+        self.state.config_mut().contained_type_accessors = generate;
+        self
+    }
+
+    /// Mark the enums generated for extensible ENUMERATEDs and CHOICEs as `#[non_exhaustive]`,
+    /// so that matches on them need a wildcard arm for the alternatives of later versions.
+    /// Enabled by default. Only applies to the `asnr` framework, since `rasn` requires
+    /// the attribute to encode and decode the extension marker.
+    /// * `enable` - whether extensible enums should be marked as non-exhaustive
+    pub fn mark_extensible_as_non_exhaustive(mut self, enable: bool) -> Self {
+        self.state.config_mut().mark_extensible_as_non_exhaustive = enable;
+        self
+    }
+
+    /// Prepend a prefix to the names of the generated types, e.g. `Asn` turns `Position`
+    /// into `AsnPosition`, to avoid collisions with types of the surrounding code.
+    /// The prefix is applied to the title-cased names and therefore should be title-cased itself.
+    /// References to the types, e.g. in SEQUENCE members, are renamed accordingly.
+    /// Fields and CHOICE options keep their names, while names that are derived
+    /// from type names, e.g. of inner types, include the prefix.
+    /// Does not apply to JSON schemas.
+    /// * `prefix` - prefix of the generated type names
+    pub fn type_prefix(mut self, prefix: &str) -> Self {
+        self.state.config_mut().type_prefix = prefix.to_owned();
+        self
+    }
+
+    /// Append a suffix to the names of the generated types, e.g. `Asn` turns `Position`
+    /// into `PositionAsn`, to avoid collisions with types of the surrounding code.
+    /// The suffix is applied to the title-cased names and therefore should be title-cased itself.
+    /// References to the types, e.g. in SEQUENCE members, are renamed accordingly.
+    /// Fields and CHOICE options keep their names, while names that are derived
+    /// from type names, e.g. of inner types, include the suffix.
+    /// Does not apply to JSON schemas.
+    /// * `suffix` - suffix of the generated type names
+    pub fn type_suffix(mut self, suffix: &str) -> Self {
+        self.state.config_mut().type_suffix = suffix.to_owned();
+        self
+    }
+
+    /// Scan the subdirectories of the directories added with `add_asn_sources_by_dir` as well.
+    /// Disabled by default.
+    /// * `recursive` - whether subdirectories should be scanned for ASN1 files
+    pub fn scan_source_dirs_recursively(mut self, recursive: bool) -> Self {
+        self.state.config_mut().recursive_source_dirs = recursive;
+        self
+    }
+
+    /// Set the extensions of the files that are added from the directories
+    /// added with `add_asn_sources_by_dir`. Defaults to `asn` and `asn1`.
+    /// * `extensions` - the file extensions, e.g. `&["asn", "asn1"]`
+    pub fn source_dir_extensions(mut self, extensions: &[&str]) -> Self {
+        self.state.config_mut().source_dir_extensions = extensions
+            .iter()
+            .map(|extension| extension.trim_start_matches('.').to_owned())
+            .collect();
+        self
+    }
+
+    /// Print `cargo:rerun-if-changed` directives for the ASN1 files and directories added by path
+    /// as well as for the directory of the output when compiling with `compile` or `compile_to_files`,
+    /// so that build scripts run again when an ASN1 source changes.
+    /// Enabled by default if the compiler runs in a build script, i.e. if `OUT_DIR` is set.
+    /// * `is_emitting` - whether the rerun directives should be printed
+    pub fn emit_rerun_directives(mut self, is_emitting: bool) -> Self {
+        self.state.config_mut().emit_rerun_directives = is_emitting;
+        self
+    }
+
+    /// Set the directory of the cache that stores the Rust representations generated for
+    /// the ASN1 declarations. Subsequent compilations reuse the cached representations of
+    /// declarations that did not change instead of generating them again. The cache is
+    /// invalidated as a whole if the framework, `no_std` support, or generator options change.
+    /// Defaults to the `OUT_DIR` of build scripts. Pass `None` to disable caching.
+    /// * `cache_dir` - directory in which the `.asnr_cache.json` cache file is written
+    pub fn cache_dir(mut self, cache_dir: Option<PathBuf>) -> Self {
+        self.state.config_mut().cache_dir = cache_dir;
+        self
+    }
+
+    /// Configure the names of the types generated for inline SEQUENCE members and CHOICE options
+    /// and for anonymous SEQUENCE OF members, e.g. their prefixes and a maximum name length.
+    /// The default configuration produces names like `InnerExampleSequenceMember1`.
+    /// * `naming` - the naming configuration
+    pub fn naming(mut self, naming: NamingConfig) -> Self {
+        self.state.config_mut().naming = naming;
+        self
+    }
+
+    /// Configure the comments at the top of the generated file, e.g. a license banner,
+    /// the names of the input files, the compiler version, or a generation timestamp.
+    /// The default configuration only generates the framework's banner.
+    /// * `header` - the header configuration
+    pub fn header(mut self, header: HeaderConfig) -> Self {
+        self.state.config_mut().header = header;
+        self
+    }
+
+    /// Append the ASN1 notation of each top-level declaration to the doc comment
+    /// of the item generated for it, as an `asn1` code block that rustdoc does not test.
+    /// The notation is taken verbatim from the ASN1 sources, without its comments.
+    /// * `embed` - whether ASN1 definitions should be embedded in the doc comments
+    pub fn embed_asn1_definitions(mut self, embed: bool) -> Self {
+        self.state.config_mut().embed_asn1_definitions = embed;
+        self
+    }
+
+    /// Skip top-level declarations that cannot be parsed instead of aborting the compilation.
+    /// Parsing resumes at the next assignment or at the END of the module,
+    /// and every skipped declaration is reported as an `UnparsedDeclaration` warning.
+    /// This allows compiling specifications that contain constructs not yet supported by ASNR.
+    /// * `skip` - whether unparsable declarations should be skipped
+    pub fn skip_unparsable_declarations(mut self, skip: bool) -> Self {
+        self.state.config_mut().skip_unparsable_declarations = skip;
+        self
+    }
+
+    /// Add derive macros to every generated struct and enum.
+    /// The derives are appended to the default derives of the selected framework.
+    /// When generating `no_std`-compatible Rust, the derive macros and the traits they
+    /// implement must be available without the standard library as well (e.g. `serde` with
+    /// `default-features = false`).
+    /// * `derives` - names of the derive macros, e.g. `&["Serialize", "Deserialize"]`
+    pub fn custom_derives(mut self, derives: &[&str]) -> Self {
+        self.state.config_mut().custom_derives = derives.iter().map(|d| d.to_string()).collect();
+        self
+    }
+
+    /// Replace the standard derives of the generated types, e.g. to drop `Debug`
+    /// on embedded targets or to add `Eq` and `Hash`.
+    /// Derives that the selected framework relies on are generated regardless.
+    /// The derives are validated against the framework and the compiled specification,
+    /// e.g. `Eq` cannot be derived for types that contain REAL values.
+    /// * `derives` - the standard derives of the generated types
+    pub fn derives(mut self, derives: &[Derive]) -> Self {
+        self.state.config_mut().derives = Some(derives.to_vec());
+        self
+    }
+
+    /// Replace the generated Rust representation of an ASN1 type with a user-provided Rust type.
+    /// The compiler emits a type alias `pub type <AsnName> = <rust_type>;` instead of the
+    /// generated representation, so the provided type must satisfy the framework's
+    /// encoding and decoding traits.
+    /// * `asn_name` - name of the ASN1 type as declared in the specification
+    /// * `rust_type` - path of the Rust type to use instead, e.g. `u8` or `crate::MyFlags`
+    pub fn with_type_override(mut self, asn_name: &str, rust_type: &str) -> Self {
+        self.state
+            .config_mut()
+            .type_overrides
+            .insert(asn_name.to_owned(), rust_type.to_owned());
+        self
+    }
+
+    /// Add derive macros to the generated Rust representation of a specific ASN1 type.
+    /// The derives are appended to the default derives and the derives set via `custom_derives`.
+    /// * `asn_name` - name of the ASN1 type as declared in the specification
+    /// * `derives` - names of the derive macros, e.g. `&["Hash", "Eq"]`
+    pub fn with_derive_override(mut self, asn_name: &str, derives: &[&str]) -> Self {
+        self.state
+            .config_mut()
+            .derive_overrides
+            .entry(asn_name.to_owned())
+            .or_default()
+            .extend(derives.iter().map(|d| d.to_string()));
+        self
+    }
+}
+
+impl Default for Asnr<AsnrMissingParams> {
+    fn default() -> Self {
+        Self::new()
+    }
 }
 
 impl Asnr<AsnrMissingParams> {
@@ -131,33 +527,7 @@ impl Asnr<AsnrMissingParams> {
         Asnr {
             state: AsnrSourcesSet {
                 sources: vec![AsnSource::Path(path_to_source.into())],
-                no_std: self.state.no_std,
-                framework: self.state.framework,
-            },
-        }
-    }
-
-    /// Generate Rust representations compatible with an environment without the standard library
-    /// * `is_supporting` - whether the generated Rust should comply with no_std
-    pub fn no_std(self, is_supporting: bool) -> Self {
-        Self {
-            state: AsnrMissingParams {
-                no_std: is_supporting,
-                framework: self.state.framework,
-            },
-        }
-    }
-
-    /// Select the framework used to represent the ASN1 data elements in Rust.
-    /// The ASNR compiler can generate code for two frameworks:
-    /// 1. the `asnr-transcoder` crate, that supports UPER only
-    /// 2. the `rasn` crate, that supports BER, CER, DER, UPER, and PER
-    /// * `framework` - which framework the ASNR compiler should generate code for
-    pub fn framework(self, framework: Framework) -> Self {
-        Self {
-            state: AsnrMissingParams {
-                no_std: self.state.no_std,
-                framework,
+                config: self.state.config,
             },
         }
     }
@@ -173,8 +543,35 @@ impl Asnr<AsnrMissingParams> {
                 sources: paths_to_sources
                     .map(|p| AsnSource::Path(p.into()))
                     .collect(),
-                no_std: self.state.no_std,
-                framework: self.state.framework,
+                config: self.state.config,
+            },
+        }
+    }
+
+    /// Add the ASN1 files of a directory to the compile command.
+    /// The directory is scanned when compiling, and the files are compiled in the order of their paths.
+    /// By default, only files with the extensions `asn` and `asn1` directly within the directory
+    /// are added, see `scan_source_dirs_recursively` and `source_dir_extensions`.
+    /// Compiling fails if the directory does not exist or does not contain any matching files.
+    /// * `dir` - path to the directory containing the ASN1 files
+    pub fn add_asn_sources_by_dir(self, dir: impl Into<PathBuf>) -> Asnr<AsnrSourcesSet> {
+        Asnr {
+            state: AsnrSourcesSet {
+                sources: vec![AsnSource::Directory(dir.into())],
+                config: self.state.config,
+            },
+        }
+    }
+
+    /// Add the standard input as an ASN1 source to the compile command, e.g. for piping
+    /// specifications into a build tool with `cat *.asn | ...`.
+    /// The standard input is read to its end when compiling.
+    /// Since it can only be read once, adding the standard input more than once has no effect.
+    pub fn add_asn_from_stdin(self) -> Asnr<AsnrSourcesSet> {
+        Asnr {
+            state: AsnrSourcesSet {
+                sources: vec![AsnSource::Stdin],
+                config: self.state.config,
             },
         }
     }
@@ -189,8 +586,7 @@ impl Asnr<AsnrMissingParams> {
         Asnr {
             state: AsnrSourcesSet {
                 sources: vec![AsnSource::Literal(literal.into())],
-                no_std: self.state.no_std,
-                framework: self.state.framework,
+                config: self.state.config,
             },
         }
     }
@@ -205,9 +601,24 @@ impl Asnr<AsnrMissingParams> {
         }
         Asnr {
             state: AsnrOutputSet {
-                output_path: path,
-                no_std: self.state.no_std,
-                framework: self.state.framework,
+                output: AsnrOutput::File(path),
+                config: self.state.config,
+            },
+        }
+    }
+
+    /// Set an output directory for the generated rust representation.
+    /// Instead of a single file, the compiler writes one file per ASN1 module,
+    /// a `common.rs` file containing the shared imports and generic types,
+    /// and a `mod.rs` file that declares and re-exports all modules.
+    /// The files of ASN1 modules named like these files are suffixed with `_module`.
+    /// The directory is created if it does not exist.
+    /// * `output_dir` - path to the output directory
+    pub fn set_output_dir(self, output_dir: impl Into<PathBuf>) -> Asnr<AsnrOutputSet> {
+        Asnr {
+            state: AsnrOutputSet {
+                output: AsnrOutput::Directory(output_dir.into()),
+                config: self.state.config,
             },
         }
     }
@@ -220,36 +631,8 @@ impl Asnr<AsnrOutputSet> {
         Asnr {
             state: AsnrCompileReady {
                 sources: vec![AsnSource::Path(path_to_source.into())],
-                no_std: self.state.no_std,
-                output_path: self.state.output_path,
-                framework: self.state.framework,
-            },
-        }
-    }
-
-    /// Generate Rust representations compatible with an environment without the standard library
-    /// * `is_supporting` - whether the generated Rust should comply with no_std
-    pub fn no_std(self, is_supporting: bool) -> Self {
-        Self {
-            state: AsnrOutputSet {
-                output_path: self.state.output_path,
-                no_std: is_supporting,
-                framework: self.state.framework,
-            },
-        }
-    }
-
-    /// Select the framework used to represent the ASN1 data elements in Rust.
-    /// The ASNR compiler can generate code for two frameworks:
-    /// 1. the `asnr-transcoder` crate, that supports UPER only
-    /// 2. the `rasn` crate, that supports BER, CER, DER, UPER, and PER
-    /// * `framework` - which framework the ASNR compiler should generate code for
-    pub fn framework(self, framework: Framework) -> Self {
-        Self {
-            state: AsnrOutputSet {
-                no_std: self.state.no_std,
-                output_path: self.state.output_path,
-                framework,
+                output: self.state.output,
+                config: self.state.config,
             },
         }
     }
@@ -265,9 +648,38 @@ impl Asnr<AsnrOutputSet> {
                 sources: paths_to_sources
                     .map(|p| AsnSource::Path(p.into()))
                     .collect(),
-                no_std: self.state.no_std,
-                output_path: self.state.output_path,
-                framework: self.state.framework,
+                output: self.state.output,
+                config: self.state.config,
+            },
+        }
+    }
+
+    /// Add the ASN1 files of a directory to the compile command.
+    /// The directory is scanned when compiling, and the files are compiled in the order of their paths.
+    /// By default, only files with the extensions `asn` and `asn1` directly within the directory
+    /// are added, see `scan_source_dirs_recursively` and `source_dir_extensions`.
+    /// Compiling fails if the directory does not exist or does not contain any matching files.
+    /// * `dir` - path to the directory containing the ASN1 files
+    pub fn add_asn_sources_by_dir(self, dir: impl Into<PathBuf>) -> Asnr<AsnrCompileReady> {
+        Asnr {
+            state: AsnrCompileReady {
+                sources: vec![AsnSource::Directory(dir.into())],
+                output: self.state.output,
+                config: self.state.config,
+            },
+        }
+    }
+
+    /// Add the standard input as an ASN1 source to the compile command, e.g. for piping
+    /// specifications into a build tool with `cat *.asn | ...`.
+    /// The standard input is read to its end when compiling.
+    /// Since it can only be read once, adding the standard input more than once has no effect.
+    pub fn add_asn_from_stdin(self) -> Asnr<AsnrCompileReady> {
+        Asnr {
+            state: AsnrCompileReady {
+                sources: vec![AsnSource::Stdin],
+                output: self.state.output,
+                config: self.state.config,
             },
         }
     }
@@ -282,9 +694,8 @@ impl Asnr<AsnrOutputSet> {
         Asnr {
             state: AsnrCompileReady {
                 sources: vec![AsnSource::Literal(literal.into())],
-                no_std: self.state.no_std,
-                output_path: self.state.output_path,
-                framework: self.state.framework,
+                output: self.state.output,
+                config: self.state.config,
             },
         }
     }
@@ -293,60 +704,45 @@ impl Asnr<AsnrOutputSet> {
 impl Asnr<AsnrSourcesSet> {
     /// Add an ASN1 source to the compile command by path
     /// * `path_to_source` - path to ASN1 file to include
-    pub fn add_asn_by_path(self, path_to_source: impl Into<PathBuf>) -> Asnr<AsnrSourcesSet> {
-        let mut sources: Vec<AsnSource> = self.state.sources;
-        sources.push(AsnSource::Path(path_to_source.into()));
-        Asnr {
-            state: AsnrSourcesSet {
-                sources,
-                no_std: self.state.no_std,
-                framework: self.state.framework,
-            },
-        }
-    }
-
-    /// Generate Rust representations compatible with an environment without the standard library
-    /// * `is_supporting` - whether the generated Rust should comply with no_std
-    pub fn no_std(self, is_supporting: bool) -> Asnr<AsnrSourcesSet> {
-        Self {
-            state: AsnrSourcesSet {
-                sources: self.state.sources,
-                no_std: is_supporting,
-                framework: self.state.framework,
-            },
-        }
-    }
-
-    /// Select the framework used to represent the ASN1 data elements in Rust.
-    /// The ASNR compiler can generate code for two frameworks:
-    /// 1. the `asnr-transcoder` crate, that supports UPER only
-    /// 2. the `rasn` crate, that supports BER, CER, DER, UPER, and PER
-    /// * `framework` - which framework the ASNR compiler should generate code for
-    pub fn framework(self, framework: Framework) -> Self {
-        Self {
-            state: AsnrSourcesSet {
-                no_std: self.state.no_std,
-                sources: self.state.sources,
-                framework,
-            },
-        }
+    pub fn add_asn_by_path(mut self, path_to_source: impl Into<PathBuf>) -> Asnr<AsnrSourcesSet> {
+        self.state
+            .sources
+            .push(AsnSource::Path(path_to_source.into()));
+        self
     }
 
     /// Add several ASN1 sources by path to the compile command
     /// * `path_to_source` - iterator of paths to the ASN1 files to be included
     pub fn add_asn_sources_by_path(
-        self,
+        mut self,
         paths_to_sources: impl Iterator<Item = impl Into<PathBuf>>,
     ) -> Asnr<AsnrSourcesSet> {
-        let mut sources: Vec<AsnSource> = self.state.sources;
-        sources.extend(paths_to_sources.map(|p| AsnSource::Path(p.into())));
-        Asnr {
-            state: AsnrSourcesSet {
-                sources,
-                no_std: self.state.no_std,
-                framework: self.state.framework,
-            },
+        self.state
+            .sources
+            .extend(paths_to_sources.map(|p| AsnSource::Path(p.into())));
+        self
+    }
+
+    /// Add the ASN1 files of a directory to the compile command.
+    /// The directory is scanned when compiling, and the files are compiled in the order of their paths.
+    /// By default, only files with the extensions `asn` and `asn1` directly within the directory
+    /// are added, see `scan_source_dirs_recursively` and `source_dir_extensions`.
+    /// Compiling fails if the directory does not exist or does not contain any matching files.
+    /// * `dir` - path to the directory containing the ASN1 files
+    pub fn add_asn_sources_by_dir(mut self, dir: impl Into<PathBuf>) -> Asnr<AsnrSourcesSet> {
+        self.state.sources.push(AsnSource::Directory(dir.into()));
+        self
+    }
+
+    /// Add the standard input as an ASN1 source to the compile command, e.g. for piping
+    /// specifications into a build tool with `cat *.asn | ...`.
+    /// The standard input is read to its end when compiling.
+    /// Since it can only be read once, adding the standard input more than once has no effect.
+    pub fn add_asn_from_stdin(mut self) -> Asnr<AsnrSourcesSet> {
+        if !self.state.sources.contains(&AsnSource::Stdin) {
+            self.state.sources.push(AsnSource::Stdin);
         }
+        self
     }
 
     /// Add a literal ASN1 source to the compile command
@@ -355,16 +751,9 @@ impl Asnr<AsnrSourcesSet> {
     /// # use asnr_compiler::Asnr;
     /// Asnr::new().add_asn_literal("My-test-integer ::= INTEGER (1..128)").compile_to_string();
     /// ```
-    pub fn add_asn_literal(self, literal: impl Into<String>) -> Asnr<AsnrSourcesSet> {
-        let mut sources: Vec<AsnSource> = self.state.sources;
-        sources.push(AsnSource::Literal(literal.into()));
-        Asnr {
-            state: AsnrSourcesSet {
-                sources,
-                no_std: self.state.no_std,
-                framework: self.state.framework,
-            },
-        }
+    pub fn add_asn_literal(mut self, literal: impl Into<String>) -> Asnr<AsnrSourcesSet> {
+        self.state.sources.push(AsnSource::Literal(literal.into()));
+        self
     }
 
     /// Set the output path for the generated rust representation.
@@ -378,9 +767,25 @@ impl Asnr<AsnrSourcesSet> {
         Asnr {
             state: AsnrCompileReady {
                 sources: self.state.sources,
-                output_path: path,
-                no_std: self.state.no_std,
-                framework: self.state.framework,
+                output: AsnrOutput::File(path),
+                config: self.state.config,
+            },
+        }
+    }
+
+    /// Set an output directory for the generated rust representation.
+    /// Instead of a single file, the compiler writes one file per ASN1 module,
+    /// a `common.rs` file containing the shared imports and generic types,
+    /// and a `mod.rs` file that declares and re-exports all modules.
+    /// The files of ASN1 modules named like these files are suffixed with `_module`.
+    /// The directory is created if it does not exist.
+    /// * `output_dir` - path to the output directory
+    pub fn set_output_dir(self, output_dir: impl Into<PathBuf>) -> Asnr<AsnrCompileReady> {
+        Asnr {
+            state: AsnrCompileReady {
+                sources: self.state.sources,
+                output: AsnrOutput::Directory(output_dir.into()),
+                config: self.state.config,
             },
         }
     }
@@ -390,90 +795,98 @@ impl Asnr<AsnrSourcesSet> {
     /// * _Ok_  - tuple containing the stringified Rust representation of the ASN1 spec as well as a vector of warnings raised during the compilation
     /// * _Err_ - Unrecoverable error, no rust representations were generated
     pub fn compile_to_string(self) -> Result<(String, Vec<Box<dyn Error>>), Box<dyn Error>> {
-        internal_compile(&self, false)
+        internal_compile(&self.state.sources, &self.state.config, false)
     }
-}
 
-impl Asnr<AsnrCompileReady> {
-    /// Add an ASN1 source to the compile command by path
-    /// * `path_to_source` - path to ASN1 file to include
-    pub fn add_asn_by_path(self, path_to_source: impl Into<PathBuf>) -> Asnr<AsnrCompileReady> {
-        let mut sources: Vec<AsnSource> = self.state.sources;
-        sources.push(AsnSource::Path(path_to_source.into()));
-        Asnr {
-            state: AsnrCompileReady {
-                output_path: self.state.output_path,
-                sources,
-                no_std: self.state.no_std,
-                framework: self.state.framework,
-            },
-        }
+    /// Runs the ASNR compiler command and returns the Rust representation as a token stream.
+    /// Returns a Result wrapping a compilation result:
+    /// * _Ok_  - tuple containing the Rust representation of the ASN1 spec as well as a vector of warnings raised during the compilation
+    /// * _Err_ - Unrecoverable error, no rust representations were generated
+    pub fn compile_to_tokens(self) -> Result<(TokenStream, Vec<Box<dyn Error>>), syn::Error> {
+        internal_compile_to_tokens(&self.state.sources, &self.state.config).map_err(|e| {
+            match e.downcast::<syn::Error>() {
+                Ok(syn_error) => *syn_error,
+                Err(e) => syn::Error::new(Span::call_site(), e),
+            }
+        })
     }
 
-    /// Generate Rust representations compatible with an environment without the standard library
-    /// * `is_supporting` - whether the generated Rust should comply with no_std
-    pub fn no_std(self, is_supporting: bool) -> Asnr<AsnrCompileReady> {
-        Self {
-            state: AsnrCompileReady {
-                output_path: self.state.output_path,
-                sources: self.state.sources,
-                no_std: is_supporting,
-                framework: self.state.framework,
-            },
-        }
+    /// Runs the ASNR compiler command and returns the Rust representation as a token stream,
+    /// like [Asnr::compile_to_tokens], but keeps the original compilation errors,
+    /// e.g. to locate a `ParserError` in the ASN1 sources.
+    /// Returns a Result wrapping a compilation result:
+    /// * _Ok_  - tuple containing the Rust representation of the ASN1 spec as well as a vector of warnings raised during the compilation
+    /// * _Err_ - Unrecoverable error, no rust representations were generated
+    pub fn compile_to_tokens_with_diagnostics(self) -> CompileResult<TokenStream> {
+        internal_compile_to_tokens(&self.state.sources, &self.state.config)
     }
 
-    /// Select the framework used to represent the ASN1 data elements in Rust.
-    /// The ASNR compiler can generate code for two frameworks:
-    /// 1. the `asnr-transcoder` crate, that supports UPER only
-    /// 2. the `rasn` crate, that supports BER, CER, DER, UPER, and PER
-    /// * `framework` - which framework the ASNR compiler should generate code for
-    pub fn framework(self, framework: Framework) -> Self {
-        Self {
-            state: AsnrCompileReady {
-                no_std: self.state.no_std,
-                sources: self.state.sources,
-                output_path: self.state.output_path,
-                framework,
-            },
-        }
+    /// Parses and validates the ASN1 sources without generating Rust representations,
+    /// e.g. for linters or documentation generators working on the ASN1 declarations.
+    /// The tagging environments of the modules are applied, references are linked,
+    /// and parameterized types are instantiated like for the generation of Rust representations.
+    /// Returns a Result wrapping a parsing result:
+    /// * _Ok_  - tuple containing the validated declarations grouped by the headers of their ASN1 modules
+    ///   as well as a vector of warnings raised while parsing and validating
+    /// * _Err_ - Unrecoverable error, the sources could not be parsed or validated
+    pub fn parse(self) -> CompileResult<Vec<(ModuleReference, Vec<ToplevelDeclaration>)>> {
+        parse_modules(&self.state.sources, &self.state.config)
+    }
+}
+
+impl Asnr<AsnrCompileReady> {
+    /// Add an ASN1 source to the compile command by path
+    /// * `path_to_source` - path to ASN1 file to include
+    pub fn add_asn_by_path(mut self, path_to_source: impl Into<PathBuf>) -> Asnr<AsnrCompileReady> {
+        self.state
+            .sources
+            .push(AsnSource::Path(path_to_source.into()));
+        self
     }
 
     /// Add several ASN1 sources by path to the compile command
     /// * `path_to_source` - iterator of paths to the ASN1 files to be included
     pub fn add_asn_sources_by_path(
-        self,
+        mut self,
         paths_to_sources: impl Iterator<Item = impl Into<PathBuf>>,
     ) -> Asnr<AsnrCompileReady> {
-        let mut sources: Vec<AsnSource> = self.state.sources;
-        sources.extend(paths_to_sources.map(|p| AsnSource::Path(p.into())));
-        Asnr {
-            state: AsnrCompileReady {
-                sources,
-                output_path: self.state.output_path,
-                no_std: self.state.no_std,
-                framework: self.state.framework,
-            },
-        }
+        self.state
+            .sources
+            .extend(paths_to_sources.map(|p| AsnSource::Path(p.into())));
+        self
     }
 
-    /// Add a literal ASN1 source to the compile command
-    /// * `literal` - literal ASN1 statement to include
+    /// Add the ASN1 files of a directory to the compile command.
+    /// The directory is scanned when compiling, and the files are compiled in the order of their paths.
+    /// By default, only files with the extensions `asn` and `asn1` directly within the directory
+    /// are added, see `scan_source_dirs_recursively` and `source_dir_extensions`.
+    /// Compiling fails if the directory does not exist or does not contain any matching files.
+    /// * `dir` - path to the directory containing the ASN1 files
+    pub fn add_asn_sources_by_dir(mut self, dir: impl Into<PathBuf>) -> Asnr<AsnrCompileReady> {
+        self.state.sources.push(AsnSource::Directory(dir.into()));
+        self
+    }
+
+    /// Add the standard input as an ASN1 source to the compile command, e.g. for piping
+    /// specifications into a build tool with `cat *.asn | ...`.
+    /// The standard input is read to its end when compiling.
+    /// Since it can only be read once, adding the standard input more than once has no effect.
+    pub fn add_asn_from_stdin(mut self) -> Asnr<AsnrCompileReady> {
+        if !self.state.sources.contains(&AsnSource::Stdin) {
+            self.state.sources.push(AsnSource::Stdin);
+        }
+        self
+    }
+
+    /// Add a literal ASN1 source to the compile command
+    /// * `literal` - literal ASN1 statement to include
     /// ```rust
     /// # use asnr_compiler::Asnr;
     /// Asnr::new().add_asn_literal("My-test-integer ::= INTEGER (1..128)").compile_to_string();
     /// ```
-    pub fn add_asn_literal(self, literal: impl Into<String>) -> Asnr<AsnrCompileReady> {
-        let mut sources: Vec<AsnSource> = self.state.sources;
-        sources.push(AsnSource::Literal(literal.into()));
-        Asnr {
-            state: AsnrCompileReady {
-                output_path: self.state.output_path,
-                sources,
-                no_std: self.state.no_std,
-                framework: self.state.framework,
-            },
-        }
+    pub fn add_asn_literal(mut self, literal: impl Into<String>) -> Asnr<AsnrCompileReady> {
+        self.state.sources.push(AsnSource::Literal(literal.into()));
+        self
     }
 
     /// Runs the ASNR compiler command and returns stringified Rust.
@@ -481,16 +894,42 @@ impl Asnr<AsnrCompileReady> {
     /// * _Ok_  - tuple containing the stringified Rust representation of the ASN1 spec as well as a vector of warnings raised during the compilation
     /// * _Err_ - Unrecoverable error, no rust representations were generated
     pub fn compile_to_string(self) -> Result<(String, Vec<Box<dyn Error>>), Box<dyn Error>> {
-        internal_compile(
-            &Asnr {
-                state: AsnrSourcesSet {
-                    sources: self.state.sources,
-                    no_std: self.state.no_std,
-                    framework: self.state.framework,
-                },
-            },
-            false,
-        )
+        internal_compile(&self.state.sources, &self.state.config, false)
+    }
+
+    /// Runs the ASNR compiler command and returns the Rust representation as a token stream.
+    /// Returns a Result wrapping a compilation result:
+    /// * _Ok_  - tuple containing the Rust representation of the ASN1 spec as well as a vector of warnings raised during the compilation
+    /// * _Err_ - Unrecoverable error, no rust representations were generated
+    pub fn compile_to_tokens(self) -> Result<(TokenStream, Vec<Box<dyn Error>>), syn::Error> {
+        internal_compile_to_tokens(&self.state.sources, &self.state.config).map_err(|e| {
+            match e.downcast::<syn::Error>() {
+                Ok(syn_error) => *syn_error,
+                Err(e) => syn::Error::new(Span::call_site(), e),
+            }
+        })
+    }
+
+    /// Runs the ASNR compiler command and returns the Rust representation as a token stream,
+    /// like [Asnr::compile_to_tokens], but keeps the original compilation errors,
+    /// e.g. to locate a `ParserError` in the ASN1 sources.
+    /// Returns a Result wrapping a compilation result:
+    /// * _Ok_  - tuple containing the Rust representation of the ASN1 spec as well as a vector of warnings raised during the compilation
+    /// * _Err_ - Unrecoverable error, no rust representations were generated
+    pub fn compile_to_tokens_with_diagnostics(self) -> CompileResult<TokenStream> {
+        internal_compile_to_tokens(&self.state.sources, &self.state.config)
+    }
+
+    /// Parses and validates the ASN1 sources without generating Rust representations,
+    /// e.g. for linters or documentation generators working on the ASN1 declarations.
+    /// The tagging environments of the modules are applied, references are linked,
+    /// and parameterized types are instantiated like for the generation of Rust representations.
+    /// Returns a Result wrapping a parsing result:
+    /// * _Ok_  - tuple containing the validated declarations grouped by the headers of their ASN1 modules
+    ///   as well as a vector of warnings raised while parsing and validating
+    /// * _Err_ - Unrecoverable error, the sources could not be parsed or validated
+    pub fn parse(self) -> CompileResult<Vec<(ModuleReference, Vec<ToplevelDeclaration>)>> {
+        parse_modules(&self.state.sources, &self.state.config)
     }
 
     /// Runs the ASNR compiler command.
@@ -498,75 +937,812 @@ impl Asnr<AsnrCompileReady> {
     /// * _Ok_  - Vector of warnings raised during the compilation
     /// * _Err_ - Unrecoverable error, no rust representations were generated
     pub fn compile(self) -> Result<Vec<Box<dyn Error>>, Box<dyn Error>> {
-        let (result, warnings) = internal_compile(
-            &Asnr {
-                state: AsnrSourcesSet {
-                    sources: self.state.sources,
-                    no_std: self.state.no_std,
-                    framework: self.state.framework,
-                },
-            },
-            true,
-        )?;
+        self.compile_to_files().map(|(_, warnings)| warnings)
+    }
 
-        fs::write(self.state.output_path, result)?;
+    /// Runs the ASNR compiler command and reports the files that have been written.
+    /// Returns a Result wrapping a compilation result:
+    /// * _Ok_  - tuple containing the paths of the written files as well as a vector of warnings raised during the compilation
+    /// * _Err_ - Unrecoverable error, no rust representations were generated
+    pub fn compile_to_files(self) -> CompileResult<Vec<PathBuf>> {
+        if self.state.config.emit_rerun_directives {
+            for directive in rerun_directives(&self.state.sources, &self.state.output) {
+                println!("{directive}");
+            }
+        }
+        match self.state.output {
+            AsnrOutput::File(path) => {
+                let (result, warnings) =
+                    internal_compile(&self.state.sources, &self.state.config, true)?;
+                fs::write(&path, result)?;
+                Ok((vec![path], warnings))
+            }
+            AsnrOutput::Directory(dir) => {
+                internal_compile_to_dir(&self.state.sources, &self.state.config, &dir)
+            }
+        }
+    }
+}
 
-        Ok(warnings)
+/// Ranks top-level declarations by the kind of declarations they typically depend on,
+/// so that generated items appear in a stable order regardless of the order
+/// in which the validator hands them over.
+/// Information object classes come first, followed by types, values,
+/// information objects, and information object sets.
+fn generation_rank(tld: &ToplevelDeclaration) -> u8 {
+    match tld {
+        ToplevelDeclaration::Information(i) => match i.value {
+            ASN1Information::ObjectClass(_) => 0,
+            ASN1Information::Object(_) => 3,
+            ASN1Information::ObjectSet(_) => 4,
+        },
+        ToplevelDeclaration::Type(_) => 1,
+        ToplevelDeclaration::Value(_) => 2,
     }
 }
 
 fn internal_compile(
-    asnr: &Asnr<AsnrSourcesSet>,
+    sources: &[AsnSource],
+    config: &AsnrConfig,
     include_file_headers: bool,
 ) -> Result<(String, Vec<Box<dyn Error>>), Box<dyn Error>> {
-    let mut result = imports_and_generic_types(
-        &asnr.state.framework,
-        None,
-        asnr.state.no_std,
-        include_file_headers,
+    let sources = &expand_sources(sources, config, &mut io::stdin())?;
+    if config.framework == Some(Framework::JsonSchema) {
+        return internal_generate_json_schema(sources, config);
+    }
+    let (result, warnings) = internal_generate(sources, config, include_file_headers)?;
+    Ok((format_bindings(&result, config.use_rustfmt), warnings))
+}
+
+fn internal_compile_to_tokens(
+    sources: &[AsnSource],
+    config: &AsnrConfig,
+) -> CompileResult<TokenStream> {
+    let sources = &expand_sources(sources, config, &mut io::stdin())?;
+    if config.framework == Some(Framework::JsonSchema) {
+        return Err(Box::new(GeneratorError::new(
+            None,
+            "JSON Schema documents cannot be represented as Rust tokens",
+            GeneratorErrorType::UnsupportedOutput,
+        )));
+    }
+    let (result, warnings) = internal_generate(sources, config, false)?;
+    let tokens = result
+        .parse::<TokenStream>()
+        .map_err(|e| syn::Error::new(e.span(), format!("Generated invalid Rust code: {e}")))?;
+    Ok((tokens, warnings))
+}
+
+/// Generates the unformatted Rust representation of the ASN1 sources
+fn internal_generate(
+    sources: &Vec<AsnSource>,
+    config: &AsnrConfig,
+    include_file_headers: bool,
+) -> CompileResult<String> {
+    let custom_derives = config.custom_derives.join(", ");
+    let custom_derive = (!custom_derives.is_empty()).then_some(custom_derives.as_str());
+    let (generated, warnings) = generate_modules(sources, config, include_file_headers)?;
+    let mut result = with_configured_derives(
+        imports_and_generic_types(
+            &generated.framework,
+            custom_derive,
+            config.no_std,
+            include_file_headers.then_some(&config.header),
+            &source_names(sources),
+        ),
+        config,
+        &config.custom_derives,
     );
+    result += &generated
+        .declarations
+        .into_iter()
+        .map(|(_, rust)| rust)
+        .collect::<String>();
+
+    Ok((with_lint_allowances(result, config), warnings))
+}
+
+/// Generates a single JSON Schema document declaring the types of all ASN1 sources
+fn internal_generate_json_schema(
+    sources: &Vec<AsnSource>,
+    config: &AsnrConfig,
+) -> CompileResult<String> {
+    let (generated, warnings) = generate_modules(sources, config, false)?;
+    let definitions = generated
+        .declarations
+        .into_iter()
+        .map(|(_, definition)| definition)
+        .collect::<Vec<String>>();
+    Ok((json_schema_document(&definitions), warnings))
+}
+
+/// Compiles the ASN1 sources into one file per ASN1 module
+/// and returns the paths of the written files.
+/// JSON Schema documents are written to a single `schema.json`,
+/// since their definitions may reference each other across modules.
+fn internal_compile_to_dir(
+    sources: &[AsnSource],
+    config: &AsnrConfig,
+    output_dir: &Path,
+) -> CompileResult<Vec<PathBuf>> {
+    let sources = &expand_sources(sources, config, &mut io::stdin())?;
+    if config.framework == Some(Framework::JsonSchema) {
+        let (schema, warnings) = internal_generate_json_schema(sources, config)?;
+        fs::create_dir_all(output_dir)?;
+        let path = output_dir.join("schema.json");
+        fs::write(&path, schema)?;
+        return Ok((vec![path], warnings));
+    }
+    let custom_derives = config.custom_derives.join(", ");
+    let custom_derive = (!custom_derives.is_empty()).then_some(custom_derives.as_str());
+    let (generated, warnings) = generate_modules(sources, config, false)?;
+    let framework = generated.framework;
+    let mut module_files = BTreeMap::<String, String>::new();
+    for (module, rust) in generated.declarations {
+        module_files
+            .entry(module_file_name(&module))
+            .or_insert_with(|| {
+                let encoding = generated
+                    .encodings
+                    .get(&module)
+                    .and_then(encoding_description);
+                encoding.map(|e| format!("//! {e}\n")).unwrap_or_default()
+                    + &module_imports(&framework, config.no_std)
+            })
+            .push_str(&rust);
+    }
+    let mut mod_file = String::from("// This file has been auto-generated by ASNR\nmod common;\n");
+    let mut re_exports = String::from("pub use common::*;\n");
+    for module in module_files.keys() {
+        mod_file += &format!("mod {module};\n");
+        re_exports += &format!("pub use {module}::*;\n");
+    }
+    mod_file += &re_exports;
+    module_files.insert(
+        String::from("common"),
+        with_configured_derives(
+            imports_and_generic_types(
+                &framework,
+                custom_derive,
+                config.no_std,
+                (framework == Framework::Asnr).then_some(&config.header),
+                &source_names(sources),
+            ),
+            config,
+            &config.custom_derives,
+        ),
+    );
+    module_files.insert(String::from("mod"), mod_file);
+
+    fs::create_dir_all(output_dir)?;
+    let mut written = vec![];
+    for (name, rust) in module_files {
+        let path = output_dir.join(format!("{name}.rs"));
+        let rust = with_lint_allowances(rust, config);
+        fs::write(&path, format_bindings(&rust, config.use_rustfmt))?;
+        written.push(path);
+    }
+    Ok((written, warnings))
+}
+
+/// Applies the configured derives, if any, to the generated Rust code
+fn with_configured_derives(rust: String, config: &AsnrConfig, custom_derives: &[String]) -> String {
+    match &config.derives {
+        Some(derives) => apply_derives(&rust, derives, custom_derives),
+        None => rust,
+    }
+}
+
+/// Appends the ASN1 notation of a top-level declaration to its comments as a fenced code block.
+/// Since `asn1` is not a Rust code block attribute, rustdoc does not run the block as a doctest.
+fn embed_asn1_definition(tld: &mut ToplevelDeclaration, definition: &str) {
+    let notation = definition
+        .lines()
+        .map(|line| format!(" {line}"))
+        .collect::<Vec<String>>()
+        .join("\n");
+    let comments = comments_mut(tld);
+    if !comments.is_empty() {
+        comments.push('\n');
+    }
+    *comments += &format!(" ```asn1\n{notation}\n ```");
+}
+
+/// Appends the description of a module's encoding rules to the comments of a top-level declaration
+fn document_encoding(tld: &mut ToplevelDeclaration, description: &str) {
+    let comments = comments_mut(tld);
+    if !comments.is_empty() {
+        comments.push_str("\n\n");
+    }
+    *comments += &format!(" {description}");
+}
+
+fn comments_mut(tld: &mut ToplevelDeclaration) -> &mut String {
+    match tld {
+        ToplevelDeclaration::Information(i) => &mut i.comments,
+        ToplevelDeclaration::Type(t) => &mut t.comments,
+        ToplevelDeclaration::Value(v) => &mut v.comments,
+    }
+}
+
+/// Returns the file names of the ASN1 sources that have been added by path
+fn source_names(sources: &[AsnSource]) -> Vec<String> {
+    sources
+        .iter()
+        .filter_map(|source| match source {
+            AsnSource::Path(path) => path
+                .file_name()
+                .map(|name| name.to_string_lossy().into_owned()),
+            AsnSource::Literal(_) | AsnSource::Directory(_) | AsnSource::Stdin => None,
+        })
+        .collect()
+}
+
+/// Formats the `cargo:rerun-if-changed` directives for the ASN1 files and directories
+/// added by path and for the directory containing the output.
+/// Literal sources and the standard input cannot change between builds and are skipped.
+fn rerun_directives(sources: &[AsnSource], output: &AsnrOutput) -> Vec<String> {
+    let output_path = match output {
+        AsnrOutput::File(path) | AsnrOutput::Directory(path) => path,
+    };
+    sources
+        .iter()
+        .filter_map(|source| match source {
+            AsnSource::Path(path) | AsnSource::Directory(path) => Some(path.as_path()),
+            AsnSource::Literal(_) | AsnSource::Stdin => None,
+        })
+        .chain(
+            output_path
+                .parent()
+                .filter(|dir| !dir.as_os_str().is_empty()),
+        )
+        .map(|path| format!("cargo:rerun-if-changed={}", path.display()))
+        .collect()
+}
+
+/// Replaces the directories among the ASN1 sources with the ASN1 files that they contain,
+/// and the standard input with a literal source holding its content.
+/// The files of a directory are sorted by path, so that the order of the compiled
+/// declarations does not depend on the order in which the file system lists them.
+/// Fails if a directory cannot be read or does not contain any ASN1 files,
+/// or if the standard input cannot be read.
+/// * `stdin` - the reader providing the content of the standard input
+fn expand_sources(
+    sources: &[AsnSource],
+    config: &AsnrConfig,
+    stdin: &mut dyn Read,
+) -> Result<Vec<AsnSource>, Box<dyn Error>> {
+    let mut expanded = vec![];
+    for source in sources {
+        let dir = match source {
+            AsnSource::Directory(dir) => dir,
+            AsnSource::Stdin => {
+                let mut literal = String::new();
+                stdin.read_to_string(&mut literal).map_err(|e| {
+                    io::Error::new(e.kind(), format!("Failed to read ASN1 from stdin: {e}"))
+                })?;
+                expanded.push(AsnSource::Literal(literal));
+                continue;
+            }
+            _ => {
+                expanded.push(source.clone());
+                continue;
+            }
+        };
+        let mut paths = vec![];
+        collect_source_files(dir, config, &mut paths).map_err(|e| {
+            io::Error::new(
+                e.kind(),
+                format!(
+                    "Failed to read ASN1 source directory {}: {e}",
+                    dir.display()
+                ),
+            )
+        })?;
+        if paths.is_empty() {
+            return Err(Box::new(io::Error::new(
+                io::ErrorKind::NotFound,
+                format!(
+                    "ASN1 source directory {} does not contain any files with the extensions {}",
+                    dir.display(),
+                    config.source_dir_extensions.join(", ")
+                ),
+            )));
+        }
+        paths.sort();
+        expanded.extend(paths.into_iter().map(AsnSource::Path));
+    }
+    Ok(expanded)
+}
+
+/// Collects the paths of the files in `dir` that have one of the configured extensions
+fn collect_source_files(
+    dir: &Path,
+    config: &AsnrConfig,
+    paths: &mut Vec<PathBuf>,
+) -> io::Result<()> {
+    for entry in fs::read_dir(dir)? {
+        let path = entry?.path();
+        if path.is_dir() {
+            if config.recursive_source_dirs {
+                collect_source_files(&path, config, paths)?;
+            }
+        } else if path.extension().is_some_and(|extension| {
+            config
+                .source_dir_extensions
+                .iter()
+                .any(|allowed| extension.eq_ignore_ascii_case(allowed.as_str()))
+        }) {
+            paths.push(path);
+        }
+    }
+    Ok(())
+}
+
+/// Names of the files that are generated in addition to the module files
+const GENERATED_FILE_NAMES: [&str; 2] = ["common", "mod"];
+
+/// Converts an ASN1 module name into the name of a Rust module file.
+/// Names that collide with the generated `common` and `mod` files are suffixed with `_module`.
+fn module_file_name(module: &str) -> String {
+    let name = module.replace(['-', '.'], "_").to_lowercase();
+    if GENERATED_FILE_NAMES.contains(&name.as_str()) {
+        name + "_module"
+    } else {
+        name
+    }
+}
+
+/// Rust representations generated for the ASN1 sources
+struct GeneratedModules {
+    /// the configured framework or, if none is configured,
+    /// the framework selected by the encoding reference default of the ASN1 modules
+    framework: Framework,
+    /// the encoding reference defaults of the ASN1 modules, by module name
+    encodings: BTreeMap<String, EncodingReferenceDefault>,
+    /// the generated Rust representations in a stable order,
+    /// each paired with the name of the ASN1 module that declares it
+    declarations: Vec<(String, String)>,
+}
+
+/// Warns if the ASN1 modules declare different encoding reference defaults,
+/// since the bindings of all modules are generated for the same framework
+fn mixed_encodings_warning(
+    encodings: &BTreeMap<String, EncodingReferenceDefault>,
+    framework: &Framework,
+) -> Option<Box<dyn Error>> {
+    let distinct = encodings
+        .values()
+        .map(|encoding| encoding.0.as_str())
+        .collect::<BTreeSet<&str>>();
+    (distinct.len() > 1).then(|| {
+        let modules = encodings
+            .iter()
+            .map(|(module, encoding)| format!("{module} ({})", encoding.0))
+            .collect::<Vec<String>>()
+            .join(", ");
+        Box::new(GeneratorError::new(
+            None,
+            &format!(
+                "The ASN1 modules {modules} declare different encoding rules. The bindings of all modules are generated for the {framework:?} framework."
+            ),
+            GeneratorErrorType::MixedEncodings,
+        )) as Box<dyn Error>
+    })
+}
+
+/// Maps the encoding reference default of an ASN1 module onto the framework supporting these encoding rules.
+/// Returns `None` for encoding rules that none of the frameworks supports.
+fn encoding_framework(encoding: &EncodingReferenceDefault) -> Option<Framework> {
+    match encoding.0.as_str() {
+        "PER" => Some(Framework::Asnr),
+        "BER" => Some(Framework::Rasn),
+        _ => None,
+    }
+}
+
+/// Lints that the generated bindings may trigger
+/// * `non_upper_case_globals` - INTEGER value constants are named in snake case
+/// * `unused_imports` - the imports of the generic types precede every module
+/// * `clippy::unnecessary_cast` - keys of information object sets are cast to `i128`
+/// * `clippy::upper_case_acronyms` - type names are derived from the ASN1 names, e.g. `CAM`
+const GENERATED_CODE_LINTS: &str =
+    "non_upper_case_globals, unused_imports, clippy::unnecessary_cast, clippy::upper_case_acronyms";
+
+/// Attaches an attribute that silences the lints of the generated bindings to each of
+/// their top-level items, if the compiler is configured to suppress them.
+/// Unlike an inner attribute, these attributes are permitted in `include!`d files.
+/// Since `syn` drops regular comments, a leading block of line comments is preserved separately.
+fn with_lint_allowances(bindings: String, config: &AsnrConfig) -> String {
+    if !config.suppress_lints {
+        return bindings;
+    }
+    let (header, code) = bindings.split_at(comment_header_length(&bindings));
+    let allowance = Attribute::parse_outer
+        .parse_str(&format!("#[allow({GENERATED_CODE_LINTS})]"))
+        .expect("lint allowances are a valid attribute");
+    match syn::parse_file(code) {
+        Ok(mut file) => {
+            for attrs in file.items.iter_mut().filter_map(item_attributes) {
+                attrs.splice(0..0, allowance.iter().cloned());
+            }
+            format!("{header}{}", file.into_token_stream())
+        }
+        Err(_) => bindings,
+    }
+}
+
+fn item_attributes(item: &mut Item) -> Option<&mut Vec<Attribute>> {
+    match item {
+        Item::Const(i) => Some(&mut i.attrs),
+        Item::Enum(i) => Some(&mut i.attrs),
+        Item::ExternCrate(i) => Some(&mut i.attrs),
+        Item::Fn(i) => Some(&mut i.attrs),
+        Item::ForeignMod(i) => Some(&mut i.attrs),
+        Item::Impl(i) => Some(&mut i.attrs),
+        Item::Macro(i) => Some(&mut i.attrs),
+        Item::Mod(i) => Some(&mut i.attrs),
+        Item::Static(i) => Some(&mut i.attrs),
+        Item::Struct(i) => Some(&mut i.attrs),
+        Item::Trait(i) => Some(&mut i.attrs),
+        Item::TraitAlias(i) => Some(&mut i.attrs),
+        Item::Type(i) => Some(&mut i.attrs),
+        Item::Union(i) => Some(&mut i.attrs),
+        Item::Use(i) => Some(&mut i.attrs),
+        _ => None,
+    }
+}
+
+/// Describes the encoding rules of an ASN1 module for the documentation of the generated bindings
+fn encoding_description(encoding: &EncodingReferenceDefault) -> Option<String> {
+    let rules = match encoding.0.as_str() {
+        "PER" => "Packed Encoding Rules",
+        "BER" => "Basic Encoding Rules",
+        "XER" => "XML Encoding Rules",
+        _ => return None,
+    };
+    Some(format!("Encoding: {} ({rules})", encoding.0))
+}
+
+/// Top-level declarations parsed from the ASN1 sources
+struct ParsedSources {
+    /// the headers of the ASN1 modules in the order of their first occurrence
+    headers: Vec<ModuleReference>,
+    /// the parsed declarations with the tagging environments of their modules applied
+    tlds: Vec<ToplevelDeclaration>,
+    /// the name of the ASN1 module declaring a top-level declaration, by declaration name
+    module_by_tld: BTreeMap<String, String>,
+    /// the encoding reference defaults of the ASN1 modules, by module name
+    encodings: BTreeMap<String, EncodingReferenceDefault>,
+    /// the frameworks selected by the encoding reference defaults, in module order
+    encoding_frameworks: Vec<Framework>,
+}
+
+/// Parses the ASN1 sources and applies the tagging environments of their modules.
+fn parse_sources(sources: &Vec<AsnSource>, config: &AsnrConfig) -> CompileResult<ParsedSources> {
     let mut warnings = Vec::<Box<dyn Error>>::new();
+    let mut headers: Vec<ModuleReference> = vec![];
     let mut modules: Vec<ToplevelDeclaration> = vec![];
-    for src in &asnr.state.sources {
+    let mut module_by_tld = BTreeMap::<String, String>::new();
+    let mut encodings = BTreeMap::<String, EncodingReferenceDefault>::new();
+    let mut encoding_frameworks = vec![];
+    for src in sources {
         let stringified_src = match src {
             AsnSource::Path(p) => read_to_string(p)?,
             AsnSource::Literal(l) => l.clone(),
+            // directories and the standard input have been replaced in `expand_sources`
+            AsnSource::Directory(_) | AsnSource::Stdin => continue,
         };
-        modules.append(
-            &mut asn_spec(&stringified_src)?
-                .into_iter()
-                .flat_map(|(header, tlds)| {
-                    tlds.into_iter().map(move |mut tld| {
-                        tld.apply_tagging_environment(&header.tagging_environment);
-                        tld
-                    })
-                })
-                .collect(),
-        );
+        let parsed = if config.skip_unparsable_declarations {
+            let (parsed, skipped) = asn_spec_with_recovery(&stringified_src)?;
+            warnings.extend(skipped.into_iter().map(|e| Box::new(e) as Box<dyn Error>));
+            parsed
+        } else {
+            asn_spec(&stringified_src)?
+        };
+        let definitions = match config.embed_asn1_definitions {
+            true => declaration_sources(&stringified_src),
+            false => BTreeMap::new(),
+        };
+        for (header, tlds) in parsed {
+            if let Some(encoding) = &header.encoding_reference_default {
+                encoding_frameworks.extend(encoding_framework(encoding));
+                encodings.insert(header.name.clone(), encoding.clone());
+            }
+            for mut tld in tlds {
+                tld.apply_tagging_environment(&header.tagging_environment);
+                if let Some(definition) = definitions.get(tld.name()) {
+                    embed_asn1_definition(&mut tld, definition);
+                }
+                module_by_tld
+                    .entry(tld.name().clone())
+                    .or_insert_with(|| header.name.clone());
+                modules.push(tld);
+            }
+            if headers.iter().all(|known| known.name != header.name) {
+                headers.push(header);
+            }
+        }
     }
-    let (valid_tlds, mut validator_errors) = Validator::new(modules).validate()?;
+    Ok((
+        ParsedSources {
+            headers,
+            tlds: modules,
+            module_by_tld,
+            encodings,
+            encoding_frameworks,
+        },
+        warnings,
+    ))
+}
+
+/// Parses and validates the ASN1 sources, grouping the validated declarations by module.
+fn parse_modules(
+    sources: &[AsnSource],
+    config: &AsnrConfig,
+) -> CompileResult<Vec<(ModuleReference, Vec<ToplevelDeclaration>)>> {
+    let sources = &expand_sources(sources, config, &mut io::stdin())?;
+    let (parsed, mut warnings) = parse_sources(sources, config)?;
+    let (modules, mut validator_errors) = Validator::new(parsed.tlds)
+        .merge_duplicate_declarations(config.merge_duplicate_declarations)
+        .validate_by_module(parsed.headers, &parsed.module_by_tld)?;
+    warnings.append(&mut validator_errors);
+    Ok((modules, warnings))
+}
+
+/// Parses, validates, and generates the ASN1 sources.
+/// With `document_encodings`, the documentation of every generated declaration names the
+/// encoding rules of its module, since a single file of bindings has no module to document.
+fn generate_modules(
+    sources: &Vec<AsnSource>,
+    config: &AsnrConfig,
+    document_encodings: bool,
+) -> CompileResult<GeneratedModules> {
+    let (
+        ParsedSources {
+            headers,
+            tlds: modules,
+            module_by_tld,
+            encodings,
+            encoding_frameworks,
+        },
+        mut warnings,
+    ) = parse_sources(sources, config)?;
+    let (mut valid_tlds, mut validator_errors) = Validator::new(modules)
+        .merge_duplicate_declarations(config.merge_duplicate_declarations)
+        .modules(&headers, &module_by_tld)
+        .validate()?;
+    valid_tlds.sort_by(|a, b| {
+        generation_rank(a)
+            .cmp(&generation_rank(b))
+            .then_with(|| a.name().cmp(b.name()))
+    });
+    let (mut valid_tlds, cycle_warnings) = topological_sort(valid_tlds);
+    validator_errors.extend(
+        cycle_warnings
+            .into_iter()
+            .map(|warning| Box::new(warning) as Box<dyn Error>),
+    );
+    if document_encodings {
+        for tld in valid_tlds.iter_mut() {
+            let description = module_by_tld
+                .get(tld.name())
+                .and_then(|module| encodings.get(module))
+                .and_then(encoding_description);
+            if let Some(description) = description {
+                document_encoding(tld, &description);
+            }
+        }
+    }
+    let framework = config
+        .framework
+        .clone()
+        .or_else(|| encoding_frameworks.first().cloned())
+        .unwrap_or_default();
+    if config.framework.is_none() {
+        warnings.extend(mixed_encodings_warning(&encodings, &framework));
+    }
+    if let Some(derives) = &config.derives {
+        validate_derives(derives, &framework, &valid_tlds)?;
+    }
+    // JSON schemas keep the ASN1 names, since the affixes only apply to Rust types
+    let affixed_names = match framework {
+        Framework::JsonSchema => BTreeMap::new(),
+        _ => affixed_type_names(&valid_tlds, &config.type_prefix, &config.type_suffix),
+    };
+    let asn_names = affixed_names
+        .iter()
+        .map(|(asn_name, rust_name)| (rust_name.clone(), asn_name.clone()))
+        .collect::<BTreeMap<String, String>>();
+    let valid_tlds = valid_tlds
+        .into_iter()
+        .map(|mut tld| {
+            tld.rename(&affixed_names);
+            tld
+        })
+        .collect::<Vec<ToplevelDeclaration>>();
+    let options = GeneratorOptions {
+        force_i128_integers: config.force_i128_integers,
+        materialize_defaults: config.materialize_defaults,
+        flatten_primitive_members: config.flatten_primitive_members,
+        contained_type_accessors: config.contained_type_accessors,
+        mark_extensible_as_non_exhaustive: config.mark_extensible_as_non_exhaustive,
+        naming: config.naming.clone(),
+        encoded_sizes: fixed_encoded_sizes(&valid_tlds),
+    };
+    // the cache cannot tell custom backends apart, so their output is never cached
+    let cache_dir = config
+        .cache_dir
+        .as_ref()
+        .filter(|_| !matches!(framework, Framework::Custom(_)));
+    let mut cache = cache_dir.map(|dir| {
+        GenerationCache::load(
+            dir,
+            &format!(
+                "{}|{framework:?}|{}|{options:?}",
+                env!("CARGO_PKG_VERSION"),
+                config.no_std
+            ),
+        )
+    });
     let (generated, mut generator_errors) = valid_tlds.into_iter().fold(
-        (String::new(), Vec::<Box<dyn Error>>::new()),
+        (Vec::<(String, String)>::new(), Vec::<Box<dyn Error>>::new()),
         |(mut rust, mut errors), tld| {
-            match generate(&asnr.state.framework, tld, None) {
-                Ok(r) => {
-                    rust = rust + &r + "\n";
-                }
+            let asn_name = asn_names.get(tld.name()).unwrap_or(tld.name());
+            // declarations introduced by the validator, such as instantiations of
+            // parameterized types, are assigned to a module of the specification
+            let module = module_by_tld
+                .get(asn_name)
+                .or_else(|| module_by_tld.values().next())
+                .cloned()
+                .unwrap_or_default();
+            let type_override = match &tld {
+                ToplevelDeclaration::Type(_) if framework == Framework::JsonSchema => None,
+                ToplevelDeclaration::Type(t) => config
+                    .type_overrides
+                    .get(asn_name)
+                    .map(|rust_type| generate_type_override(t, rust_type)),
+                _ => None,
+            };
+            let custom_derives = config
+                .custom_derives
+                .iter()
+                .chain(config.derive_overrides.get(asn_name).into_iter().flatten())
+                .cloned()
+                .collect::<Vec<String>>();
+            let derives = custom_derives.join(", ");
+            let source = format!("{tld:?}|{derives}");
+            let name = tld.name().clone();
+            let generated = match (type_override, &mut cache) {
+                (Some(alias), _) => Ok(alias),
+                (None, Some(cache)) => match cache.get(&name, &source) {
+                    Some(cached) => Ok(cached),
+                    None => generate_declaration(&framework, tld, &derives, &options)
+                        .inspect(|rust| cache.insert(&name, &source, rust)),
+                },
+                (None, None) => generate_declaration(&framework, tld, &derives, &options),
+            };
+            match generated {
+                Ok(r) => rust.push((
+                    module,
+                    with_configured_derives(r, config, &custom_derives) + "\n",
+                )),
                 Err(e) => errors.push(Box::new(e)),
             }
             (rust, errors)
         },
     );
-    result += &generated;
     warnings.append(&mut validator_errors);
     warnings.append(&mut generator_errors);
+    if let Some(Err(e)) = cache.map(|cache| cache.write()) {
+        warnings.push(Box::new(e));
+    }
+
+    Ok((
+        GeneratedModules {
+            framework,
+            encodings,
+            declarations: generated,
+        },
+        warnings,
+    ))
+}
+
+/// Maps the names of the declarations that are represented by Rust types
+/// to their title-cased names with the configured prefix and suffix.
+/// Returns an empty map if neither a prefix nor a suffix is configured.
+/// * `tlds` - the validated top-level declarations
+/// * `prefix` - prefix of the generated type names
+/// * `suffix` - suffix of the generated type names
+fn affixed_type_names(
+    tlds: &[ToplevelDeclaration],
+    prefix: &str,
+    suffix: &str,
+) -> BTreeMap<String, String> {
+    if prefix.is_empty() && suffix.is_empty() {
+        return BTreeMap::new();
+    }
+    tlds.iter()
+        .filter(|tld| match tld {
+            ToplevelDeclaration::Type(_) => true,
+            ToplevelDeclaration::Information(i) => !matches!(i.value, ASN1Information::Object(_)),
+            ToplevelDeclaration::Value(_) => false,
+        })
+        .map(|tld| {
+            (
+                tld.name().clone(),
+                format!("{prefix}{}{suffix}", to_rust_title_case(tld.name())),
+            )
+        })
+        .collect()
+}
+
+#[cfg(test)]
+thread_local! {
+    /// Number of declarations the generator has been invoked for on the current thread
+    static GENERATOR_CALLS: std::cell::Cell<usize> = const { std::cell::Cell::new(0) };
+}
+
+/// Generates the Rust representation of a single top-level declaration
+fn generate_declaration(
+    framework: &Framework,
+    tld: ToplevelDeclaration,
+    derives: &str,
+    options: &GeneratorOptions,
+) -> Result<String, GeneratorError> {
+    #[cfg(test)]
+    GENERATOR_CALLS.with(|calls| calls.set(calls.get() + 1));
+    generate(
+        framework,
+        tld,
+        (!derives.is_empty()).then_some(derives),
+        options,
+    )
+}
+
+/// Formats the generated bindings. Falls back to the unformatted bindings if formatting fails.
+/// * `bindings` - the generated Rust code
+/// * `use_rustfmt` - whether to use the external `rustfmt` binary instead of the built-in formatter
+fn format_bindings(bindings: &String, use_rustfmt: bool) -> String {
+    if use_rustfmt {
+        rustfmt(bindings).unwrap_or_else(|_| bindings.clone())
+    } else {
+        pretty_print(bindings)
+    }
+}
+
+/// Pretty-prints the generated bindings in-process.
+/// Since `syn` drops regular comments, a leading block of line comments is preserved separately.
+#[cfg(feature = "prettyplease")]
+fn pretty_print(bindings: &str) -> String {
+    let (header, code) = bindings.split_at(comment_header_length(bindings));
+    match syn::parse_file(code) {
+        Ok(file) => format!("{header}{}", prettyplease::unparse(&file)),
+        Err(_) => bindings.to_owned(),
+    }
+}
 
-    result = format_bindings(&result).unwrap_or(result);
+/// Length of the block of line comments that the bindings start with
+fn comment_header_length(bindings: &str) -> usize {
+    bindings
+        .lines()
+        .take_while(|line| line.trim().is_empty() || is_plain_line_comment(line))
+        .map(|line| line.len() + 1)
+        .sum::<usize>()
+        .min(bindings.len())
+}
+
+fn is_plain_line_comment(line: &str) -> bool {
+    let line = line.trim_start();
+    line.starts_with("//") && !line.starts_with("///") && !line.starts_with("//!")
+}
 
-    Ok((result, warnings))
+#[cfg(not(feature = "prettyplease"))]
+fn pretty_print(bindings: &str) -> String {
+    bindings.to_owned()
 }
 
-fn format_bindings(bindings: &String) -> Result<String, Box<dyn Error>> {
+fn rustfmt(bindings: &String) -> Result<String, Box<dyn Error>> {
     let mut rustfmt = PathBuf::from(env::var("CARGO_HOME")?);
     rustfmt.push("bin/rustfmt");
     let mut cmd = Command::new(&*rustfmt);
@@ -614,9 +1790,20 @@ fn format_bindings(bindings: &String) -> Result<String, Box<dyn Error>> {
 
 #[cfg(test)]
 mod tests {
-    use std::path::PathBuf;
+    use std::{
+        fs::{self, read_to_string},
+        io,
+        path::PathBuf,
+    };
+
+    use asnr_grammar::{TaggingEnvironment, ToplevelDeclaration};
 
-    use crate::Asnr;
+    use crate::{
+        expand_sources,
+        generator::error::{GeneratorError, GeneratorErrorType},
+        internal_compile, rerun_directives, AsnSource, Asnr, AsnrOutput, Derive, HeaderConfig,
+        NamingStrategy,
+    };
 
     #[test]
     fn compiles_a_simple_spec() {
@@ -646,4 +1833,2197 @@ mod tests {
                 .unwrap()
         )
     }
+
+    #[test]
+    fn merges_identical_duplicate_declarations() {
+        let (generated, warnings) = Asnr::new()
+            .add_asn_literal(
+                r#"First DEFINITIONS AUTOMATIC TAGS ::= BEGIN
+                Duplicate ::= INTEGER (1..4)
+                END"#,
+            )
+            .add_asn_literal(
+                r#"Second DEFINITIONS AUTOMATIC TAGS ::= BEGIN
+                Duplicate ::= INTEGER (1..4)
+                END"#,
+            )
+            .compile_to_string()
+            .unwrap();
+        assert_eq!(generated.matches("pub struct Duplicate").count(), 1);
+        assert_eq!(warnings.len(), 1);
+    }
+
+    #[test]
+    fn rejects_conflicting_duplicate_declarations() {
+        assert!(Asnr::new()
+            .add_asn_literal(
+                r#"First DEFINITIONS AUTOMATIC TAGS ::= BEGIN
+                Duplicate ::= INTEGER (1..4)
+                END"#,
+            )
+            .add_asn_literal(
+                r#"Second DEFINITIONS AUTOMATIC TAGS ::= BEGIN
+                Duplicate ::= INTEGER (1..8)
+                END"#,
+            )
+            .compile_to_string()
+            .is_err());
+    }
+
+    #[test]
+    fn rejects_identical_duplicates_if_merging_is_disabled() {
+        assert!(Asnr::new()
+            .merge_duplicate_declarations(false)
+            .add_asn_literal(
+                r#"First DEFINITIONS AUTOMATIC TAGS ::= BEGIN
+                Duplicate ::= INTEGER (1..4)
+                END"#,
+            )
+            .add_asn_literal(
+                r#"Second DEFINITIONS AUTOMATIC TAGS ::= BEGIN
+                Duplicate ::= INTEGER (1..4)
+                END"#,
+            )
+            .compile_to_string()
+            .is_err());
+    }
+
+    #[test]
+    fn appends_custom_derives_to_generated_types() {
+        for framework in [crate::Framework::Asnr, crate::Framework::Rasn] {
+            let (generated, _) = Asnr::new()
+                .framework(framework)
+                .custom_derives(&["Serialize", "Deserialize"])
+                .add_asn_literal(
+                    r#"Derives DEFINITIONS AUTOMATIC TAGS ::= BEGIN
+                    Wrapper ::= SEQUENCE { inner BOOLEAN }
+                    END"#,
+                )
+                .compile_to_string()
+                .unwrap();
+            assert_eq!(
+                generated.matches("Serialize, Deserialize)]").count(),
+                generated.matches("#[derive(").count()
+            );
+        }
+    }
+
+    #[test]
+    fn applies_type_and_derive_overrides() {
+        let (generated, _) = Asnr::new()
+            .with_type_override("MyBits", "u8")
+            .with_derive_override("Flag", &["Hash"])
+            .add_asn_literal(
+                r#"Overrides DEFINITIONS AUTOMATIC TAGS ::= BEGIN
+                MyBits ::= BIT STRING (SIZE(8))
+                Flag ::= BOOLEAN
+                Container ::= SEQUENCE { bits MyBits, flag Flag }
+                END"#,
+            )
+            .compile_to_string()
+            .unwrap();
+        assert!(generated.contains("pub type MyBits = u8;"));
+        assert!(!generated.contains("pub struct MyBits"));
+        assert!(generated.contains("pub bits: MyBits"));
+        assert_eq!(generated.matches("Default, Hash)]").count(), 1);
+    }
+
+    #[test]
+    fn affixes_generated_type_names() {
+        let spec = r#"Affixes DEFINITIONS AUTOMATIC TAGS ::= BEGIN
+            MESSAGE-CLASS ::= CLASS { &id INTEGER UNIQUE, &Type }
+            WITH SYNTAX { ID &id TYPE &Type }
+            Speed ::= INTEGER (0..255)
+            Level ::= ENUMERATED { low, high }
+            Flag ::= BOOLEAN
+            Position ::= SEQUENCE { level Level, speed Speed DEFAULT 3 }
+            Pick ::= CHOICE { position Position, speed Speed }
+            Messages MESSAGE-CLASS ::= { { ID 1 TYPE Position } | { ID 2 TYPE Level } }
+            Wrapper ::= SEQUENCE {
+                id MESSAGE-CLASS.&id ({Messages}),
+                body MESSAGE-CLASS.&Type ({Messages}{@id})
+            }
+            defaultLevel Level ::= low
+            END"#;
+        let (asnr, warnings) = Asnr::new()
+            .add_asn_literal(spec)
+            .type_prefix("Asn")
+            .type_suffix("Msg")
+            .with_type_override("Flag", "bool")
+            .with_derive_override("Level", &["Hash"])
+            .compile_to_string()
+            .unwrap();
+        assert!(warnings.is_empty());
+        assert!(asnr.contains("pub type AsnFlagMsg = bool;"));
+        assert!(asnr.contains("PartialEq, Default, Hash)]\npub enum AsnLevelMsg {"));
+        assert!(asnr.contains("pub struct AsnPositionMsg {\n    pub level: AsnLevelMsg,"));
+        assert!(asnr.contains("pub fn default_speed() -> AsnSpeedMsg {"));
+        assert!(asnr.contains("Position(AsnPositionMsg),\n    Speed(AsnSpeedMsg),"));
+        assert!(asnr.contains("impl<'a, I: AsBytes + Debug + 'a> Decode<'a, I> for AsnPickMsg {"));
+        assert!(asnr.contains("\"AsnPositionMsg\".into()"));
+        assert!(asnr.contains("pub enum AsnMessagesMsg {"));
+        assert!(asnr.contains("pub const DEFAULT_LEVEL: AsnLevelMsg = AsnLevelMsg::Low;"));
+        assert!(!asnr.contains(" Position "));
+        let (rasn, _) = Asnr::new()
+            .add_asn_literal(spec)
+            .framework(crate::Framework::Rasn)
+            .type_prefix("Asn")
+            .compile_to_string()
+            .unwrap();
+        assert!(rasn.contains("pub struct AsnPosition {\n    level: AsnLevel,"));
+        assert!(rasn.contains("fn asn_position_speed_default() -> AsnSpeed {\n    AsnSpeed(3)"));
+        assert!(rasn.contains("pub enum AsnPick {\n    Position(AsnPosition),"));
+        assert!(rasn.contains("pub enum AsnMessages {\n    AsnPosition(AsnPosition),"));
+        let (json, _) = Asnr::new()
+            .add_asn_literal(spec)
+            .framework(crate::Framework::JsonSchema)
+            .type_prefix("Asn")
+            .compile_to_string()
+            .unwrap();
+        assert!(!json.contains("AsnPosition"));
+    }
+
+    #[test]
+    fn selects_framework_by_encoding_reference_default() {
+        let compile = |encoding: &str, framework: Option<crate::Framework>| {
+            let compiler = Asnr::new().add_asn_literal(format!(
+                r#"Encoded DEFINITIONS {encoding} AUTOMATIC TAGS ::= BEGIN
+                Flag ::= BOOLEAN
+                END"#
+            ));
+            match framework {
+                Some(framework) => compiler.framework(framework),
+                None => compiler,
+            }
+            .compile_to_string()
+            .unwrap()
+            .0
+        };
+        assert!(compile("PER INSTRUCTIONS", None).contains("use asnr_transcoder::"));
+        assert!(compile("BER INSTRUCTIONS", None).contains("use rasn::prelude::*;"));
+        assert!(compile("XER INSTRUCTIONS", None).contains("use asnr_transcoder::"));
+        assert!(compile("PER INSTRUCTIONS", Some(crate::Framework::Rasn))
+            .contains("use rasn::prelude::*;"));
+
+        let output_path = std::env::temp_dir().join("asnr_encoding_reference_default.rs");
+        let warnings = Asnr::new()
+            .add_asn_literal(
+                r#"Encoded DEFINITIONS PER INSTRUCTIONS AUTOMATIC TAGS ::= BEGIN
+                -- a flag
+                Flag ::= BOOLEAN
+                END"#,
+            )
+            .set_output_path(&output_path)
+            .compile()
+            .unwrap();
+        assert!(warnings.is_empty());
+        let generated = read_to_string(&output_path).unwrap();
+        // inner doc comments would prevent `include!`ing the bindings
+        assert!(!generated.contains("//!"));
+        assert!(generated.contains("/// a flag\n///\n/// Encoding: PER (Packed Encoding Rules)\n"));
+        fs::remove_file(&output_path).unwrap();
+    }
+
+    #[test]
+    fn warns_about_mixed_encoding_reference_defaults() {
+        let spec = r#"Packed DEFINITIONS PER INSTRUCTIONS AUTOMATIC TAGS ::= BEGIN
+            Flag ::= BOOLEAN
+            END
+            Basic DEFINITIONS BER INSTRUCTIONS AUTOMATIC TAGS ::= BEGIN
+            Count ::= INTEGER
+            END"#;
+        let (_, warnings) = Asnr::new()
+            .add_asn_literal(spec)
+            .compile_to_string()
+            .unwrap();
+        assert_eq!(warnings.len(), 1);
+        let warning = warnings[0].downcast_ref::<GeneratorError>().unwrap();
+        assert!(matches!(warning.kind, GeneratorErrorType::MixedEncodings));
+        assert!(warning.details.contains("Basic (BER), Packed (PER)"));
+        let (_, warnings) = Asnr::new()
+            .add_asn_literal(spec)
+            .framework(crate::Framework::Rasn)
+            .compile_to_string()
+            .unwrap();
+        assert!(warnings.is_empty());
+    }
+
+    #[test]
+    fn generates_contained_type_accessors() {
+        let spec = r#"Contained DEFINITIONS AUTOMATIC TAGS ::= BEGIN
+            Data ::= SEQUENCE { id INTEGER (0..255), flag BOOLEAN }
+            Wrapper ::= SEQUENCE {
+                payload OCTET STRING (CONTAINING Data),
+                extra OCTET STRING (CONTAINING Data) OPTIONAL,
+                raw OCTET STRING
+            }
+            END"#;
+        let compile = |framework: crate::Framework, accessors: bool| {
+            Asnr::new()
+                .add_asn_literal(spec)
+                .framework(framework)
+                .contained_type_accessors(accessors)
+                .compile_to_string()
+                .unwrap()
+                .0
+        };
+        let asnr = compile(crate::Framework::Asnr, true);
+        assert!(asnr.contains(
+            ") -> Result<Data, DecodingError<I>> {\n        D::decode_contents(&self.payload.0)"
+        ));
+        assert!(asnr.contains("self.payload = InnerWrapperPayload(E::encode_contents(value)?);"));
+        assert!(asnr.contains(") -> Result<Option<Data>, DecodingError<I>> {\n        self.extra.as_ref().map(|extra| D::decode_contents(&extra.0)).transpose()"));
+        assert!(asnr.contains("self.extra = Some(InnerWrapperExtra(E::encode_contents(value)?));"));
+        assert!(!asnr.contains("decoded_raw"));
+        assert!(!compile(crate::Framework::Asnr, false).contains("decoded_payload"));
+        assert!(compile(crate::Framework::Rasn, false)
+            .contains("/// Contains an encoded [Data]\n    payload: OctetString"));
+    }
+
+    #[test]
+    fn seals_object_set_membership() {
+        let generated = Asnr::new()
+            .add_asn_literal(
+                r#"Sealed DEFINITIONS AUTOMATIC TAGS ::= BEGIN
+            CONTAINER ::= CLASS { &id INTEGER UNIQUE, &Type } WITH SYNTAX { &Type IDENTIFIED BY &id }
+            First ::= BOOLEAN
+            Second ::= IA5String
+            Containers CONTAINER ::= { {First IDENTIFIED BY 1} | {Second IDENTIFIED BY 2} | {Second IDENTIFIED BY 3} }
+            END"#,
+            )
+            .compile_to_string()
+            .unwrap()
+            .0;
+        assert!(generated.contains(
+            "pub trait ContainersMember: containers_member::Sealed + Into<Containers> {}"
+        ));
+        assert!(generated.contains("impl ContainersMember for First {}"));
+        // the key of Second is ambiguous
+        assert!(!generated.contains("impl ContainersMember for Second {}"));
+        assert!(!generated.contains("impl From<Second> for Containers"));
+    }
+
+    #[test]
+    fn embeds_asn1_definitions_in_doc_comments() {
+        let spec = r#"Embedded DEFINITIONS AUTOMATIC TAGS ::= BEGIN
+            -- A flagged point
+            Point ::= SEQUENCE {
+                x INTEGER (0..255),
+                flag BOOLEAN
+            }
+            origin Point ::= { x 0, flag FALSE }
+            END"#;
+        let compile = |framework: crate::Framework| {
+            Asnr::new()
+                .add_asn_literal(spec)
+                .framework(framework)
+                .embed_asn1_definitions(true)
+                .compile_to_string()
+                .unwrap()
+                .0
+        };
+        let generated = [
+            compile(crate::Framework::Asnr),
+            compile(crate::Framework::Rasn),
+        ];
+        for generated in generated {
+            assert!(generated.contains(
+                "/// A flagged point\n/// ```asn1\n/// Point ::= SEQUENCE {\n///     x INTEGER (0..255),\n///     flag BOOLEAN\n/// }\n/// ```\n"
+            ));
+            assert!(generated
+                .contains("/// ```asn1\n/// origin Point ::= { x 0, flag FALSE }\n/// ```\n"));
+        }
+        assert!(!Asnr::new()
+            .add_asn_literal(spec)
+            .compile_to_string()
+            .unwrap()
+            .0
+            .contains("```asn1"));
+    }
+
+    #[test]
+    fn generates_configured_file_header() {
+        let source_path = std::env::temp_dir().join("asnr_file_header.asn");
+        let output_path = std::env::temp_dir().join("asnr_file_header.rs");
+        fs::write(
+            &source_path,
+            r#"Header DEFINITIONS AUTOMATIC TAGS ::= BEGIN
+            Flag ::= BOOLEAN
+            END"#,
+        )
+        .unwrap();
+        let compile = |framework: crate::Framework, header: HeaderConfig| {
+            Asnr::new()
+                .add_asn_by_path(&source_path)
+                .framework(framework)
+                .header(header)
+                .set_output_path(&output_path)
+                .compile()
+                .unwrap();
+            read_to_string(&output_path).unwrap()
+        };
+        assert!(compile(crate::Framework::Asnr, HeaderConfig::default())
+            .starts_with("// This file has been auto-generated by ASNR\n#![allow(dead_code)]\n"));
+        assert!(compile(crate::Framework::Rasn, HeaderConfig::default()).starts_with("\n"));
+
+        let header = HeaderConfig {
+            banner: Some(String::from("SPDX-License-Identifier: MIT\n// DO NOT EDIT")),
+            list_sources: true,
+            include_version: true,
+            include_timestamp: false,
+        };
+        let expected = format!(
+            "// SPDX-License-Identifier: MIT\n// DO NOT EDIT\n\
+            // Generated from: asnr_file_header.asn\n\
+            // Generated by asnr-compiler {}\n",
+            env!("CARGO_PKG_VERSION")
+        );
+        assert!(compile(crate::Framework::Asnr, header.clone())
+            .starts_with(&format!("{expected}#![allow(dead_code)]\n")));
+        assert!(compile(crate::Framework::Rasn, header).starts_with(&expected));
+        fs::remove_file(&source_path).unwrap();
+        fs::remove_file(&output_path).unwrap();
+    }
+
+    #[test]
+    fn adds_asn_sources_by_dir() {
+        let source_dir = std::env::temp_dir().join("asnr_adds_asn_sources_by_dir");
+        let output_path = source_dir.join("generated.rs");
+        let _ = fs::remove_dir_all(&source_dir);
+        fs::create_dir_all(source_dir.join("nested")).unwrap();
+        let module = |name: &str| {
+            format!("{name} DEFINITIONS AUTOMATIC TAGS ::= BEGIN\n{name}-Flag ::= BOOLEAN\nEND")
+        };
+        fs::write(source_dir.join("b.asn"), module("B")).unwrap();
+        fs::write(source_dir.join("a.asn1"), module("A")).unwrap();
+        fs::write(source_dir.join("nested").join("c.ASN"), module("C")).unwrap();
+        fs::write(source_dir.join("notes.txt"), "not ASN1").unwrap();
+        let compile = |recursive: bool| {
+            Asnr::new()
+                .add_asn_sources_by_dir(&source_dir)
+                .scan_source_dirs_recursively(recursive)
+                .header(HeaderConfig {
+                    list_sources: true,
+                    ..Default::default()
+                })
+                .set_output_path(&output_path)
+                .compile()
+                .unwrap();
+            read_to_string(&output_path).unwrap()
+        };
+        let generated = compile(false);
+        assert!(generated.contains("// Generated from: a.asn1, b.asn\n"));
+        assert!(generated.contains("pub struct AFlag(pub bool);"));
+        assert!(!generated.contains("pub struct CFlag(pub bool);"));
+        let generated = compile(true);
+        assert!(generated.contains("// Generated from: a.asn1, b.asn, c.ASN\n"));
+        assert!(generated.contains("pub struct CFlag(pub bool);"));
+
+        let error = Asnr::new()
+            .add_asn_sources_by_dir(&source_dir)
+            .source_dir_extensions(&[".asn2"])
+            .compile_to_string()
+            .unwrap_err();
+        assert!(error
+            .to_string()
+            .contains("does not contain any files with the extensions asn2"));
+        let error = Asnr::new()
+            .add_asn_sources_by_dir(source_dir.join("missing"))
+            .compile_to_string()
+            .unwrap_err();
+        assert!(error
+            .to_string()
+            .starts_with("Failed to read ASN1 source directory"));
+        fs::remove_dir_all(&source_dir).unwrap();
+    }
+
+    #[test]
+    fn parses_modules_without_generating() {
+        let (modules, warnings) = Asnr::new()
+            .add_asn_literal(
+                r#"Templates DEFINITIONS IMPLICIT TAGS ::= BEGIN
+                Container{Content} ::= SEQUENCE { content Content }
+                Tagged ::= [APPLICATION 1] INTEGER
+                END"#,
+            )
+            .add_asn_literal(
+                r#"Messages DEFINITIONS AUTOMATIC TAGS ::= BEGIN
+                Message ::= SEQUENCE { wrapped Container{BOOLEAN} }
+                END"#,
+            )
+            .parse()
+            .unwrap();
+        assert!(warnings.is_empty());
+        let names = modules
+            .iter()
+            .map(|(header, tlds)| {
+                (
+                    header.name.as_str(),
+                    tlds.iter().map(|tld| tld.name().as_str()).collect(),
+                )
+            })
+            .collect::<Vec<(&str, Vec<&str>)>>();
+        assert_eq!(
+            names,
+            vec![
+                ("Templates", vec!["Container-Of-Boolean", "Tagged"]),
+                ("Messages", vec!["Message"])
+            ]
+        );
+        let ToplevelDeclaration::Type(tagged) = &modules[0].1[1] else {
+            panic!("Tagged should be a type declaration");
+        };
+        assert_eq!(
+            tagged.tag.as_ref().map(|tag| &tag.environment),
+            Some(&TaggingEnvironment::Implicit)
+        );
+    }
+
+    #[test]
+    fn formats_rerun_directives() {
+        let sources = vec![
+            AsnSource::Path(PathBuf::from("specs/a.asn")),
+            AsnSource::Literal("Test-Bool ::= BOOLEAN".into()),
+            AsnSource::Directory(PathBuf::from("specs/nested")),
+            AsnSource::Stdin,
+        ];
+        assert_eq!(
+            rerun_directives(
+                &sources,
+                &AsnrOutput::File(PathBuf::from("src/generated.rs"))
+            ),
+            vec![
+                "cargo:rerun-if-changed=specs/a.asn",
+                "cargo:rerun-if-changed=specs/nested",
+                "cargo:rerun-if-changed=src",
+            ]
+        );
+        assert_eq!(
+            rerun_directives(
+                &sources[1..2],
+                &AsnrOutput::File(PathBuf::from("generated.rs"))
+            ),
+            Vec::<String>::new()
+        );
+        let asnr = Asnr::new()
+            .add_asn_by_path("specs/a.asn")
+            .set_output_path("src/generated.rs");
+        assert_eq!(
+            asnr.state.config.emit_rerun_directives,
+            std::env::var_os("OUT_DIR").is_some()
+        );
+        let asnr = asnr.emit_rerun_directives(false);
+        assert!(!asnr.state.config.emit_rerun_directives);
+    }
+
+    #[test]
+    fn adds_asn_from_stdin() {
+        let module = |name: &str, declaration: &str| {
+            format!("{name} DEFINITIONS AUTOMATIC TAGS ::= BEGIN\n{declaration}\nEND")
+        };
+        let asnr = Asnr::new()
+            .add_asn_from_stdin()
+            .add_asn_literal(module("Literal", "Test-Bool ::= BOOLEAN"))
+            .add_asn_from_stdin();
+        assert_eq!(
+            asnr.state.sources,
+            vec![
+                AsnSource::Stdin,
+                AsnSource::Literal(module("Literal", "Test-Bool ::= BOOLEAN"))
+            ]
+        );
+        let mut stdin = io::Cursor::new(module("Piped", "Test-Int ::= INTEGER (0..7)"));
+        let sources = expand_sources(&asnr.state.sources, &asnr.state.config, &mut stdin).unwrap();
+        assert_eq!(
+            sources[0],
+            AsnSource::Literal(module("Piped", "Test-Int ::= INTEGER (0..7)"))
+        );
+        let (generated, _) = internal_compile(&sources, &asnr.state.config, false).unwrap();
+        assert!(generated.contains("pub struct TestInt(pub u8);"));
+        assert!(generated.contains("pub struct TestBool(pub bool);"));
+    }
+
+    #[test]
+    fn reuses_cached_declarations() {
+        let cache_dir = std::env::temp_dir().join("asnr_reuses_cached_declarations");
+        let _ = fs::remove_dir_all(&cache_dir);
+        let spec = |name_size| {
+            format!(
+                r#"Cached DEFINITIONS AUTOMATIC TAGS ::= BEGIN
+                Id ::= INTEGER (0..255)
+                Name ::= IA5String (SIZE(1..{name_size}))
+                Person ::= SEQUENCE {{ id Id, name Name }}
+                END"#
+            )
+        };
+        let compile = |spec: String, framework: crate::Framework| {
+            crate::GENERATOR_CALLS.with(|calls| calls.set(0));
+            let (rust, _) = Asnr::new()
+                .add_asn_literal(spec)
+                .framework(framework)
+                .cache_dir(Some(cache_dir.clone()))
+                .compile_to_string()
+                .unwrap();
+            (rust, crate::GENERATOR_CALLS.with(|calls| calls.get()))
+        };
+        let (first, calls) = compile(spec(32), crate::Framework::Asnr);
+        assert_eq!(calls, 3);
+        assert!(cache_dir.join(crate::cache::CACHE_FILE_NAME).exists());
+        let (second, calls) = compile(spec(32), crate::Framework::Asnr);
+        assert_eq!(calls, 0);
+        assert_eq!(first, second);
+        let (changed, calls) = compile(spec(64), crate::Framework::Asnr);
+        assert_eq!(calls, 1);
+        assert!(changed.contains("64"));
+        assert_eq!(compile(spec(64), crate::Framework::Rasn).1, 3);
+        fs::remove_dir_all(&cache_dir).unwrap();
+    }
+
+    #[test]
+    fn writes_one_file_per_module_to_output_dir() {
+        let output_dir = std::env::temp_dir().join("asnr_writes_one_file_per_module");
+        let _ = fs::remove_dir_all(&output_dir);
+        let (written, _) = Asnr::new()
+            .add_asn_literal(
+                r#"First-Module DEFINITIONS AUTOMATIC TAGS ::= BEGIN
+                Flag ::= BOOLEAN
+                END"#,
+            )
+            .add_asn_literal(
+                r#"Second-Module DEFINITIONS AUTOMATIC TAGS ::= BEGIN
+                Container ::= SEQUENCE { flag Flag }
+                END"#,
+            )
+            .set_output_dir(&output_dir)
+            .compile_to_files()
+            .unwrap();
+        assert_eq!(
+            written,
+            vec![
+                output_dir.join("common.rs"),
+                output_dir.join("first_module.rs"),
+                output_dir.join("mod.rs"),
+                output_dir.join("second_module.rs"),
+            ]
+        );
+        let mod_file = read_to_string(output_dir.join("mod.rs")).unwrap();
+        assert!(mod_file.contains("mod first_module;"));
+        assert!(mod_file.contains("pub use second_module::*;"));
+        let common = read_to_string(output_dir.join("common.rs")).unwrap();
+        assert!(common.contains("pub struct Asn1Open"));
+        let second = read_to_string(output_dir.join("second_module.rs")).unwrap();
+        assert!(second.contains("use super::*;"));
+        assert!(second.contains("pub struct Container"));
+        assert!(!second.contains("pub struct Flag"));
+        fs::remove_dir_all(&output_dir).unwrap();
+    }
+
+    #[test]
+    fn renames_module_files_colliding_with_generated_files() {
+        let output_dir = std::env::temp_dir().join("asnr_renames_colliding_module_files");
+        let _ = fs::remove_dir_all(&output_dir);
+        let (written, _) = Asnr::new()
+            .add_asn_literal(
+                r#"Common DEFINITIONS AUTOMATIC TAGS ::= BEGIN
+                Flag ::= BOOLEAN
+                END"#,
+            )
+            .add_asn_literal(
+                r#"MOD DEFINITIONS AUTOMATIC TAGS ::= BEGIN
+                Container ::= SEQUENCE { flag Flag }
+                END"#,
+            )
+            .set_output_dir(&output_dir)
+            .compile_to_files()
+            .unwrap();
+        assert_eq!(
+            written,
+            vec![
+                output_dir.join("common.rs"),
+                output_dir.join("common_module.rs"),
+                output_dir.join("mod.rs"),
+                output_dir.join("mod_module.rs"),
+            ]
+        );
+        let mod_file = read_to_string(output_dir.join("mod.rs")).unwrap();
+        assert!(mod_file.contains("mod common_module;"));
+        assert!(mod_file.contains("pub use mod_module::*;"));
+        let common = read_to_string(output_dir.join("common.rs")).unwrap();
+        assert!(common.contains("pub struct Asn1Open"));
+        assert!(!common.contains("pub struct Flag"));
+        let renamed = read_to_string(output_dir.join("common_module.rs")).unwrap();
+        assert!(renamed.contains("pub struct Flag"));
+        fs::remove_dir_all(&output_dir).unwrap();
+    }
+
+    #[cfg(feature = "prettyplease")]
+    #[test]
+    fn formats_bindings_in_process() {
+        let bindings = String::from(
+            "// This file has been auto-generated by ASNR\n#![allow(dead_code)]\npub struct   Flag(pub bool);impl Flag{ pub fn is_set(&self)->bool{self.0} }",
+        );
+        assert_eq!(
+            crate::format_bindings(&bindings, false),
+            r#"// This file has been auto-generated by ASNR
+#![allow(dead_code)]
+pub struct Flag(pub bool);
+impl Flag {
+    pub fn is_set(&self) -> bool {
+        self.0
+    }
+}
+"#
+        );
+        let invalid = String::from("pub struct {");
+        assert_eq!(crate::format_bindings(&invalid, false), invalid);
+    }
+
+    #[test]
+    fn compiles_to_tokens() {
+        let (tokens, _) = Asnr::new()
+            .add_asn_literal(
+                r#"Tokens DEFINITIONS AUTOMATIC TAGS ::= BEGIN
+                Flag ::= BOOLEAN
+                END"#,
+            )
+            .compile_to_tokens()
+            .unwrap();
+        assert!(tokens.to_string().contains("pub struct Flag"));
+        assert!(Asnr::new()
+            .add_asn_literal("Tokens DEFINITIONS ::= BEGIN Flag ::= END")
+            .compile_to_tokens()
+            .is_err());
+        assert!(Asnr::new()
+            .add_asn_literal("Tokens DEFINITIONS ::= BEGIN Flag ::= END")
+            .compile_to_tokens_with_diagnostics()
+            .unwrap_err()
+            .is::<crate::ParserError>());
+    }
+
+    #[test]
+    fn chooses_integer_width_from_constraints() {
+        let spec = r#"Widths DEFINITIONS AUTOMATIC TAGS ::= BEGIN
+            Small ::= INTEGER (0..255)
+            Signed ::= INTEGER (-40..40)
+            Extensible ::= INTEGER (0..255,...)
+            Unconstrained ::= INTEGER
+            Item ::= SEQUENCE { count INTEGER (0..1000) }
+            END"#;
+        let (asnr, _) = Asnr::new()
+            .add_asn_literal(spec)
+            .compile_to_string()
+            .unwrap();
+        assert!(asnr.contains("pub struct Small(pub u8);"));
+        assert!(asnr.contains("pub struct Signed(pub i8);"));
+        assert!(asnr.contains("pub struct Extensible(pub i128);"));
+        assert!(asnr.contains("pub struct Unconstrained(pub i128);"));
+        assert!(asnr.contains("pub struct InnerItemCount(pub u16);"));
+        let (forced, _) = Asnr::new()
+            .add_asn_literal(spec)
+            .force_i128_integers(true)
+            .compile_to_string()
+            .unwrap();
+        assert!(forced.contains("pub struct Small(pub i128);"));
+        assert!(forced.contains("pub struct InnerItemCount(pub i128);"));
+        let (rasn, _) = Asnr::new()
+            .add_asn_literal(spec)
+            .framework(crate::Framework::Rasn)
+            .compile_to_string()
+            .unwrap();
+        assert!(rasn.contains("pub struct Small(pub u8);"));
+        assert!(rasn.contains("pub struct Unconstrained(pub Integer);"));
+        assert!(rasn.contains("count: u16"));
+    }
+
+    #[test]
+    fn generates_constants_for_distinguished_values() {
+        let spec = r#"Distinguished DEFINITIONS AUTOMATIC TAGS ::= BEGIN
+            AccelerationMagnitudeValue ::= INTEGER {
+                positiveOutOfRange(160), unavailable(161), invalid(-1)
+            } (0..161)
+            END"#;
+        for framework in [crate::Framework::Asnr, crate::Framework::Rasn] {
+            let (rust, _) = Asnr::new()
+                .add_asn_literal(spec)
+                .framework(framework)
+                .compile_to_string()
+                .unwrap();
+            assert!(rust.contains("pub const POSITIVE_OUT_OF_RANGE: Self = Self(160);"));
+            assert!(rust.contains("pub const UNAVAILABLE: Self = Self(161);"));
+            assert!(!rust.contains("pub const INVALID"));
+        }
+    }
+
+    #[test]
+    fn boxes_recursive_members() {
+        let spec = r#"Recursion DEFINITIONS AUTOMATIC TAGS ::= BEGIN
+            Node ::= SEQUENCE { value INTEGER (0..255), next Node OPTIONAL }
+            Expression ::= CHOICE { literal INTEGER (0..255), sum Sum }
+            Sum ::= SEQUENCE { left Expression, right Expression }
+            END"#;
+        let (asnr, warnings) = Asnr::new()
+            .add_asn_literal(spec)
+            .compile_to_string()
+            .unwrap();
+        assert!(warnings.is_empty());
+        assert!(asnr.contains("pub next: Option<Box<Node>>,"));
+        assert!(asnr.contains("Sum(Box<Sum>),"));
+        assert!(asnr.contains("pub left: Expression,"));
+        assert!(asnr.contains(".map(|(i, v)| (i, Some(Box::new(v))))"));
+        assert!(asnr.contains("Sum::encode::<E>(*inner.clone(), output)"));
+        let (rasn, _) = Asnr::new()
+            .add_asn_literal(spec)
+            .framework(crate::Framework::Rasn)
+            .compile_to_string()
+            .unwrap();
+        assert!(rasn.contains("next: Option<Box<Node>>"));
+        assert!(rasn.contains("Sum(Box<Sum>)"));
+    }
+
+    #[test]
+    fn applies_naming_config() {
+        let spec = r#"Naming DEFINITIONS AUTOMATIC TAGS ::= BEGIN
+            ExampleSequence ::= SEQUENCE {
+                member-1 SEQUENCE { item-code INTEGER (0..15) },
+                members SEQUENCE OF SEQUENCE { flag BOOLEAN }
+            }
+            END"#;
+        let (generated, _) = Asnr::new()
+            .add_asn_literal(spec)
+            .naming(crate::NamingConfig {
+                inner_prefix: Some("Nested".into()),
+                anonymous_prefix: "Item".into(),
+                ..Default::default()
+            })
+            .compile_to_string()
+            .unwrap();
+        assert!(generated.contains("pub member_1: NestedExampleSequenceMember1,"));
+        assert!(generated.contains("pub struct NestedNestedExampleSequenceMember1Itemcode("));
+        assert!(generated.contains("pub struct ItemNestedExampleSequenceMembers {"));
+        assert!(!generated.contains("InnerExampleSequence"));
+        let (truncated, _) = Asnr::new()
+            .add_asn_literal(spec)
+            .naming(crate::NamingConfig {
+                max_length: Some(30),
+                ..Default::default()
+            })
+            .compile_to_string()
+            .unwrap();
+        assert!(truncated.contains("pub member_1: InnerExampleSequenceMember1,"));
+        assert!(!truncated.contains("InnerInnerExampleSequenceMember1Itemcode"));
+        let truncated_name = truncated
+            .split(|c: char| !c.is_alphanumeric())
+            .find(|word| word.starts_with("InnerInnerExampleSeque"))
+            .unwrap();
+        assert_eq!(truncated_name.len(), 30);
+    }
+
+    #[test]
+    fn flattens_primitive_members() {
+        let spec = r#"Flattening DEFINITIONS AUTOMATIC TAGS ::= BEGIN
+            ExampleSequence ::= SEQUENCE {
+                member-1 IA5String (SIZE (1..24)),
+                member-2 INTEGER (0..15) DEFAULT 3,
+                ...,
+                extension BOOLEAN OPTIONAL
+            }
+            END"#;
+        let (nested, _) = Asnr::new()
+            .add_asn_literal(spec)
+            .compile_to_string()
+            .unwrap();
+        assert!(nested.contains("pub member_1: InnerExampleSequenceMember1,"));
+        let (flattened, _) = Asnr::new()
+            .add_asn_literal(spec)
+            .flatten_primitive_members(true)
+            .compile_to_string()
+            .unwrap();
+        assert!(!flattened.contains("InnerExampleSequence"));
+        assert!(flattened.contains("pub member_1: String,"));
+        assert!(flattened.contains("pub member_2: Option<u8>,"));
+        assert!(flattened.contains("pub extension: Option<bool>,"));
+        assert!(flattened.contains("D::decode_character_string(CharacterString {"));
+        assert!(flattened.contains("E::encode_character_string(CharacterString {"));
+        assert!(flattened.contains("pub fn default_member_2() -> u8"));
+    }
+
+    #[test]
+    fn generates_sequence_constructors() {
+        let spec = r#"Constructors DEFINITIONS AUTOMATIC TAGS ::= BEGIN
+            ExampleSequence ::= SEQUENCE {
+                member-1 IA5String (SIZE (1..24)),
+                member-2 INTEGER (0..15),
+                member-3 BOOLEAN OPTIONAL,
+                member-4 INTEGER (0..15) DEFAULT 3,
+                ...,
+                extension BOOLEAN
+            }
+            END"#;
+        let (rust, _) = Asnr::new()
+            .add_asn_literal(spec)
+            .compile_to_string()
+            .unwrap();
+        assert!(rust.contains("pub fn new(member_1: impl Into<String>, member_2: u8) -> Self"));
+        assert!(rust.contains("member_1: InnerExampleSequenceMember1(member_1.into()),"));
+        assert!(rust.contains("member_2: InnerExampleSequenceMember2(member_2),"));
+        assert!(rust.contains("member_3: None,"));
+        assert!(rust.contains("member_4: Some(ExampleSequence::default_member_4()),"));
+        assert!(rust.contains("extension: None,"));
+        let (flattened, _) = Asnr::new()
+            .add_asn_literal(spec)
+            .flatten_primitive_members(true)
+            .compile_to_string()
+            .unwrap();
+        assert!(flattened.contains("member_1: member_1.into(),"));
+        assert!(flattened.contains("member_2,"));
+    }
+
+    #[test]
+    fn generates_sequence_builders() {
+        let spec = r#"Builders DEFINITIONS AUTOMATIC TAGS ::= BEGIN
+            Inner ::= BOOLEAN
+            Large ::= SEQUENCE {
+                a INTEGER (0..255),
+                b IA5String,
+                c BOOLEAN OPTIONAL,
+                d INTEGER (0..10) DEFAULT 3,
+                e OCTET STRING,
+                f Inner
+            }
+            END"#;
+        let (rust, _) = Asnr::new()
+            .add_asn_literal(spec)
+            .compile_to_string()
+            .unwrap();
+        assert!(!rust.contains("pub fn new("));
+        assert!(rust.contains("pub fn builder() -> LargeBuilder"));
+        assert!(rust.contains("pub struct LargeBuilder"));
+        assert!(rust.contains("pub fn b(mut self, b: impl Into<String>) -> Self"));
+        assert!(rust.contains("pub fn f(mut self, f: Inner) -> Self"));
+        assert!(rust.contains("pub fn build(self) -> Option<Large>"));
+        assert!(rust.contains("a: self.a?,"));
+        assert!(rust.contains("c: self.c,"));
+        assert!(rust.contains("d: self.d.or_else(|| Some(Large::default_d())),"));
+    }
+
+    #[test]
+    fn skips_unparsable_declarations() {
+        let spec = r#"Recovery DEFINITIONS AUTOMATIC TAGS ::= BEGIN
+            Before ::= BOOLEAN
+            Unsupported ::= SEQUENCE { member }
+            After ::= INTEGER (0..7)
+            END"#;
+        assert!(Asnr::new().add_asn_literal(spec).compile_to_string().is_err());
+        let (rust, warnings) = Asnr::new()
+            .add_asn_literal(spec)
+            .skip_unparsable_declarations(true)
+            .compile_to_string()
+            .unwrap();
+        assert!(rust.contains("pub struct Before"));
+        assert!(rust.contains("pub struct After"));
+        assert!(!rust.contains("Unsupported"));
+        assert_eq!(warnings.len(), 1);
+        let warning = warnings[0].downcast_ref::<crate::ParserError>().unwrap();
+        assert!(matches!(warning.kind, crate::ParserErrorType::UnparsedDeclaration));
+    }
+
+    #[test]
+    fn generates_named_bit_accessors() {
+        let spec = r#"NamedBits DEFINITIONS AUTOMATIC TAGS ::= BEGIN
+            Permissions ::= BIT STRING { read(0), write(1), execute(4) } (SIZE(8))
+            END"#;
+        for framework in [crate::Framework::Asnr, crate::Framework::Rasn] {
+            let (rust, _) = Asnr::new()
+                .add_asn_literal(spec)
+                .framework(framework)
+                .compile_to_string()
+                .unwrap();
+            assert!(rust.contains("pub const READ: usize = 0;"));
+            assert!(rust.contains("pub const EXECUTE: usize = 4;"));
+            assert!(rust.contains("pub fn write(&self) -> bool"));
+            assert!(rust.contains("pub fn set_write(&mut self, value: bool)"));
+            assert!(rust.contains("let len = self.0.len().max(Self::EXECUTE + 1);"));
+        }
+    }
+
+    #[test]
+    fn marks_extensible_enums_as_non_exhaustive() {
+        let spec = r#"Extensible DEFINITIONS AUTOMATIC TAGS ::= BEGIN
+            Level ::= ENUMERATED { low, high, ... }
+            Pick ::= CHOICE { flag BOOLEAN, ..., nothing NULL }
+            Fixed ::= ENUMERATED { on, off }
+            END"#;
+        for framework in [crate::Framework::Asnr, crate::Framework::Rasn] {
+            let (rust, _) = Asnr::new()
+                .add_asn_literal(spec)
+                .framework(framework)
+                .compile_to_string()
+                .unwrap();
+            assert_eq!(rust.matches("#[non_exhaustive]").count(), 2);
+            assert!(rust.contains("#[non_exhaustive]\npub enum Level {"));
+            assert!(rust.contains("#[non_exhaustive]\npub enum Pick {"));
+        }
+        let (rust, _) = Asnr::new().add_asn_literal(spec).compile_to_string().unwrap();
+        assert!(rust.contains("UnknownChoiceValue(Vec<u8>)"));
+    }
+
+    #[test]
+    fn omits_non_exhaustive_on_request() {
+        let spec = r#"Extensible DEFINITIONS AUTOMATIC TAGS ::= BEGIN
+            Level ::= ENUMERATED { low, high, ... }
+            Pick ::= CHOICE { flag BOOLEAN, ..., nothing NULL }
+            END"#;
+        let (rust, _) = Asnr::new()
+            .add_asn_literal(spec)
+            .mark_extensible_as_non_exhaustive(false)
+            .compile_to_string()
+            .unwrap();
+        assert!(!rust.contains("#[non_exhaustive]"));
+        assert!(rust.contains("UnknownExtension"));
+        assert!(rust.contains("UnknownChoiceValue(Vec<u8>)"));
+        let (rust, _) = Asnr::new()
+            .add_asn_literal(spec)
+            .framework(crate::Framework::Rasn)
+            .mark_extensible_as_non_exhaustive(false)
+            .compile_to_string()
+            .unwrap();
+        assert_eq!(rust.matches("#[non_exhaustive]").count(), 2);
+    }
+
+    #[test]
+    fn requires_wildcard_arms_for_non_exhaustive_enums() {
+        // `#[non_exhaustive]` only takes effect outside of the defining crate,
+        // so the generated enum and the match on it are compiled as separate crates
+        let dir = std::env::temp_dir().join("asnr_requires_wildcard_arms");
+        let _ = std::fs::remove_dir_all(&dir);
+        std::fs::create_dir_all(&dir).unwrap();
+        let rustc = std::env::var("RUSTC").unwrap_or_else(|_| String::from("rustc"));
+        let compile = |name: &str, source: &str| {
+            let path = dir.join(format!("{name}.rs"));
+            std::fs::write(&path, source).unwrap();
+            std::process::Command::new(&rustc)
+                .args(["--edition", "2021", "--crate-type", "lib"])
+                .args(["--crate-name", name])
+                .arg("--out-dir")
+                .arg(&dir)
+                .arg("-L")
+                .arg(&dir)
+                .args(["--extern", "generated"])
+                .arg(path)
+                .output()
+                .unwrap()
+        };
+        let exhaustive_match = "pub fn describe(level: generated::Level) -> u8 {
+            match level {
+                generated::Level::Low => 0,
+                generated::Level::High => 1,
+                generated::Level::UnknownExtension => 2,
+            }
+        }";
+        let wildcard_match = exhaustive_match.replace("=> 2,", "=> 2,\n_ => 3,");
+        for non_exhaustive in [true, false] {
+            let (rust, _) = Asnr::new()
+                .add_asn_literal(
+                    r#"Extensible DEFINITIONS AUTOMATIC TAGS ::= BEGIN
+                    Level ::= ENUMERATED { low, high, ... }
+                    END"#,
+                )
+                .mark_extensible_as_non_exhaustive(non_exhaustive)
+                .compile_to_string()
+                .unwrap();
+            let start = rust[..rust.find("pub enum Level {").unwrap()]
+                .rfind("#[derive")
+                .unwrap();
+            let end = start + rust[start..].find("\n}\n").unwrap() + 3;
+            assert!(compile("generated", &rust[start..end]).status.success());
+            let output = compile("exhaustive", exhaustive_match);
+            assert_eq!(output.status.success(), !non_exhaustive);
+            if non_exhaustive {
+                assert!(String::from_utf8_lossy(&output.stderr).contains("E0004"));
+            }
+            assert!(compile("wildcard", &wildcard_match).status.success());
+        }
+        std::fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[test]
+    fn applies_configured_derives() {
+        let spec = r#"Derives DEFINITIONS AUTOMATIC TAGS ::= BEGIN
+            Color ::= ENUMERATED { red, green }
+            Shape ::= SEQUENCE { color Color, size INTEGER (0..15) OPTIONAL }
+            END"#;
+        let (rust, _) = Asnr::new()
+            .add_asn_literal(spec)
+            .derives(&[Derive::Debug, Derive::Clone, Derive::PartialEq, Derive::Eq, Derive::Hash])
+            .custom_derives(&["Serialize"])
+            .compile_to_string()
+            .unwrap();
+        assert!(rust.contains("#[derive(Default, Debug, Clone, PartialEq, Eq, Hash, Serialize)]"));
+        assert!(!rust.contains("#[derive(Debug, Clone, PartialEq, Default"));
+        let (rust, _) = Asnr::new()
+            .add_asn_literal(spec)
+            .framework(crate::Framework::Rasn)
+            .derives(&[Derive::Clone])
+            .compile_to_string()
+            .unwrap();
+        assert!(rust.contains("#[derive(AsnType, Decode, Encode, Clone)]"));
+        assert!(rust.contains("#[derive(AsnType, Copy, Decode, Encode, Clone)]"));
+        assert!(!rust.contains("Debug"));
+    }
+
+    #[test]
+    fn rejects_incompatible_derives() {
+        let spec = r#"Derives DEFINITIONS AUTOMATIC TAGS ::= BEGIN
+            Measurement ::= SEQUENCE { value REAL }
+            END"#;
+        for derives in [
+            &[Derive::Clone][..],
+            &[Derive::Debug, Derive::PartialEq, Derive::Eq][..],
+            &[Derive::Debug, Derive::Clone, Derive::Eq][..],
+            &[Derive::Debug, Derive::Clone, Derive::PartialEq, Derive::Eq][..],
+        ] {
+            let error = Asnr::new()
+                .add_asn_literal(spec)
+                .derives(derives)
+                .compile_to_string()
+                .unwrap_err();
+            assert!(matches!(
+                error.downcast_ref::<GeneratorError>().unwrap().kind,
+                GeneratorErrorType::IncompatibleDerives
+            ));
+        }
+        assert!(Asnr::new()
+            .add_asn_literal(spec)
+            .framework(crate::Framework::Rasn)
+            .derives(&[Derive::Clone, Derive::PartialEq])
+            .compile_to_string()
+            .is_ok());
+    }
+
+    #[test]
+    fn generates_object_identifier_values() {
+        let spec = r#"ObjectIdentifiers DEFINITIONS AUTOMATIC TAGS ::= BEGIN
+            id-ecPublicKey OBJECT IDENTIFIER ::= { 1 2 840 10045 2 1 }
+            id-named OBJECT IDENTIFIER ::= { iso 3 }
+            id-nested OBJECT IDENTIFIER ::= { iso member-body 840 }
+            id-unnumbered OBJECT IDENTIFIER ::= { iso member-body(2) us }
+            END"#;
+        let (rust, warnings) = Asnr::new()
+            .add_asn_literal(spec)
+            .compile_to_string()
+            .unwrap();
+        assert!(rust.contains("pub const ID_EC_PUBLIC_KEY: &[u32] = &[1, 2, 840, 10045, 2, 1];"));
+        assert!(rust.contains("pub const ID_NAMED: &[u32] = &[1, 3];"));
+        assert!(rust.contains("pub const ID_NESTED: &[u32] = &[1, 2, 840];"));
+        assert!(!rust.contains("ID_UNNUMBERED"));
+        assert_eq!(warnings.len(), 2);
+        assert!(matches!(
+            warnings[0]
+                .downcast_ref::<crate::validator::error::ValidatorError>()
+                .unwrap()
+                .kind,
+            crate::validator::error::ValidatorErrorType::UnknownObjectIdentifierArc
+        ));
+        assert!(matches!(
+            warnings[1].downcast_ref::<GeneratorError>().unwrap().kind,
+            GeneratorErrorType::UnnumberedObjectIdentifierArc
+        ));
+    }
+
+    #[test]
+    fn reports_unresolved_imports() {
+        let base = r#"Base { iso 3 1 } DEFINITIONS AUTOMATIC TAGS ::= BEGIN
+            Speed ::= INTEGER (0..100)
+            END"#;
+        let user = r#"User DEFINITIONS AUTOMATIC TAGS ::= BEGIN
+            IMPORTS Speed, Heading FROM Base { iso 3 1 }
+                Position FROM Geo { iso 3 2 };
+            Report ::= SEQUENCE { speed Speed }
+            END"#;
+        let (_, warnings) = Asnr::new()
+            .add_asn_literal(base)
+            .add_asn_literal(user)
+            .compile_to_string()
+            .unwrap();
+        let unresolved = warnings
+            .iter()
+            .filter_map(|w| w.downcast_ref::<crate::validator::error::ValidatorError>())
+            .filter(|e| {
+                matches!(
+                    e.kind,
+                    crate::validator::error::ValidatorErrorType::UnresolvedImport
+                )
+            })
+            .map(|e| e.details.clone())
+            .collect::<Vec<_>>();
+        assert_eq!(
+            unresolved,
+            vec![
+                "Module User imports Heading from module Base, which does not declare it",
+                "Module User imports from module Geo that is not compiled"
+            ]
+        );
+    }
+
+    #[test]
+    fn does_not_resolve_oid_arc_names_out_of_position() {
+        let spec = r#"ObjectIdentifiers DEFINITIONS AUTOMATIC TAGS ::= BEGIN
+            id-child-as-root OBJECT IDENTIFIER ::= { member-body 840 }
+            id-root-as-child OBJECT IDENTIFIER ::= { iso itu-t 5 }
+            END"#;
+        let (rust, warnings) = Asnr::new()
+            .add_asn_literal(spec)
+            .compile_to_string()
+            .unwrap();
+        assert!(!rust.contains("ID_CHILD_AS_ROOT"));
+        assert!(!rust.contains("ID_ROOT_AS_CHILD"));
+        assert_eq!(
+            warnings
+                .iter()
+                .filter(|w| matches!(
+                    w.downcast_ref::<crate::validator::error::ValidatorError>()
+                        .map(|e| &e.kind),
+                    Some(crate::validator::error::ValidatorErrorType::UnknownObjectIdentifierArc)
+                ))
+                .count(),
+            2
+        );
+    }
+
+    #[test]
+    fn generates_enumerated_string_conversions() {
+        let spec = r#"EnumeratedStrings DEFINITIONS AUTOMATIC TAGS ::= BEGIN
+            Level ::= ENUMERATED { positiveOutOfRange(160), UPPER, ..., extra }
+            END"#;
+        let (rust, _) = Asnr::new()
+            .add_asn_literal(spec)
+            .compile_to_string()
+            .unwrap();
+        assert!(rust.contains("impl core::fmt::Display for Level"));
+        assert!(rust.contains(r#"Self::PositiveOutOfRange => write!(f, "positiveOutOfRange"),"#));
+        assert!(rust.contains(r#"Self::UnknownExtension => write!(f, "UnknownExtension"),"#));
+        assert!(rust.contains("impl core::str::FromStr for Level"));
+        assert!(rust.contains(
+            r#""positiveOutOfRange" | "PositiveOutOfRange" => Ok(Self::PositiveOutOfRange),"#
+        ));
+        assert!(rust.contains(r#""UPPER" => Ok(Self::UPPER),"#));
+        assert!(rust.contains(r#""extra" | "Extra" => Ok(Self::Extra),"#));
+    }
+
+    #[test]
+    fn converts_enumerated_from_asn1_names() {
+        let spec = r#"EnumeratedNames DEFINITIONS AUTOMATIC TAGS ::= BEGIN
+            Level ::= ENUMERATED { positiveOutOfRange(160), UPPER, ..., extra }
+            END"#;
+        let compile = |framework: crate::Framework, derives: &[&str]| {
+            Asnr::new()
+                .add_asn_literal(spec)
+                .framework(framework)
+                .custom_derives(derives)
+                .compile_to_string()
+                .unwrap()
+                .0
+        };
+        let asnr = compile(crate::Framework::Asnr, &["serde::Serialize"]);
+        assert!(asnr.contains("impl TryFrom<&str> for Level"));
+        assert!(asnr.contains(
+            "#[serde(rename = \"positiveOutOfRange\")]\n    PositiveOutOfRange = 160,"
+        ));
+        assert!(asnr.contains("#[serde(rename = \"extra\")]\n    Extra = 2,"));
+        assert_eq!(asnr.matches("#[serde(rename").count(), 2);
+
+        let rasn = compile(crate::Framework::Rasn, &["Deserialize"]);
+        assert!(rasn.contains("impl TryFrom<&str> for Level"));
+        assert!(rasn.contains(r#""positiveOutOfRange" => Ok(Self::PositiveOutOfRange),"#));
+        assert!(rasn.contains(r#""UPPER" => Ok(Self::UPPER),"#));
+        assert!(!rasn.contains(r#""PositiveOutOfRange" =>"#));
+        assert_eq!(rasn.matches("#[serde(rename").count(), 2);
+
+        assert!(!compile(crate::Framework::Rasn, &[]).contains("serde"));
+        assert!(!compile(crate::Framework::Asnr, &["Hash"]).contains("serde"));
+    }
+
+    #[test]
+    fn generates_application_and_private_tags() {
+        let spec = r#"Tags DEFINITIONS AUTOMATIC TAGS ::= BEGIN
+            AppInt ::= [APPLICATION 5] IMPLICIT INTEGER
+            PrivSeq ::= [PRIVATE 3] EXPLICIT SEQUENCE { flag [APPLICATION 1] BOOLEAN }
+            END"#;
+        let (rust, _) = Asnr::new()
+            .add_asn_literal(spec)
+            .framework(crate::Framework::Rasn)
+            .compile_to_string()
+            .unwrap();
+        assert!(rust.contains("#[rasn(delegate, tag(application, 5))]\npub struct AppInt"));
+        assert!(rust.contains("#[rasn(tag(explicit(private, 3)))]\npub struct PrivSeq"));
+        assert!(rust.contains("#[rasn(tag(application, 1))]"));
+
+        let (rust, _) = Asnr::new()
+            .add_asn_literal(spec)
+            .compile_to_string()
+            .unwrap();
+        assert!(rust.contains(
+            r#"impl AppInt {
+    pub const TAG: AsnTag = AsnTag {
+        tag_class: TagClass::Application,
+        id: 5,
+        environment: TaggingEnvironment::Implicit,
+    };
+}"#
+        ));
+        assert!(rust.contains(
+            r#"impl PrivSeq {
+    pub const TAG: AsnTag = AsnTag {
+        tag_class: TagClass::Private,
+        id: 3,
+        environment: TaggingEnvironment::Explicit,
+    };
+}"#
+        ));
+    }
+
+    #[test]
+    fn generates_rasn_choice_with_mixed_tags() {
+        let spec = r#"Tags DEFINITIONS IMPLICIT TAGS ::= BEGIN
+            Inner ::= CHOICE { number INTEGER, flag BOOLEAN }
+            TaggedInner ::= [APPLICATION 2] Inner
+            Mixed ::= CHOICE {
+                plain [0] INTEGER,
+                wrapped [1] EXPLICIT BOOLEAN,
+                app [APPLICATION 3] IA5String,
+                reference [2] Inner,
+                inline [3] CHOICE { a INTEGER },
+                nested [4] SEQUENCE { inner [0] BOOLEAN }
+            }
+            END"#;
+        let (rust, warnings) = Asnr::new()
+            .add_asn_literal(spec)
+            .framework(crate::Framework::Rasn)
+            .compile_to_string()
+            .unwrap();
+        assert!(warnings.is_empty());
+        assert!(syn::parse_file(&rust).is_ok());
+        assert!(rust.contains(
+            "#[rasn(delegate, tag(explicit(application, 2)))]\npub struct TaggedInner(pub Inner);"
+        ));
+        assert!(rust.contains(
+            r#"pub enum Mixed {
+    #[rasn(tag(context, 0))]
+    Plain(Integer),
+    #[rasn(tag(explicit(context, 1)))]
+    Wrapped(bool),
+    #[rasn(tag(application, 3))]
+    App(Ia5String),
+    #[rasn(tag(explicit(context, 2)))]
+    Reference(Inner),
+    #[rasn(tag(explicit(context, 3)))]
+    Inline(MixedInline),
+    #[rasn(tag(context, 4))]
+    Nested(MixedNested),
+}"#
+        ));
+        assert!(rust.contains("    #[rasn(tag(context, 0))]\n    inner: bool,"));
+    }
+
+    #[test]
+    fn generates_object_identifier_types() {
+        let (rust, _) = Asnr::new()
+            .add_asn_literal(
+                r#"Oids DEFINITIONS AUTOMATIC TAGS ::= BEGIN
+            AlgorithmId ::= OBJECT IDENTIFIER
+            rsa AlgorithmId ::= { 1 37 }
+            END"#,
+            )
+            .compile_to_string()
+            .unwrap();
+        assert!(rust.contains("pub struct AlgorithmId(pub Vec<u32>);"));
+        assert!(rust.contains("D::decode_object_identifier(input)"));
+        assert!(rust.contains("E::encode_object_identifier("));
+        assert!(rust.contains("pub const RSA: &[u32] = &[1, 37];"));
+    }
+
+    #[test]
+    fn generates_real_types() {
+        let (rust, _) = Asnr::new()
+            .add_asn_literal(
+                r#"Reals DEFINITIONS AUTOMATIC TAGS ::= BEGIN
+            Temperature ::= REAL
+            Measurement ::= SEQUENCE { value REAL }
+            pi REAL ::= 3.14159
+            boiling Temperature ::= 100.0
+            Ratio-Value ::= REAL
+            half Ratio-Value ::= 0.5
+            END"#,
+            )
+            .compile_to_string()
+            .unwrap();
+        assert!(rust.contains("pub struct Temperature(pub f64);"));
+        assert!(rust.contains("pub struct InnerMeasurementValue(pub f64);"));
+        assert!(rust.contains("D::decode_real(input)"));
+        assert!(rust.contains("E::encode_real(encodable.0, output)"));
+        assert!(rust.contains("pub const PI: f64 = 3.14159;"));
+        assert!(rust.contains("pub const BOILING: Temperature = Temperature(100.0);"));
+        assert!(rust.contains("pub const HALF: RatioValue = RatioValue(0.5);"));
+    }
+
+    #[test]
+    fn generates_real_values_in_decimal_notation() {
+        let (rust, _) = Asnr::new()
+            .add_asn_literal(
+                r#"Reals DEFINITIONS AUTOMATIC TAGS ::= BEGIN
+            Ratio ::= REAL (-1.0..100.0)
+            pi REAL ::= 3.14
+            offset REAL ::= -0.05
+            END"#,
+            )
+            .compile_to_string()
+            .unwrap();
+        assert!(rust.contains("pub const PI: f64 = 3.14;"));
+        assert!(rust.contains("pub const OFFSET: f64 = -0.05;"));
+        assert!(rust.contains("ASN1Value::Real(- 1.0_f64)"));
+        assert!(rust.contains("ASN1Value::Real(100.0_f64)"));
+    }
+
+    #[test]
+    fn names_extension_groups_by_position() {
+        let spec = r#"Groups DEFINITIONS AUTOMATIC TAGS ::= BEGIN
+            Versioned ::= SEQUENCE {
+                base INTEGER (0..7),
+                ...,
+                [[ alternate-code INTEGER (0..254), note IA5String OPTIONAL ]],
+                [[ 2: flag BOOLEAN ]]
+            }
+            END"#;
+        let (rust, warnings) = Asnr::new()
+            .add_asn_literal(spec)
+            .compile_to_string()
+            .unwrap();
+        assert!(warnings.is_empty());
+        assert!(rust.contains("pub ext_group_1: Option<InnerVersionedExtGroup1>,"));
+        assert!(rust.contains("pub ext_group_2: Option<InnerVersionedExtGroup2>,"));
+        assert!(rust.contains("self.ext_group_1.as_ref().map(|group| &group.alternate_code)"));
+        assert!(rust.contains("self.ext_group_1.as_ref().and_then(|group| group.note.as_ref())"));
+        assert!(rust.contains("pub fn flag(&self) -> Option<&InnerInnerVersionedExtGroup2Flag>"));
+
+        let (rust, _) = Asnr::new()
+            .add_asn_literal(spec)
+            .framework(crate::Framework::Rasn)
+            .compile_to_string()
+            .unwrap();
+        assert!(rust.contains("pub struct VersionedExtGroup1 {"));
+        assert!(rust.contains("ext_group_2: VersionedExtGroup2,"));
+    }
+
+    #[test]
+    fn generates_lint_free_bindings() {
+        let spec = r#"Lints DEFINITIONS AUTOMATIC TAGS ::= BEGIN
+            CAM ::= SEQUENCE { header ItsPduHeader, parameters CamParameters }
+            ItsPduHeader ::= SEQUENCE { messageID INTEGER { denm(1), cam(2) } (0..255), stationID StationID }
+            StationID ::= INTEGER (0..4294967295)
+            CamParameters ::= SEQUENCE {
+                lights ExteriorLights,
+                history SEQUENCE (SIZE(0..40)) OF PathPoint,
+                role VehicleRole DEFAULT publicTransport,
+                special CHOICE { dangerous NULL, label UTF8String (SIZE(1..10)) } OPTIONAL,
+                ...
+            }
+            VehicleRole ::= ENUMERATED { default, publicTransport, ... }
+            ExteriorLights ::= BIT STRING { lowBeamHeadlightsOn(0), highBeamHeadlightsOn(1) } (SIZE(8))
+            PathPoint ::= SEQUENCE { deltaTime INTEGER (1..65535) OPTIONAL, flag BOOLEAN DEFAULT TRUE }
+            MESSAGE-CLASS ::= CLASS { &id INTEGER UNIQUE, &Type } WITH SYNTAX { ID &id TYPE &Type }
+            Messages MESSAGE-CLASS ::= { { ID 1 TYPE PathPoint } | { ID 2 TYPE StationID }, ... }
+            Envelope ::= SEQUENCE { id MESSAGE-CLASS.&id ({Messages}), body MESSAGE-CLASS.&Type ({Messages}{@id}) }
+            maxStations INTEGER ::= 250
+            END"#;
+        for flatten in [false, true] {
+            let (rust, warnings) = Asnr::new()
+                .add_asn_literal(spec)
+                .flatten_primitive_members(flatten)
+                .materialize_defaults(flatten)
+                .suppress_lints(true)
+                .compile_to_string()
+                .unwrap();
+            assert!(warnings.is_empty());
+            let mut file = syn::parse_file(&rust).unwrap();
+            assert!(!rust.contains("#![allow(non_upper_case_globals"));
+            assert!(file
+                .items
+                .iter_mut()
+                .filter_map(crate::item_attributes)
+                .all(|attrs| attrs[0].path().is_ident("allow")));
+            for pattern in [
+                "x if x == 0 =>",
+                "return Ok(output);",
+                "*&parent",
+                ".len() <= Self::",
+                "details: format!(\"Index",
+                "details: format!(\"Key",
+                "let mut int_encoder",
+                "let mut sequence_encoder",
+            ] {
+                assert!(!rust.contains(pattern), "{pattern} in generated bindings");
+            }
+            // flattened booleans are `Copy`
+            assert_eq!(rust.contains("self.flag.clone()"), !flatten);
+        }
+        let (rust, _) = Asnr::new()
+            .add_asn_literal(spec)
+            .compile_to_string()
+            .unwrap();
+        assert!(!rust.contains("clippy::upper_case_acronyms"));
+    }
+
+    #[test]
+    fn generates_sequence_default_values() {
+        let spec = r#"Defaults DEFINITIONS AUTOMATIC TAGS ::= BEGIN
+            Settings ::= SEQUENCE { flag BOOLEAN, count INTEGER (0..255) }
+            Config ::= SEQUENCE {
+                settings Settings DEFAULT { flag TRUE, count 5 },
+                inline SEQUENCE { enabled BOOLEAN, level INTEGER (0..7) OPTIONAL } DEFAULT { enabled FALSE }
+            }
+            END"#;
+        let (rust, warnings) = Asnr::new()
+            .add_asn_literal(spec)
+            .compile_to_string()
+            .unwrap();
+        assert!(warnings.is_empty());
+        assert!(rust.contains("pub fn default_settings() -> Settings"));
+        assert!(rust.contains("flag: InnerSettingsFlag(true),"));
+        assert!(rust.contains("count: InnerSettingsCount(5),"));
+        assert!(rust.contains("enabled: InnerInnerConfigInlineEnabled(false),"));
+        assert!(rust.contains("level: None,"));
+
+        let (rust, warnings) = Asnr::new()
+            .add_asn_literal(spec)
+            .framework(crate::Framework::Rasn)
+            .compile_to_string()
+            .unwrap();
+        assert!(warnings.is_empty());
+        assert!(rust.contains("Settings { flag: true, count: 5 }"));
+        assert!(rust.contains("fn config_inline_default() -> ConfigInline"));
+        assert!(rust.contains("enabled: false,"));
+    }
+
+    #[test]
+    fn generates_conversions_for_inner_newtypes() {
+        let (rust, warnings) = Asnr::new()
+            .add_asn_literal(
+                r#"Conversions DEFINITIONS AUTOMATIC TAGS ::= BEGIN
+            Example ::= SEQUENCE {
+                name IA5String (SIZE (1..24)),
+                count INTEGER (0..15),
+                payload OCTET STRING
+            }
+            END"#,
+            )
+            .compile_to_string()
+            .unwrap();
+        assert!(warnings.is_empty());
+        assert!(rust.contains("impl From<u8> for InnerExampleCount"));
+        assert!(rust.contains("impl From<InnerExampleCount> for u8"));
+        assert!(rust.contains("impl core::ops::Deref for InnerExamplePayload"));
+        assert!(rust.contains("type Target = Vec<u8>;"));
+        assert!(rust.contains("impl From<&str> for InnerExampleName"));
+        assert!(rust.contains("impl AsRef<str> for InnerExampleName"));
+        assert!(!rust.contains("impl AsRef<str> for InnerExampleCount"));
+    }
+
+    #[test]
+    fn generates_string_values() {
+        let spec = r#"Values DEFINITIONS AUTOMATIC TAGS ::= BEGIN
+            Vendor-Name ::= UTF8String
+            vendor UTF8String ::= "ACME"
+            brand Vendor-Name ::= "Acme\Road"
+            END"#;
+        for (framework, conversion) in [
+            (crate::Framework::Asnr, "into()"),
+            (crate::Framework::Rasn, "try_into().unwrap()"),
+        ] {
+            let (rust, warnings) = Asnr::new()
+                .add_asn_literal(spec)
+                .framework(framework)
+                .compile_to_string()
+                .unwrap();
+            assert!(warnings.is_empty());
+            assert!(rust.contains(r#"pub const VENDOR: &str = "ACME";"#));
+            assert!(rust.contains("pub fn brand() -> VendorName {"));
+            assert!(rust.contains(&format!(r#"VendorName("Acme\\Road".{conversion})"#)));
+        }
+    }
+
+    #[test]
+    fn generates_bit_string_values() {
+        let (rust, warnings) = Asnr::new()
+            .add_asn_literal(
+                r#"Flags DEFINITIONS AUTOMATIC TAGS ::= BEGIN
+            Flags ::= BIT STRING { read(0), write(1), execute(2) }
+            defaultFlags BIT STRING ::= '101'B
+            noFlags Flags ::= ''B
+            END"#,
+            )
+            .compile_to_string()
+            .unwrap();
+        assert!(warnings.is_empty());
+        assert!(rust.contains("pub const DEFAULT_FLAGS: &[bool] = &[true, false, true];"));
+        assert!(rust.contains("pub const NO_FLAGS: &[bool] = &[];"));
+    }
+
+    #[test]
+    fn generates_referenced_and_enumerated_values() {
+        let (rust, warnings) = Asnr::new()
+            .add_asn_literal(
+                r#"References DEFINITIONS AUTOMATIC TAGS ::= BEGIN
+            Limit ::= INTEGER (0..1000)
+            upperLimit Limit ::= 500
+            max Limit ::= upperLimit
+            Mode ::= ENUMERATED { standby, active }
+            mode Mode ::= standby
+            fallback Mode ::= mode
+            dangling Limit ::= nowhere
+            END"#,
+            )
+            .compile_to_string()
+            .unwrap();
+        assert!(rust.contains("pub const max: Limit = Limit(500);"));
+        assert!(rust.contains("pub const MODE: Mode = Mode::Standby;"));
+        assert!(rust.contains("pub const FALLBACK: Mode = Mode::Standby;"));
+        assert!(!rust.contains("DANGLING"));
+        assert_eq!(warnings.len(), 1);
+        assert!(matches!(
+            warnings[0].downcast_ref::<GeneratorError>().unwrap().kind,
+            GeneratorErrorType::UnresolvedValueReference
+        ));
+    }
+
+    #[test]
+    fn applies_enumeral_naming_strategies() {
+        let spec = r#"EnumeralNaming DEFINITIONS AUTOMATIC TAGS ::= BEGIN
+            Algorithm ::= ENUMERATED { SHA256, sha-RSA }
+            Config ::= SEQUENCE { algorithm Algorithm DEFAULT sha-RSA }
+            END"#;
+        let compile = |framework, enumerals| {
+            Asnr::new()
+                .add_asn_literal(spec)
+                .framework(framework)
+                .naming(crate::NamingConfig {
+                    enumerals,
+                    ..Default::default()
+                })
+                .compile_to_string()
+                .unwrap()
+                .0
+        };
+        for (enumerals, expected) in [
+            (
+                NamingStrategy::TitleCase,
+                ["Sha256 = 0,", "ShaRsa = 1,", "Algorithm::ShaRsa"],
+            ),
+            (
+                NamingStrategy::PreserveCase,
+                ["SHA256 = 0,", "ShaRSA = 1,", "Algorithm::ShaRSA"],
+            ),
+        ] {
+            for framework in [crate::Framework::Asnr, crate::Framework::Rasn] {
+                let rust = compile(framework, enumerals);
+                for snippet in expected {
+                    assert!(rust.contains(snippet), "missing {snippet}");
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn generates_boolean_values() {
+        let spec = r#"BooleanValues DEFINITIONS AUTOMATIC TAGS ::= BEGIN
+            My-Flag ::= BOOLEAN
+            enabled BOOLEAN ::= TRUE
+            flag-disabled My-Flag ::= FALSE
+            END"#;
+        for framework in [crate::Framework::Asnr, crate::Framework::Rasn] {
+            let (rust, _) = Asnr::new()
+                .add_asn_literal(spec)
+                .framework(framework)
+                .compile_to_string()
+                .unwrap();
+            assert!(rust.contains("pub const ENABLED: bool = true;"));
+            assert!(rust.contains("pub const FLAG_DISABLED: MyFlag = MyFlag(false);"));
+        }
+    }
+
+    #[test]
+    fn generates_choice_values_of_referenced_types() {
+        let (rust, warnings) = Asnr::new()
+            .add_asn_literal(
+                r#"ChoiceValues DEFINITIONS AUTOMATIC TAGS ::= BEGIN
+            My-Int ::= INTEGER (0..255)
+            My-Choice ::= CHOICE { x My-Int, y INTEGER (0..7), z CHOICE { w My-Int } }
+            c My-Choice ::= x : 5
+            d My-Choice ::= y : 3
+            e My-Choice ::= z : w : 1
+            END"#,
+            )
+            .compile_to_string()
+            .unwrap();
+        assert!(warnings.is_empty());
+        assert!(rust.contains("pub const C: MyChoice = MyChoice::X(MyInt(5));"));
+        assert!(rust.contains("pub const D: MyChoice = MyChoice::Y(InnerMyChoiceY(3));"));
+        assert!(rust.contains("pub const E: MyChoice = MyChoice::Z(InnerMyChoiceZ::W(MyInt(1)));"));
+    }
+
+    #[test]
+    fn materializes_default_values() {
+        let spec = r#"Defaults DEFINITIONS AUTOMATIC TAGS ::= BEGIN
+            Color ::= ENUMERATED { red, green }
+            Settings ::= SEQUENCE {
+                level INTEGER (0..10) DEFAULT 3,
+                color Color DEFAULT green,
+                label IA5String DEFAULT "none",
+                id INTEGER (0..255)
+            }
+            END"#;
+        let compile = |materialize| {
+            Asnr::new()
+                .add_asn_literal(spec)
+                .materialize_defaults(materialize)
+                .compile_to_string()
+                .unwrap()
+                .0
+        };
+        let generated = compile(false);
+        assert!(generated.contains("pub fn default_level() -> InnerSettingsLevel"));
+        assert!(generated.contains("InnerSettingsLevel(3)"));
+        assert!(generated.contains("Color::Green"));
+        assert!(generated.contains(r#"InnerSettingsLabel("none".into())"#));
+        assert!(generated.contains("color: Some(Self::default_color()),"));
+        assert!(generated.contains("id: Default::default(),"));
+        assert!(generated.contains(
+            "pub fn level(&self) -> u8 {\n        self.level.clone().unwrap_or_else(Self::default_level).0"
+        ));
+        assert!(generated.contains(
+            "pub fn color(&self) -> Color {\n        self.color.clone().unwrap_or_else(Self::default_color)\n"
+        ));
+        assert!(generated.contains("pub fn label(&self) -> String {"));
+        assert!(!generated.contains("pub fn id(&self)"));
+        assert!(!generated.contains("impl Default for Settings"));
+        let materialized = compile(true);
+        assert!(materialized.contains("impl Default for Settings"));
+        assert!(materialized.contains("Self::new_with_defaults()"));
+    }
+
+    #[test]
+    fn generates_items_in_stable_order() {
+        let spec = r#"Ordering DEFINITIONS AUTOMATIC TAGS ::= BEGIN
+            a-value Zebra ::= 4
+            Zebra ::= INTEGER (0..7)
+            Container ::= SEQUENCE { zebra Zebra, apple Apple }
+            Apple ::= BOOLEAN
+            END"#;
+        let compile = || {
+            Asnr::new()
+                .add_asn_literal(spec)
+                .compile_to_string()
+                .unwrap()
+                .0
+        };
+        let generated = compile();
+        assert_eq!(generated, compile());
+        let apple = generated.find("pub struct Apple").unwrap();
+        let container = generated.find("pub struct Container").unwrap();
+        let zebra = generated.find("pub struct Zebra").unwrap();
+        let value = generated.find("pub const a_value").unwrap();
+        assert!(apple < zebra && zebra < container && container < value);
+    }
+
+    #[test]
+    fn generates_json_schema_documents() {
+        let (schema, warnings) = Asnr::new()
+            .add_asn_literal(
+                r#"Shapes DEFINITIONS AUTOMATIC TAGS ::= BEGIN
+            Size ::= INTEGER (1..100)
+            Label ::= UTF8String (SIZE(1..16))
+            Color ::= ENUMERATED { red, green, blue }
+            Shape ::= CHOICE { circle Size, square Size }
+            Figure ::= SEQUENCE {
+                label Label,
+                visible BOOLEAN,
+                shape Shape,
+                color Color OPTIONAL
+            }
+            END"#,
+            )
+            .framework(crate::Framework::JsonSchema)
+            .compile_to_string()
+            .unwrap();
+        assert!(warnings.is_empty());
+        let schema: serde_json::Value = serde_json::from_str(&schema).unwrap();
+        // compiling a schema validates it against the metaschema of its draft.
+        // Since Draft 7 only knows `definitions`, the `$defs` are checked as such as well.
+        let mut options = jsonschema::JSONSchema::options();
+        let draft_7 = options.with_draft(jsonschema::Draft::Draft7);
+        let defs = &schema["$defs"];
+        assert!(draft_7.compile(&schema).is_ok());
+        assert!(draft_7
+            .compile(&serde_json::json!({ "definitions": defs }))
+            .is_ok());
+        assert_eq!(
+            defs["Size"],
+            serde_json::json!({ "type": "integer", "minimum": 1, "maximum": 100 })
+        );
+        assert_eq!(
+            defs["Label"],
+            serde_json::json!({ "type": "string", "minLength": 1, "maxLength": 16 })
+        );
+        assert_eq!(defs["Color"], serde_json::json!({ "enum": ["red", "green", "blue"] }));
+        assert_eq!(defs["Shape"]["oneOf"].as_array().unwrap().len(), 2);
+        assert_eq!(
+            defs["Figure"]["properties"]["shape"],
+            serde_json::json!({ "$ref": "#/$defs/Shape" })
+        );
+        assert_eq!(
+            defs["Figure"]["required"],
+            serde_json::json!(["label", "visible", "shape"])
+        );
+
+        let figures = draft_7
+            .compile(&serde_json::json!({ "$ref": "#/$defs/Figure", "$defs": defs }))
+            .unwrap();
+        assert!(figures.is_valid(&serde_json::json!({
+            "label": "unit circle",
+            "visible": true,
+            "shape": { "circle": 1 }
+        })));
+        assert!(!figures.is_valid(&serde_json::json!({
+            "label": "unit circle",
+            "visible": true,
+            "shape": { "circle": 101 }
+        })));
+    }
+
+    #[test]
+    fn generates_rasn_choice_values() {
+        let spec = r#"ChoiceValues DEFINITIONS AUTOMATIC TAGS ::= BEGIN
+            Shape ::= CHOICE {
+                circle INTEGER (0..255),
+                rect SEQUENCE { width INTEGER, filled BOOLEAN },
+                label IA5String,
+                nested CHOICE { empty NULL, count INTEGER }
+            }
+            unit-circle Shape ::= circle : 1
+            square Shape ::= rect : { width 4, filled TRUE }
+            labelled Shape ::= label : "shape"
+            nested-count Shape ::= nested : count : 7
+            END"#;
+        let (rust, warnings) = Asnr::new()
+            .add_asn_literal(spec)
+            .framework(crate::Framework::Rasn)
+            .compile_to_string()
+            .unwrap();
+        assert!(warnings.is_empty());
+        let value_fns = syn::parse_file(&rust)
+            .unwrap()
+            .items
+            .into_iter()
+            .filter_map(|item| match item {
+                syn::Item::Fn(f) => Some(f.sig.ident.to_string()),
+                _ => None,
+            })
+            .collect::<Vec<String>>();
+        assert_eq!(
+            value_fns,
+            ["labelled", "nested_count", "square", "unit_circle"]
+        );
+        assert!(rust.contains("pub fn unit_circle() -> Shape {\n    Shape::Circle(1)\n}"));
+        assert!(rust.contains(
+            "Shape::Rect(ShapeRect {\n        width: 4.into(),\n        filled: true,\n    })"
+        ));
+        assert!(rust.contains(r#"Shape::Label("shape".try_into().unwrap())"#));
+        assert!(rust.contains("Shape::Nested(ShapeNested::Count(7.into()))"));
+    }
+
+    #[test]
+    fn generates_rasn_sequence_values() {
+        let spec = r#"SequenceValues DEFINITIONS AUTOMATIC TAGS ::= BEGIN
+            Color ::= ENUMERATED { red, green }
+            Level ::= INTEGER (0..100)
+            Point ::= SEQUENCE { x INTEGER, y INTEGER (0..255) }
+            Marker ::= SEQUENCE {
+                position Point,
+                shape ENUMERATED { dot, cross },
+                level Level DEFAULT 50,
+                color Color OPTIONAL,
+                label IA5String OPTIONAL
+            }
+            origin-x INTEGER ::= 0
+            origin Marker ::= { position { x origin-x, y 0 }, color green, shape cross }
+            broken Marker ::= { position { x unknown-x, y 1 }, shape dot }
+            END"#;
+        let (rust, warnings) = Asnr::new()
+            .add_asn_literal(spec)
+            .framework(crate::Framework::Rasn)
+            .compile_to_string()
+            .unwrap();
+        assert!(syn::parse_file(&rust).is_ok());
+        assert!(rust.contains(
+            "pub fn origin() -> Marker {\n    Marker {\n        position: Point { x: 0.into(), y: 0 },\n        shape: MarkerShape::Cross,\n        level: Level(50),\n        color: Some(Color::Green),\n        label: None,\n    }\n}"
+        ));
+        assert!(!rust.contains("fn broken"));
+        assert_eq!(warnings.len(), 1);
+        assert!(warnings[0].to_string().contains("unknown-x of member x"));
+    }
+
+    #[test]
+    fn generates_rasn_information_object_sets() {
+        let spec = r#"ObjectSets DEFINITIONS AUTOMATIC TAGS ::= BEGIN
+            CONTAINER ::= CLASS { &id INTEGER UNIQUE, &Type } WITH SYNTAX { &Type IDENTIFIED BY &id }
+            FirstContainer ::= SEQUENCE { a INTEGER (0..255), b BOOLEAN }
+            SecondContainer ::= IA5String (SIZE(1..8))
+            Containers CONTAINER ::= { {FirstContainer IDENTIFIED BY 1} | {SecondContainer IDENTIFIED BY 2} }
+            OpenContainers CONTAINER ::= { {FirstContainer IDENTIFIED BY 1}, ... }
+            WrappedContainer ::= SEQUENCE {
+                containerId CONTAINER.&id ({Containers}),
+                data CONTAINER.&Type ({Containers}{@containerId})
+            }
+            END"#;
+        let (rust, warnings) = Asnr::new()
+            .add_asn_literal(spec)
+            .framework(crate::Framework::Rasn)
+            .compile_to_string()
+            .unwrap();
+        assert!(warnings.is_empty());
+        assert!(syn::parse_file(&rust).is_ok());
+        assert!(rust.contains(
+            "#[rasn(choice)]\npub enum Containers {\n    FirstContainer(FirstContainer),\n    SecondContainer(SecondContainer),\n}"
+        ));
+        assert!(rust.contains("pub const SECOND_CONTAINER: i128 = 2;"));
+        assert!(rust.contains("Self::SecondContainer(_) => Self::SECOND_CONTAINER,"));
+        assert!(rust.contains(
+            "#[derive(Debug, Clone, PartialEq)]\npub enum OpenContainers {\n    FirstContainer(FirstContainer),\n    UnknownClassImplementation(Any),\n}"
+        ));
+        assert!(rust.contains("pub fn key(&self) -> Option<i128>"));
+        assert!(rust.contains("data: Any,"));
+    }
+
+    #[test]
+    fn generates_rasn_constraint_attributes() {
+        let spec = r#"Constraints DEFINITIONS AUTOMATIC TAGS ::= BEGIN
+            Speed ::= INTEGER (0..161)
+            ExtensibleSpeed ::= INTEGER (0..161, ...)
+            Limited ::= INTEGER ((0..100) ^ (50..200))
+            Name ::= IA5String (SIZE(1..24))
+            ExtensibleName ::= UTF8String (SIZE(1..24, ...))
+            Id ::= OCTET STRING (SIZE(4))
+            Flags ::= BIT STRING (SIZE(8))
+            Speeds ::= SEQUENCE (SIZE(1..8, ...)) OF Speed
+            Record ::= SEQUENCE { count INTEGER (1..3), data OCTET STRING (SIZE(2..4)) }
+            END"#;
+        let (rust, warnings) = Asnr::new()
+            .add_asn_literal(spec)
+            .framework(crate::Framework::Rasn)
+            .compile_to_string()
+            .unwrap();
+        assert!(warnings.is_empty());
+        assert!(syn::parse_file(&rust).is_ok());
+        for (attribute, declaration) in [
+            (r#"value("0..=161")"#, "pub struct Speed(pub u8);"),
+            (r#"value("0..=161", extensible)"#, "pub struct ExtensibleSpeed(pub Integer);"),
+            (r#"value("50..=100")"#, "pub struct Limited(pub u8);"),
+            (r#"size("1..=24")"#, "pub struct Name(pub Ia5String);"),
+            (r#"size("1..=24", extensible)"#, "pub struct ExtensibleName(pub Utf8String);"),
+            (r#"size("4..=4")"#, "pub struct Id(pub OctetString);"),
+            (r#"size("8..=8")"#, "pub struct Flags(pub BitString);"),
+            (r#"size("1..=8", extensible)"#, "pub struct Speeds(pub SequenceOf<Speed>);"),
+        ] {
+            assert!(
+                rust.contains(&format!("#[rasn(delegate, {attribute})]\n{declaration}")),
+                "missing {attribute} on {declaration}"
+            );
+        }
+        assert!(rust.contains(
+            "    #[rasn(value(\"1..=3\"))]\n    count: u8,\n    #[rasn(size(\"2..=4\"))]\n    data: OctetString,"
+        ));
+    }
+
+    #[test]
+    fn generates_rasn_permitted_alphabet_attributes() {
+        let spec = r#"Alphabets DEFINITIONS AUTOMATIC TAGS ::= BEGIN
+            Digits ::= NumericString (FROM("0".."4"))
+            Sign ::= IA5String (FROM("+"))
+            Hex ::= IA5String (FROM("0".."9" | "A".."F"))
+            Code ::= IA5String (SIZE(1..4) ^ FROM("a".."z"))
+            Lower ::= IA5String (FROM("a".."z", ...))
+            Label ::= SEQUENCE { text IA5String (FROM("ab")) }
+            END"#;
+        let (rust, warnings) = Asnr::new()
+            .add_asn_literal(spec)
+            .framework(crate::Framework::Rasn)
+            .compile_to_string()
+            .unwrap();
+        assert!(warnings.is_empty());
+        assert!(syn::parse_file(&rust).is_ok());
+        for declaration in [
+            "#[rasn(delegate, from(\"0\"..\"4\"))]\npub struct Digits(pub NumericString);",
+            "#[rasn(delegate, from(\"+\"))]\npub struct Sign(pub Ia5String);",
+            "#[rasn(delegate, from(\"0\"..\"9\", \"A\"..\"F\"))]\npub struct Hex(pub Ia5String);",
+            "#[rasn(delegate, size(\"1..=4\"), from(\"a\"..\"z\"))]\npub struct Code(pub Ia5String);",
+            "#[rasn(delegate)]\npub struct Lower(pub Ia5String);",
+            "    #[rasn(from(\"a\", \"b\"))]\n    text: Ia5String,",
+        ] {
+            assert!(rust.contains(declaration), "missing {declaration}");
+        }
+    }
+
+    #[test]
+    fn generates_encoded_size_of_fixed_sequences() {
+        let (rust, _) = Asnr::new()
+            .add_asn_literal(
+                r#"Fixed DEFINITIONS AUTOMATIC TAGS ::= BEGIN
+                FixedSeq ::= SEQUENCE { a INTEGER(0..3), b INTEGER(0..7), c BOOLEAN }
+                Outer ::= SEQUENCE { inner FixedSeq, bits BIT STRING (SIZE(4)), color ENUMERATED { red, green, blue } }
+                Optional ::= SEQUENCE { a INTEGER(0..3) OPTIONAL }
+                Extensible ::= SEQUENCE { a INTEGER(0..3), ... }
+                Unbounded ::= SEQUENCE { a INTEGER }
+                END"#,
+            )
+            .compile_to_string()
+            .unwrap();
+        assert!(rust.contains("impl FixedSeq {\n    /// Size of the UPER encoding in bits\n    pub const ENCODED_SIZE_BITS: usize = 6;"));
+        assert!(rust.contains("pub const ENCODED_SIZE_BITS: usize = 12;"));
+        assert_eq!(rust.matches("ENCODED_SIZE_BITS").count(), 2);
+    }
+
+    #[test]
+    fn generates_rasn_bindings_for_no_std() {
+        let compile = |no_std| {
+            Asnr::new()
+                .add_asn_literal(
+                    r#"NoStd DEFINITIONS AUTOMATIC TAGS ::= BEGIN
+                    Item ::= SEQUENCE {
+                        name IA5String DEFAULT "item",
+                        bits BIT STRING (SIZE(4)) DEFAULT '1010'B
+                    }
+                    END"#,
+                )
+                .framework(crate::Framework::Rasn)
+                .no_std(no_std)
+                .compile_to_string()
+                .unwrap()
+                .0
+        };
+        let rust = compile(true);
+        assert!(syn::parse_file(&rust).is_ok());
+        assert!(rust.contains("extern crate alloc;\nuse alloc::{string::String, vec, vec::Vec};"));
+        assert!(rust.contains("-> String"));
+        assert!(!rust.contains("std::"));
+        assert!(!compile(false).contains("alloc"));
+    }
+
+    #[test]
+    fn generates_rasn_bit_string_values() {
+        let (rust, warnings) = Asnr::new()
+            .add_asn_literal(
+                r#"Bits DEFINITIONS AUTOMATIC TAGS ::= BEGIN
+                Permissions ::= BIT STRING { read(0), write(1), execute(2) } (SIZE(3))
+                readWrite Permissions ::= '110'B
+                mask BIT STRING ::= 'A0'H
+                END"#,
+            )
+            .framework(crate::Framework::Rasn)
+            .compile_to_string()
+            .unwrap();
+        assert!(warnings.is_empty());
+        assert!(syn::parse_file(&rust).is_ok());
+        for declaration in [
+            "pub const WRITE: usize = 1;",
+            "pub fn read_write() -> Permissions {\n    Permissions(vec![true, true, false].iter().collect())\n}",
+            "pub fn mask() -> BitString {\n    vec![true, false, true, false, false, false, false, false].iter().collect()\n}",
+        ] {
+            assert!(rust.contains(declaration), "missing {declaration}");
+        }
+    }
+
+    #[test]
+    fn generates_rasn_set_and_set_of() {
+        let spec = r#"Sets DEFINITIONS AUTOMATIC TAGS ::= BEGIN
+            Names ::= SET (SIZE(1..4)) OF IA5String
+            Settings ::= SET {
+                id INTEGER (0..255),
+                name [1] IA5String OPTIONAL,
+                flags SET OF BOOLEAN OPTIONAL
+            }
+            END"#;
+        let (rust, warnings) = Asnr::new()
+            .add_asn_literal(spec)
+            .framework(crate::Framework::Rasn)
+            .compile_to_string()
+            .unwrap();
+        assert!(warnings.is_empty());
+        assert!(syn::parse_file(&rust).is_ok());
+        for declaration in [
+            "#[rasn(delegate, tag(universal, 17), size(\"1..=4\"))]\npub struct Names(pub SequenceOf<AnonymousNames>);",
+            "#[rasn(set)]\npub struct Settings {",
+            "    #[rasn(tag(context, 1))]\n    name: Option<Ia5String>,",
+            "    flags: Option<SettingsFlags>,",
+            "#[rasn(delegate, tag(universal, 17))]\npub struct SettingsFlags(pub SequenceOf<AnonymousSettingsFlags>);",
+        ] {
+            assert!(rust.contains(declaration), "missing {declaration}");
+        }
+    }
+
+    #[test]
+    fn reports_unsupported_constructs_as_warnings() {
+        let spec = r#"Unsupported DEFINITIONS AUTOMATIC TAGS ::= BEGIN
+            Level ::= ENUMERATED { low, high }
+            default-level Level ::= low
+            pi REAL ::= 3.14
+            Universal ::= UniversalString
+            Settings ::= SEQUENCE { level ENUMERATED { low, high } DEFAULT high }
+            Members ::= SET OF INTEGER
+            END"#;
+        let compile = |framework| {
+            let (rust, warnings) = Asnr::new()
+                .add_asn_literal(spec)
+                .framework(framework)
+                .compile_to_string()
+                .unwrap();
+            let warnings = warnings
+                .iter()
+                .map(|w| w.downcast_ref::<GeneratorError>().unwrap().to_string())
+                .collect::<Vec<String>>();
+            (rust, warnings)
+        };
+        let (rust, warnings) = compile(crate::Framework::Rasn);
+        assert!(rust.contains("fn settings_level_default() -> SettingsLevel {"));
+        assert!(rust.contains("SettingsLevel::High"));
+        assert_eq!(
+            warnings,
+            vec![
+                "UnsupportedConstruct generating Rust representation for Universal: \
+                The Rasn framework does not support UniversalStrings yet",
+                "UnsupportedConstruct generating Rust representation for default-level: \
+                The Rasn framework does not support ENUMERATED values yet",
+                "UnsupportedConstruct generating Rust representation for pi: \
+                The Rasn framework does not support REAL values yet",
+            ]
+        );
+        let (rust, warnings) = compile(crate::Framework::Asnr);
+        assert!(!rust.contains("pub struct Members"));
+        assert_eq!(
+            warnings,
+            vec![
+                "UnsupportedConstruct generating Rust representation for Members: \
+                The Asnr framework does not support SET OF types yet"
+            ]
+        );
+    }
+
+    #[test]
+    fn generates_asnr_set() {
+        let set = r#"Sets DEFINITIONS AUTOMATIC TAGS ::= BEGIN
+            Settings ::= SET {
+                id INTEGER (0..255),
+                enabled BOOLEAN OPTIONAL
+            }
+            END"#;
+        let (rust, warnings) = Asnr::new()
+            .add_asn_literal(set)
+            .framework(crate::Framework::Asnr)
+            .compile_to_string()
+            .unwrap();
+        assert!(warnings.is_empty());
+        assert!(syn::parse_file(&rust).is_ok());
+        assert!(rust.contains("pub struct Settings {"));
+        let (sequence, _) = Asnr::new()
+            .add_asn_literal(set.replace("SET {", "SEQUENCE {"))
+            .framework(crate::Framework::Asnr)
+            .compile_to_string()
+            .unwrap();
+        assert_eq!(rust, sequence);
+    }
+
+    /// Example backend that generates plain data structs without codec implementations
+    struct PlainStructs;
+
+    impl crate::Generator for PlainStructs {
+        fn prelude(&self, no_std: bool) -> String {
+            match no_std {
+                true => String::from("extern crate alloc;\nuse alloc::vec::Vec;\n"),
+                false => String::new(),
+            }
+        }
+
+        fn generate(
+            &self,
+            tld: crate::ToplevelDeclaration,
+            custom_derive: Option<&str>,
+            _options: &crate::GeneratorOptions,
+        ) -> Result<String, GeneratorError> {
+            use crate::{to_rust_snake_case, to_rust_title_case, ToplevelDeclaration};
+            use asnr_grammar::ASN1Type;
+
+            let unsupported = |tld: ToplevelDeclaration| {
+                Err(GeneratorError::new(
+                    Some(tld),
+                    "Unsupported by plain structs",
+                    GeneratorErrorType::Asn1TypeMismatch,
+                ))
+            };
+            let rust_type = |r#type: &ASN1Type| match r#type {
+                ASN1Type::Boolean => Some(String::from("bool")),
+                ASN1Type::Integer(_) => Some(String::from("i64")),
+                ASN1Type::OctetString(_) => Some(String::from("Vec<u8>")),
+                ASN1Type::ElsewhereDeclaredType(e) => Some(to_rust_title_case(&e.identifier)),
+                _ => None,
+            };
+            let derive = custom_derive.map_or(String::new(), |d| format!("#[derive({d})]"));
+            let t = match tld {
+                ToplevelDeclaration::Type(t) => t,
+                _ => return Ok(String::new()),
+            };
+            let name = to_rust_title_case(&t.name);
+            match &t.r#type {
+                ASN1Type::Sequence(s) => {
+                    let mut members = String::new();
+                    for m in &s.members {
+                        match rust_type(&m.r#type) {
+                            Some(r) => {
+                                members += &format!("pub {}: {r},", to_rust_snake_case(&m.name))
+                            }
+                            None => return unsupported(ToplevelDeclaration::Type(t.clone())),
+                        }
+                    }
+                    Ok(format!("{derive}pub struct {name} {{ {members} }}"))
+                }
+                r#type => match rust_type(r#type) {
+                    Some(r) => Ok(format!("pub type {name} = {r};")),
+                    None => unsupported(ToplevelDeclaration::Type(t)),
+                },
+            }
+        }
+    }
+
+    #[test]
+    fn generates_bindings_with_custom_framework() {
+        let (rust, warnings) = Asnr::new()
+            .add_asn_literal(
+                r#"Plain DEFINITIONS AUTOMATIC TAGS ::= BEGIN
+                Id ::= INTEGER (0..255)
+                Person ::= SEQUENCE { id Id, active BOOLEAN, photo OCTET STRING }
+                Flags ::= BIT STRING
+                END"#,
+            )
+            .custom_framework(Box::new(PlainStructs))
+            .custom_derives(&["Debug"])
+            .no_std(true)
+            .compile_to_string()
+            .unwrap();
+        assert!(rust.starts_with("extern crate alloc;\nuse alloc::vec::Vec;\n"));
+        assert!(rust.contains("pub type Id = i64;"));
+        assert!(rust.contains(
+            "#[derive(Debug)]\npub struct Person {\n    pub id: Id,\n    pub active: bool,\n    pub photo: Vec<u8>,\n}"
+        ));
+        assert!(!rust.contains("asnr_transcoder"));
+        assert!(!rust.contains("Flags"));
+        assert_eq!(warnings.len(), 1);
+        assert!(warnings[0].to_string().contains("Flags"));
+    }
 }
@@ -3,7 +3,7 @@ use nom::{
     character::complete::char,
     combinator::{into, opt},
     multi::many0,
-    sequence::{separated_pair, terminated, tuple},
+    sequence::{pair, separated_pair, terminated, tuple},
     IResult,
 };
 
@@ -13,7 +13,11 @@ use super::{constraint::constraint, *};
 
 pub fn choice_value<'a>(input: &'a str) -> IResult<&'a str, ASN1Value> {
     map(
-        skip_ws_and_comments(separated_pair(identifier, char(':'), asn1_value)),
+        skip_ws_and_comments(separated_pair(
+            identifier,
+            skip_ws_and_comments(char(':')),
+            asn1_value,
+        )),
         |(id, val)| ASN1Value::Choice(id.to_owned(), Box::new(val)),
     )(input)
 }
@@ -32,20 +36,23 @@ pub fn choice<'a>(input: &'a str) -> IResult<&'a str, ASN1Type> {
     map(
         preceded(
             skip_ws_and_comments(tag(CHOICE)),
-            in_braces(tuple((
-                many0(terminated(
-                    skip_ws_and_comments(choice_option),
-                    optional_comma,
-                )),
-                opt(terminated(
-                    extension_marker,
-                    opt(skip_ws_and_comments(char(COMMA))),
-                )),
-                opt(many0(terminated(
-                    skip_ws_and_comments(choice_option),
-                    optional_comma,
+            pair(
+                in_braces(tuple((
+                    many0(terminated(
+                        skip_ws_and_comments(choice_option),
+                        optional_comma,
+                    )),
+                    opt(terminated(
+                        extension_marker,
+                        opt(skip_ws_and_comments(char(COMMA))),
+                    )),
+                    opt(many0(terminated(
+                        skip_ws_and_comments(choice_option),
+                        optional_comma,
+                    ))),
                 ))),
-            ))),
+                opt(constraint),
+            ),
         ),
         |m| ASN1Type::Choice(m.into()),
     )(input)
@@ -64,10 +71,28 @@ fn choice_option<'a>(input: &'a str) -> IResult<&'a str, ChoiceOption> {
 mod tests {
     use asnr_grammar::{
         types::{Choice, ChoiceOption},
-        ASN1Type,
+        ASN1Type, ASN1Value,
     };
 
-    use crate::parser::choice;
+    use crate::parser::{choice, choice::choice_value};
+
+    #[test]
+    fn parses_choice_values() {
+        assert_eq!(
+            choice_value("nested : count : 7").unwrap().1,
+            ASN1Value::Choice(
+                "nested".into(),
+                Box::new(ASN1Value::Choice(
+                    "count".into(),
+                    Box::new(ASN1Value::Integer(7))
+                ))
+            )
+        );
+        assert_eq!(
+            choice_value("flag:TRUE").unwrap().1,
+            ASN1Value::Choice("flag".into(), Box::new(ASN1Value::Boolean(true)))
+        );
+    }
 
     #[test]
     fn parses_extensible_choice() {
@@ -88,19 +113,22 @@ mod tests {
                         name: "normal".into(),
                         tag: None,
                         r#type: ASN1Type::Null,
-                        constraints: vec![]
+                        constraints: vec![],
+                        is_recursive: false,
                     },
                     ChoiceOption {
                         name: "high".into(),
                         tag: None,
                         r#type: ASN1Type::Null,
-                        constraints: vec![]
+                        constraints: vec![],
+                        is_recursive: false,
                     },
                     ChoiceOption {
                         name: "medium".into(),
                         tag: None,
                         r#type: ASN1Type::Null,
-                        constraints: vec![]
+                        constraints: vec![],
+                        is_recursive: false,
                     }
                 ],
                 constraints: vec![]
@@ -4,7 +4,7 @@ use nom::{
     character::complete::{
         alpha1, alphanumeric1, char, i128, multispace0, multispace1, one_of, u64,
     },
-    combinator::{into, map_res, opt, peek, recognize, value},
+    combinator::{into, map, map_res, opt, peek, recognize, value},
     error::Error,
     multi::{many0, many1},
     sequence::{delimited, pair, preceded, terminated},
@@ -167,14 +167,26 @@ pub fn all_value<'a>(input: &'a str) -> IResult<&'a str, ASN1Value> {
 }
 
 pub fn asn_tag<'a>(input: &'a str) -> IResult<&'a str, AsnTag> {
-    into(in_brackets(pair(
-        opt(skip_ws_and_comments(alt((
-            tag(PRIVATE),
-            tag(APPLICATION),
-            tag(UNIVERSAL),
-        )))),
-        skip_ws_and_comments(u64),
-    )))(input)
+    map(
+        pair(
+            into(in_brackets(pair(
+                opt(skip_ws_and_comments(alt((
+                    tag(PRIVATE),
+                    tag(APPLICATION),
+                    tag(UNIVERSAL),
+                )))),
+                skip_ws_and_comments(u64),
+            ))),
+            opt(skip_ws_and_comments(alt((
+                value(TaggingEnvironment::Implicit, tag(IMPLICIT)),
+                value(TaggingEnvironment::Explicit, tag(EXPLICIT)),
+            )))),
+        ),
+        |(t, environment): (AsnTag, Option<TaggingEnvironment>)| AsnTag {
+            environment: environment.unwrap_or(t.environment),
+            ..t
+        },
+    )(input)
 }
 
 pub fn range_seperator<'a>(input: &'a str) -> IResult<&'a str, RangeSeperator> {
@@ -240,6 +252,37 @@ mod tests {
 
     use super::*;
 
+    #[test]
+    fn parses_tags() {
+        assert_eq!(
+            asn_tag("[APPLICATION 5] IMPLICIT INTEGER").unwrap(),
+            (
+                " INTEGER",
+                AsnTag {
+                    environment: TaggingEnvironment::Implicit,
+                    tag_class: TagClass::Application,
+                    id: 5
+                }
+            )
+        );
+        assert_eq!(
+            asn_tag("[PRIVATE 3]EXPLICIT SEQUENCE").unwrap().1,
+            AsnTag {
+                environment: TaggingEnvironment::Explicit,
+                tag_class: TagClass::Private,
+                id: 3
+            }
+        );
+        assert_eq!(
+            asn_tag("[2] BOOLEAN").unwrap().1,
+            AsnTag {
+                environment: TaggingEnvironment::Automatic,
+                tag_class: TagClass::ContextSpecific,
+                id: 2
+            }
+        );
+    }
+
     #[test]
     fn parses_line_comment() {
         let line = r#"-- Test, one, two, three/
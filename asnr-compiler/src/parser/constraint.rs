@@ -37,6 +37,7 @@ pub fn constraint<'a>(input: &'a str) -> IResult<&'a str, Vec<Constraint>> {
 
 pub fn single_constraint<'a>(input: &'a str) -> IResult<&'a str, Constraint> {
     skip_ws_and_comments(in_parentheses(alt((
+        map(content_constraint, Constraint::ContentConstraint),
         map(table_constraint, |t| Constraint::TableConstraint(t)),
         map(element_set, |set| Constraint::SubtypeConstraint(set)),
     ))))(input)
@@ -328,6 +329,20 @@ fn table_constraint<'a>(input: &'a str) -> IResult<&'a str, TableConstraint> {
     )(input)
 }
 
+fn content_constraint(input: &str) -> IResult<&str, ContentConstraint> {
+    let encoded_by = || preceded(skip_ws_and_comments(tag(ENCODED_BY)), asn1_value);
+    into(alt((
+        pair(
+            map(
+                preceded(skip_ws_and_comments(tag(CONTAINING)), asn1_type),
+                Some,
+            ),
+            opt(encoded_by()),
+        ),
+        map(encoded_by(), |v| (None, Some(v))),
+    )))(input)
+}
+
 fn relational_constraint<'a>(input: &'a str) -> IResult<&'a str, RelationalConstraint> {
     into(skip_ws_and_comments(preceded(
         char(AT),
@@ -1089,4 +1104,44 @@ mod tests {
                 ]
             )
     }
+
+    #[test]
+    fn parses_content_constraint() {
+        assert_eq!(
+            constraint("(CONTAINING ToBeSignedData)").unwrap().1,
+            vec![Constraint::ContentConstraint(ContentConstraint {
+                containing: Some(ASN1Type::ElsewhereDeclaredType(DeclarationElsewhere {
+                    identifier: "ToBeSignedData".into(),
+                    constraints: vec![]
+                })),
+                encoded_by: None
+            })]
+        );
+        assert_eq!(
+            constraint("( CONTAINING INTEGER (0..7) ENCODED BY per-unaligned )")
+                .unwrap()
+                .1,
+            vec![Constraint::ContentConstraint(ContentConstraint {
+                containing: Some(ASN1Type::Integer(Integer {
+                    constraints: vec![Constraint::SubtypeConstraint(ElementSet {
+                        set: ElementOrSetOperation::Element(SubtypeElement::ValueRange {
+                            min: Some(ASN1Value::Integer(0)),
+                            max: Some(ASN1Value::Integer(7)),
+                            extensible: false
+                        }),
+                        extensible: false
+                    })],
+                    distinguished_values: None
+                })),
+                encoded_by: Some(ASN1Value::ElsewhereDeclaredValue("per-unaligned".into()))
+            })]
+        );
+        assert_eq!(
+            constraint("(ENCODED BY per-unaligned)").unwrap().1,
+            vec![Constraint::ContentConstraint(ContentConstraint {
+                containing: None,
+                encoded_by: Some(ASN1Value::ElsewhereDeclaredValue("per-unaligned".into()))
+            })]
+        );
+    }
 }
@@ -5,6 +5,50 @@ use std::error::Error;
 pub struct ParserError {
     pub details: String,
     pub kind: ParserErrorType,
+    /// Byte offset of the parsing failure within the ASN1 source, if known
+    pub offset: Option<usize>,
+}
+
+impl ParserError {
+    /// Converts a nom error into a `ParserError` that records the offset
+    /// of the parsing failure within the parsed `source`
+    pub(crate) fn located_in(source: &str, error: nom::Err<nom::error::Error<&str>>) -> Self {
+        let offset = match &error {
+            nom::Err::Incomplete(_) => source.len(),
+            nom::Err::Error(e) | nom::Err::Failure(e) => source.len() - e.input.len(),
+        };
+        Self {
+            offset: Some(offset),
+            ..error.into()
+        }
+    }
+
+    /// Reports a top-level declaration that has been skipped because it could not be parsed
+    /// * `raw` - the skipped section of the ASN1 source
+    /// * `reason` - the error that occurred parsing the declaration
+    pub(crate) fn unparsed_declaration(raw: &str, reason: ParserError) -> Self {
+        let declaration = raw.lines().next().unwrap_or_default().trim();
+        Self {
+            details: format!("Skipped declaration `{declaration}`: {reason}"),
+            kind: ParserErrorType::UnparsedDeclaration,
+            offset: reason.offset,
+        }
+    }
+
+    /// Returns the line and column of the parsing failure
+    /// as well as the affected line of the `source`, with the failure position marked
+    /// * `source` - the ASN1 source that failed to parse
+    pub fn snippet(&self, source: &str) -> Option<String> {
+        let preceding = source.get(..self.offset?)?;
+        let line_start = preceding.rfind('\n').map_or(0, |i| i + 1);
+        let line_number = preceding.matches('\n').count() + 1;
+        let column = preceding[line_start..].chars().count() + 1;
+        let line = source[line_start..].lines().next().unwrap_or_default();
+        Some(format!(
+            "line {line_number}, column {column}:\n{line}\n{}^",
+            " ".repeat(column - 1)
+        ))
+    }
 }
 
 impl<'a> From<nom::Err<nom::error::Error<&'a str>>> for ParserError {
@@ -13,14 +57,17 @@ impl<'a> From<nom::Err<nom::error::Error<&'a str>>> for ParserError {
             nom::Err::Incomplete(_) => Self {
                 details: "Unexpected end of input!".into(),
                 kind: ParserErrorType::NotEnoughData,
+                offset: None,
             },
             nom::Err::Error(e) => Self {
                 details: "Error matching ASN syntax while parsing:".to_owned() + e.input,
                 kind: ParserErrorType::MatchingError(e.code),
+                offset: None,
             },
             nom::Err::Failure(e) => Self {
                 details: "Unrecoverable error while parsing:".to_owned() + e.input,
                 kind: ParserErrorType::Failure(e.code),
+                offset: None,
             },
         }
     }
@@ -31,6 +78,7 @@ pub enum ParserErrorType {
     NotEnoughData,
     MatchingError(nom::error::ErrorKind),
     Failure(nom::error::ErrorKind),
+    UnparsedDeclaration,
 }
 
 impl Error for ParserError {}
@@ -2,7 +2,7 @@ use nom::{
     branch::alt,
     bytes::complete::tag,
     character::complete::{alphanumeric1, char, one_of},
-    combinator::{into, map, opt, recognize, value},
+    combinator::{into, map, opt, recognize, value, verify},
     multi::{many0, many1, separated_list0, separated_list1},
     sequence::{pair, preceded, terminated, tuple},
     IResult,
@@ -17,6 +17,7 @@ use super::{
         optional_marker, skip_ws_and_comments, uppercase_identifier,
     },
     constraint::constraint,
+    integer::integer_value,
 };
 
 pub fn information_object_class<'a>(input: &'a str) -> IResult<&'a str, InformationObjectClass> {
@@ -83,6 +84,8 @@ fn custom_syntax_information_object<'a>(
             value(SyntaxApplication::Comma, char(COMMA)),
             map(syntax_literal, |m| SyntaxApplication::Literal(m.into())),
             map(object_set, |m| SyntaxApplication::ObjectSetDeclaration(m)),
+            // integers would otherwise be parsed as type references
+            map(integer_value, SyntaxApplication::ValueReference),
             map(asn1_type, |m| SyntaxApplication::TypeReference(m)),
             map(asn1_value, |m| SyntaxApplication::ValueReference(m)),
         ))))),
@@ -172,7 +175,10 @@ fn syntax_token<'a>(input: &'a str) -> IResult<&'a str, SyntaxToken> {
 }
 
 fn syntax_literal<'a>(input: &'a str) -> IResult<&'a str, &'a str> {
-    uppercase_identifier(input)
+    // literals are words, so that numbers are parsed as values
+    verify(uppercase_identifier, |literal: &str| {
+        literal.starts_with(|c: char| c.is_ascii_uppercase())
+    })(input)
 }
 
 #[cfg(test)]
@@ -210,7 +216,8 @@ mod tests {
                                         constraints: vec![],
                                         distinguished_values: None
                                     }),
-                                    constraints: vec![]
+                                    constraints: vec![],
+                                    is_recursive: false,
                                 },
                                 ChoiceOption {
                                     name: "global".into(),
@@ -218,7 +225,8 @@ mod tests {
                                     r#type: ASN1Type::OctetString(OctetString {
                                         constraints: vec![],
                                     }),
-                                    constraints: vec![]
+                                    constraints: vec![],
+                                    is_recursive: false,
                                 }
                             ],
                             constraints: vec![]
@@ -281,6 +289,27 @@ mod tests {
         )
     }
 
+    #[test]
+    fn parses_object_set_with_integer_keys() {
+        assert_eq!(
+            object_set(r#"{{FirstContainer IDENTIFIED BY 1} | {SecondContainer IDENTIFIED BY 2}}"#)
+                .unwrap()
+                .1
+                .values[1],
+            ObjectSetValue::Inline(InformationObjectFields::CustomSyntax(vec![
+                SyntaxApplication::TypeReference(ASN1Type::ElsewhereDeclaredType(
+                    DeclarationElsewhere {
+                        identifier: "SecondContainer".into(),
+                        constraints: vec![]
+                    }
+                )),
+                SyntaxApplication::Literal("IDENTIFIED".into()),
+                SyntaxApplication::Literal("BY".into()),
+                SyntaxApplication::ValueReference(ASN1Value::Integer(2))
+            ]))
+        )
+    }
+
     #[test]
     fn parses_information_object_with_custom_syntax() {
         assert_eq!(
@@ -8,10 +8,14 @@
 //! of `common`, which contains parsers for the more
 //! generic elements of ASN1 syntax, and `util`, which
 //! contains helper parsers not specific to ASN1's notation.
+use std::collections::BTreeMap;
+
 use nom::{
     branch::alt,
     bytes::complete::tag,
-    combinator::{into, map, opt},
+    character::complete::multispace1,
+    combinator::{consumed, into, map, opt},
+    error::{Error, ErrorKind},
     multi::{many0, many1},
     sequence::{pair, preceded, terminated, tuple},
     IResult,
@@ -34,11 +38,12 @@ use self::{
     null::*,
     object_identifier::*,
     octet_string::*,
-    parameterization::parameterization,
+    parameterization::{parameterization, parameters},
     real::*,
     sequence::{sequence, sequence_value},
     sequence_of::*,
     set::*,
+    set_of::*,
 };
 
 mod bit_string;
@@ -48,7 +53,7 @@ mod choice;
 mod common;
 mod constraint;
 mod enumerated;
-mod error;
+pub(crate) mod error;
 mod information_object_class;
 mod integer;
 mod module_reference;
@@ -60,28 +65,166 @@ mod real;
 mod sequence;
 mod sequence_of;
 mod set;
+mod set_of;
 mod util;
 
-pub fn asn_spec<'a>(
-    input: &'a str,
-) -> Result<Vec<(ModuleReference, Vec<ToplevelDeclaration>)>, ParserError> {
+/// Top-level declarations of the parsed ASN1 modules, grouped by module
+pub type ParsedModules = Vec<(ModuleReference, Vec<ToplevelDeclaration>)>;
+
+pub fn asn_spec(input: &str) -> Result<ParsedModules, ParserError> {
     many1(pair(
+        module_reference,
+        terminated(
+            many0(skip_ws(top_level_declaration)),
+            skip_ws_and_comments(tag(END)),
+        ),
+    ))(input)
+    .map(|(_, res)| res)
+    .map_err(|e| ParserError::located_in(input, e))
+}
+
+/// Parses an ASN1 specification like `asn_spec`, but skips top-level declarations that cannot be parsed.
+/// The parser resumes at the next assignment or at the END of the module.
+/// Returns the parsed modules and an `UnparsedDeclaration` error for every skipped declaration.
+pub fn asn_spec_with_recovery(input: &str) -> Result<(ParsedModules, Vec<ParserError>), ParserError> {
+    let (_, modules) = many1(pair(
         module_reference,
         terminated(
             many0(skip_ws(alt((
-                map(top_level_information_declaration, |m| {
-                    ToplevelDeclaration::Information(m)
-                }),
-                map(top_level_type_declaration, |m| ToplevelDeclaration::Type(m)),
-                map(top_level_value_declaration, |m| {
-                    ToplevelDeclaration::Value(m)
-                }),
+                map(top_level_declaration, Ok),
+                map(skipped_declaration, Err),
             )))),
             skip_ws_and_comments(tag(END)),
         ),
     ))(input)
-    .map(|(_, res)| res)
-    .map_err(|e| e.into())
+    .map_err(|e| ParserError::located_in(input, e))?;
+    let mut skipped = vec![];
+    let modules = modules
+        .into_iter()
+        .map(|(header, items)| {
+            let tlds = items
+                .into_iter()
+                .filter_map(|item| {
+                    item.map_err(|raw: &str| {
+                        let remaining = &input[raw.as_ptr() as usize - input.as_ptr() as usize..];
+                        let reason = match top_level_type_declaration(remaining) {
+                            Err(e) => ParserError::located_in(input, e),
+                            Ok(_) => ParserError::located_in(
+                                input,
+                                nom::Err::Error(Error::new(remaining, ErrorKind::Verify)),
+                            ),
+                        };
+                        skipped.push(ParserError::unparsed_declaration(raw, reason));
+                    })
+                    .ok()
+                })
+                .collect();
+            (header, tlds)
+        })
+        .collect();
+    Ok((modules, skipped))
+}
+
+/// Returns the ASN1 notation of the top-level declarations of an ASN1 specification by their names.
+/// Leading comments are stripped and the indentation of the notation is normalized.
+/// Declarations that cannot be parsed are omitted.
+pub fn declaration_sources(input: &str) -> BTreeMap<String, String> {
+    let parsed = many1(pair(
+        module_reference,
+        terminated(
+            many0(skip_ws(alt((
+                map(consumed(top_level_declaration), Some),
+                map(skipped_declaration, |_| None),
+            )))),
+            skip_ws_and_comments(tag(END)),
+        ),
+    ))(input);
+    let Ok((_, modules)) = parsed else {
+        return BTreeMap::new();
+    };
+    modules
+        .into_iter()
+        .flat_map(|(_, items)| items.into_iter().flatten())
+        .map(|(source, tld)| {
+            let notation =
+                many0(alt((comment, multispace1)))(source).map_or(source, |(notation, _)| notation);
+            (tld.name().clone(), dedent(notation.trim_end()))
+        })
+        .collect()
+}
+
+/// Removes the indentation that the lines following the first line have in common
+fn dedent(notation: &str) -> String {
+    let indentation = notation
+        .lines()
+        .skip(1)
+        .filter(|line| !line.trim().is_empty())
+        .map(|line| line.len() - line.trim_start().len())
+        .min()
+        .unwrap_or(0);
+    notation
+        .lines()
+        .enumerate()
+        .map(|(i, line)| match i {
+            0 => line,
+            _ => line.get(indentation..).unwrap_or(line.trim_start()),
+        })
+        .collect::<Vec<&str>>()
+        .join("\n")
+}
+
+fn top_level_declaration(input: &str) -> IResult<&str, ToplevelDeclaration> {
+    alt((
+        map(top_level_information_declaration, ToplevelDeclaration::Information),
+        map(top_level_type_declaration, ToplevelDeclaration::Type),
+        map(top_level_value_declaration, ToplevelDeclaration::Value),
+    ))(input)
+}
+
+/// Consumes an unparsable top-level declaration up to the next assignment
+/// or to the END of the module, whichever comes first.
+fn skipped_declaration(input: &str) -> IResult<&str, &str> {
+    let (body, _) = many0(alt((comment, multispace1)))(input)?;
+    if body.is_empty() || starts_with_keyword(body, END) {
+        return Err(nom::Err::Error(Error::new(input, ErrorKind::Verify)));
+    }
+    let own_assignment = body.find(ASSIGN).map_or(0, |i| i + ASSIGN.len());
+    let next_declaration = body[own_assignment..]
+        .find(ASSIGN)
+        .map(|i| declaration_start(body, own_assignment + i));
+    let module_end = (0..body.len()).find(|i| {
+        body.is_char_boundary(*i)
+            && (*i == 0 || body[..*i].ends_with(char::is_whitespace))
+            && starts_with_keyword(&body[*i..], END)
+    });
+    let stop = match (next_declaration, module_end) {
+        (Some(declaration), Some(end)) => declaration.min(end),
+        (declaration, end) => declaration.or(end).unwrap_or(body.len()),
+    };
+    if stop == 0 {
+        return Err(nom::Err::Error(Error::new(input, ErrorKind::Verify)));
+    }
+    Ok((&body[stop..], body[..stop].trim_end()))
+}
+
+/// Returns the start of the line that holds the header of the declaration
+/// whose assignment `::=` is found at `assignment`
+fn declaration_start(input: &str, assignment: usize) -> usize {
+    let line_start = |i: usize| input[..i].rfind('\n').map_or(0, |n| n + 1);
+    let start = line_start(assignment);
+    if input[start..assignment].trim().is_empty() && start > 0 {
+        line_start(start - 1)
+    } else {
+        start
+    }
+}
+
+fn starts_with_keyword(input: &str, keyword: &str) -> bool {
+    input.starts_with(keyword)
+        && input[keyword.len()..]
+            .chars()
+            .next()
+            .is_none_or(|c| !(c.is_alphanumeric() || c == '-' || c == '_'))
 }
 
 pub fn top_level_type_declaration<'a>(input: &'a str) -> IResult<&'a str, ToplevelTypeDeclaration> {
@@ -109,6 +252,7 @@ pub fn asn1_type<'a>(input: &'a str) -> IResult<&'a str, ASN1Type> {
         object_identifier,
         sequence_of,
         sequence,
+        set_of,
         set,
         real,
         choice,
@@ -158,6 +302,17 @@ pub fn elsewhere_declared_type<'a>(input: &'a str) -> IResult<&'a str, ASN1Type>
 
 fn top_level_value_declaration<'a>(input: &'a str) -> IResult<&'a str, ToplevelValueDeclaration> {
     into(alt((
+        // Parse OBJECT IDENTIFIER values first, since values like `{ iso 3 }`
+        // would also match a SEQUENCE value
+        tuple((
+            skip_ws(many0(comment)),
+            skip_ws(value_identifier),
+            skip_ws(tag(OBJECT_IDENTIFIER)),
+            preceded(
+                assignment,
+                map(object_identifier_value, ASN1Value::ObjectIdentifier),
+            ),
+        )),
         tuple((
             skip_ws(many0(comment)),
             skip_ws(value_identifier),
@@ -170,11 +325,11 @@ fn top_level_value_declaration<'a>(input: &'a str) -> IResult<&'a str, ToplevelV
             ))),
             preceded(assignment, asn1_value),
         )),
-        // Cover object identifiers
+        // Cover object identifiers, also when typed by a reference to an OBJECT IDENTIFIER type
         tuple((
             skip_ws(many0(comment)),
             skip_ws(value_identifier),
-            skip_ws(tag(OBJECT_IDENTIFIER)),
+            skip_ws(alt((tag(OBJECT_IDENTIFIER), identifier))),
             preceded(
                 assignment,
                 map(object_identifier_value, |oid| ASN1Value::ObjectIdentifier(oid)),
@@ -189,7 +344,7 @@ fn top_level_information_object_declaration<'a>(
     into(tuple((
         skip_ws(many0(comment)),
         skip_ws(identifier),
-        skip_ws(uppercase_identifier),
+        class_link,
         preceded(assignment, information_object),
     )))(input)
 }
@@ -200,7 +355,7 @@ fn top_level_object_set_declaration<'a>(
     into(tuple((
         skip_ws(many0(comment)),
         skip_ws(identifier),
-        skip_ws(uppercase_identifier),
+        class_link,
         preceded(assignment, object_set),
     )))(input)
 }
@@ -211,10 +366,17 @@ fn top_level_object_class_declaration<'a>(
     into(tuple((
         skip_ws(many0(comment)),
         skip_ws(uppercase_identifier),
+        opt(parameterization),
         preceded(assignment, information_object_class),
     )))(input)
 }
 
+/// Parses the class that an information object or object set belongs to,
+/// including the actual parameters if the class is parameterized.
+fn class_link(input: &str) -> IResult<&str, ClassLink> {
+    into(pair(skip_ws(uppercase_identifier), opt(parameters)))(input)
+}
+
 #[cfg(test)]
 mod tests {
     use core::panic;
@@ -230,7 +392,91 @@ mod tests {
 
     use crate::parser::top_level_information_declaration;
 
-    use super::top_level_type_declaration;
+    use super::{
+        asn_spec, asn_spec_with_recovery, error::ParserErrorType, top_level_type_declaration,
+        top_level_value_declaration,
+    };
+
+    #[test]
+    fn parses_name_only_object_identifier_arcs() {
+        assert_eq!(
+            top_level_value_declaration("id-named OBJECT IDENTIFIER ::= { iso 3 }")
+                .unwrap()
+                .1
+                .value,
+            ASN1Value::ObjectIdentifier(ObjectIdentifierValue(vec!["iso".into(), 3.into()]))
+        );
+    }
+
+    #[test]
+    fn reports_offset_of_parsing_failure() {
+        let spec = "Test DEFINITIONS AUTOMATIC TAGS ::= BEGIN\n  Valid ::= BOOLEAN\n  Invalid ::= SEQUENCE { member }\nEND";
+        let error = asn_spec(spec).unwrap_err();
+        assert_eq!(error.offset, spec.find("Invalid"));
+        assert_eq!(
+            error.snippet(spec).unwrap(),
+            "line 3, column 3:\n  Invalid ::= SEQUENCE { member }\n  ^"
+        );
+    }
+
+    #[test]
+    fn structurally_identical_types_share_signatures() {
+        let spec = r#"Signatures DEFINITIONS AUTOMATIC TAGS ::= BEGIN
+            Percent ::= INTEGER (0..100)
+            Score ::= INTEGER(0 .. 100)
+            Pair-a ::= SEQUENCE { flag BOOLEAN, count INTEGER (0..100) OPTIONAL, ... }
+            Pair-b ::= SEQUENCE {
+                enabled BOOLEAN,
+                -- differently named member
+                amount INTEGER (0..100) OPTIONAL,
+                ...
+            }
+            Pair-c ::= SEQUENCE { flag BOOLEAN, count INTEGER (0..100), ... }
+            END"#;
+        let signatures = asn_spec(spec).unwrap()[0]
+            .1
+            .iter()
+            .map(|tld| match tld {
+                ToplevelDeclaration::Type(t) => t.r#type.type_signature(),
+                _ => panic!("Expected type declaration"),
+            })
+            .collect::<Vec<String>>();
+        assert_eq!(signatures[0], "int:0:100");
+        assert_eq!(signatures[0], signatures[1]);
+        assert_eq!(signatures[2], "seq:[bool,int:0:100?,...]");
+        assert_eq!(signatures[2], signatures[3]);
+        assert_ne!(signatures[2], signatures[4]);
+    }
+
+    #[test]
+    fn skips_unparsable_declarations() {
+        let spec = r#"Test DEFINITIONS AUTOMATIC TAGS ::= BEGIN
+  Valid ::= BOOLEAN
+  Invalid ::= SEQUENCE {
+    member
+  }
+  Other ::= INTEGER (0..7)
+  Trailing ::= CHOICE { , }
+END
+Second DEFINITIONS AUTOMATIC TAGS ::= BEGIN
+  Third ::= OCTET STRING
+END"#;
+        let (modules, skipped) = asn_spec_with_recovery(spec).unwrap();
+        assert_eq!(
+            modules
+                .iter()
+                .map(|(_, tlds)| tlds.iter().map(|t| t.name().as_str()).collect::<Vec<_>>())
+                .collect::<Vec<_>>(),
+            vec![vec!["Valid", "Other"], vec!["Third"]]
+        );
+        assert_eq!(skipped.len(), 2);
+        assert!(matches!(skipped[0].kind, ParserErrorType::UnparsedDeclaration));
+        assert!(skipped[0].details.contains("`Invalid ::= SEQUENCE {`"));
+        let offset = skipped[0].offset.unwrap();
+        assert!(spec.find("Invalid").unwrap() < offset && offset < spec.find("Other").unwrap());
+        assert!(skipped[1].details.contains("Trailing"));
+        assert!(asn_spec(spec).is_err());
+    }
 
     #[test]
     fn parses_toplevel_simple_integer_declaration() {
@@ -466,6 +712,7 @@ mod tests {
                 comments: "comments".into(),
                 name: "CpmContainers".into(),
                 class: Some(ClassLink::ByName("CPM-CONTAINER-ID-AND-TYPE".into())),
+                parameterization: None,
                 value: ASN1Information::ObjectSet(ObjectSet {
                     values: vec![
                         ObjectSetValue::Inline(InformationObjectFields::CustomSyntax(vec![
@@ -513,6 +760,7 @@ mod tests {
                 comments: "".into(),
                 name: "Reg-AdvisorySpeed".into(),
                 class: Some(ClassLink::ByName("REG-EXT-ID-AND-TYPE".into())),
+                parameterization: None,
                 value: ASN1Information::ObjectSet(ObjectSet {
                     values: vec![],
                     extensible: Some(0)
@@ -536,6 +784,7 @@ mod tests {
                 comments: "".into(),
                 name: "REG-EXT-ID-AND-TYPE".into(),
                 class: None,
+                parameterization: None,
                 value: ASN1Information::ObjectClass(InformationObjectClass {
                     fields: vec![
                         InformationObjectClassField {
@@ -615,7 +864,8 @@ mod tests {
                             ),
                             default_value: None,
                             is_optional: false,
-                            constraints: vec![]
+                            constraints: vec![],
+                            is_recursive: false,
                         },
                         SequenceOrSetMember {
                             name: "regExtValue".into(),
@@ -644,9 +894,11 @@ mod tests {
                             ),
                             default_value: None,
                             is_optional: false,
-                            constraints: vec![]
+                            constraints: vec![],
+                            is_recursive: false,
                         }
-                    ]
+                    ],
+                    extension_groups: vec![],
                 }),
                 parameterization: Some(Parameterization {
                     parameters: vec![ParameterizationArgument {
@@ -681,19 +933,22 @@ mod tests {
                             name: "normal".into(),
                             tag: None,
                             r#type: ASN1Type::Null,
-                            constraints: vec![]
+                            constraints: vec![],
+                            is_recursive: false,
                         },
                         ChoiceOption {
                             name: "high".into(),
                             tag: None,
                             r#type: ASN1Type::Null,
-                            constraints: vec![]
+                            constraints: vec![],
+                            is_recursive: false,
                         },
                         ChoiceOption {
                             name: "medium".into(),
                             tag: None,
                             r#type: ASN1Type::Null,
-                            constraints: vec![]
+                            constraints: vec![],
+                            is_recursive: false,
                         }
                     ],
                     constraints: vec![]
@@ -1,14 +1,15 @@
 use asnr_grammar::{
     EncodingReferenceDefault, ExtensibilityEnvironment, Import, ModuleReference,
-    TaggingEnvironment, ASSIGN, AUTOMATIC, BEGIN, COMMA, DEFINITIONS, EXPLICIT,
-    EXTENSIBILITY_IMPLIED, FROM, IMPLICIT, IMPORTS, INSTRUCTIONS, SEMICOLON, TAGS, WITH_SUCCESSORS,
+    TaggingEnvironment, ALL, ASSIGN, AUTOMATIC, BEGIN, COMMA, DEFINITIONS, EXPLICIT,
+    EXTENSIBILITY_IMPLIED, EXPORTS, FROM, IMPLICIT, IMPORTS, INSTRUCTIONS, SEMICOLON, TAGS,
+    WITH_SUCCESSORS,
 };
 use nom::{
     branch::alt,
     bytes::complete::tag,
     character::complete::char,
-    combinator::{into, map, opt},
-    multi::{many1, separated_list1},
+    combinator::{into, map, opt, value},
+    multi::{many1, separated_list0, separated_list1},
     sequence::{delimited, pair, preceded, terminated, tuple},
     IResult,
 };
@@ -27,10 +28,29 @@ pub fn module_reference<'a>(input: &'a str) -> IResult<&'a str, ModuleReference>
             environments,
             skip_ws_and_comments(pair(tag(ASSIGN), skip_ws_and_comments(tag(BEGIN)))),
         )),
+        opt(exports),
         opt(imports),
     ))))(input)
 }
 
+/// Parses an `EXPORTS` clause. Returns `None` for `EXPORTS ALL`.
+fn exports(input: &str) -> IResult<&str, Option<Vec<&str>>> {
+    skip_ws_and_comments(delimited(
+        tag(EXPORTS),
+        alt((
+            value(None, skip_ws_and_comments(tag(ALL))),
+            map(
+                separated_list0(
+                    skip_ws_and_comments(char(COMMA)),
+                    skip_ws_and_comments(identifier),
+                ),
+                Some,
+            ),
+        )),
+        skip_ws_and_comments(char(SEMICOLON)),
+    ))(input)
+}
+
 fn imports<'a>(input: &'a str) -> IResult<&'a str, Vec<Import>> {
     skip_ws_and_comments(delimited(
         tag(IMPORTS),
@@ -69,7 +89,7 @@ fn environments<'a>(
     tuple((
         opt(skip_ws_and_comments(into(terminated(
             identifier,
-            tag(INSTRUCTIONS),
+            skip_ws(tag(INSTRUCTIONS)),
         )))),
         skip_ws_and_comments(terminated(
             map(
@@ -108,7 +128,7 @@ mod tests {
     
     BEGIN
     "#).unwrap().1,
-    ModuleReference {name:"ETSI-ITS-CDD".into(),module_identifier:Some(ObjectIdentifierValue(vec![ObjectIdentifierArc{name:Some("itu-t".into()),number:Some(0)},ObjectIdentifierArc{name:Some("identified-organization".into()),number:Some(4)},ObjectIdentifierArc{name:Some("etsi".into()),number:Some(0)},ObjectIdentifierArc{name:Some("itsDomain".into()),number:Some(5)},ObjectIdentifierArc{name:Some("wg1".into()),number:Some(1)},ObjectIdentifierArc{name:None,number:Some(102894)},ObjectIdentifierArc{name:Some("cdd".into()),number:Some(2)},ObjectIdentifierArc{name:Some("major-version-3".into()),number:Some(3)},ObjectIdentifierArc{name:Some("minor-version-1".into()),number:Some(1)}])),encoding_reference_default:None,tagging_environment:asnr_grammar::TaggingEnvironment::Automatic,extensibility_environment:asnr_grammar::ExtensibilityEnvironment::Explicit, imports: vec![] }
+    ModuleReference {name:"ETSI-ITS-CDD".into(),module_identifier:Some(ObjectIdentifierValue(vec![ObjectIdentifierArc{name:Some("itu-t".into()),number:Some(0)},ObjectIdentifierArc{name:Some("identified-organization".into()),number:Some(4)},ObjectIdentifierArc{name:Some("etsi".into()),number:Some(0)},ObjectIdentifierArc{name:Some("itsDomain".into()),number:Some(5)},ObjectIdentifierArc{name:Some("wg1".into()),number:Some(1)},ObjectIdentifierArc{name:None,number:Some(102894)},ObjectIdentifierArc{name:Some("cdd".into()),number:Some(2)},ObjectIdentifierArc{name:Some("major-version-3".into()),number:Some(3)},ObjectIdentifierArc{name:Some("minor-version-1".into()),number:Some(1)}])),encoding_reference_default:None,tagging_environment:asnr_grammar::TaggingEnvironment::Automatic,extensibility_environment:asnr_grammar::ExtensibilityEnvironment::Explicit, imports: vec![], exports: None }
   )
     }
 
@@ -130,6 +150,55 @@ mod tests {
         FROM CPM-OriginatingStationContainers {itu-t (0) identified-organization (4) etsi (0) itsDomain (5) wg1 (1) ts (103324) originatingStationContainers (2) major-version-1 (1) minor-version-1(1)}
         WITH SUCCESSORS;        
     "#).unwrap().1,
-    ModuleReference { name: "CPM-PDU-Descriptions".into(), module_identifier: Some(ObjectIdentifierValue(vec![ObjectIdentifierArc { name: Some("itu-t".into()), number: Some(0) }, ObjectIdentifierArc { name: Some("identified-organization".into()), number: Some(4) }, ObjectIdentifierArc { name: Some("etsi".into()), number: Some(0) }, ObjectIdentifierArc { name: Some("itsDomain".into()), number: Some(5) }, ObjectIdentifierArc { name: Some("wg1".into()), number: Some(1) }, ObjectIdentifierArc { name: Some("ts".into()), number: Some(103324) }, ObjectIdentifierArc { name: Some("cpm".into()), number: Some(1) }, ObjectIdentifierArc { name: Some("major-version-1".into()), number: Some(1) }, ObjectIdentifierArc { name: Some("minor-version-1".into()), number: Some(1) }])), encoding_reference_default: None, tagging_environment: TaggingEnvironment::Automatic, extensibility_environment: ExtensibilityEnvironment::Explicit, imports: vec![Import { types: vec!["ItsPduHeader".into(), "MessageRateHz".into(), "MessageSegmentationInfo".into(), "OrdinalNumber1B".into(), "ReferencePosition".into(), "StationType".into(), "TimestampIts".into()], origin_name: "ETSI-ITS-CDD".into(), origin_identifier: ObjectIdentifierValue(vec![ObjectIdentifierArc { name: Some("itu-t".into()), number: Some(0) }, ObjectIdentifierArc { name: Some("identified-organization".into()), number: Some(4) }, ObjectIdentifierArc { name: Some("etsi".into()), number: Some(0) }, ObjectIdentifierArc { name: Some("itsDomain".into()), number: Some(5) }, ObjectIdentifierArc { name: Some("wg1".into()), number: Some(1) }, ObjectIdentifierArc { name: Some("ts".into()), number: Some(102894) }, ObjectIdentifierArc { name: Some("cdd".into()), number: Some(2) }, ObjectIdentifierArc { name: Some("major-version-3".into()), number: Some(3) }, ObjectIdentifierArc { name: Some("minor-version-1".into()), number: Some(1) }]), with_successors: true }, Import { types: vec!["OriginatingRsuContainer".into(), "OriginatingVehicleContainer".into()], origin_name: "CPM-OriginatingStationContainers".into(), origin_identifier: ObjectIdentifierValue(vec![ObjectIdentifierArc { name: Some("itu-t".into()), number: Some(0) }, ObjectIdentifierArc { name: Some("identified-organization".into()), number: Some(4) }, ObjectIdentifierArc { name: Some("etsi".into()), number: Some(0) }, ObjectIdentifierArc { name: Some("itsDomain".into()), number: Some(5) }, ObjectIdentifierArc { name: Some("wg1".into()), number: Some(1) }, ObjectIdentifierArc { name: Some("ts".into()), number: Some(103324) }, ObjectIdentifierArc { name: Some("originatingStationContainers".into()), number: Some(2) }, ObjectIdentifierArc { name: Some("major-version-1".into()), number: Some(1) }, ObjectIdentifierArc { name: Some("minor-version-1".into()), number: Some(1) }]), with_successors: true }] })
+    ModuleReference { name: "CPM-PDU-Descriptions".into(), module_identifier: Some(ObjectIdentifierValue(vec![ObjectIdentifierArc { name: Some("itu-t".into()), number: Some(0) }, ObjectIdentifierArc { name: Some("identified-organization".into()), number: Some(4) }, ObjectIdentifierArc { name: Some("etsi".into()), number: Some(0) }, ObjectIdentifierArc { name: Some("itsDomain".into()), number: Some(5) }, ObjectIdentifierArc { name: Some("wg1".into()), number: Some(1) }, ObjectIdentifierArc { name: Some("ts".into()), number: Some(103324) }, ObjectIdentifierArc { name: Some("cpm".into()), number: Some(1) }, ObjectIdentifierArc { name: Some("major-version-1".into()), number: Some(1) }, ObjectIdentifierArc { name: Some("minor-version-1".into()), number: Some(1) }])), encoding_reference_default: None, tagging_environment: TaggingEnvironment::Automatic, extensibility_environment: ExtensibilityEnvironment::Explicit, imports: vec![Import { types: vec!["ItsPduHeader".into(), "MessageRateHz".into(), "MessageSegmentationInfo".into(), "OrdinalNumber1B".into(), "ReferencePosition".into(), "StationType".into(), "TimestampIts".into()], origin_name: "ETSI-ITS-CDD".into(), origin_identifier: ObjectIdentifierValue(vec![ObjectIdentifierArc { name: Some("itu-t".into()), number: Some(0) }, ObjectIdentifierArc { name: Some("identified-organization".into()), number: Some(4) }, ObjectIdentifierArc { name: Some("etsi".into()), number: Some(0) }, ObjectIdentifierArc { name: Some("itsDomain".into()), number: Some(5) }, ObjectIdentifierArc { name: Some("wg1".into()), number: Some(1) }, ObjectIdentifierArc { name: Some("ts".into()), number: Some(102894) }, ObjectIdentifierArc { name: Some("cdd".into()), number: Some(2) }, ObjectIdentifierArc { name: Some("major-version-3".into()), number: Some(3) }, ObjectIdentifierArc { name: Some("minor-version-1".into()), number: Some(1) }]), with_successors: true }, Import { types: vec!["OriginatingRsuContainer".into(), "OriginatingVehicleContainer".into()], origin_name: "CPM-OriginatingStationContainers".into(), origin_identifier: ObjectIdentifierValue(vec![ObjectIdentifierArc { name: Some("itu-t".into()), number: Some(0) }, ObjectIdentifierArc { name: Some("identified-organization".into()), number: Some(4) }, ObjectIdentifierArc { name: Some("etsi".into()), number: Some(0) }, ObjectIdentifierArc { name: Some("itsDomain".into()), number: Some(5) }, ObjectIdentifierArc { name: Some("wg1".into()), number: Some(1) }, ObjectIdentifierArc { name: Some("ts".into()), number: Some(103324) }, ObjectIdentifierArc { name: Some("originatingStationContainers".into()), number: Some(2) }, ObjectIdentifierArc { name: Some("major-version-1".into()), number: Some(1) }, ObjectIdentifierArc { name: Some("minor-version-1".into()), number: Some(1) }]), with_successors: true }], exports: None })
+    }
+
+    #[test]
+    fn parses_a_module_reference_with_exports() {
+        let with_exports = module_reference(
+            r#"Exporting DEFINITIONS AUTOMATIC TAGS ::=
+        BEGIN
+        EXPORTS Foo, Bar;
+        IMPORTS Baz FROM Other { iso (1) 2 };
+        "#,
+        )
+        .unwrap()
+        .1;
+        assert_eq!(
+            with_exports.export_list(),
+            Some([String::from("Foo"), String::from("Bar")].as_slice())
+        );
+        assert_eq!(with_exports.imports.len(), 1);
+        let exporting_all = module_reference(
+            r#"Exporting DEFINITIONS AUTOMATIC TAGS ::=
+        BEGIN
+        EXPORTS ALL;
+        "#,
+        )
+        .unwrap()
+        .1;
+        assert_eq!(exporting_all.export_list(), None);
+        let without_exports = module_reference(
+            r#"Exporting DEFINITIONS AUTOMATIC TAGS ::= BEGIN
+        "#,
+        )
+        .unwrap()
+        .1;
+        assert_eq!(without_exports.export_list(), None);
+    }
+
+    #[test]
+    fn parses_a_module_reference_with_encoding_reference_default() {
+        let with_encoding = module_reference(
+            r#"Encoded DEFINITIONS PER INSTRUCTIONS IMPLICIT TAGS ::= BEGIN
+        "#,
+        )
+        .unwrap()
+        .1;
+        assert_eq!(
+            with_encoding.encoding_reference_default,
+            Some(EncodingReferenceDefault("PER".into()))
+        );
+        assert_eq!(with_encoding.tagging_environment, TaggingEnvironment::Implicit);
     }
 }
@@ -2,9 +2,9 @@ use asnr_grammar::*;
 use nom::{
     branch::alt,
     bytes::complete::tag,
-    character::complete::{char, i64, u64, i32},
-    combinator::{map, opt, value},
-    sequence::{delimited, preceded, separated_pair, tuple},
+    character::complete::{char, digit1, i32, i64, one_of},
+    combinator::{map, map_res, opt, recognize, value},
+    sequence::{delimited, preceded, tuple},
     IResult,
 };
 
@@ -38,20 +38,18 @@ pub fn real<'a>(input: &'a str) -> IResult<&'a str, ASN1Type> {
     )(input)
 }
 
+/// Parses a real number in decimal notation, e.g. `-0.05` or `1.5E3`.
+/// The fractional part is mandatory, so that ranges such as `1..5` are not mistaken for reals.
 fn dot_notation<'a>(input: &'a str) -> IResult<&'a str, f64> {
-    map(
-        skip_ws_and_comments(separated_pair(i64, char('.'), u64)),
-        |(wholes, decimals)| {
-            let mut decimals_f64 = decimals as f64;
-            while decimals_f64 > 1. {
-                decimals_f64 /= 10.
-            }
-            if wholes >= 0 {
-                wholes as f64 + decimals_f64
-            } else {
-                wholes as f64 - decimals_f64
-            }
-        },
+    map_res(
+        skip_ws_and_comments(recognize(tuple((
+            opt(char('-')),
+            digit1,
+            char('.'),
+            digit1,
+            opt(tuple((one_of("eE"), opt(one_of("+-")), digit1))),
+        )))),
+        |literal: &str| literal.parse::<f64>(),
     )(input)
 }
 
@@ -177,7 +175,13 @@ mod tests {
     #[test]
     fn parses_dot_notation_real_value() {
         assert_eq!(real_value("2.23412").unwrap().1, ASN1Value::Real(2.23412));
-        assert_eq!(real_value("-12.23412").unwrap().1, ASN1Value::Real(-12.23412))
+        assert_eq!(real_value("-12.23412").unwrap().1, ASN1Value::Real(-12.23412));
+        assert_eq!(real_value("3.05").unwrap().1, ASN1Value::Real(3.05));
+        assert_eq!(real_value("3.10").unwrap().1, ASN1Value::Real(3.1));
+        assert_eq!(real_value("-0.5").unwrap().1, ASN1Value::Real(-0.5));
+        assert_eq!(real_value("1.5E3").unwrap().1, ASN1Value::Real(1500.0));
+        assert_eq!(real_value("2.5e-1").unwrap().1, ASN1Value::Real(0.25));
+        assert!(real_value("1..5").is_err());
     }
 
     #[test]
@@ -49,37 +49,7 @@ pub fn sequence<'a>(input: &'a str) -> IResult<&'a str, ASN1Type> {
                         optional_comma,
                     )),
                     opt(terminated(extension_marker, opt(char(COMMA)))),
-                    opt(many0(terminated(
-                        skip_ws_and_comments(alt((
-                            map(
-                                in_version_brackets(preceded(
-                                    opt(pair(
-                                        skip_ws_and_comments(i128),
-                                        skip_ws_and_comments(char(':')),
-                                    )),
-                                    skip_ws_and_comments(many1(terminated(
-                                        skip_ws_and_comments(sequence_or_set_member),
-                                        optional_comma,
-                                    ))),
-                                )),
-                                |ext_group| SequenceOrSetMember {
-                                    name: String::from("ext_group_")
-                                        + &ext_group.first().unwrap().name,
-                                    tag: None,
-                                    r#type: ASN1Type::Sequence(SequenceOrSet {
-                                        extensible: None,
-                                        constraints: vec![],
-                                        members: ext_group,
-                                    }),
-                                    default_value: None,
-                                    is_optional: false,
-                                    constraints: vec![],
-                                },
-                            ),
-                            sequence_or_set_member,
-                        ))),
-                        optional_comma,
-                    ))),
+                    opt(extension_additions),
                 ))),
                 opt(constraint),
             ),
@@ -88,6 +58,63 @@ pub fn sequence<'a>(input: &'a str) -> IResult<&'a str, ASN1Type> {
     )(input)
 }
 
+/// An extension addition of a SEQUENCE or SET
+enum ExtensionAddition {
+    Group(ExtensionAdditionGroup),
+    Member(SequenceOrSetMember),
+}
+
+/// Parses the extension additions of a SEQUENCE or SET following the first extension marker.
+/// The members following a second extension marker are root components (see X.680 §25.4)
+/// and are returned separately.
+pub fn extension_additions<'a>(input: &'a str) -> IResult<&'a str, ExtensionAdditions> {
+    map(
+        pair(
+            many0(terminated(
+                skip_ws_and_comments(alt((
+                    map(extension_addition_group, ExtensionAddition::Group),
+                    map(sequence_or_set_member, ExtensionAddition::Member),
+                ))),
+                optional_comma,
+            )),
+            opt(preceded(
+                terminated(extension_marker, optional_comma),
+                many0(terminated(
+                    skip_ws_and_comments(sequence_or_set_member),
+                    optional_comma,
+                )),
+            )),
+        ),
+        |(additions, root_components)| {
+            let mut members = vec![];
+            let mut groups = vec![];
+            for addition in additions {
+                match addition {
+                    ExtensionAddition::Group(mut group) => {
+                        group.position = members.len();
+                        groups.push(group);
+                    }
+                    ExtensionAddition::Member(member) => members.push(member),
+                }
+            }
+            (members, groups, root_components.unwrap_or_default())
+        },
+    )(input)
+}
+
+pub fn extension_addition_group<'a>(input: &'a str) -> IResult<&'a str, ExtensionAdditionGroup> {
+    into(in_version_brackets(pair(
+        opt(terminated(
+            skip_ws_and_comments(i128),
+            skip_ws_and_comments(char(':')),
+        )),
+        skip_ws_and_comments(many1(terminated(
+            skip_ws_and_comments(sequence_or_set_member),
+            optional_comma,
+        ))),
+    )))(input)
+}
+
 pub fn sequence_or_set_member<'a>(input: &'a str) -> IResult<&'a str, SequenceOrSetMember> {
     into(tuple((
         skip_ws_and_comments(identifier),
@@ -161,6 +188,29 @@ mod tests {
         );
     }
 
+    #[test]
+    fn parses_member_tagging() {
+        let member = sequence_or_set_member("foo [3] EXPLICIT INTEGER")
+            .unwrap()
+            .1;
+        assert_eq!(
+            member.tag,
+            Some(AsnTag {
+                environment: TaggingEnvironment::Explicit,
+                tag_class: TagClass::ContextSpecific,
+                id: 3
+            })
+        );
+        assert_eq!(
+            sequence_or_set_member("member-a [APPLICATION 1] IMPLICIT INTEGER")
+                .unwrap()
+                .1
+                .tag
+                .map(|t| (t.tag_class, t.environment)),
+            Some((TagClass::Application, TaggingEnvironment::Implicit))
+        );
+    }
+
     #[test]
     fn parses_subtyped_sequence() {
         assert_eq!(
@@ -184,8 +234,10 @@ mod tests {
                     default_value: None,
                     is_optional: true,
                     constraints: vec![],
+                    is_recursive: false,
                 }
-            ]
+            ],
+            extension_groups: vec![],
         })
     )
     }
@@ -215,7 +267,8 @@ mod tests {
                         }),
                         default_value: None,
                         is_optional: false,
-                        constraints: vec![]
+                        constraints: vec![],
+                        is_recursive: false,
                     },
                     SequenceOrSetMember {
                         name: "confidence".into(),
@@ -228,8 +281,10 @@ mod tests {
                         default_value: None,
                         is_optional: false,
                         constraints: vec![],
+                        is_recursive: false,
                     }
-                ]
+                ],
+                extension_groups: vec![],
             })
         )
     }
@@ -262,6 +317,7 @@ mod tests {
                         default_value: None,
                         is_optional: false,
                         constraints: vec![],
+                        is_recursive: false,
                     },
                     SequenceOrSetMember {
                         name: "yCoordinate".into(),
@@ -274,6 +330,7 @@ mod tests {
                         default_value: None,
                         is_optional: false,
                         constraints: vec![],
+                        is_recursive: false,
                     },
                     SequenceOrSetMember {
                         name: "zCoordinate".into(),
@@ -286,8 +343,10 @@ mod tests {
                         default_value: None,
                         is_optional: true,
                         constraints: vec![],
+                        is_recursive: false,
                     }
-                ]
+                ],
+                extension_groups: vec![],
             })
         )
     }
@@ -320,6 +379,7 @@ mod tests {
                         default_value: None,
                         is_optional: true,
                         constraints: vec![],
+                        is_recursive: false,
                     },
                     SequenceOrSetMember {
                         name: "deltaAltitude".into(),
@@ -333,6 +393,7 @@ mod tests {
                         )),
                         is_optional: true,
                         constraints: vec![],
+                        is_recursive: false,
                     },
                     SequenceOrSetMember {
                         name: "altitudeConfidence".into(),
@@ -346,8 +407,10 @@ mod tests {
                         )),
                         is_optional: true,
                         constraints: vec![],
+                        is_recursive: false,
                     }
-                ]
+                ],
+                extension_groups: vec![],
             })
         )
     }
@@ -386,6 +449,7 @@ mod tests {
                         default_value: None,
                         is_optional: false,
                         constraints: vec![],
+                        is_recursive: false,
                     },
                     SequenceOrSetMember {
                         name: "limitedQuantity".into(),
@@ -394,6 +458,7 @@ mod tests {
                         default_value: Some(ASN1Value::Boolean(false)),
                         is_optional: true,
                         constraints: vec![],
+                        is_recursive: false,
                     },
                     SequenceOrSetMember {
                         name: "emergencyActionCode".into(),
@@ -417,8 +482,10 @@ mod tests {
                         default_value: None,
                         is_optional: true,
                         constraints: vec![],
+                        is_recursive: false,
                     }
-                ]
+                ],
+                extension_groups: vec![],
             })
         )
     }
@@ -465,6 +532,7 @@ mod tests {
                                 default_value: None,
                                 is_optional: false,
                                 constraints: vec![],
+                                is_recursive: false,
                             },
                             SequenceOrSetMember {
                                 name: "this-is-annoying".into(),
@@ -474,6 +542,7 @@ mod tests {
                                 default_value: Some(ASN1Value::Boolean(true)),
                                 is_optional: true,
                                 constraints: vec![],
+                                is_recursive: false,
                             },
                             SequenceOrSetMember {
                                 name: "another".into(),
@@ -507,18 +576,24 @@ mod tests {
                                         default_value: Some(ASN1Value::BitString(vec![false])),
                                         is_optional: true,
                                         constraints: vec![],
-                                    }]
+                                        is_recursive: false,
+                                    }],
+                                    extension_groups: vec![],
                                 }),
                                 default_value: None,
                                 is_optional: true,
                                 constraints: vec![],
+                                is_recursive: false,
                             }
-                        ]
+                        ],
+                        extension_groups: vec![],
                     }),
                     default_value: None,
                     is_optional: false,
                     constraints: vec![],
-                }]
+                    is_recursive: false,
+                }],
+                extension_groups: vec![],
             })
         )
     }
@@ -560,69 +635,130 @@ mod tests {
             ASN1Type::Sequence(SequenceOrSet {
                 extensible: Some(1),
                 constraints: vec![],
+                members: vec![SequenceOrSetMember {
+                    name: "item-code".into(),
+                    tag: None,
+                    r#type: ASN1Type::Integer(Integer {
+                        constraints: vec![Constraint::SubtypeConstraint(ElementSet {
+                            set: ElementOrSetOperation::Element(SubtypeElement::ValueRange {
+                                min: Some(ASN1Value::Integer(0)),
+                                max: Some(ASN1Value::Integer(254)),
+                                extensible: false
+                            }),
+                            extensible: false
+                        })],
+                        distinguished_values: None
+                    }),
+                    default_value: None,
+                    is_optional: false,
+                    constraints: vec![],
+                    is_recursive: false,
+                }],
+                extension_groups: vec![ExtensionAdditionGroup {
+                    version: None,
+                    position: 1,
+                    members: vec![
+                        SequenceOrSetMember {
+                            name: "alternate-item-code".into(),
+                            tag: None,
+                            r#type: ASN1Type::Integer(Integer {
+                                constraints: vec![Constraint::SubtypeConstraint(ElementSet {
+                                    set: ElementOrSetOperation::Element(
+                                        SubtypeElement::ValueRange {
+                                            min: Some(ASN1Value::Integer(0)),
+                                            max: Some(ASN1Value::Integer(254)),
+                                            extensible: false
+                                        }
+                                    ),
+                                    extensible: false
+                                })],
+                                distinguished_values: None
+                            }),
+                            default_value: None,
+                            is_optional: false,
+                            constraints: vec![],
+                            is_recursive: false,
+                        },
+                        SequenceOrSetMember {
+                            name: "and-another".into(),
+                            tag: None,
+                            r#type: ASN1Type::Boolean,
+                            default_value: Some(ASN1Value::Boolean(true)),
+                            is_optional: true,
+                            constraints: vec![],
+                            is_recursive: false,
+                        }
+                    ]
+                }],
+            })
+        )
+    }
+
+    #[test]
+    fn parses_root_components_after_extension_additions() {
+        assert_eq!(
+            sequence(
+                "SEQUENCE { base INTEGER, ..., [[ v1 BOOLEAN ]], [[ 2: v2 IA5String ]], ..., trailer BOOLEAN }"
+            )
+            .unwrap()
+            .1,
+            ASN1Type::Sequence(SequenceOrSet {
+                extensible: Some(2),
+                constraints: vec![],
                 members: vec![
                     SequenceOrSetMember {
-                        name: "item-code".into(),
+                        name: "base".into(),
                         tag: None,
                         r#type: ASN1Type::Integer(Integer {
-                            constraints: vec![Constraint::SubtypeConstraint(ElementSet {
-                                set: ElementOrSetOperation::Element(SubtypeElement::ValueRange {
-                                    min: Some(ASN1Value::Integer(0)),
-                                    max: Some(ASN1Value::Integer(254)),
-                                    extensible: false
-                                }),
-                                extensible: false
-                            })],
+                            constraints: vec![],
                             distinguished_values: None
                         }),
                         default_value: None,
                         is_optional: false,
-                        constraints: vec![]
+                        constraints: vec![],
+                        is_recursive: false,
                     },
                     SequenceOrSetMember {
-                        name: "ext_group_alternate-item-code".into(),
+                        name: "trailer".into(),
                         tag: None,
-                        r#type: ASN1Type::Sequence(SequenceOrSet {
-                            extensible: None,
-                            constraints: vec![],
-                            members: vec![
-                                SequenceOrSetMember {
-                                    name: "alternate-item-code".into(),
-                                    tag: None,
-                                    r#type: ASN1Type::Integer(Integer {
-                                        constraints: vec![Constraint::SubtypeConstraint(
-                                            ElementSet {
-                                                set: ElementOrSetOperation::Element(
-                                                    SubtypeElement::ValueRange {
-                                                        min: Some(ASN1Value::Integer(0)),
-                                                        max: Some(ASN1Value::Integer(254)),
-                                                        extensible: false
-                                                    }
-                                                ),
-                                                extensible: false
-                                            }
-                                        )],
-                                        distinguished_values: None
-                                    }),
-                                    default_value: None,
-                                    is_optional: false,
-                                    constraints: vec![]
-                                },
-                                SequenceOrSetMember {
-                                    name: "and-another".into(),
-                                    tag: None,
-                                    r#type: ASN1Type::Boolean,
-                                    default_value: Some(ASN1Value::Boolean(true)),
-                                    is_optional: true,
-                                    constraints: vec![]
-                                }
-                            ]
-                        }),
+                        r#type: ASN1Type::Boolean,
                         default_value: None,
                         is_optional: false,
-                        constraints: vec![]
+                        constraints: vec![],
+                        is_recursive: false,
+                    }
+                ],
+                extension_groups: vec![
+                    ExtensionAdditionGroup {
+                        version: None,
+                        position: 2,
+                        members: vec![SequenceOrSetMember {
+                            name: "v1".into(),
+                            tag: None,
+                            r#type: ASN1Type::Boolean,
+                            default_value: None,
+                            is_optional: false,
+                            constraints: vec![],
+                            is_recursive: false,
+                        }]
+                    },
+                    ExtensionAdditionGroup {
+                        version: Some(2),
+                        position: 2,
+                        members: vec![SequenceOrSetMember {
+                            name: "v2".into(),
+                            tag: None,
+                            r#type: ASN1Type::CharacterString(CharacterString {
+                                constraints: vec![],
+                                r#type: CharacterStringType::IA5String
+                            }),
+                            default_value: None,
+                            is_optional: false,
+                            constraints: vec![],
+                            is_recursive: false,
+                        }]
                     }
-                ]
+                ],
             })
         )
     }
@@ -9,7 +9,7 @@ use nom::{
 
 use asnr_grammar::*;
 
-use super::{common::optional_comma, constraint::constraint, *, sequence::sequence_or_set_member};
+use super::{common::optional_comma, constraint::constraint, *, sequence::{extension_additions, sequence_or_set_member}};
 
 /// Tries to parse an ASN1 SET
 ///
@@ -32,10 +32,7 @@ pub fn set<'a>(input: &'a str) -> IResult<&'a str, ASN1Type> {
                         optional_comma,
                     )),
                     opt(terminated(extension_marker, opt(char(COMMA)))),
-                    opt(many0(terminated(
-                        skip_ws_and_comments(sequence_or_set_member),
-                        optional_comma,
-                    ))),
+                    opt(extension_additions),
                 ))),
                 opt(constraint),
             ),
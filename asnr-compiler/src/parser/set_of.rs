@@ -0,0 +1,78 @@
+use asnr_grammar::*;
+use nom::{
+    bytes::complete::tag,
+    combinator::{map, opt},
+    sequence::{pair, preceded},
+    IResult,
+};
+
+use super::{
+    asn1_type,
+    common::{opt_parentheses, skip_ws_and_comments, value_identifier},
+    constraint::constraint,
+};
+
+/// Tries to parse an ASN1 SET OF
+///
+/// *`input` - string slice to be matched against
+///
+/// `set_of` will try to match an SET OF declaration in the `input` string.
+/// If the match succeeds, the parser will consume the match and return the remaining string
+/// and a wrapped `SequenceOf` value representing the ASN1 declaration.
+/// If the match fails, the parser will not consume the input and will return an error.
+pub fn set_of(input: &str) -> IResult<&str, ASN1Type> {
+    map(
+        pair(
+            preceded(
+                skip_ws_and_comments(tag(SET)),
+                opt(opt_parentheses(constraint)),
+            ),
+            preceded(
+                skip_ws_and_comments(pair(tag(OF), opt(skip_ws_and_comments(value_identifier)))),
+                asn1_type,
+            ),
+        ),
+        |m| ASN1Type::SetOf(m.into()),
+    )(input)
+}
+
+#[cfg(test)]
+mod tests {
+    use asnr_grammar::{constraints::*, types::*, *};
+
+    use crate::parser::set_of;
+
+    #[test]
+    fn parses_simple_set_of() {
+        assert_eq!(
+            set_of("SET OF BOOLEAN").unwrap().1,
+            ASN1Type::SetOf(SequenceOf {
+                constraints: vec![],
+                r#type: Box::new(ASN1Type::Boolean)
+            })
+        );
+    }
+
+    #[test]
+    fn parses_constrained_set_of_elsewhere_declared_type() {
+        assert_eq!(
+            set_of("SET (SIZE (1..4)) OF Things").unwrap().1,
+            ASN1Type::SetOf(SequenceOf {
+                constraints: vec![Constraint::SubtypeConstraint(ElementSet {
+                    set: ElementOrSetOperation::Element(SubtypeElement::SizeConstraint(Box::new(
+                        ElementOrSetOperation::Element(SubtypeElement::ValueRange {
+                            min: Some(ASN1Value::Integer(1)),
+                            max: Some(ASN1Value::Integer(4)),
+                            extensible: false
+                        })
+                    ))),
+                    extensible: false
+                })],
+                r#type: Box::new(ASN1Type::ElsewhereDeclaredType(DeclarationElsewhere {
+                    identifier: "Things".into(),
+                    constraints: vec![]
+                }))
+            })
+        );
+    }
+}
@@ -22,6 +22,14 @@ impl ValidatorError {
 pub enum ValidatorErrorType {
     MissingDependency,
     InvalidConstraintsError,
+    DuplicateDeclaration,
+    CircularDependency,
+    UnreachableChoiceAlternative,
+    UnsupportedTableConstraint,
+    MandatoryAfterOptional,
+    UnknownObjectIdentifierArc,
+    UnresolvedImport,
+    UnrepresentableDistinguishedValue,
 }
 
 impl Error for ValidatorError {}
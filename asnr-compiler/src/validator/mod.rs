@@ -7,27 +7,97 @@
 //! constraints and value definitions.
 pub(crate) mod error;
 
-use std::{error::Error, collections::BTreeMap};
+use std::{error::Error, collections::{btree_map::Entry, BTreeMap, BTreeSet}};
 
 use asnr_grammar::{
     constraints::*,
+    encoding_rules::per_visible::per_visible_range_constraints,
+    error::GrammarError,
+    information_object::*,
+    parameterization::Parameterization,
     types::*,
     *,
 };
 
+use asnr_grammar::utils::{resolve_oid_arc_name, to_rust_title_case};
+
+use crate::{generator::templates::fits_integer_type, CompileResult};
+
 use self::error::{ValidatorError, ValidatorErrorType};
 
+/// The header of an ASN1 module paired with the module's validated top-level declarations
+pub type ModuleDeclarations = (ModuleReference, Vec<ToplevelDeclaration>);
+
 pub struct Validator {
     tlds: BTreeMap<String, ToplevelDeclaration>,
+    duplicates: Vec<ToplevelDeclaration>,
+    merge_duplicates: bool,
+    modules: Vec<ModuleReference>,
+    module_by_tld: BTreeMap<String, String>,
 }
 
 impl Validator {
     pub fn new(tlds: Vec<ToplevelDeclaration>) -> Validator {
-        Self { tlds: tlds.into_iter().map(|tld| (tld.name().to_owned(), tld)).collect() }
+        let mut unique = BTreeMap::new();
+        let mut duplicates = vec![];
+        for tld in tlds {
+            if let Some(previous) = unique.insert(tld.name().to_owned(), tld) {
+                duplicates.push(previous);
+            }
+        }
+        Self {
+            tlds: unique,
+            duplicates,
+            merge_duplicates: true,
+            modules: vec![],
+            module_by_tld: BTreeMap::new(),
+        }
     }
 
-    fn link(mut self) -> Result<(Self, Vec<Box<dyn Error>>), ValidatorError> {
+    /// Sets the headers of the ASN1 modules declaring the top-level declarations,
+    /// so that the imports of the modules can be resolved.
+    /// * `modules` - the headers of the ASN1 modules
+    /// * `module_by_tld` - the name of the module declaring a top-level declaration, by declaration name
+    pub fn modules(
+        mut self,
+        modules: &[ModuleReference],
+        module_by_tld: &BTreeMap<String, String>,
+    ) -> Self {
+        self.modules = modules.to_vec();
+        self.module_by_tld = module_by_tld.clone();
+        self
+    }
+
+    /// Sets whether structurally identical top-level declarations with the same name
+    /// are merged into a single declaration. Merging is enabled by default.
+    pub fn merge_duplicate_declarations(mut self, merge: bool) -> Self {
+        self.merge_duplicates = merge;
+        self
+    }
+
+    fn check_duplicates(&mut self) -> Result<Vec<Box<dyn Error>>, ValidatorError> {
         let mut warnings: Vec<Box<dyn Error>> = vec![];
+        for duplicate in self.duplicates.drain(..) {
+            let name = duplicate.name().to_owned();
+            if self.merge_duplicates && self.tlds.get(&name) == Some(&duplicate) {
+                warnings.push(Box::new(ValidatorError::new(
+                    Some(name.clone()),
+                    &format!("Merged identical duplicate declarations of {name}"),
+                    ValidatorErrorType::DuplicateDeclaration,
+                )));
+            } else {
+                return Err(ValidatorError::new(
+                    Some(name.clone()),
+                    &format!("Found conflicting duplicate declarations of {name}"),
+                    ValidatorErrorType::DuplicateDeclaration,
+                ));
+            }
+        }
+        Ok(warnings)
+    }
+
+    fn link(mut self) -> Result<(Self, Vec<Box<dyn Error>>), ValidatorError> {
+        let mut warnings: Vec<Box<dyn Error>> = self.resolve_imports();
         let mut keys = self.tlds.keys().cloned().collect::<Vec<String>>();
         while let Some(key) = keys.pop() {
             if self.has_class_field_reference(&key) {
@@ -53,8 +123,9 @@ impl Validator {
                 self.tlds.insert(tld.name().clone(), tld);
             } else if self.has_constraint_reference(&key) {
                 let mut tld = self.tlds.remove(&key).ok_or(ValidatorError { data_element: Some(key), details: "Could not find toplevel declaration to remove!".into(), kind: ValidatorErrorType::MissingDependency } )?;
-                if !tld.link_constraint_reference(&self.tlds) {
-                    warnings.push(
+                match tld.link_constraint_reference(&self.tlds) {
+                    Ok(true) => (),
+                    Ok(false) => warnings.push(
                         Box::new(
                             ValidatorError { 
                                 data_element: Some(tld.name().to_string()), 
@@ -64,6 +135,33 @@ impl Validator {
                                 kind: ValidatorErrorType::MissingDependency
                             }
                         )
+                    ),
+                    Err(e) => warnings.push(
+                        Box::new(
+                            ValidatorError { 
+                                data_element: Some(tld.name().to_string()), 
+                                details: format!(
+                                    "Failed to link cross-reference in constraint of {}: {}", 
+                                    tld.name(), e), 
+                                kind: ValidatorErrorType::InvalidConstraintsError
+                            }
+                        )
+                    ),
+                }
+                self.tlds.insert(tld.name().clone(), tld);
+            } else if self.has_object_set_reference(&key) {
+                let mut tld = self.tlds.remove(&key).ok_or(ValidatorError { data_element: Some(key), details: "Could not find toplevel declaration to remove!".into(), kind: ValidatorErrorType::MissingDependency } )?;
+                if !tld.link_object_set_reference(&self.tlds) {
+                    warnings.push(
+                        Box::new(
+                            ValidatorError { 
+                                data_element: Some(tld.name().to_string()), 
+                                details: format!(
+                                    "Failed to link cross-reference in information object set {}", 
+                                    tld.name()), 
+                                kind: ValidatorErrorType::MissingDependency
+                            }
+                        )
                     )
                 }
                 self.tlds.insert(tld.name().clone(), tld);
@@ -77,18 +175,24 @@ impl Validator {
                             tld.value = ASN1Value::Integer(val);
                             self.tlds.remove(&key);
                             self.tlds.insert(tld.name.clone(),ToplevelDeclaration::Value(tld));
+                            continue;
                           }
                         },
-                        ASN1Type::Enumerated(_) => {
+                        ASN1Type::Enumerated(ref e) if e.members.iter().any(|m| &m.name == id) => {
                             tld.value = ASN1Value::EnumeratedValue { enumerated: ty.name.clone(), enumerable: id.to_owned() };
                             self.tlds.remove(&key);
                             self.tlds.insert(tld.name.clone(), ToplevelDeclaration::Value(tld));
+                            continue;
                         }
                         _ => ()
                       }
                     },
                     _ => ()
                   }
+                  if let Some(value) = self.resolve_value_reference(id, 0) {
+                      tld.value = value;
+                      self.tlds.insert(tld.name.clone(), ToplevelDeclaration::Value(tld));
+                  }
               }
             }
         }
@@ -96,19 +200,105 @@ impl Validator {
         Ok((self, warnings))
     }
 
+    /// Follows a reference to an elsewhere declared value to the value that it refers to
+    fn resolve_value_reference(&self, identifier: &String, depth: usize) -> Option<ASN1Value> {
+        // guards against circular value references
+        if depth > self.tlds.len() {
+            return None;
+        }
+        match self.tlds.get(identifier) {
+            Some(ToplevelDeclaration::Value(v)) => match &v.value {
+                ASN1Value::ElsewhereDeclaredValue(id) => self.resolve_value_reference(id, depth + 1),
+                value => Some(value.clone()),
+            },
+            _ => None,
+        }
+    }
+
+    /// Checks whether a type declaration depends on a value, e.g. on `fifteen`
+    /// in `Departures ::= SEQUENCE (SIZE(0..fifteen)) OF Departure`.
+    /// Unresolved dependencies are considered values if they are value references,
+    /// i.e. start with a lower-case letter, so that they are reported when linking fails.
     fn has_constraint_reference(&mut self, key: &String) -> bool {
+        let Some(tld @ ToplevelDeclaration::Type(_)) = self.tlds.get(key) else {
+            return false;
+        };
+        tld.dependencies()
+            .iter()
+            .any(|dependency| match self.tlds.get(dependency) {
+                Some(ToplevelDeclaration::Value(_)) => true,
+                Some(_) => false,
+                None => dependency.starts_with(|c: char| c.is_lowercase()),
+            })
+    }
+
+    /// Looks up the imports of the modules in the modules they are imported from.
+    /// Imports from modules that are not compiled and imported names that the originating
+    /// module does not declare are reported as warnings.
+    fn resolve_imports(&self) -> Vec<Box<dyn Error>> {
+        let all_modules = self
+            .modules
+            .iter()
+            .map(|module| {
+                let declarations = self
+                    .tlds
+                    .values()
+                    .filter(|tld| self.module_by_tld.get(tld.name()) == Some(&module.name))
+                    .cloned()
+                    .collect();
+                (module.clone(), declarations)
+            })
+            .collect::<Vec<ModuleDeclarations>>();
+        let mut warnings: Vec<Box<dyn Error>> = vec![];
+        for module in &self.modules {
+            for import in &module.imports {
+                let is_compiled = all_modules.iter().any(|(origin, _)| {
+                    origin.name == import.origin_name
+                        || origin.module_identifier.as_ref() == Some(&import.origin_identifier)
+                });
+                if !is_compiled {
+                    warnings.push(Box::new(ValidatorError::new(
+                        None,
+                        &format!(
+                            "Module {} imports from module {} that is not compiled",
+                            module.name, import.origin_name
+                        ),
+                        ValidatorErrorType::UnresolvedImport,
+                    )));
+                    continue;
+                }
+                let resolved = import
+                    .resolve(&all_modules)
+                    .map(|tld| tld.name())
+                    .collect::<BTreeSet<&String>>();
+                for name in import.types.iter().filter(|name| !resolved.contains(name)) {
+                    warnings.push(Box::new(ValidatorError::new(
+                        Some(name.clone()),
+                        &format!(
+                            "Module {} imports {name} from module {}, which does not declare it",
+                            module.name, import.origin_name
+                        ),
+                        ValidatorErrorType::UnresolvedImport,
+                    )));
+                }
+            }
+        }
+        warnings
+    }
+
+    fn has_default_value_reference(&mut self, key: &String) -> bool {
         self
             .tlds
             .get(key)
-            .map(|t| t.has_constraint_reference())
+            .map(|t| t.has_default_reference())
             .unwrap_or(false)
     }
 
-    fn has_default_value_reference(&mut self, key: &String) -> bool {
+    fn has_object_set_reference(&mut self, key: &String) -> bool {
         self
             .tlds
             .get(key)
-            .map(|t| t.has_default_reference())
+            .map(|t| t.has_object_set_reference())
             .unwrap_or(false)
     }
 
@@ -123,22 +313,1259 @@ impl Validator {
             .unwrap_or(false)
     }
 
+    /// Drops the relational constraints of open type members whose types cannot be looked up
+    /// in a generated information object set, so that they are decoded as plain open types.
+    /// A lookup requires an object set with INTEGER keys that is fully linked to its class
+    /// and objects, and a mandatory key member that precedes the open type member.
+    fn unlink_unsupported_table_constraints(&mut self) -> Vec<Box<dyn Error>> {
+        let mut warnings: Vec<Box<dyn Error>> = vec![];
+        let keys = self.tlds.keys().cloned().collect::<Vec<String>>();
+        for key in keys {
+            let Some(ToplevelDeclaration::Type(mut tld)) = self.tlds.get(&key).cloned() else {
+                continue;
+            };
+            let ASN1Type::Sequence(ref mut seq) = tld.r#type else {
+                continue;
+            };
+            let members = seq.members.clone();
+            let mut unlinked = false;
+            for (index, member) in seq.members.iter_mut().enumerate() {
+                let ASN1Type::InformationObjectFieldReference(ref mut reference) = member.r#type
+                else {
+                    continue;
+                };
+                for constraint in reference.constraints.iter_mut() {
+                    let Constraint::TableConstraint(table) = constraint else {
+                        continue;
+                    };
+                    if table.linked_fields.is_empty()
+                        || self.supports_lookup(table, &members[..index])
+                    {
+                        continue;
+                    }
+                    table.linked_fields = vec![];
+                    unlinked = true;
+                    // references to undeclared object sets are not looked up at all
+                    if !table.object_set.values.iter().all(|v| match v {
+                        ObjectSetValue::Reference(set_name) => self.tlds.contains_key(set_name),
+                        _ => true,
+                    }) {
+                        continue;
+                    }
+                    warnings.push(Box::new(ValidatorError::new(
+                        Some(tld.name.clone()),
+                        &format!(
+                            "Open type member {} cannot be looked up in its object set and is decoded as a plain open type",
+                            member.name
+                        ),
+                        ValidatorErrorType::UnsupportedTableConstraint,
+                    )));
+                }
+            }
+            if unlinked {
+                self.tlds.insert(key, ToplevelDeclaration::Type(tld));
+            }
+        }
+        warnings
+    }
+
+    /// Checks whether the type of an open type member can be looked up in the object set
+    /// of its table constraint.
+    /// * `preceding_members` - the members that precede the open type member
+    fn supports_lookup(&self, table: &TableConstraint, preceding_members: &[SequenceOrSetMember]) -> bool {
+        let (set_name, key_member) = match (&table.object_set.values[..], &table.linked_fields[..]) {
+            ([ObjectSetValue::Reference(set_name)], [RelationalConstraint { field_name, level: 0 }]) => {
+                (set_name, field_name)
+            }
+            _ => return false,
+        };
+        let class = match self.tlds.get(set_name) {
+            Some(ToplevelDeclaration::Information(ToplevelInformationDeclaration {
+                class: Some(ClassLink::ByReference(class)),
+                value: ASN1Information::ObjectSet(set),
+                ..
+            })) if set.values.iter().all(|v| matches!(v, ObjectSetValue::Inline(_))) => class,
+            _ => return false,
+        };
+        let has_integer_key = class
+            .fields
+            .iter()
+            .find(|f| f.is_unique)
+            .and_then(|f| f.r#type.as_ref())
+            .map_or(false, |t| self.is_integer(t, 0));
+        has_integer_key
+            && preceding_members.iter().any(|m| {
+                &m.name == key_member
+                    && !m.is_optional
+                    && m.default_value.is_none()
+                    && self.is_integer(&m.r#type, 0)
+            })
+    }
+
+    /// Checks whether a type is an INTEGER or refers to an INTEGER
+    fn is_integer(&self, r#type: &ASN1Type, depth: usize) -> bool {
+        match r#type {
+            ASN1Type::Integer(_) => true,
+            // guards against circular type references
+            ASN1Type::ElsewhereDeclaredType(e) if depth <= self.tlds.len() => {
+                matches!(
+                    self.tlds.get(&e.identifier),
+                    Some(ToplevelDeclaration::Type(t)) if self.is_integer(&t.r#type, depth + 1)
+                )
+            }
+            _ => false,
+        }
+    }
+
+    /// Numbers the arcs of OBJECT IDENTIFIER values that are only given by name, e.g. `iso`
+    /// in `{ iso 3 }`, using the well-known names of the root arcs and of the arcs below them.
+    /// Arcs with unknown names are left unnumbered and reported as warnings.
+    fn resolve_object_identifier_arcs(&mut self) -> Vec<Box<dyn Error>> {
+        let mut warnings: Vec<Box<dyn Error>> = vec![];
+        for tld in self.tlds.values_mut() {
+            let ToplevelDeclaration::Value(ToplevelValueDeclaration {
+                name,
+                value: ASN1Value::ObjectIdentifier(oid),
+                ..
+            }) = tld
+            else {
+                continue;
+            };
+            let mut root = None;
+            for (index, arc) in oid.0.iter_mut().enumerate() {
+                if let (Some(arc_name), None) = (&arc.name, arc.number) {
+                    // names of arcs further down the tree are not well-known
+                    arc.number = match index {
+                        0 => resolve_oid_arc_name(None, arc_name),
+                        1 => root.and_then(|root| resolve_oid_arc_name(Some(root), arc_name)),
+                        _ => None,
+                    };
+                    if arc.number.is_none() {
+                        warnings.push(Box::new(ValidatorError::new(
+                            Some(name.clone()),
+                            &format!("Failed to resolve the number of the OBJECT IDENTIFIER arc {arc_name}"),
+                            ValidatorErrorType::UnknownObjectIdentifierArc,
+                        )));
+                    }
+                }
+                if index == 0 {
+                    root = arc.number;
+                }
+            }
+        }
+        warnings
+    }
+
+    /// Flags the members of SEQUENCEs and SETs and the options of CHOICEs whose types
+    /// refer back to the containing declaration by value, so that the generators box them.
+    /// Members of a cycle that is already broken by a flagged member are not flagged.
+    fn mark_recursive_members(&mut self) {
+        let keys = self.tlds.keys().cloned().collect::<Vec<String>>();
+        for key in keys {
+            let Some(ToplevelDeclaration::Type(mut tld)) = self.tlds.get(&key).cloned() else {
+                continue;
+            };
+            match &mut tld.r#type {
+                ASN1Type::Sequence(s) | ASN1Type::Set(s) => {
+                    for member in s.all_members_mut() {
+                        member.is_recursive =
+                            refers_by_value(&member.r#type, &key, &self.tlds, &mut BTreeSet::new());
+                    }
+                }
+                ASN1Type::Choice(c) => {
+                    for option in &mut c.options {
+                        option.is_recursive =
+                            refers_by_value(&option.r#type, &key, &self.tlds, &mut BTreeSet::new());
+                    }
+                }
+                _ => continue,
+            }
+            self.tlds.insert(key, ToplevelDeclaration::Type(tld));
+        }
+    }
+
+    /// Makes the tags of references to untagged CHOICE types explicit, since CHOICE types
+    /// cannot be tagged implicitly (see X.680 31.2.7). Tags of inline CHOICE types are made
+    /// explicit when the module's tagging environment is applied.
+    fn make_choice_reference_tags_explicit(&mut self) {
+        let keys = self.tlds.keys().cloned().collect::<Vec<String>>();
+        for key in keys {
+            let Some(ToplevelDeclaration::Type(mut tld)) = self.tlds.get(&key).cloned() else {
+                continue;
+            };
+            let mut changed = make_tag_explicit(&mut tld.tag, &tld.r#type, &self.tlds);
+            changed |= make_component_tags_explicit(&mut tld.r#type, &self.tlds);
+            if changed {
+                self.tlds.insert(key, ToplevelDeclaration::Type(tld));
+            }
+        }
+    }
+
+    /// Links the members of SEQUENCE and CHOICE value declarations to their types,
+    /// so that the generators can construct the values without looking up the types.
+    /// Absent members are populated with their DEFAULT values, values of OPTIONAL members
+    /// are wrapped in `ASN1Value::Optional`, and references to values and enumerals are resolved.
+    /// References that cannot be resolved are kept for the generators to report.
+    /// Values of aliases of SEQUENCEs and CHOICEs are not linked.
+    fn link_composite_values(&mut self) {
+        let keys = self.tlds.keys().cloned().collect::<Vec<String>>();
+        for key in keys {
+            let Some(ToplevelDeclaration::Value(mut tld)) = self.tlds.get(&key).cloned() else {
+                continue;
+            };
+            if !matches!(tld.value, ASN1Value::Sequence(_) | ASN1Value::Choice(_, _)) {
+                continue;
+            }
+            let Some(ToplevelDeclaration::Type(ty)) = self.tlds.get(&tld.type_name) else {
+                continue;
+            };
+            let linked = link_nested_value(&ty.r#type, &ty.name, tld.value, &self.tlds, 0);
+            if matches!(linked, ASN1Value::Sequence(_) | ASN1Value::Choice(_, _)) {
+                tld.value = linked;
+                self.tlds.insert(key, ToplevelDeclaration::Value(tld));
+            }
+        }
+    }
+
+    /// Links the SEQUENCE and CHOICE values of DEFAULT members to the members of their types,
+    /// in the same manner as [Validator::link_composite_values] links value declarations.
+    fn link_default_values(&mut self) {
+        let keys = self.tlds.keys().cloned().collect::<Vec<String>>();
+        for key in keys {
+            let Some(ToplevelDeclaration::Type(mut tld)) = self.tlds.get(&key).cloned() else {
+                continue;
+            };
+            if link_member_defaults(&mut tld.r#type, &tld.name, &self.tlds) {
+                self.tlds.insert(key, ToplevelDeclaration::Type(tld));
+            }
+        }
+    }
+
+    /// Replaces the instantiations of parameterized types by monomorphized top-level declarations,
+    /// in which the formal type parameters are substituted by the actual parameters.
+    /// A declaration that consists of nothing but an instantiation, e.g. `Speeds ::= Container {Speed}`,
+    /// declares the instantiated type itself, and other instantiations with the same actual parameters
+    /// refer to it. Otherwise, instantiations are named after the parameterized type and
+    /// the actual parameters, e.g. `Container-Of-Speed`. The parameterized types are removed,
+    /// since their formal parameters cannot be represented. Parameterized types
+    /// with formal parameters that do not denote types are left untouched.
+    fn monomorphize_parameterized_types(&mut self) {
+        let templates = self
+            .tlds
+            .values()
+            .filter_map(|tld| match tld {
+                ToplevelDeclaration::Type(ToplevelTypeDeclaration {
+                    name,
+                    r#type,
+                    parameterization: Some(parameterization),
+                    ..
+                }) if parameterization.parameters.iter().all(|p| p.name.is_none()) => {
+                    Some((name.clone(), (parameterization.clone(), r#type.clone())))
+                }
+                _ => None,
+            })
+            .collect::<BTreeMap<String, (Parameterization, ASN1Type)>>();
+        if templates.is_empty() {
+            return;
+        }
+        for name in templates.keys() {
+            self.tlds.remove(name);
+        }
+        let mut instances = BTreeMap::<String, String>::new();
+        for tld in self.tlds.values_mut() {
+            if let ToplevelDeclaration::Type(t) = tld {
+                if let Some((key, instance)) = instantiate(&t.r#type, &templates) {
+                    if let Entry::Vacant(entry) = instances.entry(key) {
+                        entry.insert(t.name.clone());
+                        t.r#type = instance;
+                    }
+                }
+            }
+        }
+        let mut pending = self.tlds.keys().cloned().collect::<Vec<String>>();
+        while let Some(key) = pending.pop() {
+            let Some(ToplevelDeclaration::Type(mut tld)) = self.tlds.remove(&key) else {
+                continue;
+            };
+            let mut created = vec![];
+            replace_instantiations(
+                &mut tld.r#type,
+                &templates,
+                &mut instances,
+                &self.tlds,
+                &mut created,
+            );
+            self.tlds.insert(key, ToplevelDeclaration::Type(tld));
+            for instance in created {
+                pending.push(instance.name.clone());
+                self.tlds
+                    .insert(instance.name.clone(), ToplevelDeclaration::Type(instance));
+            }
+        }
+    }
+
     pub fn validate(
         mut self,
     ) -> Result<(Vec<ToplevelDeclaration>, Vec<Box<dyn Error>>), Box<dyn Error>> {
-        let warnings: Vec<Box<dyn Error>>;
-        (self, warnings) = self.link()?;
-        Ok(self.tlds.into_iter().fold(
+        let mut warnings = self.check_duplicates()?;
+        self.monomorphize_parameterized_types();
+        let mut link_warnings: Vec<Box<dyn Error>>;
+        (self, link_warnings) = self.link()?;
+        warnings.append(&mut link_warnings);
+        warnings.append(&mut self.unlink_unsupported_table_constraints());
+        warnings.append(&mut self.resolve_object_identifier_arcs());
+        self.mark_recursive_members();
+        self.make_choice_reference_tags_explicit();
+        self.link_composite_values();
+        self.link_default_values();
+        Ok(self.tlds.iter().fold(
             (Vec::<ToplevelDeclaration>::new(), warnings),
             |(mut tlds, mut errors), (_, tld)| {
-                match tld.validate() {
-                    Ok(_) => tlds.push(tld),
+                match tld
+                    .validate()
+                    .and_then(|_| validate_class_field_references(tld, &self.tlds))
+                    .and_then(|warnings| {
+                        validate_subtype_constraints(tld, &self.tlds).map(|_| warnings)
+                    }) {
+                    Ok(class_warnings) => {
+                        errors.extend(
+                            class_warnings
+                                .into_iter()
+                                .chain(unreachable_choice_alternatives(tld, &self.tlds))
+                                .chain(mandatory_members_after_optional(tld))
+                                .chain(unrepresentable_distinguished_values(tld))
+                                .map(|w| Box::new(w) as Box<dyn Error>),
+                        );
+                        tlds.push(tld.clone())
+                    }
                     Err(e) => errors.push(Box::new(e)),
                 }
                 (tlds, errors)
             },
         ))
     }
+
+    /// Validates the top-level declarations and groups them by the ASN1 modules declaring them.
+    /// Declarations introduced by the validator, such as instantiations of parameterized types,
+    /// are assigned to the module of the declaration they are derived from if it can be told,
+    /// and to the first module otherwise. Modules keep the order of `modules`.
+    /// * `modules` - the headers of the ASN1 modules
+    /// * `module_by_tld` - the name of the module declaring a top-level declaration, by declaration name
+    pub fn validate_by_module(
+        self,
+        modules: Vec<ModuleReference>,
+        module_by_tld: &BTreeMap<String, String>,
+    ) -> CompileResult<Vec<ModuleDeclarations>> {
+        let (tlds, warnings) = self.modules(&modules, module_by_tld).validate()?;
+        let mut grouped = modules
+            .into_iter()
+            .map(|module| (module, vec![]))
+            .collect::<Vec<ModuleDeclarations>>();
+        for tld in tlds {
+            let module_name = module_by_tld.get(tld.name()).or_else(|| {
+                module_by_tld
+                    .iter()
+                    .filter(|(name, _)| tld.name().starts_with(&format!("{name}-Of-")))
+                    .max_by_key(|(name, _)| name.len())
+                    .map(|(_, module)| module)
+            });
+            let index = module_name
+                .and_then(|name| grouped.iter().position(|(module, _)| &module.name == name))
+                .unwrap_or_default();
+            if let Some((_, declarations)) = grouped.get_mut(index) {
+                declarations.push(tld);
+            }
+        }
+        Ok((grouped, warnings))
+    }
+}
+
+/// Links a value to the members of its type. See [Validator::link_composite_values].
+/// * `type_name` - the name of the type, from which the names of its inline types are derived
+fn link_nested_value(
+    r#type: &ASN1Type,
+    type_name: &str,
+    value: ASN1Value,
+    tlds: &BTreeMap<String, ToplevelDeclaration>,
+    depth: usize,
+) -> ASN1Value {
+    // guards against circular type and value references
+    if depth > tlds.len() {
+        return value;
+    }
+    match (r#type, value) {
+        (ASN1Type::ElsewhereDeclaredType(e), value) => match tlds.get(&e.identifier) {
+            Some(ToplevelDeclaration::Type(t)) => ASN1Value::LinkedNestedValue {
+                r#type: Box::new(t.r#type.clone()),
+                value: Box::new(link_nested_value(
+                    &t.r#type,
+                    &t.name,
+                    value,
+                    tlds,
+                    depth + 1,
+                )),
+            },
+            _ => value,
+        },
+        (ASN1Type::Enumerated(e), ASN1Value::ElsewhereDeclaredValue(id))
+            if e.members.iter().any(|m| m.name == id) =>
+        {
+            ASN1Value::EnumeratedValue {
+                enumerated: type_name.to_owned(),
+                enumerable: id,
+            }
+        }
+        (r#type, ASN1Value::ElsewhereDeclaredValue(id)) => match tlds.get(&id) {
+            Some(ToplevelDeclaration::Value(v)) => {
+                link_nested_value(r#type, type_name, v.value.clone(), tlds, depth + 1)
+            }
+            _ => ASN1Value::ElsewhereDeclaredValue(id),
+        },
+        (ASN1Type::Sequence(s) | ASN1Type::Set(s), ASN1Value::Sequence(mut values)) => {
+            let mut linked = vec![];
+            for member in &s.members {
+                let value = values
+                    .iter()
+                    .position(|(name, _)| name == &member.name)
+                    .map(|index| *values.remove(index).1)
+                    .or_else(|| member.default_value.clone())
+                    .map(|v| {
+                        let inner_type_name = inline_type_name(type_name, &member.name);
+                        ASN1Value::LinkedNestedValue {
+                            r#type: Box::new(member.r#type.clone()),
+                            value: Box::new(link_nested_value(
+                                &member.r#type,
+                                &inner_type_name,
+                                v,
+                                tlds,
+                                depth + 1,
+                            )),
+                        }
+                    });
+                let value = match value {
+                    Some(v) if member.is_optional && member.default_value.is_none() => {
+                        ASN1Value::Optional(Some(Box::new(v)))
+                    }
+                    Some(v) => v,
+                    None if member.is_optional => ASN1Value::Optional(None),
+                    None => continue,
+                };
+                linked.push((member.name.clone(), Box::new(value)));
+            }
+            // values of undeclared members are kept for the generators
+            linked.append(&mut values);
+            ASN1Value::Sequence(linked)
+        }
+        (ASN1Type::Choice(c), ASN1Value::Choice(option, value)) => {
+            match c.options.iter().find(|o| o.name == option) {
+                Some(o) => {
+                    let inner_type_name = inline_type_name(type_name, &option);
+                    let value = ASN1Value::LinkedNestedValue {
+                        r#type: Box::new(o.r#type.clone()),
+                        value: Box::new(link_nested_value(
+                            &o.r#type,
+                            &inner_type_name,
+                            *value,
+                            tlds,
+                            depth + 1,
+                        )),
+                    };
+                    ASN1Value::Choice(option, Box::new(value))
+                }
+                None => ASN1Value::Choice(option, value),
+            }
+        }
+        (_, value) => value,
+    }
+}
+
+/// Instantiates a parameterized type if `r#type` refers to one of the `templates`
+/// with actual parameters, and no further constraints.
+/// Returns a key identifying the instantiation and the instantiated type.
+fn instantiate(
+    r#type: &ASN1Type,
+    templates: &BTreeMap<String, (Parameterization, ASN1Type)>,
+) -> Option<(String, ASN1Type)> {
+    let ASN1Type::ElsewhereDeclaredType(DeclarationElsewhere {
+        identifier,
+        constraints,
+    }) = r#type
+    else {
+        return None;
+    };
+    let [Constraint::Parameter(actual_parameters)] = constraints.as_slice() else {
+        return None;
+    };
+    let (parameterization, template) = templates.get(identifier)?;
+    let mut instance = template.clone();
+    instance.substitute_parameters(parameterization, actual_parameters);
+    Some((format!("{identifier}{actual_parameters:?}"), instance))
+}
+
+/// Replaces the instantiations of parameterized types within `r#type` by references
+/// to monomorphized declarations. Instantiations that have not been declared yet
+/// are added to `created`.
+/// * `instances` - the names of the declared instantiations by their keys
+/// * `tlds` - the top-level declarations, whose names are not reused for instantiations
+fn replace_instantiations(
+    r#type: &mut ASN1Type,
+    templates: &BTreeMap<String, (Parameterization, ASN1Type)>,
+    instances: &mut BTreeMap<String, String>,
+    tlds: &BTreeMap<String, ToplevelDeclaration>,
+    created: &mut Vec<ToplevelTypeDeclaration>,
+) {
+    match r#type {
+        ASN1Type::ElsewhereDeclaredType(e) if templates.contains_key(&e.identifier) => {
+            let Some(position) = e
+                .constraints
+                .iter()
+                .position(|c| matches!(c, Constraint::Parameter(_)))
+            else {
+                return;
+            };
+            let Constraint::Parameter(mut actual_parameters) = e.constraints.remove(position)
+            else {
+                return;
+            };
+            for parameter in actual_parameters.iter_mut() {
+                if let Parameter::TypeParameter(t) = parameter {
+                    replace_instantiations(t, templates, instances, tlds, created);
+                }
+            }
+            let reference = ASN1Type::ElsewhereDeclaredType(DeclarationElsewhere {
+                identifier: e.identifier.clone(),
+                constraints: vec![Constraint::Parameter(actual_parameters.clone())],
+            });
+            let Some((key, instance)) = instantiate(&reference, templates) else {
+                return;
+            };
+            let name = match instances.get(&key) {
+                Some(name) => name.clone(),
+                None => {
+                    let name = instance_name(&e.identifier, &actual_parameters, |n| {
+                        tlds.contains_key(n) || instances.values().any(|i| i == n)
+                    });
+                    instances.insert(key, name.clone());
+                    created.push(ToplevelTypeDeclaration {
+                        comments: String::new(),
+                        tag: None,
+                        name: name.clone(),
+                        r#type: instance,
+                        parameterization: None,
+                    });
+                    name
+                }
+            };
+            e.identifier = name;
+        }
+        ASN1Type::Sequence(s) | ASN1Type::Set(s) => s.all_members_mut().for_each(|m| {
+            replace_instantiations(&mut m.r#type, templates, instances, tlds, created)
+        }),
+        ASN1Type::Choice(c) => c.options.iter_mut().for_each(|o| {
+            replace_instantiations(&mut o.r#type, templates, instances, tlds, created)
+        }),
+        ASN1Type::SequenceOf(s) | ASN1Type::SetOf(s) => {
+            replace_instantiations(&mut s.r#type, templates, instances, tlds, created)
+        }
+        _ => (),
+    }
+}
+
+/// Names an instantiation of a parameterized type after the type and its actual parameters,
+/// e.g. `Container-Of-Speed`. Names that are `taken` are suffixed with a counter.
+fn instance_name(
+    template: &str,
+    actual_parameters: &[Parameter],
+    taken: impl Fn(&str) -> bool,
+) -> String {
+    let parameters = actual_parameters
+        .iter()
+        .map(|p| match p {
+            Parameter::TypeParameter(ASN1Type::ElsewhereDeclaredType(e)) => e.identifier.clone(),
+            Parameter::TypeParameter(ASN1Type::CharacterString(c)) => format!("{:?}", c.r#type),
+            Parameter::TypeParameter(t) => match t {
+                ASN1Type::Null => "Null",
+                ASN1Type::Boolean => "Boolean",
+                ASN1Type::Integer(_) => "Integer",
+                ASN1Type::Real(_) => "Real",
+                ASN1Type::BitString(_) => "BitString",
+                ASN1Type::OctetString(_) => "OctetString",
+                ASN1Type::Enumerated(_) => "Enumerated",
+                ASN1Type::Choice(_) => "Choice",
+                ASN1Type::Sequence(_) => "Sequence",
+                ASN1Type::SequenceOf(_) => "SequenceOf",
+                ASN1Type::Set(_) => "Set",
+                ASN1Type::SetOf(_) => "SetOf",
+                ASN1Type::ObjectIdentifier(_) => "ObjectIdentifier",
+                _ => "Type",
+            }
+            .to_owned(),
+            _ => "Value".to_owned(),
+        })
+        .collect::<Vec<String>>()
+        .join("-");
+    let name = format!("{template}-Of-{parameters}");
+    let mut candidate = name.clone();
+    let mut counter = 1;
+    while taken(&candidate) {
+        counter += 1;
+        candidate = format!("{name}-{counter}");
+    }
+    candidate
+}
+
+/// Names the inline type of a member like the linking of DEFAULT enumerals does
+fn inline_type_name(parent_name: &str, member_name: &String) -> String {
+    format!(
+        "{}{}",
+        to_rust_title_case(&parent_name.to_owned()),
+        to_rust_title_case(member_name)
+    )
+}
+
+/// Checks whether a type contains the top-level declaration `target` by value,
+/// i.e. not behind the indirection of a SEQUENCE OF or of an already boxed member.
+/// * `visited` - the names of the declarations that have already been traversed
+fn refers_by_value(
+    r#type: &ASN1Type,
+    target: &str,
+    tlds: &BTreeMap<String, ToplevelDeclaration>,
+    visited: &mut BTreeSet<String>,
+) -> bool {
+    match r#type {
+        ASN1Type::ElsewhereDeclaredType(e) => {
+            e.identifier == target
+                || (visited.insert(e.identifier.clone())
+                    && matches!(
+                        tlds.get(&e.identifier),
+                        Some(ToplevelDeclaration::Type(t))
+                            if refers_by_value(&t.r#type, target, tlds, visited)
+                    ))
+        }
+        ASN1Type::Sequence(s) | ASN1Type::Set(s) => s
+            .all_members()
+            .any(|m| !m.is_recursive && refers_by_value(&m.r#type, target, tlds, visited)),
+        ASN1Type::Choice(c) => c
+            .options
+            .iter()
+            .any(|o| !o.is_recursive && refers_by_value(&o.r#type, target, tlds, visited)),
+        _ => false,
+    }
+}
+
+/// Sorts top-level declarations so that every declaration follows the declarations it depends on.
+/// The strongly connected components of the dependency graph are determined with Tarjan's algorithm
+/// and emitted in dependency order, so that mutually recursive declarations are kept together.
+/// Recursion is valid as long as it passes an indirection, e.g. a SEQUENCE OF, an OPTIONAL member,
+/// or a CHOICE with a non-recursive alternative. For circular declarations that have no finite value,
+/// a warning naming the declarations of the cycle is returned.
+pub fn topological_sort(
+    tlds: Vec<ToplevelDeclaration>,
+) -> (Vec<ToplevelDeclaration>, Vec<ValidatorError>) {
+    let index_by_name = tlds
+        .iter()
+        .enumerate()
+        .map(|(index, tld)| (tld.name(), index))
+        .collect::<BTreeMap<&String, usize>>();
+    let dependencies = tlds
+        .iter()
+        .map(|tld| {
+            tld.dependencies()
+                .iter()
+                .filter_map(|dependency| index_by_name.get(dependency).copied())
+                .collect::<Vec<usize>>()
+        })
+        .collect::<Vec<_>>();
+    let mut tarjan = Tarjan {
+        dependencies: &dependencies,
+        next_index: 0,
+        indices: vec![None; tlds.len()],
+        low_links: vec![0; tlds.len()],
+        stack: vec![],
+        on_stack: vec![false; tlds.len()],
+        components: vec![],
+    };
+    for node in 0..tlds.len() {
+        if tarjan.indices[node].is_none() {
+            tarjan.visit(node);
+        }
+    }
+    let components = tarjan.components;
+    let warnings = components
+        .iter()
+        .filter_map(|component| {
+            let infinite = infinite_declarations(component, &tlds);
+            (!infinite.is_empty()).then(|| {
+                ValidatorError::new(
+                    None,
+                    &format!(
+                        "Circular dependencies without indirection between the declarations {}",
+                        infinite.join(", ")
+                    ),
+                    ValidatorErrorType::CircularDependency,
+                )
+            })
+        })
+        .collect();
+    let mut tlds = tlds.into_iter().map(Some).collect::<Vec<_>>();
+    (
+        components
+            .into_iter()
+            .flatten()
+            .filter_map(|index| tlds[index].take())
+            .collect(),
+        warnings,
+    )
+}
+
+/// State of Tarjan's strongly connected components algorithm over the dependency graph
+/// of top-level declarations, given as the indices of each declaration's dependencies
+struct Tarjan<'a> {
+    dependencies: &'a [Vec<usize>],
+    next_index: usize,
+    indices: Vec<Option<usize>>,
+    low_links: Vec<usize>,
+    stack: Vec<usize>,
+    on_stack: Vec<bool>,
+    /// The strongly connected components in dependency order,
+    /// each in the original order of the declarations
+    components: Vec<Vec<usize>>,
+}
+
+impl Tarjan<'_> {
+    fn visit(&mut self, node: usize) {
+        self.indices[node] = Some(self.next_index);
+        self.low_links[node] = self.next_index;
+        self.next_index += 1;
+        self.stack.push(node);
+        self.on_stack[node] = true;
+        for &dependency in &self.dependencies[node] {
+            match self.indices[dependency] {
+                None => {
+                    self.visit(dependency);
+                    self.low_links[node] = self.low_links[node].min(self.low_links[dependency]);
+                }
+                Some(index) if self.on_stack[dependency] => {
+                    self.low_links[node] = self.low_links[node].min(index);
+                }
+                Some(_) => (),
+            }
+        }
+        if Some(self.low_links[node]) == self.indices[node] {
+            let mut component = vec![];
+            while let Some(member) = self.stack.pop() {
+                self.on_stack[member] = false;
+                component.push(member);
+                if member == node {
+                    break;
+                }
+            }
+            component.sort_unstable();
+            self.components.push(component);
+        }
+    }
+}
+
+/// Returns the names of the declarations of a strongly connected component of the dependency graph
+/// that have no finite value, because their recursion does not pass an indirection.
+/// Starting from the declarations that do not depend on the component, declarations are
+/// marked as finite until a fixpoint is reached.
+fn infinite_declarations(component: &[usize], tlds: &[ToplevelDeclaration]) -> Vec<String> {
+    let names = component
+        .iter()
+        .map(|index| tlds[*index].name())
+        .collect::<BTreeSet<&String>>();
+    let mut finite = BTreeSet::<&String>::new();
+    loop {
+        let newly_finite = component
+            .iter()
+            .map(|index| &tlds[*index])
+            .filter(|tld| !finite.contains(tld.name()))
+            .filter(|tld| match tld {
+                ToplevelDeclaration::Type(t) => has_finite_value(&t.r#type, &names, &finite),
+                other => other
+                    .dependencies()
+                    .iter()
+                    .all(|dependency| !names.contains(dependency) || finite.contains(dependency)),
+            })
+            .map(|tld| tld.name())
+            .collect::<Vec<_>>();
+        if newly_finite.is_empty() {
+            break;
+        }
+        finite.extend(newly_finite);
+    }
+    names
+        .into_iter()
+        .filter(|name| !finite.contains(name))
+        .cloned()
+        .collect()
+}
+
+/// Checks whether a type has a finite value, given the declarations of a strongly connected component
+/// that are already known to have finite values
+fn has_finite_value(
+    r#type: &ASN1Type,
+    component: &BTreeSet<&String>,
+    finite: &BTreeSet<&String>,
+) -> bool {
+    match r#type {
+        ASN1Type::ElsewhereDeclaredType(e) => {
+            !component.contains(&e.identifier) || finite.contains(&e.identifier)
+        }
+        ASN1Type::Sequence(s) | ASN1Type::Set(s) => s
+            .all_members()
+            .filter(|m| !m.is_optional && m.default_value.is_none())
+            .all(|m| has_finite_value(&m.r#type, component, finite)),
+        ASN1Type::Choice(c) => c
+            .options
+            .iter()
+            .any(|o| has_finite_value(&o.r#type, component, finite)),
+        _ => true,
+    }
+}
+
+/// Checks that the constraints of a subtype declaration, such as `Child ::= Parent (5..10)`,
+/// lie within the extension root of the effective constraints of the parent type.
+fn validate_subtype_constraints(
+    tld: &ToplevelDeclaration,
+    tlds: &BTreeMap<String, ToplevelDeclaration>,
+) -> Result<(), ValidatorError> {
+    let ToplevelDeclaration::Type(ToplevelTypeDeclaration {
+        name,
+        r#type: ASN1Type::ElsewhereDeclaredType(reference),
+        ..
+    }) = tld
+    else {
+        return Ok(());
+    };
+    if reference.constraints.is_empty() {
+        return Ok(());
+    }
+    let Some((signed, parent_constraints)) = effective_constraints(&reference.identifier, tlds, 0)
+    else {
+        return Ok(());
+    };
+    let to_validator_error = |e: GrammarError| {
+        ValidatorError::new(
+            Some(name.clone()),
+            &e.details,
+            ValidatorErrorType::InvalidConstraintsError,
+        )
+    };
+    let parent =
+        per_visible_range_constraints(signed, &parent_constraints).map_err(to_validator_error)?;
+    let child =
+        per_visible_range_constraints(signed, &reference.constraints).map_err(to_validator_error)?;
+    let (parent_min, parent_max) = (parent.min::<i128>(), parent.max::<i128>());
+    let (child_min, child_max) = (child.min::<i128>(), child.max::<i128>());
+    let disjoint = are_disjoint((child_min, child_max), (parent_min, parent_max));
+    let exceeding = matches!((child_min, parent_min), (Some(c), Some(p)) if c < p)
+        || matches!((child_max, parent_max), (Some(c), Some(p)) if c > p);
+    if disjoint {
+        Err(ValidatorError::new(
+            Some(name.clone()),
+            &format!(
+                "Constraints of {name} are disjoint from the constraints of its parent type {}",
+                reference.identifier
+            ),
+            ValidatorErrorType::InvalidConstraintsError,
+        ))
+    } else if exceeding {
+        Err(ValidatorError::new(
+            Some(name.clone()),
+            &format!(
+                "Constraints of {name} exceed the constraints of its parent type {}",
+                reference.identifier
+            ),
+            ValidatorErrorType::InvalidConstraintsError,
+        ))
+    } else {
+        Ok(())
+    }
+}
+
+/// Checks whether two value or size ranges, given as optional lower and upper bounds, cannot overlap
+fn are_disjoint(first: (Option<i128>, Option<i128>), second: (Option<i128>, Option<i128>)) -> bool {
+    matches!((first.1, second.0), (Some(f), Some(s)) if f < s)
+        || matches!((first.0, second.1), (Some(f), Some(s)) if f > s)
+}
+
+/// Warns about the alternatives of a CHOICE that its component constraints, such as
+/// `Restricted ::= Alternatives (WITH COMPONENTS { number (3..5) })`, rule out,
+/// because the constraints of the component are disjoint from the alternative's own constraints.
+fn unreachable_choice_alternatives(
+    tld: &ToplevelDeclaration,
+    tlds: &BTreeMap<String, ToplevelDeclaration>,
+) -> Vec<ValidatorError> {
+    let ToplevelDeclaration::Type(ToplevelTypeDeclaration { name, r#type, .. }) = tld else {
+        return vec![];
+    };
+    let (choice, constraints) = match r#type {
+        ASN1Type::Choice(c) => (c, &c.constraints),
+        ASN1Type::ElsewhereDeclaredType(e) if !e.constraints.is_empty() => {
+            match resolve_choice(&e.identifier, tlds, 0) {
+                Some(c) => (c, &e.constraints),
+                None => return vec![],
+            }
+        }
+        _ => return vec![],
+    };
+    constraints
+        .iter()
+        .filter_map(|c| match c {
+            Constraint::SubtypeConstraint(ElementSet {
+                set:
+                    ElementOrSetOperation::Element(
+                        SubtypeElement::SingleTypeConstraint(inner)
+                        | SubtypeElement::MultipleTypeConstraints(inner),
+                    ),
+                ..
+            }) => Some(&inner.constraints),
+            _ => None,
+        })
+        .flatten()
+        .filter(|component| !component.constraints.is_empty())
+        .filter_map(|component| {
+            let option = choice
+                .options
+                .iter()
+                .find(|o| o.name == component.identifier)?;
+            let (signed, mut option_constraints) = type_constraints(&option.r#type, tlds, 0)?;
+            option_constraints.append(&mut option.constraints.clone());
+            let alternative = per_visible_range_constraints(signed, &option_constraints).ok()?;
+            let restriction = per_visible_range_constraints(signed, &component.constraints).ok()?;
+            are_disjoint(
+                (alternative.min::<i128>(), alternative.max::<i128>()),
+                (restriction.min::<i128>(), restriction.max::<i128>()),
+            )
+            .then(|| {
+                ValidatorError::new(
+                    Some(name.clone()),
+                    &format!(
+                        "Alternative {} of {name} can never be selected, because its constraints are disjoint from the constraints imposed on it",
+                        option.name
+                    ),
+                    ValidatorErrorType::UnreachableChoiceAlternative,
+                )
+            })
+        })
+        .collect()
+}
+
+/// Warns about mandatory members in the root component list of a SEQUENCE that follow
+/// an optional member, such as `mandatory` in `SEQUENCE { opt BOOLEAN OPTIONAL, mandatory INTEGER }`.
+/// X.680 permits this order, but it is often a mistake in the specification.
+fn mandatory_members_after_optional(tld: &ToplevelDeclaration) -> Vec<ValidatorError> {
+    let ToplevelDeclaration::Type(ToplevelTypeDeclaration {
+        name,
+        r#type: ASN1Type::Sequence(sequence),
+        ..
+    }) = tld
+    else {
+        return vec![];
+    };
+    let root_length = sequence
+        .extensible
+        .map_or(sequence.members.len(), |e| e.min(sequence.members.len()));
+    let mut preceding_optional = None;
+    sequence.members[..root_length]
+        .iter()
+        .filter_map(|member| {
+            if member.is_optional {
+                preceding_optional = Some(&member.name);
+                return None;
+            }
+            preceding_optional.map(|optional| {
+                ValidatorError::new(
+                    Some(name.clone()),
+                    &format!(
+                        "Mandatory member {} of {name} follows the optional member {optional}",
+                        member.name
+                    ),
+                    ValidatorErrorType::MandatoryAfterOptional,
+                )
+            })
+        })
+        .collect()
+}
+
+/// Warns about distinguished values of an INTEGER that do not fit the Rust integer type
+/// derived from its constraints, such as `unknown(-1)` in `INTEGER { unknown(-1) } (0..255)`.
+/// The generators do not declare constants for these values.
+fn unrepresentable_distinguished_values(tld: &ToplevelDeclaration) -> Vec<ValidatorError> {
+    let ToplevelDeclaration::Type(ToplevelTypeDeclaration {
+        name,
+        r#type: ASN1Type::Integer(integer),
+        ..
+    }) = tld
+    else {
+        return vec![];
+    };
+    let integer_type = integer.type_token();
+    integer
+        .distinguished_values
+        .iter()
+        .flatten()
+        .filter(|value| !fits_integer_type(value.value, &integer_type))
+        .map(|value| {
+            ValidatorError::new(
+                Some(name.clone()),
+                &format!(
+                    "Distinguished value {}({}) of {name} does not fit the integer type {integer_type} and is not declared as a constant",
+                    value.name, value.value
+                ),
+                ValidatorErrorType::UnrepresentableDistinguishedValue,
+            )
+        })
+        .collect()
+}
+
+/// Makes an implicit tag of a reference to an untagged CHOICE type explicit.
+/// Returns `true` if the tag was changed.
+fn make_tag_explicit(
+    tag: &mut Option<AsnTag>,
+    r#type: &ASN1Type,
+    tlds: &BTreeMap<String, ToplevelDeclaration>,
+) -> bool {
+    match (tag, r#type) {
+        (Some(tag), ASN1Type::ElsewhereDeclaredType(e))
+            if tag.environment != TaggingEnvironment::Explicit
+                && is_untagged_choice(&e.identifier, tlds, 0) =>
+        {
+            tag.environment = TaggingEnvironment::Explicit;
+            true
+        }
+        _ => false,
+    }
+}
+
+/// Makes the tags of the components of a type, and of their inline types, explicit
+/// if they refer to untagged CHOICE types. Returns `true` if any tag was changed.
+fn make_component_tags_explicit(
+    r#type: &mut ASN1Type,
+    tlds: &BTreeMap<String, ToplevelDeclaration>,
+) -> bool {
+    match r#type {
+        ASN1Type::Sequence(s) | ASN1Type::Set(s) => {
+            s.all_members_mut().fold(false, |changed, m| {
+                make_tag_explicit(&mut m.tag, &m.r#type, tlds)
+                    | make_component_tags_explicit(&mut m.r#type, tlds)
+                    | changed
+            })
+        }
+        ASN1Type::Choice(c) => c.options.iter_mut().fold(false, |changed, o| {
+            make_tag_explicit(&mut o.tag, &o.r#type, tlds)
+                | make_component_tags_explicit(&mut o.r#type, tlds)
+                | changed
+        }),
+        ASN1Type::SequenceOf(s) | ASN1Type::SetOf(s) => {
+            make_component_tags_explicit(&mut s.r#type, tlds)
+        }
+        _ => false,
+    }
+}
+
+/// Links the composite DEFAULT values of a type's members and of the members of its inline types.
+/// Returns whether any value has been linked.
+/// * `type_name` - the name of the type, from which the names of its inline types are derived
+fn link_member_defaults(
+    r#type: &mut ASN1Type,
+    type_name: &str,
+    tlds: &BTreeMap<String, ToplevelDeclaration>,
+) -> bool {
+    match r#type {
+        ASN1Type::Sequence(s) | ASN1Type::Set(s) => {
+            s.all_members_mut().fold(false, |changed, m| {
+                let inner_type_name = inline_type_name(type_name, &m.name);
+                let linked = match m.default_value.take() {
+                    Some(value @ (ASN1Value::Sequence(_) | ASN1Value::Choice(_, _))) => {
+                        m.default_value = Some(link_nested_value(
+                            &m.r#type,
+                            &inner_type_name,
+                            value,
+                            tlds,
+                            0,
+                        ));
+                        true
+                    }
+                    value => {
+                        m.default_value = value;
+                        false
+                    }
+                };
+                link_member_defaults(&mut m.r#type, &inner_type_name, tlds) | linked | changed
+            })
+        }
+        ASN1Type::Choice(c) => c.options.iter_mut().fold(false, |changed, o| {
+            link_member_defaults(&mut o.r#type, &inline_type_name(type_name, &o.name), tlds)
+                | changed
+        }),
+        ASN1Type::SequenceOf(s) | ASN1Type::SetOf(s) => {
+            link_member_defaults(&mut s.r#type, type_name, tlds)
+        }
+        _ => false,
+    }
+}
+
+/// Checks whether a type reference refers to a CHOICE type without a tag of its own
+fn is_untagged_choice(
+    identifier: &String,
+    tlds: &BTreeMap<String, ToplevelDeclaration>,
+    depth: usize,
+) -> bool {
+    // guards against circular type references
+    if depth > tlds.len() {
+        return false;
+    }
+    match tlds.get(identifier) {
+        Some(ToplevelDeclaration::Type(t)) if t.tag.is_none() => match &t.r#type {
+            ASN1Type::Choice(_) => true,
+            ASN1Type::ElsewhereDeclaredType(e) => {
+                is_untagged_choice(&e.identifier, tlds, depth + 1)
+            }
+            _ => false,
+        },
+        _ => false,
+    }
+}
+
+/// Resolves a type reference to the CHOICE that it refers to, if any
+fn resolve_choice<'a>(
+    identifier: &String,
+    tlds: &'a BTreeMap<String, ToplevelDeclaration>,
+    depth: usize,
+) -> Option<&'a Choice> {
+    // guards against circular type references
+    if depth > tlds.len() {
+        return None;
+    }
+    match tlds.get(identifier) {
+        Some(ToplevelDeclaration::Type(t)) => match &t.r#type {
+            ASN1Type::Choice(c) => Some(c),
+            ASN1Type::ElsewhereDeclaredType(e) => resolve_choice(&e.identifier, tlds, depth + 1),
+            _ => None,
+        },
+        _ => None,
+    }
+}
+
+/// Resolves the constraints of a referenced type that restrict its values or its size.
+/// Returns whether the constraints restrict signed values along with the constraints.
+/// Constrained references are resolved to their own constraints, since these
+/// are checked against their parent type in turn.
+fn effective_constraints(
+    identifier: &String,
+    tlds: &BTreeMap<String, ToplevelDeclaration>,
+    depth: usize,
+) -> Option<(bool, Vec<Constraint>)> {
+    // guards against circular type references
+    if depth > tlds.len() {
+        return None;
+    }
+    match tlds.get(identifier) {
+        Some(ToplevelDeclaration::Type(t)) => type_constraints(&t.r#type, tlds, depth),
+        _ => None,
+    }
+}
+
+/// Resolves the constraints that restrict the values or the size of a type.
+/// See [effective_constraints].
+fn type_constraints(
+    r#type: &ASN1Type,
+    tlds: &BTreeMap<String, ToplevelDeclaration>,
+    depth: usize,
+) -> Option<(bool, Vec<Constraint>)> {
+    match r#type {
+        ASN1Type::Integer(i) => Some((true, i.constraints.clone())),
+        ASN1Type::BitString(b) => Some((false, b.constraints.clone())),
+        ASN1Type::OctetString(o) => Some((false, o.constraints.clone())),
+        ASN1Type::CharacterString(c) => Some((false, c.constraints.clone())),
+        ASN1Type::SequenceOf(s) | ASN1Type::SetOf(s) => Some((false, s.constraints.clone())),
+        ASN1Type::ElsewhereDeclaredType(e) if !e.constraints.is_empty() => {
+            effective_constraints(&e.identifier, tlds, depth + 1)
+                .map(|(signed, _)| (signed, e.constraints.clone()))
+        }
+        ASN1Type::ElsewhereDeclaredType(e) => effective_constraints(&e.identifier, tlds, depth + 1),
+        _ => None,
+    }
+}
+
+/// Checks that every information object class field reference of a type declaration
+/// points to a field that is defined by the referenced class.
+/// Classes that are not declared, e.g. because they are imported from a module
+/// that is not compiled, cannot be checked and are reported as warnings.
+fn validate_class_field_references(
+    tld: &ToplevelDeclaration,
+    tlds: &BTreeMap<String, ToplevelDeclaration>,
+) -> Result<Vec<ValidatorError>, ValidatorError> {
+    fn check(
+        ty: &ASN1Type,
+        tlds: &BTreeMap<String, ToplevelDeclaration>,
+        warnings: &mut Vec<ValidatorError>,
+    ) -> Result<(), ValidatorError> {
+        match ty {
+            ASN1Type::InformationObjectFieldReference(reference) => {
+                let Some(declaration) = tlds.get(&reference.class) else {
+                    warnings.push(ValidatorError::new(
+                        None,
+                        &format!(
+                            "Cannot check the field reference to the undeclared information object class {}",
+                            reference.class
+                        ),
+                        ValidatorErrorType::MissingDependency,
+                    ));
+                    return Ok(());
+                };
+                let class = declaration
+                    .is_class_with_name(&reference.class)
+                    .ok_or_else(|| {
+                        ValidatorError::new(
+                            None,
+                            &format!("{} is not an information object class", reference.class),
+                            ValidatorErrorType::MissingDependency,
+                        )
+                    })?;
+                if class.get_field(&reference.field_path).is_none() {
+                    return Err(ValidatorError::new(
+                        None,
+                        &format!(
+                            "Information object class {} has no field {}",
+                            reference.class,
+                            reference
+                                .field_path
+                                .iter()
+                                .map(|id| id.identifier())
+                                .collect::<Vec<String>>()
+                                .join(".")
+                        ),
+                        ValidatorErrorType::MissingDependency,
+                    ));
+                }
+                Ok(())
+            }
+            ASN1Type::Sequence(s) | ASN1Type::Set(s) => s
+                .all_members()
+                .try_for_each(|m| check(&m.r#type, tlds, warnings)),
+            ASN1Type::Choice(c) => c
+                .options
+                .iter()
+                .try_for_each(|o| check(&o.r#type, tlds, warnings)),
+            ASN1Type::SequenceOf(s) | ASN1Type::SetOf(s) => check(&s.r#type, tlds, warnings),
+            _ => Ok(()),
+        }
+    }
+    let mut warnings = vec![];
+    if let ToplevelDeclaration::Type(t) = tld {
+        check(&t.r#type, tlds, &mut warnings).map_err(|mut e| {
+            e.specify_data_element(t.name.clone());
+            e
+        })?;
+        for warning in &mut warnings {
+            warning.specify_data_element(t.name.clone());
+        }
+    }
+    Ok(warnings)
 }
 
 pub trait Validate {
@@ -156,8 +1583,29 @@ impl Validate for ToplevelDeclaration {
                 Ok(())
             }
             ToplevelDeclaration::Value(_v) => Ok(()),
-            ToplevelDeclaration::Information(_i) => Ok(()),
+            ToplevelDeclaration::Information(i) => {
+                if let ASN1Information::ObjectClass(class) = &i.value {
+                    if let Err(mut e) = class.validate() {
+                        e.specify_data_element(i.name.clone());
+                        return Err(e);
+                    }
+                }
+                Ok(())
+            }
+        }
+    }
+}
+
+impl Validate for InformationObjectClass {
+    fn validate(&self) -> Result<(), ValidatorError> {
+        if self.fields.iter().filter(|f| f.is_unique).count() > 1 {
+            return Err(ValidatorError::new(
+                None,
+                "Information object class declares more than one UNIQUE field!",
+                ValidatorErrorType::InvalidConstraintsError,
+            ));
         }
+        Ok(())
     }
 }
 
@@ -224,3 +1672,486 @@ impl Validate for Constraint {
         Ok(())
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use std::collections::BTreeMap;
+
+    use asnr_grammar::{
+        error::GrammarErrorType,
+        information_object::{ASN1Information, ClassLink, ToplevelInformationDeclaration},
+        ASN1Type, ASN1Value, ToplevelDeclaration, ToplevelTypeDeclaration,
+    };
+
+    use crate::{
+        parser::asn_spec,
+        validator::{error::{ValidatorError, ValidatorErrorType}, topological_sort, Validator},
+    };
+
+    fn parse(input: &str) -> Vec<ToplevelDeclaration> {
+        asn_spec(input)
+            .unwrap()
+            .into_iter()
+            .flat_map(|(_, tlds)| tlds)
+            .collect()
+    }
+
+    fn validate_subtype(spec: &str) -> Vec<ToplevelDeclaration> {
+        let tlds = asn_spec(spec)
+            .unwrap()
+            .into_iter()
+            .flat_map(|(_, tlds)| tlds)
+            .collect();
+        Validator::new(tlds).validate().unwrap().0
+    }
+
+    #[test]
+    fn sorts_declarations_topologically() {
+        let (sorted, warnings) = topological_sort(parse(
+            r#"Test DEFINITIONS AUTOMATIC TAGS ::= BEGIN
+            Foo ::= SEQUENCE { bar Bar, baz Baz }
+            Bar ::= SEQUENCE { baz Baz }
+            default-baz Baz ::= 4
+            Baz ::= INTEGER (0..7)
+            Unrelated ::= BOOLEAN
+            END"#,
+        ));
+        assert!(warnings.is_empty());
+        assert_eq!(
+            sorted.iter().map(|tld| tld.name().as_str()).collect::<Vec<_>>(),
+            vec!["Baz", "Bar", "Foo", "default-baz", "Unrelated"]
+        );
+    }
+
+    #[test]
+    fn sorts_recursive_declarations_together() {
+        let (sorted, warnings) = topological_sort(parse(
+            r#"Test DEFINITIONS AUTOMATIC TAGS ::= BEGIN
+            Root ::= SEQUENCE { tree Tree }
+            Tree ::= SEQUENCE { leaf Leaf, children Forest }
+            Forest ::= SEQUENCE OF Tree
+            Leaf ::= BOOLEAN
+            END"#,
+        ));
+        assert!(warnings.is_empty());
+        assert_eq!(
+            sorted
+                .iter()
+                .map(|tld| tld.name().as_str())
+                .collect::<Vec<_>>(),
+            vec!["Leaf", "Tree", "Forest", "Root"]
+        );
+    }
+
+    #[test]
+    fn reports_circular_dependencies_without_indirection() {
+        let (sorted, warnings) = topological_sort(parse(
+            r#"Test DEFINITIONS AUTOMATIC TAGS ::= BEGIN
+            Outer ::= SEQUENCE { inner Inner }
+            Inner ::= SEQUENCE { outer Outer, leaf Leaf }
+            Leaf ::= BOOLEAN
+            Expression ::= CHOICE { literal INTEGER, sum Sum }
+            Sum ::= SEQUENCE { left Expression, right Expression }
+            END"#,
+        ));
+        assert_eq!(sorted.len(), 5);
+        assert_eq!(warnings.len(), 1);
+        assert!(matches!(
+            warnings[0].kind,
+            ValidatorErrorType::CircularDependency
+        ));
+        assert!(warnings[0].details.contains("Outer") && warnings[0].details.contains("Inner"));
+        assert!(!warnings[0].details.contains("Leaf"));
+        assert!(!warnings[0].details.contains("Expression"));
+    }
+
+    #[test]
+    fn marks_recursive_members() {
+        let (tlds, _) = Validator::new(parse(
+            r#"Test DEFINITIONS AUTOMATIC TAGS ::= BEGIN
+            Node ::= SEQUENCE { value INTEGER, next Node OPTIONAL }
+            Expression ::= CHOICE { literal INTEGER, sum Sum }
+            Sum ::= SEQUENCE { left Expression, right Expression }
+            Tree ::= SEQUENCE { children Forest }
+            Forest ::= SEQUENCE OF Tree
+            END"#,
+        ))
+        .validate()
+        .unwrap();
+        let recursive = |name: &str| match tlds.iter().find(|tld| tld.name() == name) {
+            Some(ToplevelDeclaration::Type(ToplevelTypeDeclaration {
+                r#type: ASN1Type::Sequence(s),
+                ..
+            })) => s.members.iter().map(|m| m.is_recursive).collect::<Vec<_>>(),
+            Some(ToplevelDeclaration::Type(ToplevelTypeDeclaration {
+                r#type: ASN1Type::Choice(c),
+                ..
+            })) => c.options.iter().map(|o| o.is_recursive).collect::<Vec<_>>(),
+            _ => unreachable!(),
+        };
+        assert_eq!(recursive("Node"), vec![false, true]);
+        assert_eq!(recursive("Expression"), vec![false, true]);
+        assert_eq!(recursive("Sum"), vec![false, false]);
+        assert_eq!(recursive("Tree"), vec![false]);
+    }
+
+    #[test]
+    fn validates_subtype_constraints_against_parent_type() {
+        let contained = validate_subtype(
+            r#"Test DEFINITIONS AUTOMATIC TAGS ::= BEGIN
+            Parent ::= INTEGER (0..20)
+            Child ::= Parent (5..10)
+            END"#,
+        );
+        assert_eq!(contained.len(), 2);
+        let exceeding = validate_subtype(
+            r#"Test DEFINITIONS AUTOMATIC TAGS ::= BEGIN
+            Parent ::= INTEGER (0..20)
+            Child ::= Parent (5..30)
+            END"#,
+        );
+        assert_eq!(exceeding.len(), 1);
+        let disjoint = validate_subtype(
+            r#"Test DEFINITIONS AUTOMATIC TAGS ::= BEGIN
+            Parent ::= INTEGER (0..20)
+            Child ::= Parent (25..30)
+            END"#,
+        );
+        assert_eq!(disjoint.len(), 1);
+        assert_eq!(disjoint[0].name(), "Parent");
+        let extensible_parent = validate_subtype(
+            r#"Test DEFINITIONS AUTOMATIC TAGS ::= BEGIN
+            Parent ::= INTEGER (0..20,...)
+            Contained ::= Parent (0..5)
+            Disjoint ::= Parent (25..30)
+            END"#,
+        );
+        assert_eq!(
+            extensible_parent
+                .iter()
+                .map(|tld| tld.name().as_str())
+                .collect::<Vec<&str>>(),
+            vec!["Contained", "Parent"]
+        );
+    }
+
+    #[test]
+    fn warns_about_unreachable_choice_alternatives() {
+        let (tlds, warnings) = Validator::new(parse(
+            r#"Test DEFINITIONS AUTOMATIC TAGS ::= BEGIN
+            Small ::= INTEGER (7..10)
+            Alternatives ::= CHOICE { a INTEGER (0..5), b Small, c INTEGER }
+            Restricted ::= Alternatives (WITH COMPONENTS { a (3..5), b (3..5), c (3..5) })
+            Inline ::= CHOICE { a INTEGER (0..5), b INTEGER (7..10) } (WITH COMPONENTS { a (3..5) })
+            END"#,
+        ))
+        .validate()
+        .unwrap();
+        assert_eq!(tlds.len(), 4);
+        assert_eq!(warnings.len(), 1);
+        assert!(matches!(
+            warnings[0].downcast_ref::<ValidatorError>().unwrap(),
+            ValidatorError {
+                data_element: Some(name),
+                kind: ValidatorErrorType::UnreachableChoiceAlternative,
+                ..
+            } if name == "Restricted"
+        ));
+        assert!(warnings[0].to_string().contains("Alternative b"));
+    }
+
+    #[test]
+    fn reports_constraint_references_of_unexpected_type() {
+        let spec = r#"Test DEFINITIONS AUTOMATIC TAGS ::= BEGIN
+            flag BOOLEAN ::= TRUE
+            Bounded ::= INTEGER (0..flag)
+            END"#;
+        let tlds = parse(spec)
+            .into_iter()
+            .map(|tld| (tld.name().clone(), tld))
+            .collect::<BTreeMap<String, ToplevelDeclaration>>();
+        let mut bounded = tlds.get("Bounded").unwrap().clone();
+        assert!(matches!(
+            bounded.link_constraint_reference(&tlds).unwrap_err().kind,
+            GrammarErrorType::UnexpectedType { expected, found }
+                if expected == "INTEGER, REAL, or character string" && found == "BOOLEAN"
+        ));
+
+        let (_, warnings) = Validator::new(parse(spec)).validate().unwrap();
+        let invalid = warnings
+            .iter()
+            .filter_map(|w| match w.downcast_ref::<ValidatorError>() {
+                Some(ValidatorError {
+                    data_element: Some(name),
+                    kind: ValidatorErrorType::InvalidConstraintsError,
+                    ..
+                }) => Some(name.as_str()),
+                _ => None,
+            })
+            .collect::<Vec<&str>>();
+        assert_eq!(invalid, vec!["Bounded"]);
+    }
+
+    #[test]
+    fn links_constraint_references_to_values() {
+        let (tlds, warnings) = Validator::new(parse(
+            r#"Test DEFINITIONS AUTOMATIC TAGS ::= BEGIN
+            fifteen INTEGER ::= 15
+            Departures ::= SEQUENCE (SIZE(0..fifteen)) OF BOOLEAN
+            Unbounded ::= INTEGER (0..missing)
+            END"#,
+        ))
+        .validate()
+        .unwrap();
+        assert!(matches!(
+            tlds.iter().find(|tld| tld.name() == "Departures"),
+            Some(ToplevelDeclaration::Type(ToplevelTypeDeclaration {
+                r#type: ASN1Type::SequenceOf(s),
+                ..
+            })) if format!("{:?}", s.constraints).contains("Integer(15)")
+        ));
+        assert_eq!(warnings.len(), 1);
+        assert!(warnings[0]
+            .to_string()
+            .contains("Failed to link cross-reference to elsewhere defined value in constraint of Unbounded"));
+    }
+
+    #[test]
+    fn monomorphizes_parameterized_types() {
+        let (tlds, _) = Validator::new(parse(
+            r#"Test DEFINITIONS AUTOMATIC TAGS ::= BEGIN
+            Container {ElementType} ::= SEQUENCE { id INTEGER, element ElementType }
+            Speed ::= INTEGER (0..100)
+            Heading ::= INTEGER (0..359)
+            SpeedContainer ::= Container {Speed}
+            Report ::= SEQUENCE { a Container {Speed}, b Container {Heading} }
+            END"#,
+        ))
+        .validate()
+        .unwrap();
+        let names = tlds
+            .iter()
+            .map(|tld| tld.name().as_str())
+            .collect::<Vec<&str>>();
+        assert!(!names.contains(&"Container"));
+        assert_eq!(names.iter().filter(|n| n.contains("Container")).count(), 2);
+        let members = tlds
+            .iter()
+            .find_map(|tld| match tld {
+                ToplevelDeclaration::Type(ToplevelTypeDeclaration {
+                    name,
+                    r#type: ASN1Type::Sequence(s),
+                    ..
+                }) if name == "Report" => Some(
+                    s.members
+                        .iter()
+                        .map(|m| match &m.r#type {
+                            ASN1Type::ElsewhereDeclaredType(e) => e.identifier.clone(),
+                            _ => String::new(),
+                        })
+                        .collect::<Vec<String>>(),
+                ),
+                _ => None,
+            })
+            .unwrap();
+        assert_eq!(members, vec!["SpeedContainer", "Container-Of-Heading"]);
+        assert!(names.contains(&"Container-Of-Heading"));
+    }
+
+    #[test]
+    fn warns_about_mandatory_members_after_optional_members() {
+        let (tlds, warnings) = Validator::new(parse(
+            r#"Test DEFINITIONS AUTOMATIC TAGS ::= BEGIN
+            Seq ::= SEQUENCE { opt BOOLEAN OPTIONAL, mandatory INTEGER }
+            Ordered ::= SEQUENCE { mandatory INTEGER, opt BOOLEAN OPTIONAL, ..., added INTEGER }
+            END"#,
+        ))
+        .validate()
+        .unwrap();
+        assert_eq!(tlds.len(), 2);
+        assert_eq!(warnings.len(), 1);
+        assert!(matches!(
+            warnings[0].downcast_ref::<ValidatorError>().unwrap(),
+            ValidatorError {
+                data_element: Some(name),
+                kind: ValidatorErrorType::MandatoryAfterOptional,
+                ..
+            } if name == "Seq"
+        ));
+        assert!(warnings[0].to_string().contains("Mandatory member mandatory"));
+    }
+
+    #[test]
+    fn warns_about_distinguished_values_outside_the_integer_type() {
+        let (tlds, warnings) = Validator::new(parse(
+            r#"Test DEFINITIONS AUTOMATIC TAGS ::= BEGIN
+            Level ::= INTEGER { unknown(-1), low(0), high(255) } (0..255)
+            Unconstrained ::= INTEGER { unknown(-1) }
+            END"#,
+        ))
+        .validate()
+        .unwrap();
+        assert_eq!(tlds.len(), 2);
+        assert_eq!(warnings.len(), 1);
+        assert!(matches!(
+            warnings[0].downcast_ref::<ValidatorError>().unwrap(),
+            ValidatorError {
+                data_element: Some(name),
+                kind: ValidatorErrorType::UnrepresentableDistinguishedValue,
+                ..
+            } if name == "Level"
+        ));
+        assert!(warnings[0].to_string().contains("unknown(-1) of Level"));
+    }
+
+    #[test]
+    fn rejects_reference_to_undefined_class_field() {
+        let (tlds, errors) = Validator::new(parse(
+            r#"Test DEFINITIONS AUTOMATIC TAGS ::= BEGIN
+            MY-CLASS ::= CLASS { &id INTEGER UNIQUE, &Type }
+            Container ::= SEQUENCE { id MY-CLASS.&id({Set}), value MY-CLASS.&Missing({Set}{@id}) }
+            END"#,
+        ))
+        .validate()
+        .unwrap();
+        assert!(tlds.iter().all(|tld| tld.name() != "Container"));
+        assert_eq!(errors.len(), 1);
+        assert!(errors[0].to_string().contains("Missing"));
+    }
+
+    #[test]
+    fn accepts_reference_to_defined_class_field() {
+        let (tlds, errors) = Validator::new(parse(
+            r#"Test DEFINITIONS AUTOMATIC TAGS ::= BEGIN
+            MY-CLASS ::= CLASS { &id INTEGER UNIQUE, &Type }
+            Container ::= SEQUENCE { id MY-CLASS.&id({Set}), value MY-CLASS.&Type({Set}{@id}) }
+            END"#,
+        ))
+        .validate()
+        .unwrap();
+        assert_eq!(tlds.len(), 2);
+        assert!(errors.is_empty());
+    }
+
+    #[test]
+    fn warns_about_references_to_undeclared_classes() {
+        let (tlds, warnings) = Validator::new(parse(
+            r#"Test DEFINITIONS AUTOMATIC TAGS ::= BEGIN
+            IMPORTS IMPORTED-CLASS FROM Other { iso 3 1 };
+            Container ::= SEQUENCE { id IMPORTED-CLASS.&id({Set}), value IMPORTED-CLASS.&Type({Set}{@id}) }
+            END"#,
+        ))
+        .validate()
+        .unwrap();
+        assert!(tlds.iter().any(|tld| tld.name() == "Container"));
+        assert_eq!(warnings.len(), 2);
+        assert!(warnings.iter().all(|w| matches!(
+            w.downcast_ref::<ValidatorError>(),
+            Some(ValidatorError {
+                data_element: Some(name),
+                kind: ValidatorErrorType::MissingDependency,
+                details,
+            }) if name == "Container" && details.contains("IMPORTED-CLASS")
+        )));
+    }
+
+    #[test]
+    fn rejects_class_with_multiple_unique_fields() {
+        let (tlds, errors) = Validator::new(parse(
+            r#"Test DEFINITIONS AUTOMATIC TAGS ::= BEGIN
+            MY-CLASS ::= CLASS { &id INTEGER UNIQUE, &code INTEGER UNIQUE }
+            END"#,
+        ))
+        .validate()
+        .unwrap();
+        assert!(tlds.is_empty());
+        assert_eq!(errors.len(), 1);
+    }
+
+    #[test]
+    fn links_sequence_values_to_their_types() {
+        let tlds = validate_subtype(
+            r#"Test DEFINITIONS AUTOMATIC TAGS ::= BEGIN
+            Color ::= ENUMERATED { red, green }
+            Pixel ::= SEQUENCE { x INTEGER, color Color, alpha INTEGER DEFAULT 255, label IA5String OPTIONAL }
+            origin-x INTEGER ::= 0
+            origin Pixel ::= { x origin-x, color green }
+            END"#,
+        );
+        let Some(ToplevelDeclaration::Value(origin)) = tlds.iter().find(|t| t.name() == "origin")
+        else {
+            panic!("Expected value declaration of origin");
+        };
+        let ASN1Value::Sequence(members) = &origin.value else {
+            panic!("Expected SEQUENCE value");
+        };
+        let linked_values = members
+            .iter()
+            .map(|(name, value)| match value.as_ref() {
+                ASN1Value::LinkedNestedValue { value, .. } => (name.as_str(), value.as_ref()),
+                value => (name.as_str(), value),
+            })
+            .collect::<Vec<_>>();
+        assert_eq!(linked_values[0], ("x", &ASN1Value::Integer(0)));
+        assert!(matches!(
+            linked_values[1],
+            ("color", ASN1Value::LinkedNestedValue { value, .. })
+                if **value == ASN1Value::EnumeratedValue {
+                    enumerated: "Color".into(),
+                    enumerable: "green".into()
+                }
+        ));
+        assert_eq!(linked_values[2], ("alpha", &ASN1Value::Integer(255)));
+        assert_eq!(linked_values[3], ("label", &ASN1Value::Optional(None)));
+        assert_eq!(linked_values.len(), 4);
+    }
+
+    #[test]
+    fn links_sequence_default_values_to_their_types() {
+        let tlds = validate_subtype(
+            r#"Test DEFINITIONS AUTOMATIC TAGS ::= BEGIN
+            Settings ::= SEQUENCE { flag BOOLEAN, count INTEGER, label IA5String OPTIONAL }
+            Config ::= SEQUENCE { settings Settings DEFAULT { flag TRUE, count 5 } }
+            END"#,
+        );
+        let Some(ToplevelDeclaration::Type(config)) = tlds.iter().find(|t| t.name() == "Config")
+        else {
+            panic!("Expected type declaration of Config");
+        };
+        let ASN1Type::Sequence(config) = &config.r#type else {
+            panic!("Expected SEQUENCE type");
+        };
+        let Some(ASN1Value::LinkedNestedValue { r#type, value }) =
+            &config.members[0].default_value
+        else {
+            panic!("Expected linked DEFAULT value");
+        };
+        assert!(matches!(r#type.as_ref(), ASN1Type::Sequence(_)));
+        let ASN1Value::Sequence(members) = value.as_ref() else {
+            panic!("Expected SEQUENCE value");
+        };
+        let names = members.iter().map(|(name, _)| name.as_str()).collect::<Vec<_>>();
+        assert_eq!(names, vec!["flag", "count", "label"]);
+        assert_eq!(*members[2].1, ASN1Value::Optional(None));
+    }
+
+    #[test]
+    fn substitutes_actual_parameters_of_parameterized_class() {
+        let tlds = validate_subtype(
+            r#"Test DEFINITIONS AUTOMATIC TAGS ::= BEGIN
+            MY-CLASS { Type } ::= CLASS { &id INTEGER UNIQUE, &field Type }
+            Flags MY-CLASS { BOOLEAN } ::= { { &id 1, &field TRUE } }
+            END"#,
+        );
+        let Some(ToplevelDeclaration::Information(ToplevelInformationDeclaration {
+            class: Some(ClassLink::ByReference(class)),
+            value: ASN1Information::ObjectSet(_),
+            ..
+        })) = tlds.iter().find(|t| t.name() == "Flags")
+        else {
+            panic!("Expected object set linked to its class");
+        };
+        assert_eq!(class.fields[0].r#type, Some(ASN1Type::Integer(Default::default())));
+        assert_eq!(class.fields[1].r#type, Some(ASN1Type::Boolean));
+    }
+}
@@ -1,3 +1,5 @@
+use core::cmp::Ordering;
+
 use alloc::{boxed::Box, collections::BTreeMap, format, string::String, vec, vec::Vec};
 
 use crate::{
@@ -25,6 +27,7 @@ pub struct ExtensionMarker();
 pub enum Constraint {
     SubtypeConstraint(ElementSet),
     TableConstraint(TableConstraint),
+    ContentConstraint(ContentConstraint),
     Parameter(Vec<Parameter>),
 }
 
@@ -33,18 +36,20 @@ impl Constraint {
         &mut self,
         identifier: &String,
         tlds: &BTreeMap<String, ToplevelDeclaration>,
-    ) -> bool {
+    ) -> Result<bool, GrammarError> {
         match self {
             Constraint::SubtypeConstraint(t) => t.set.link_cross_reference(identifier, tlds),
-            _ => false,
+            _ => Ok(false),
         }
     }
 
-    pub(super) fn has_cross_reference(&self) -> bool {
-        if let Self::SubtypeConstraint(c) = self {
-            c.set.has_cross_reference()
-        } else {
-            false
+    /// Returns a compact representation of the constraint for `ASN1Type::type_signature`.
+    /// Constraints without a dedicated representation fall back to their debug output.
+    pub(super) fn signature(&self) -> String {
+        match self {
+            Constraint::SubtypeConstraint(c) if c.extensible => c.set.signature() + ":...",
+            Constraint::SubtypeConstraint(c) => c.set.signature(),
+            c => format!("{c:?}"),
         }
     }
 
@@ -210,6 +215,25 @@ impl From<(ObjectSet, Option<Vec<RelationalConstraint>>)> for TableConstraint {
     }
 }
 
+/// Representation of a contents constraint, which restricts the contents of
+/// a BIT STRING or an OCTET STRING to an encoded value of a type
+/// _See: ITU-T X.682 (02/2021) 11_
+#[derive(Debug, Clone, PartialEq)]
+pub struct ContentConstraint {
+    pub containing: Option<ASN1Type>,
+    /// Object identifier of the encoding rules of the contained value
+    pub encoded_by: Option<ASN1Value>,
+}
+
+impl From<(Option<ASN1Type>, Option<ASN1Value>)> for ContentConstraint {
+    fn from(value: (Option<ASN1Type>, Option<ASN1Value>)) -> Self {
+        Self {
+            containing: value.0,
+            encoded_by: value.1,
+        }
+    }
+}
+
 /// Representation of a table's relational constraint
 /// _See: ITU-T X.682 (02/2021) 10.7_
 #[derive(Debug, Clone, PartialEq)]
@@ -502,37 +526,143 @@ pub enum SubtypeElement {
                                                   // RecurrenceRange
 }
 
+/// Links a reference in the lower or upper bound of a value range.
+/// Value ranges are only defined for numeric values and for characters,
+/// so a reference to a value of any other type yields an `UnexpectedType` error.
+fn link_range_bound(
+    bound: &mut ASN1Value,
+    identifier: &String,
+    tlds: &BTreeMap<String, ToplevelDeclaration>,
+) -> Result<bool, GrammarError> {
+    let declared_type = match bound {
+        ASN1Value::ElsewhereDeclaredValue(name) => match tlds.get(name) {
+            Some(ToplevelDeclaration::Value(v)) => v.type_name.clone(),
+            _ => String::new(),
+        },
+        _ => String::new(),
+    };
+    let linked = bound.link_elsewhere_declared(identifier, tlds)?;
+    let mut value = &*bound;
+    while let ASN1Value::LinkedNestedValue { value: inner, .. } = value {
+        value = inner;
+    }
+    if linked
+        && !matches!(
+            value,
+            ASN1Value::Integer(_)
+                | ASN1Value::Real(_)
+                | ASN1Value::String(_)
+                | ASN1Value::ElsewhereDeclaredValue(_)
+                | ASN1Value::EnumeratedValue { .. }
+        )
+    {
+        return Err(GrammarError {
+            details: format!(
+                "Value of type {declared_type} cannot bound a value range of {identifier}"
+            ),
+            kind: GrammarErrorType::UnexpectedType {
+                expected: "INTEGER, REAL, or character string".into(),
+                found: declared_type,
+            },
+        });
+    }
+    Ok(linked)
+}
+
 impl SubtypeElement {
+    /// Checks whether the values permitted by two subtype elements overlap.
+    /// Single values are treated as ranges with identical bounds, and
+    /// absent range bounds are treated as unbounded.
+    /// Elements whose permitted values cannot be compared, e.g. because they
+    /// reference elsewhere declared values or constrain different properties
+    /// of a type, are assumed to overlap.
+    pub fn overlaps(&self, other: &SubtypeElement) -> bool {
+        match (self, other) {
+            (SubtypeElement::SizeConstraint(a), SubtypeElement::SizeConstraint(b)) => a.overlaps(b),
+            (SubtypeElement::SingleValue { .. } | SubtypeElement::ValueRange { .. }, _)
+            | (_, SubtypeElement::SingleValue { .. } | SubtypeElement::ValueRange { .. }) => {
+                match (self.as_bounds(), other.as_bounds()) {
+                    (Some((min1, max1)), Some((min2, max2))) => {
+                        !is_below(max1, min2) && !is_below(max2, min1)
+                    }
+                    _ => true,
+                }
+            }
+            _ => true,
+        }
+    }
+
+    fn as_bounds(&self) -> Option<(Option<&ASN1Value>, Option<&ASN1Value>)> {
+        match self {
+            SubtypeElement::SingleValue { value, .. } => Some((Some(value), Some(value))),
+            SubtypeElement::ValueRange { min, max, .. } => Some((min.as_ref(), max.as_ref())),
+            _ => None,
+        }
+    }
+
+    fn signature(&self) -> String {
+        let (signature, extensible) = match self {
+            SubtypeElement::SingleValue { value, extensible } => (value.signature(), *extensible),
+            SubtypeElement::ValueRange {
+                min,
+                max,
+                extensible,
+            } => (
+                format!(
+                    "{}:{}",
+                    min.as_ref().map_or("min".into(), |v| v.signature()),
+                    max.as_ref().map_or("max".into(), |v| v.signature())
+                ),
+                *extensible,
+            ),
+            SubtypeElement::ContainedSubtype {
+                subtype,
+                extensible,
+            } => (subtype.type_signature(), *extensible),
+            SubtypeElement::SizeConstraint(s) => (format!("size({})", s.signature()), false),
+            SubtypeElement::PermittedAlphabet(a) => (format!("from({})", a.signature()), false),
+            SubtypeElement::TypeConstraint(t) => (t.type_signature(), false),
+            e => (format!("{e:?}"), false),
+        };
+        if extensible {
+            signature + ":..."
+        } else {
+            signature
+        }
+    }
+
     pub(super) fn link_cross_reference(
         &mut self,
         identifier: &String,
         tlds: &BTreeMap<String, ToplevelDeclaration>,
-    ) -> bool {
+    ) -> Result<bool, GrammarError> {
         match self {
             SubtypeElement::SingleValue {
                 value,
                 extensible: _,
             } => value.link_elsewhere_declared(identifier, tlds),
             SubtypeElement::PermittedAlphabet(e) => e.link_cross_reference(identifier, tlds),
-            SubtypeElement::PatternConstraint(_) => false,
-            SubtypeElement::UserDefinedConstraint(_) => false,
-            SubtypeElement::PropertySettings(_) => false,
+            SubtypeElement::PatternConstraint(_) => Ok(false),
+            SubtypeElement::UserDefinedConstraint(_) => Ok(false),
+            SubtypeElement::PropertySettings(_) => Ok(false),
             SubtypeElement::ContainedSubtype {
                 subtype,
                 extensible: _,
-            } => subtype.link_subtype_constraint(tlds),
+            } => Ok(subtype.link_subtype_constraint(tlds)),
             SubtypeElement::ValueRange {
                 min,
                 max,
                 extensible: _,
             } => {
-                let a = min
-                    .as_mut()
-                    .map_or(false, |m| m.link_elsewhere_declared(identifier, tlds));
-                let b = max
-                    .as_mut()
-                    .map_or(false, |m| m.link_elsewhere_declared(identifier, tlds));
-                a || b
+                let a = match min {
+                    Some(m) => link_range_bound(m, identifier, tlds)?,
+                    None => false,
+                };
+                let b = match max {
+                    Some(m) => link_range_bound(m, identifier, tlds)?,
+                    None => false,
+                };
+                Ok(a || b)
             }
             SubtypeElement::SizeConstraint(s) => s.link_cross_reference(identifier, tlds),
             SubtypeElement::TypeConstraint(t) => t.link_constraint_reference(identifier, tlds),
@@ -541,40 +671,9 @@ impl SubtypeElement {
                 .constraints
                 .iter_mut()
                 .flat_map(|cc| &mut cc.constraints)
-                .map(|c| c.link_cross_reference(identifier, tlds))
-                .fold(false, |acc, b| acc || b),
-        }
-    }
-
-    pub(super) fn has_cross_reference(&self) -> bool {
-        match self {
-            SubtypeElement::SingleValue {
-                value,
-                extensible: _,
-            } => value.is_elsewhere_declared(),
-            SubtypeElement::PatternConstraint(_) => false,
-            SubtypeElement::UserDefinedConstraint(_) => false,
-            SubtypeElement::PropertySettings(_) => false,
-            SubtypeElement::PermittedAlphabet(e) => e.has_cross_reference(),
-            SubtypeElement::ContainedSubtype {
-                subtype,
-                extensible: _,
-            } => subtype.contains_constraint_reference(),
-            SubtypeElement::ValueRange {
-                min,
-                max,
-                extensible: _,
-            } => {
-                min.as_ref().map_or(false, |s| s.is_elsewhere_declared())
-                    && max.as_ref().map_or(false, |s| s.is_elsewhere_declared())
-            }
-            SubtypeElement::SizeConstraint(s) => s.has_cross_reference(),
-            SubtypeElement::TypeConstraint(t) => t.contains_class_field_reference(),
-            SubtypeElement::MultipleTypeConstraints(s)
-            | SubtypeElement::SingleTypeConstraint(s) => s
-                .constraints
-                .iter()
-                .any(|cc| cc.constraints.iter().any(|c| c.has_cross_reference())),
+                .try_fold(false, |linked, c| {
+                    Ok(c.link_cross_reference(identifier, tlds)? || linked)
+                }),
         }
     }
 }
@@ -646,26 +745,49 @@ pub enum ElementOrSetOperation {
 }
 
 impl ElementOrSetOperation {
+    /// Checks whether the values permitted by two element set operations overlap.
+    /// Intersections and exclusions are approximated by their operands,
+    /// so that overlaps are never missed.
+    pub fn overlaps(&self, other: &ElementOrSetOperation) -> bool {
+        match (self, other) {
+            (ElementOrSetOperation::Element(a), ElementOrSetOperation::Element(b)) => a.overlaps(b),
+            (ElementOrSetOperation::SetOperation(s), o)
+            | (o, ElementOrSetOperation::SetOperation(s)) => {
+                let base = o.overlaps(&ElementOrSetOperation::Element(s.base.clone()));
+                match s.operator {
+                    SetOperator::Union => base || s.operant.overlaps(o),
+                    SetOperator::Intersection => base && s.operant.overlaps(o),
+                    SetOperator::Except => base,
+                }
+            }
+        }
+    }
+
     pub(super) fn link_cross_reference(
         &mut self,
         identifier: &String,
         tlds: &BTreeMap<String, ToplevelDeclaration>,
-    ) -> bool {
+    ) -> Result<bool, GrammarError> {
         match self {
             ElementOrSetOperation::Element(e) => e.link_cross_reference(identifier, tlds),
             ElementOrSetOperation::SetOperation(s) => {
-                let a = s.base.link_cross_reference(identifier, tlds);
-                let b = s.operant.link_cross_reference(identifier, tlds);
-                a || b
+                let a = s.base.link_cross_reference(identifier, tlds)?;
+                let b = s.operant.link_cross_reference(identifier, tlds)?;
+                Ok(a || b)
             }
         }
     }
 
-    pub(super) fn has_cross_reference(&self) -> bool {
+    fn signature(&self) -> String {
         match self {
-            ElementOrSetOperation::Element(e) => e.has_cross_reference(),
+            ElementOrSetOperation::Element(e) => e.signature(),
             ElementOrSetOperation::SetOperation(s) => {
-                s.base.has_cross_reference() || s.operant.has_cross_reference()
+                let operator = match s.operator {
+                    SetOperator::Intersection => "^",
+                    SetOperator::Union => "|",
+                    SetOperator::Except => "\\",
+                };
+                s.base.signature() + operator + &s.operant.signature()
             }
         }
     }
@@ -687,3 +809,113 @@ impl From<(SubtypeElement, SetOperator, ElementOrSetOperation)> for SetOperation
         }
     }
 }
+
+/// Checks whether an upper bound lies below a lower bound.
+/// Absent bounds are unbounded, and incomparable bounds are never below each other.
+fn is_below(upper: Option<&ASN1Value>, lower: Option<&ASN1Value>) -> bool {
+    match (upper, lower) {
+        (Some(upper), Some(lower)) => compare_values(upper, lower) == Some(Ordering::Less),
+        _ => false,
+    }
+}
+
+fn compare_values(a: &ASN1Value, b: &ASN1Value) -> Option<Ordering> {
+    match (a, b) {
+        (ASN1Value::Integer(a), ASN1Value::Integer(b)) => Some(a.cmp(b)),
+        (ASN1Value::Real(a), ASN1Value::Real(b)) => a.partial_cmp(b),
+        (ASN1Value::Integer(a), ASN1Value::Real(b)) => (*a as f64).partial_cmp(b),
+        (ASN1Value::Real(a), ASN1Value::Integer(b)) => a.partial_cmp(&(*b as f64)),
+        (ASN1Value::ElsewhereDeclaredValue(_), _) | (_, ASN1Value::ElsewhereDeclaredValue(_)) => {
+            None
+        }
+        (a, b) if a == b => Some(Ordering::Equal),
+        _ => None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn single(value: i128) -> SubtypeElement {
+        SubtypeElement::SingleValue {
+            value: ASN1Value::Integer(value),
+            extensible: false,
+        }
+    }
+
+    fn range(min: Option<i128>, max: Option<i128>) -> SubtypeElement {
+        SubtypeElement::ValueRange {
+            min: min.map(ASN1Value::Integer),
+            max: max.map(ASN1Value::Integer),
+            extensible: false,
+        }
+    }
+
+    fn size(element: SubtypeElement) -> SubtypeElement {
+        SubtypeElement::SizeConstraint(Box::new(ElementOrSetOperation::Element(element)))
+    }
+
+    #[test]
+    fn detects_overlapping_ranges() {
+        assert!(range(Some(0), Some(5)).overlaps(&range(Some(5), Some(10))));
+        assert!(range(Some(3), Some(4)).overlaps(&range(Some(0), Some(10))));
+        assert!(!range(Some(0), Some(4)).overlaps(&range(Some(5), Some(10))));
+        assert!(!range(Some(5), Some(10)).overlaps(&range(Some(0), Some(4))));
+    }
+
+    #[test]
+    fn detects_overlapping_open_ended_ranges() {
+        assert!(range(None, Some(0)).overlaps(&range(Some(0), None)));
+        assert!(!range(None, Some(-1)).overlaps(&range(Some(0), None)));
+        assert!(range(None, None).overlaps(&range(Some(3), Some(3))));
+        assert!(range(Some(10), None).overlaps(&range(Some(20), None)));
+        assert!(!range(Some(10), None).overlaps(&range(None, Some(9))));
+    }
+
+    #[test]
+    fn detects_overlapping_single_values() {
+        assert!(single(4).overlaps(&range(Some(0), Some(5))));
+        assert!(range(None, Some(4)).overlaps(&single(4)));
+        assert!(!single(6).overlaps(&range(Some(0), Some(5))));
+        assert!(single(1).overlaps(&single(1)));
+        assert!(!single(1).overlaps(&single(2)));
+        assert!(SubtypeElement::SingleValue {
+            value: ASN1Value::String("a".into()),
+            extensible: false
+        }
+        .overlaps(&SubtypeElement::SingleValue {
+            value: ASN1Value::String("a".into()),
+            extensible: true
+        }));
+    }
+
+    #[test]
+    fn detects_overlapping_size_constraints() {
+        assert!(size(range(Some(1), Some(8))).overlaps(&size(single(8))));
+        assert!(!size(range(Some(1), Some(8))).overlaps(&size(range(Some(9), None))));
+        let one_or_two = SubtypeElement::SizeConstraint(Box::new(
+            ElementOrSetOperation::SetOperation(SetOperation {
+                base: single(1),
+                operator: SetOperator::Union,
+                operant: Box::new(ElementOrSetOperation::Element(single(2))),
+            }),
+        ));
+        assert!(size(single(2)).overlaps(&one_or_two));
+        assert!(!size(single(4)).overlaps(&one_or_two));
+    }
+
+    #[test]
+    fn assumes_overlap_of_incomparable_elements() {
+        assert!(single(1).overlaps(&SubtypeElement::SingleValue {
+            value: ASN1Value::ElsewhereDeclaredValue("limit".into()),
+            extensible: false
+        }));
+        assert!(size(single(1)).overlaps(&single(2)));
+        assert!(SubtypeElement::SingleValue {
+            value: ASN1Value::String("a".into()),
+            extensible: false
+        }
+        .overlaps(&single(1)));
+    }
+}
@@ -0,0 +1,383 @@
+//! The `dependencies` module collects the names of the top-level declarations
+//! that a top-level declaration refers to, e.g. the `Bar` in `Foo ::= SEQUENCE { bar Bar }`.
+use alloc::{collections::BTreeSet, string::String};
+
+use crate::{constraints::*, information_object::*, *};
+
+/// Traverses an ASN1 element and inserts the names of the referenced
+/// top-level declarations into `dependencies`
+pub(crate) trait CollectDependencies {
+    fn collect_dependencies(&self, dependencies: &mut BTreeSet<String>);
+}
+
+impl<T: CollectDependencies> CollectDependencies for Option<T> {
+    fn collect_dependencies(&self, dependencies: &mut BTreeSet<String>) {
+        if let Some(inner) = self {
+            inner.collect_dependencies(dependencies)
+        }
+    }
+}
+
+impl<T: CollectDependencies> CollectDependencies for Vec<T> {
+    fn collect_dependencies(&self, dependencies: &mut BTreeSet<String>) {
+        self.iter()
+            .for_each(|inner| inner.collect_dependencies(dependencies))
+    }
+}
+
+impl<T: CollectDependencies> CollectDependencies for Box<T> {
+    fn collect_dependencies(&self, dependencies: &mut BTreeSet<String>) {
+        self.as_ref().collect_dependencies(dependencies)
+    }
+}
+
+impl ToplevelDeclaration {
+    /// Returns the names of all top-level declarations that this declaration refers to,
+    /// including references in constraints, default values, and information objects.
+    /// Dummy references of a parameterized declaration are not included.
+    /// The types of recursive members are included as well, so the dependencies
+    /// of recursive declarations are cyclic.
+    pub fn dependencies(&self) -> BTreeSet<String> {
+        let mut dependencies = BTreeSet::new();
+        match self {
+            ToplevelDeclaration::Type(t) => {
+                t.r#type.collect_dependencies(&mut dependencies);
+                remove_dummy_references(&t.parameterization, &mut dependencies);
+            }
+            ToplevelDeclaration::Value(v) => {
+                insert_type_name(&v.type_name, &mut dependencies);
+                v.value.collect_dependencies(&mut dependencies);
+            }
+            ToplevelDeclaration::Information(i) => {
+                if let Some(class) = i.class.as_ref().and_then(ClassLink::name) {
+                    dependencies.insert(class.clone());
+                }
+                if let Some(ClassLink::ByParameterizedName(_, parameters)) = &i.class {
+                    parameters.collect_dependencies(&mut dependencies);
+                }
+                i.value.collect_dependencies(&mut dependencies);
+                remove_dummy_references(&i.parameterization, &mut dependencies);
+            }
+        }
+        dependencies.remove(self.name());
+        dependencies
+    }
+}
+
+/// Removes the dummy references of a parameterized declaration from its dependencies
+/// and inserts the governors of its parameters instead
+fn remove_dummy_references(
+    parameterization: &Option<Parameterization>,
+    dependencies: &mut BTreeSet<String>,
+) {
+    if let Some(parameterization) = parameterization {
+        for parameter in &parameterization.parameters {
+            match &parameter.name {
+                Some(dummy) => {
+                    dependencies.remove(dummy);
+                    insert_type_name(&parameter.r#type, dependencies);
+                }
+                None => {
+                    dependencies.remove(&parameter.r#type);
+                }
+            }
+        }
+    }
+}
+
+/// Inserts a type name into the dependencies unless it names a built-in ASN1 type
+fn insert_type_name(type_name: &str, dependencies: &mut BTreeSet<String>) {
+    let is_built_in = type_name.contains(' ')
+        || ASN1_KEYWORDS.contains(&type_name)
+        || CHARACTER_STRING_TYPES.contains(&type_name);
+    if !is_built_in {
+        dependencies.insert(type_name.to_owned());
+    }
+}
+
+impl CollectDependencies for ASN1Type {
+    fn collect_dependencies(&self, dependencies: &mut BTreeSet<String>) {
+        match self {
+            ASN1Type::Choice(c) => {
+                c.constraints.collect_dependencies(dependencies);
+                for option in &c.options {
+                    option.r#type.collect_dependencies(dependencies);
+                    option.constraints.collect_dependencies(dependencies);
+                }
+            }
+            ASN1Type::Sequence(s) | ASN1Type::Set(s) => {
+                s.constraints.collect_dependencies(dependencies);
+                for member in s.all_members() {
+                    member.r#type.collect_dependencies(dependencies);
+                    member.constraints.collect_dependencies(dependencies);
+                    member.default_value.collect_dependencies(dependencies);
+                }
+            }
+            ASN1Type::SequenceOf(s) | ASN1Type::SetOf(s) => {
+                s.constraints.collect_dependencies(dependencies);
+                s.r#type.collect_dependencies(dependencies);
+            }
+            ASN1Type::ElsewhereDeclaredType(e) => {
+                dependencies.insert(e.identifier.clone());
+                e.constraints.collect_dependencies(dependencies);
+            }
+            ASN1Type::InformationObjectFieldReference(r) => {
+                dependencies.insert(r.class.clone());
+                r.constraints.collect_dependencies(dependencies);
+            }
+            ASN1Type::Real(r) => r.constraints.collect_dependencies(dependencies),
+            other => other.constraints().collect_dependencies(dependencies),
+        }
+    }
+}
+
+impl CollectDependencies for ASN1Value {
+    fn collect_dependencies(&self, dependencies: &mut BTreeSet<String>) {
+        match self {
+            ASN1Value::ElsewhereDeclaredValue(name) => {
+                dependencies.insert(name.clone());
+            }
+            ASN1Value::EnumeratedValue { enumerated, .. } if !enumerated.is_empty() => {
+                dependencies.insert(enumerated.clone());
+            }
+            ASN1Value::Choice(_, value) => value.collect_dependencies(dependencies),
+            ASN1Value::Sequence(members) => members
+                .iter()
+                .for_each(|(_, value)| value.collect_dependencies(dependencies)),
+            _ => (),
+        }
+    }
+}
+
+impl CollectDependencies for Constraint {
+    fn collect_dependencies(&self, dependencies: &mut BTreeSet<String>) {
+        match self {
+            Constraint::SubtypeConstraint(s) => s.set.collect_dependencies(dependencies),
+            Constraint::TableConstraint(t) => t.object_set.collect_dependencies(dependencies),
+            Constraint::ContentConstraint(c) => {
+                if let Some(containing) = &c.containing {
+                    containing.collect_dependencies(dependencies)
+                }
+            }
+            Constraint::Parameter(parameters) => parameters.collect_dependencies(dependencies),
+        }
+    }
+}
+
+impl CollectDependencies for Parameter {
+    fn collect_dependencies(&self, dependencies: &mut BTreeSet<String>) {
+        match self {
+            Parameter::ValueParameter(v) => v.collect_dependencies(dependencies),
+            Parameter::TypeParameter(t) => t.collect_dependencies(dependencies),
+            Parameter::InformationObjectParameter(o) => o.collect_dependencies(dependencies),
+            Parameter::ObjectSetParameter(o) => o.collect_dependencies(dependencies),
+        }
+    }
+}
+
+impl CollectDependencies for ElementOrSetOperation {
+    fn collect_dependencies(&self, dependencies: &mut BTreeSet<String>) {
+        match self {
+            ElementOrSetOperation::Element(e) => e.collect_dependencies(dependencies),
+            ElementOrSetOperation::SetOperation(s) => {
+                s.base.collect_dependencies(dependencies);
+                s.operant.collect_dependencies(dependencies);
+            }
+        }
+    }
+}
+
+impl CollectDependencies for SubtypeElement {
+    fn collect_dependencies(&self, dependencies: &mut BTreeSet<String>) {
+        match self {
+            SubtypeElement::SingleValue { value, .. } => value.collect_dependencies(dependencies),
+            SubtypeElement::ContainedSubtype { subtype, .. } => {
+                subtype.collect_dependencies(dependencies)
+            }
+            SubtypeElement::ValueRange { min, max, .. } => {
+                min.collect_dependencies(dependencies);
+                max.collect_dependencies(dependencies);
+            }
+            SubtypeElement::PermittedAlphabet(e) | SubtypeElement::SizeConstraint(e) => {
+                e.collect_dependencies(dependencies)
+            }
+            SubtypeElement::TypeConstraint(t) => t.collect_dependencies(dependencies),
+            SubtypeElement::SingleTypeConstraint(c)
+            | SubtypeElement::MultipleTypeConstraints(c) => c
+                .constraints
+                .iter()
+                .for_each(|component| component.constraints.collect_dependencies(dependencies)),
+            SubtypeElement::PatternConstraint(_)
+            | SubtypeElement::UserDefinedConstraint(_)
+            | SubtypeElement::PropertySettings(_) => (),
+        }
+    }
+}
+
+impl CollectDependencies for ASN1Information {
+    fn collect_dependencies(&self, dependencies: &mut BTreeSet<String>) {
+        match self {
+            ASN1Information::ObjectClass(c) => c.fields.iter().for_each(|field| {
+                field.r#type.collect_dependencies(dependencies);
+                field.default.collect_dependencies(dependencies);
+            }),
+            ASN1Information::ObjectSet(s) => s.collect_dependencies(dependencies),
+            ASN1Information::Object(o) => {
+                dependencies.insert(o.supertype.clone());
+                o.fields.collect_dependencies(dependencies);
+            }
+        }
+    }
+}
+
+impl CollectDependencies for ObjectSet {
+    fn collect_dependencies(&self, dependencies: &mut BTreeSet<String>) {
+        for value in &self.values {
+            match value {
+                ObjectSetValue::Reference(name) => {
+                    dependencies.insert(name.clone());
+                }
+                ObjectSetValue::Inline(fields) => fields.collect_dependencies(dependencies),
+            }
+        }
+    }
+}
+
+impl CollectDependencies for InformationObjectFields {
+    fn collect_dependencies(&self, dependencies: &mut BTreeSet<String>) {
+        match self {
+            InformationObjectFields::DefaultSyntax(fields) => {
+                for field in fields {
+                    match field {
+                        InformationObjectField::TypeField(t) => {
+                            t.r#type.collect_dependencies(dependencies)
+                        }
+                        InformationObjectField::FixedValueField(f) => {
+                            f.value.collect_dependencies(dependencies)
+                        }
+                        InformationObjectField::ObjectSetField(o) => {
+                            o.value.collect_dependencies(dependencies)
+                        }
+                    }
+                }
+            }
+            InformationObjectFields::CustomSyntax(applications) => {
+                for application in applications {
+                    match application {
+                        SyntaxApplication::ObjectSetDeclaration(o) => {
+                            o.collect_dependencies(dependencies)
+                        }
+                        SyntaxApplication::ValueReference(v) => {
+                            v.collect_dependencies(dependencies)
+                        }
+                        SyntaxApplication::TypeReference(t) => {
+                            t.collect_dependencies(dependencies)
+                        }
+                        SyntaxApplication::Comma | SyntaxApplication::Literal(_) => (),
+                    }
+                }
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use alloc::{collections::BTreeSet, string::String, vec};
+
+    use crate::{types::*, *};
+
+    fn type_declaration(name: &str, r#type: ASN1Type) -> ToplevelDeclaration {
+        ToplevelDeclaration::Type(ToplevelTypeDeclaration {
+            comments: String::new(),
+            tag: None,
+            name: name.into(),
+            r#type,
+            parameterization: None,
+        })
+    }
+
+    #[test]
+    fn collects_referenced_type_names() {
+        let foo = type_declaration(
+            "Foo",
+            ASN1Type::Sequence(SequenceOrSet {
+                extensible: None,
+                constraints: vec![],
+                members: vec![SequenceOrSetMember {
+                    name: "bar".into(),
+                    tag: None,
+                    r#type: ASN1Type::ElsewhereDeclaredType(DeclarationElsewhere {
+                        identifier: "Bar".into(),
+                        constraints: vec![],
+                    }),
+                    default_value: None,
+                    is_optional: false,
+                    constraints: vec![],
+                    is_recursive: false,
+                }],
+                extension_groups: vec![],
+            }),
+        );
+        assert_eq!(foo.dependencies(), BTreeSet::from([String::from("Bar")]));
+    }
+
+    #[test]
+    fn collects_types_of_recursive_members() {
+        let expression = type_declaration(
+            "Expression",
+            ASN1Type::Choice(Choice {
+                extensible: None,
+                options: vec![ChoiceOption {
+                    name: "sum".into(),
+                    tag: None,
+                    r#type: ASN1Type::ElsewhereDeclaredType(DeclarationElsewhere {
+                        identifier: "Sum".into(),
+                        constraints: vec![],
+                    }),
+                    constraints: vec![],
+                    is_recursive: true,
+                }],
+                constraints: vec![],
+            }),
+        );
+        assert_eq!(
+            expression.dependencies(),
+            BTreeSet::from([String::from("Sum")])
+        );
+    }
+
+    #[test]
+    fn collects_references_in_constraints_and_values() {
+        let mut size = Integer::from((0, 1, false));
+        if let Some(constraints::Constraint::SubtypeConstraint(set)) = size.constraints.first_mut()
+        {
+            set.set = constraints::ElementOrSetOperation::Element(
+                constraints::SubtypeElement::ValueRange {
+                    min: Some(ASN1Value::Integer(0)),
+                    max: Some(ASN1Value::ElsewhereDeclaredValue("upper-bound".into())),
+                    extensible: false,
+                },
+            );
+        }
+        let counter = type_declaration("Counter", ASN1Type::Integer(size));
+        assert_eq!(
+            counter.dependencies(),
+            BTreeSet::from([String::from("upper-bound")])
+        );
+        let value = ToplevelDeclaration::Value(ToplevelValueDeclaration {
+            comments: String::new(),
+            name: "default-counter".into(),
+            type_name: "Counter".into(),
+            value: ASN1Value::Integer(1),
+        });
+        assert_eq!(value.dependencies(), BTreeSet::from([String::from("Counter")]));
+        let integer = ToplevelDeclaration::Value(ToplevelValueDeclaration {
+            comments: String::new(),
+            name: "upper-bound".into(),
+            type_name: INTEGER.into(),
+            value: ASN1Value::Integer(1),
+        });
+        assert!(integer.dependencies().is_empty());
+    }
+}
@@ -4,7 +4,7 @@ use crate::{
     constraints::{Constraint, ElementOrSetOperation, SetOperation, SetOperator, SubtypeElement},
     error::{GrammarError, GrammarErrorType},
     types::{Choice, Enumerated},
-    ASN1Type, ASN1Value, CharacterStringType,
+    index_in_character_set, ASN1Type, ASN1Value, CharacterStringType,
 };
 use alloc::{collections::BTreeMap, format, string::String, vec, vec::Vec};
 use num::ToPrimitive;
@@ -57,10 +57,7 @@ impl PerVisibleAlphabetConstraints {
             None => Ok(None),
             Some(SubtypeElement::PermittedAlphabet(elem_or_set)) => match &**elem_or_set {
                 ElementOrSetOperation::Element(e) => Self::from_subtype_elem(Some(e), string_type),
-                ElementOrSetOperation::SetOperation(s) => Self::from_subtype_elem(
-                    fold_constraint_set(s, Some(&string_type.character_set()))?.as_ref(),
-                    string_type,
-                ),
+                ElementOrSetOperation::SetOperation(s) => Self::from_alphabet_set(s, string_type),
             },
             Some(SubtypeElement::SingleValue { value, extensible }) => match (value, extensible) {
                 (ASN1Value::String(s), false) => {
@@ -92,6 +89,9 @@ impl PerVisibleAlphabetConstraints {
                 if *extensible {
                     return Ok(None);
                 }
+                if char_set.is_empty() {
+                    return Self::from_code_point_range(min.as_ref(), max.as_ref(), string_type);
+                }
                 let (lower, upper) = match (min, max) {
                     (Some(ASN1Value::String(min)), Some(ASN1Value::String(max))) => (
                         find_string_index(min, &char_set)?,
@@ -132,7 +132,7 @@ impl PerVisibleAlphabetConstraints {
                         PerVisibleAlphabetConstraints::default_for(string_type);
                     for c in &c_string.constraints {
                         PerVisibleAlphabetConstraints::try_new(c, c_string.r#type)?
-                            .map(|mut p| permitted_alphabet += &mut p);
+                            .map(|mut p| permitted_alphabet.union_with(&mut p));
                     }
                     Ok(Some(permitted_alphabet))
                 } else {
@@ -143,8 +143,83 @@ impl PerVisibleAlphabetConstraints {
         }
     }
 
+    /// Creates permitted alphabet constraints from a set operation within a `FROM` constraint.
+    /// Unions of alphabets permit exactly the characters of their operands, rather than
+    /// every character between the operands' bounds, so they are not folded into a single range.
+    /// A union with an alphabet that is not PER-visible is not PER-visible itself.
+    fn from_alphabet_set(
+        set: &SetOperation,
+        string_type: CharacterStringType,
+    ) -> Result<Option<Self>, GrammarError> {
+        if set.operator != SetOperator::Union {
+            return Self::from_subtype_elem(
+                fold_constraint_set(set, Some(&string_type.character_set()))?.as_ref(),
+                string_type,
+            );
+        }
+        let base = Self::from_subtype_elem(Some(&set.base), string_type)?;
+        let operant = match &*set.operant {
+            ElementOrSetOperation::Element(e) => Self::from_subtype_elem(Some(e), string_type)?,
+            ElementOrSetOperation::SetOperation(s) => Self::from_alphabet_set(s, string_type)?,
+        };
+        Ok(base.zip(operant).map(|(mut base, mut operant)| {
+            base.union_with(&mut operant);
+            base
+        }))
+    }
+
+    /// Creates permitted alphabet constraints for string types whose character set
+    /// is too large to be enumerated. The characters are indexed by their code points.
+    fn from_code_point_range(
+        min: Option<&ASN1Value>,
+        max: Option<&ASN1Value>,
+        string_type: CharacterStringType,
+    ) -> Result<Option<Self>, GrammarError> {
+        let code_point = |value: Option<&ASN1Value>, default: u32| match value {
+            Some(ASN1Value::String(s)) => s.chars().next().map(|c| c as u32).ok_or(GrammarError {
+                details: "Empty string as permitted alphabet boundary!".into(),
+                kind: GrammarErrorType::UnpackingError,
+            }),
+            _ => Ok(default),
+        };
+        let lower = code_point(min, 0)?;
+        let upper = code_point(
+            max,
+            ((1u64 << string_type.char_bit_size()) - 1).min(char::MAX as u64) as u32,
+        )?;
+        if lower > upper {
+            return Err(GrammarError {
+                details: format!("Invalid range for permitted alphabet: Range: {lower}..={upper}"),
+                kind: GrammarErrorType::UnpackingError,
+            });
+        }
+        Ok(Some(PerVisibleAlphabetConstraints {
+            string_type,
+            character_by_index: (lower..=upper)
+                .filter_map(char::from_u32)
+                .enumerate()
+                .collect(),
+            index_by_character: None,
+            charset_subsets: vec![CharsetSubset::Range {
+                from: char::from_u32(lower),
+                to: char::from_u32(upper),
+            }],
+        }))
+    }
+
+    /// Returns whether characters are encoded by their raw code points.
+    /// This is the case for known-multiplier strings whose character set
+    /// is too large to be enumerated and whose permitted alphabet is not constrained.
+    pub fn is_raw_encoded(&self) -> bool {
+        self.character_by_index.is_empty()
+            && self.string_type.has_raw_character_set()
+            && self.string_type.is_known_multiplier_string()
+    }
+
     pub fn bit_length(&self) -> usize {
-        if self.is_known_multiplier_string() {
+        if self.is_raw_encoded() {
+            self.string_type.char_bit_size()
+        } else if self.is_known_multiplier_string() {
             let charset_size = self.character_by_index.len() as i128;
             bit_length(0, charset_size - 1)
         } else {
@@ -214,6 +289,64 @@ impl PerVisibleAlphabetConstraints {
         s.finalize();
         s
     }
+
+    /// Adds the permitted characters of `other` to the permitted alphabet,
+    /// moving them out of `other`. This is what `+=` does.
+    /// The characters are indexed in the order of the string type's character set.
+    pub fn union_with(&mut self, other: &mut Self) {
+        let mut characters = core::mem::take(&mut self.character_by_index)
+            .into_values()
+            .collect::<Vec<char>>();
+        characters.extend(core::mem::take(&mut other.character_by_index).into_values());
+        self.reindex(characters);
+        self.charset_subsets.append(&mut other.charset_subsets);
+    }
+
+    /// Removes the characters that are not permitted by `other` from the permitted alphabet.
+    /// An empty alphabet does not constrain the characters of a string type,
+    /// so intersecting with an empty alphabet leaves the permitted alphabet unchanged.
+    /// Likewise, the intersection of disjoint alphabets no longer constrains the characters.
+    pub fn intersect_with(&mut self, other: &Self) {
+        if other.character_by_index.is_empty() {
+            return;
+        }
+        let characters = if self.character_by_index.is_empty() {
+            other.character_by_index.values().copied().collect()
+        } else {
+            self.character_by_index
+                .values()
+                .copied()
+                .filter(|c| other.permits(c))
+                .collect::<Vec<char>>()
+        };
+        self.charset_subsets = characters
+            .iter()
+            .copied()
+            .map(CharsetSubset::Single)
+            .collect();
+        self.reindex(characters);
+    }
+
+    /// Checks whether every character permitted by the alphabet is permitted by `other`.
+    pub fn is_subset_of(&self, other: &Self) -> bool {
+        if other.character_by_index.is_empty() {
+            return true;
+        }
+        !self.character_by_index.is_empty()
+            && self.character_by_index.values().all(|c| other.permits(c))
+    }
+
+    fn permits(&self, character: &char) -> bool {
+        self.character_by_index.values().any(|c| c == character)
+    }
+
+    fn reindex(&mut self, mut characters: Vec<char>) {
+        let char_set = self.string_type.character_set();
+        characters.sort_by_key(|c| index_in_character_set(&char_set, *c));
+        characters.dedup();
+        self.character_by_index = characters.into_iter().enumerate().collect();
+        self.index_by_character = None;
+    }
 }
 
 fn find_string_index(
@@ -225,9 +358,7 @@ fn find_string_index(
 }
 
 fn find_char_index(char_set: &BTreeMap<usize, char>, as_char: char) -> Result<usize, GrammarError> {
-    char_set
-        .iter()
-        .find_map(|(i, c)| (as_char == *c).then(|| *i))
+    index_in_character_set(char_set, as_char)
         .ok_or(
             GrammarError {
                 details: format!("Character {as_char} is not in char set: {:?}", char_set),
@@ -239,15 +370,20 @@ fn find_char_index(char_set: &BTreeMap<usize, char>, as_char: char) -> Result<us
 
 impl AddAssign<&mut PerVisibleAlphabetConstraints> for PerVisibleAlphabetConstraints {
     fn add_assign(&mut self, rhs: &mut PerVisibleAlphabetConstraints) {
-        self.character_by_index.append(&mut rhs.character_by_index)
+        self.union_with(rhs)
     }
 }
 
+#[derive(Debug, Clone, PartialEq)]
 pub struct PerVisibleRangeConstraints {
     min: Option<i128>,
     max: Option<i128>,
     extensible: bool,
     is_size_constraint: bool,
+    /// Ranges that are removed from the permitted values by an EXCEPT clause.
+    /// They are only considered when checking values, because X.691 ignores
+    /// EXCEPT clauses when determining the PER-visible bounds (see 10.3.21).
+    excepted: Vec<(Option<i128>, Option<i128>)>,
 }
 
 impl Default for PerVisibleRangeConstraints {
@@ -257,6 +393,7 @@ impl Default for PerVisibleRangeConstraints {
             max: None,
             extensible: false,
             is_size_constraint: false,
+            excepted: vec![],
         }
     }
 }
@@ -268,6 +405,7 @@ impl PerVisibleRangeConstraints {
             max: None,
             extensible: false,
             is_size_constraint: false,
+            excepted: vec![],
         }
     }
 
@@ -325,8 +463,11 @@ impl PerVisibleRangeConstraints {
             details: "Failed to convert integer to u128!".into(),
             kind: GrammarErrorType::PerVisibleConstraintError,
         })?;
-        let lies_within =
-            self.min.map_or(true, |m| as_i128 >= m) && self.max.map_or(true, |m| as_i128 <= m);
+        let lies_within = self.min.is_none_or(|m| as_i128 >= m)
+            && self.max.is_none_or(|m| as_i128 <= m)
+            && !self.excepted.iter().any(|(min, max)| {
+                min.is_none_or(|m| as_i128 >= m) && max.is_none_or(|m| as_i128 <= m)
+            });
         if !lies_within && !self.is_extensible() {
             Err(GrammarError {
                 details: "Provided value that violates non-extensible constraints!".into(),
@@ -336,6 +477,46 @@ impl PerVisibleRangeConstraints {
             Ok(lies_within)
         }
     }
+
+    /// Computes the values of the constraints that do not lie within `other`.
+    /// Returns `None` if no values remain, and a second range if `other` splits the
+    /// constraints in two, e.g. `(0..100) EXCEPT (40..60)` results in `0..39` and `61..100`.
+    pub fn complement(
+        &self,
+        other: &PerVisibleRangeConstraints,
+    ) -> Option<(
+        PerVisibleRangeConstraints,
+        Option<PerVisibleRangeConstraints>,
+    )> {
+        let segment = |min: Option<i128>, max: Option<i128>| {
+            min.zip(max)
+                .is_none_or(|(min, max)| min <= max)
+                .then(|| PerVisibleRangeConstraints {
+                    min,
+                    max,
+                    ..self.clone()
+                })
+        };
+        let below = match other.min {
+            Some(other_min) if self.min.is_none_or(|m| m < other_min) => segment(
+                self.min,
+                Some(self.max.map_or(other_min - 1, |m| m.min(other_min - 1))),
+            ),
+            _ => None,
+        };
+        let above = match other.max {
+            Some(other_max) if self.max.is_none_or(|m| m > other_max) => segment(
+                Some(self.min.map_or(other_max + 1, |m| m.max(other_max + 1))),
+                self.max,
+            ),
+            _ => None,
+        };
+        match (below, above) {
+            (Some(below), above) => Some((below, above)),
+            (None, Some(above)) => Some((above, None)),
+            (None, None) => None,
+        }
+    }
 }
 
 impl From<&Enumerated> for PerVisibleRangeConstraints {
@@ -345,6 +526,7 @@ impl From<&Enumerated> for PerVisibleRangeConstraints {
             max: Some(value.extensible.map_or(value.members.len() - 1, |i| i - 1) as i128),
             extensible: value.extensible.is_some(),
             is_size_constraint: false,
+            excepted: vec![],
         }
     }
 }
@@ -356,6 +538,7 @@ impl From<&Choice> for PerVisibleRangeConstraints {
             max: Some(value.extensible.map_or(value.options.len() - 1, |i| i - 1) as i128),
             extensible: value.extensible.is_some(),
             is_size_constraint: false,
+            excepted: vec![],
         }
     }
 }
@@ -370,6 +553,7 @@ impl AddAssign<PerVisibleRangeConstraints> for PerVisibleRangeConstraints {
         };
         self.extensible = self.extensible || rhs.extensible;
         self.is_size_constraint = self.is_size_constraint || rhs.is_size_constraint;
+        self.excepted.extend(rhs.excepted);
     }
 }
 
@@ -381,7 +565,18 @@ impl TryFrom<&Constraint> for PerVisibleRangeConstraints {
             Constraint::SubtypeConstraint(c) => match &c.set {
                 ElementOrSetOperation::Element(e) => Some(e).try_into(),
                 ElementOrSetOperation::SetOperation(s) => {
-                    fold_constraint_set(&s, None)?.as_ref().try_into()
+                    let mut constraints: Self =
+                        fold_constraint_set(s, None)?.as_ref().try_into()?;
+                    constraints.is_size_constraint |= has_size_constraint(s);
+                    if let (SetOperator::Except, ElementOrSetOperation::Element(e)) =
+                        (&s.operator, &*s.operant)
+                    {
+                        if is_integer_valued(e) {
+                            let excepted = Self::try_from(Some(e))?;
+                            constraints.excepted.push((excepted.min, excepted.max));
+                        }
+                    }
+                    Ok(constraints)
                 }
             },
             _ => Ok(Self::default()),
@@ -401,6 +596,7 @@ impl TryFrom<Option<&SubtypeElement>> for PerVisibleRangeConstraints {
                     max: val,
                     extensible: *extensible,
                     is_size_constraint: false,
+                    excepted: vec![],
                 })
             }
             Some(SubtypeElement::ValueRange {
@@ -412,6 +608,7 @@ impl TryFrom<Option<&SubtypeElement>> for PerVisibleRangeConstraints {
                 max: max.as_ref().map(|i| i.unwrap_as_integer().ok()).flatten(),
                 extensible: *extensible,
                 is_size_constraint: false,
+                excepted: vec![],
             }),
             Some(SubtypeElement::SizeConstraint(s)) => match &**s {
                 ElementOrSetOperation::Element(e) => <Option<&SubtypeElement> as TryInto<
@@ -501,6 +698,16 @@ pub fn per_visible_range_constraints(
     Ok(constraints)
 }
 
+/// Checks whether a set operation combines a size constraint with other constraints,
+/// e.g. `SIZE(1..4) ^ FROM("a".."z")`. The folded bounds of such an operation are size bounds.
+fn has_size_constraint(set: &SetOperation) -> bool {
+    matches!(set.base, SubtypeElement::SizeConstraint(_))
+        || match &*set.operant {
+            ElementOrSetOperation::Element(e) => matches!(e, SubtypeElement::SizeConstraint(_)),
+            ElementOrSetOperation::SetOperation(s) => has_size_constraint(s),
+        }
+}
+
 /// 10.3.21	If a constraint that is PER-visible is part of an INTERSECTION construction,
 /// then the resulting constraint is PER-visible, and consists of the INTERSECTION of
 /// all PER-visible parts (with the non-PER-visible parts ignored).  
@@ -508,6 +715,7 @@ pub fn per_visible_range_constraints(
 /// then the resulting constraint is not PER-visible.  
 /// If a constraint has an EXCEPT clause, the EXCEPT and the following value set is completely ignored,
 /// whether the value set following the EXCEPT is PER-visible or not.
+/// EXCEPT clauses that exclude every value of an integer constraint are rejected nonetheless.
 fn fold_constraint_set(
     set: &SetOperation,
     char_set: Option<&BTreeMap<usize, char>>,
@@ -788,13 +996,33 @@ fn fold_constraint_set(
             }
             _ => unreachable!(),
         },
-        SetOperator::Except => {
-            if set.base.per_visible() {
-                Ok(Some(set.base.clone()))
-            } else {
-                Ok(None)
+        SetOperator::Except => match (&set.base, &folded_operant) {
+            (b, _) if !b.per_visible() => Ok(None),
+            (b, Some(f)) if char_set.is_none() && is_integer_valued(b) && is_integer_valued(f) => {
+                let base = PerVisibleRangeConstraints::try_from(Some(b))?;
+                if base
+                    .complement(&PerVisibleRangeConstraints::try_from(Some(f))?)
+                    .is_none()
+                {
+                    return Err(GrammarError {
+                        details: format!("Empty EXCEPT result for {:?} and {:?}", b, f),
+                        kind: GrammarErrorType::UnpackingError,
+                    });
+                }
+                Ok(Some(b.clone()))
             }
-        }
+            (b, _) => Ok(Some(b.clone())),
+        },
+    }
+}
+
+fn is_integer_valued(element: &SubtypeElement) -> bool {
+    match element {
+        SubtypeElement::SingleValue { value, .. } => matches!(value, ASN1Value::Integer(_)),
+        SubtypeElement::ValueRange { min, max, .. } => [min, max]
+            .iter()
+            .all(|v| v.as_ref().is_none_or(|v| matches!(v, ASN1Value::Integer(_)))),
+        _ => false,
     }
 }
 
@@ -1513,4 +1741,217 @@ mod tests {
             None
         );
     }
+
+    #[test]
+    fn uses_raw_code_points_for_large_character_sets() {
+        let mut bmp_alphabet =
+            PerVisibleAlphabetConstraints::default_for(CharacterStringType::BMPString);
+        bmp_alphabet.finalize();
+        assert!(bmp_alphabet.is_raw_encoded());
+        assert_eq!(bmp_alphabet.bit_length(), 16);
+        let mut universal_alphabet =
+            PerVisibleAlphabetConstraints::default_for(CharacterStringType::UniversalString);
+        universal_alphabet.finalize();
+        assert_eq!(universal_alphabet.bit_length(), 32);
+        assert!(CharacterStringType::TeletexString.character_set().is_empty());
+        assert!(!PerVisibleAlphabetConstraints::default_for(CharacterStringType::IA5String)
+            .is_raw_encoded());
+    }
+
+    #[test]
+    fn initializes_per_visible_alphabet_from_code_point_range() {
+        let constrained = PerVisibleAlphabetConstraints::try_new(
+            &Constraint::SubtypeConstraint(ElementSet {
+                extensible: false,
+                set: ElementOrSetOperation::Element(SubtypeElement::ValueRange {
+                    min: Some(ASN1Value::String("一".to_owned())),
+                    max: Some(ASN1Value::String("丏".to_owned())),
+                    extensible: false,
+                }),
+            }),
+            CharacterStringType::BMPString,
+        )
+        .unwrap()
+        .unwrap();
+        assert!(!constrained.is_raw_encoded());
+        assert_eq!(constrained.bit_length(), 4);
+        assert_eq!(constrained.get_char_by_index(5).unwrap(), &'丅');
+    }
+
+    fn ia5_alphabet(characters: &str) -> PerVisibleAlphabetConstraints {
+        PerVisibleAlphabetConstraints::try_new(
+            &Constraint::SubtypeConstraint(ElementSet {
+                extensible: false,
+                set: ElementOrSetOperation::Element(SubtypeElement::SingleValue {
+                    value: ASN1Value::String(characters.into()),
+                    extensible: false,
+                }),
+            }),
+            CharacterStringType::IA5String,
+        )
+        .unwrap()
+        .unwrap()
+    }
+
+    fn characters(alphabet: &PerVisibleAlphabetConstraints) -> String {
+        alphabet.character_by_index.values().collect()
+    }
+
+    #[test]
+    fn unites_alphabet_constraints() {
+        let mut alphabet = ia5_alphabet("DCA");
+        alphabet.union_with(&mut ia5_alphabet("BA"));
+        assert_eq!(characters(&alphabet), "ABCD");
+        assert_eq!(alphabet.bit_length(), 2);
+        alphabet += &mut ia5_alphabet("z");
+        assert_eq!(characters(&alphabet), "ABCDz");
+        alphabet.finalize();
+        assert_eq!(alphabet.index_by_character_map().unwrap()[&'z'], 4);
+    }
+
+    #[test]
+    fn intersects_alphabet_constraints() {
+        let mut alphabet = ia5_alphabet("ABCD");
+        alphabet.intersect_with(&ia5_alphabet("DBXY"));
+        assert_eq!(characters(&alphabet), "BD");
+        assert_eq!(alphabet.get_char_by_index(1).unwrap(), &'D');
+        alphabet.intersect_with(&PerVisibleAlphabetConstraints::default_for(
+            CharacterStringType::IA5String,
+        ));
+        assert_eq!(characters(&alphabet), "BD");
+    }
+
+    #[test]
+    fn checks_alphabet_subsets() {
+        let unconstrained =
+            PerVisibleAlphabetConstraints::default_for(CharacterStringType::IA5String);
+        assert!(ia5_alphabet("AC").is_subset_of(&ia5_alphabet("ABC")));
+        assert!(ia5_alphabet("ABC").is_subset_of(&ia5_alphabet("ABC")));
+        assert!(!ia5_alphabet("ABD").is_subset_of(&ia5_alphabet("ABC")));
+        assert!(ia5_alphabet("ABD").is_subset_of(&unconstrained));
+        assert!(!unconstrained.is_subset_of(&ia5_alphabet("ABC")));
+    }
+
+    #[test]
+    fn unites_permitted_alphabet_ranges() {
+        let char_range = |min: &str, max: &str| SubtypeElement::ValueRange {
+            min: Some(ASN1Value::String(min.into())),
+            max: Some(ASN1Value::String(max.into())),
+            extensible: false,
+        };
+        let alphabet = PerVisibleAlphabetConstraints::try_new(
+            &Constraint::SubtypeConstraint(ElementSet {
+                extensible: false,
+                set: ElementOrSetOperation::Element(SubtypeElement::PermittedAlphabet(Box::new(
+                    ElementOrSetOperation::SetOperation(SetOperation {
+                        base: char_range("0", "1"),
+                        operator: SetOperator::Union,
+                        operant: Box::new(ElementOrSetOperation::Element(char_range("A", "B"))),
+                    }),
+                ))),
+            }),
+            CharacterStringType::IA5String,
+        )
+        .unwrap()
+        .unwrap();
+        assert_eq!(characters(&alphabet), "01AB");
+        assert_eq!(
+            alphabet.charset_subsets(),
+            &vec![
+                CharsetSubset::Range {
+                    from: Some('0'),
+                    to: Some('1')
+                },
+                CharsetSubset::Range {
+                    from: Some('A'),
+                    to: Some('B')
+                }
+            ]
+        );
+    }
+
+    fn range(min: Option<i128>, max: Option<i128>) -> PerVisibleRangeConstraints {
+        PerVisibleRangeConstraints {
+            min,
+            max,
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn complements_range_constraints() {
+        assert_eq!(
+            range(Some(0), Some(100)).complement(&range(Some(40), Some(60))),
+            Some((range(Some(0), Some(39)), Some(range(Some(61), Some(100)))))
+        );
+        assert_eq!(
+            range(Some(0), Some(100)).complement(&range(Some(90), None)),
+            Some((range(Some(0), Some(89)), None))
+        );
+        assert_eq!(
+            range(Some(0), Some(100)).complement(&range(None, Some(9))),
+            Some((range(Some(10), Some(100)), None))
+        );
+        assert_eq!(
+            range(Some(0), Some(10)).complement(&range(Some(20), Some(30))),
+            Some((range(Some(0), Some(10)), None))
+        );
+        assert_eq!(
+            range(None, None).complement(&range(Some(0), Some(0))),
+            Some((range(None, Some(-1)), Some(range(Some(1), None))))
+        );
+        assert_eq!(
+            range(Some(3), Some(5)).complement(&range(Some(0), Some(10))),
+            None
+        );
+        assert_eq!(range(Some(3), Some(5)).complement(&range(None, None)), None);
+    }
+
+    #[test]
+    fn excludes_excepted_values_from_range_constraints() {
+        let constraint = Constraint::SubtypeConstraint(ElementSet {
+            extensible: false,
+            set: ElementOrSetOperation::SetOperation(SetOperation {
+                base: SubtypeElement::ValueRange {
+                    min: Some(ASN1Value::Integer(0)),
+                    max: Some(ASN1Value::Integer(10)),
+                    extensible: false,
+                },
+                operator: SetOperator::Except,
+                operant: Box::new(ElementOrSetOperation::Element(
+                    SubtypeElement::SingleValue {
+                        value: ASN1Value::Integer(5),
+                        extensible: false,
+                    },
+                )),
+            }),
+        });
+        let constraints = PerVisibleRangeConstraints::try_from(&constraint).unwrap();
+        assert_eq!(constraints.min::<i128>(), Some(0));
+        assert_eq!(constraints.max::<i128>(), Some(10));
+        assert!(constraints.lies_within(&4).unwrap());
+        assert!(constraints.lies_within(&6).unwrap());
+        assert!(constraints.lies_within(&5).is_err());
+    }
+
+    #[test]
+    fn rejects_except_clauses_excluding_all_values() {
+        assert!(fold_constraint_set(
+            &SetOperation {
+                base: SubtypeElement::ValueRange {
+                    min: Some(ASN1Value::Integer(3)),
+                    max: Some(ASN1Value::Integer(5)),
+                    extensible: false,
+                },
+                operator: SetOperator::Except,
+                operant: Box::new(ElementOrSetOperation::Element(SubtypeElement::ValueRange {
+                    min: Some(ASN1Value::Integer(0)),
+                    max: Some(ASN1Value::Integer(10)),
+                    extensible: false,
+                })),
+            },
+            None
+        )
+        .is_err());
+    }
 }
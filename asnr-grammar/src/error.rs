@@ -19,6 +19,12 @@ pub enum GrammarErrorType {
     UnpackingError,
     LinkerError,
     PerVisibleConstraintError,
+    TagEncodingError,
+    /// A reference resolves to a declaration of another kind than the referencing notation requires
+    UnexpectedType {
+        expected: String,
+        found: String,
+    },
 }
 
 impl Display for GrammarError {
@@ -30,3 +36,38 @@ impl Display for GrammarError {
         )
     }
 }
+
+impl core::error::Error for GrammarError {}
+
+#[cfg(test)]
+mod tests {
+    extern crate std;
+
+    use std::{boxed::Box, error::Error};
+
+    use super::*;
+
+    fn unpack() -> core::result::Result<(), GrammarError> {
+        Err(GrammarError::new("Missing value", GrammarErrorType::UnpackingError))
+    }
+
+    #[test]
+    fn propagates_as_boxed_error() {
+        fn propagate() -> core::result::Result<(), Box<dyn Error>> {
+            unpack()?;
+            Ok(())
+        }
+        fn propagate_thread_safe() -> core::result::Result<(), Box<dyn Error + Send + Sync>> {
+            unpack()?;
+            Ok(())
+        }
+        assert_eq!(
+            std::format!("{}", propagate().unwrap_err()),
+            "UnpackingError in ASN grammar: Missing value"
+        );
+        assert!(propagate_thread_safe()
+            .unwrap_err()
+            .downcast_ref::<GrammarError>()
+            .is_some());
+    }
+}
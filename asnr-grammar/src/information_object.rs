@@ -1,5 +1,7 @@
-use crate::{constraints::*, utils::walk_object_field_ref_path, *};
-use alloc::{string::String, vec, vec::Vec};
+use crate::{
+    constraints::*, parameterization::Parameterization, utils::walk_object_field_ref_path, *,
+};
+use alloc::{collections::BTreeMap, string::String, vec, vec::Vec};
 
 #[derive(Debug, Clone, PartialEq)]
 pub struct ToplevelInformationDeclaration {
@@ -7,46 +9,87 @@ pub struct ToplevelInformationDeclaration {
     pub name: String,
     pub class: Option<ClassLink>,
     pub value: ASN1Information,
+    pub parameterization: Option<Parameterization>,
 }
 
 #[derive(Debug, Clone, PartialEq)]
 pub enum ClassLink {
     ByName(String),
+    /// Refers to an instance of a parameterized class, e.g. `MY-CLASS { INTEGER }`
+    ByParameterizedName(String, Vec<Parameter>),
     ByReference(InformationObjectClass),
 }
 
-impl From<(Vec<&str>, &str, &str, InformationObjectFields)> for ToplevelInformationDeclaration {
-    fn from(value: (Vec<&str>, &str, &str, InformationObjectFields)) -> Self {
+impl From<(&str, Option<Vec<Parameter>>)> for ClassLink {
+    fn from(value: (&str, Option<Vec<Parameter>>)) -> Self {
+        match value.1 {
+            Some(parameters) => Self::ByParameterizedName(value.0.into(), parameters),
+            None => Self::ByName(value.0.into()),
+        }
+    }
+}
+
+impl ClassLink {
+    /// Returns the name of the referenced class if it is linked by name
+    pub fn name(&self) -> Option<&String> {
+        match self {
+            ClassLink::ByName(name) | ClassLink::ByParameterizedName(name, _) => Some(name),
+            ClassLink::ByReference(_) => None,
+        }
+    }
+}
+
+impl From<(Vec<&str>, &str, ClassLink, InformationObjectFields)>
+    for ToplevelInformationDeclaration
+{
+    fn from(value: (Vec<&str>, &str, ClassLink, InformationObjectFields)) -> Self {
         Self {
             comments: value.0.join("\n"),
             name: value.1.into(),
-            class: Some(ClassLink::ByName(value.2.into())),
             value: ASN1Information::Object(InformationObject {
-                supertype: value.2.into(),
+                supertype: value.2.name().cloned().unwrap_or_default(),
                 fields: value.3,
             }),
+            class: Some(value.2),
+            parameterization: None,
         }
     }
 }
 
-impl From<(Vec<&str>, &str, &str, ObjectSet)> for ToplevelInformationDeclaration {
-    fn from(value: (Vec<&str>, &str, &str, ObjectSet)) -> Self {
+impl From<(Vec<&str>, &str, ClassLink, ObjectSet)> for ToplevelInformationDeclaration {
+    fn from(value: (Vec<&str>, &str, ClassLink, ObjectSet)) -> Self {
         Self {
             comments: value.0.join("\n"),
             name: value.1.into(),
-            class: Some(ClassLink::ByName(value.2.into())),
+            class: Some(value.2),
             value: ASN1Information::ObjectSet(value.3),
+            parameterization: None,
         }
     }
 }
 
-impl From<(Vec<&str>, &str, InformationObjectClass)> for ToplevelInformationDeclaration {
-    fn from(value: (Vec<&str>, &str, InformationObjectClass)) -> Self {
+impl
+    From<(
+        Vec<&str>,
+        &str,
+        Option<Parameterization>,
+        InformationObjectClass,
+    )> for ToplevelInformationDeclaration
+{
+    fn from(
+        value: (
+            Vec<&str>,
+            &str,
+            Option<Parameterization>,
+            InformationObjectClass,
+        ),
+    ) -> Self {
         Self {
             comments: value.0.join("\n"),
             name: value.1.into(),
             class: None,
-            value: ASN1Information::ObjectClass(value.2),
+            value: ASN1Information::ObjectClass(value.3),
+            parameterization: value.2,
         }
     }
 }
@@ -125,6 +168,59 @@ impl InformationObjectClass {
     ) -> Option<&InformationObjectClassField> {
         walk_object_field_ref_path(&self.fields, path, 0)
     }
+
+    /// Replaces the formal type parameters of a parameterized class by the actual types
+    /// that the class is instantiated with throughout the class' field declarations.
+    /// Formal parameters that do not denote types are left untouched.
+    /// * `parameterization` - the formal parameters of the class
+    /// * `actual_parameters` - the actual parameters in the order of the formal parameters
+    pub fn substitute_parameters(
+        &mut self,
+        parameterization: &Parameterization,
+        actual_parameters: &[Parameter],
+    ) {
+        for (formal, actual) in parameterization.parameters.iter().zip(actual_parameters) {
+            if let (None, Parameter::TypeParameter(actual)) = (&formal.name, actual) {
+                for field in self.fields.iter_mut() {
+                    if let Some(ref mut r#type) = field.r#type {
+                        substitute_type_reference(r#type, &formal.r#type, actual);
+                    }
+                }
+            }
+        }
+    }
+}
+
+/// Replaces every reference to the type `name` within `r#type` by `actual`,
+/// including references in the actual parameters of parameterized type references
+pub(crate) fn substitute_type_reference(r#type: &mut ASN1Type, name: &String, actual: &ASN1Type) {
+    match r#type {
+        ASN1Type::ElsewhereDeclaredType(e) if &e.identifier == name => *r#type = actual.clone(),
+        ASN1Type::ElsewhereDeclaredType(e) => e
+            .constraints
+            .iter_mut()
+            .filter_map(|c| match c {
+                Constraint::Parameter(parameters) => Some(parameters),
+                _ => None,
+            })
+            .flatten()
+            .for_each(|p| {
+                if let Parameter::TypeParameter(t) = p {
+                    substitute_type_reference(t, name, actual)
+                }
+            }),
+        ASN1Type::SequenceOf(s) | ASN1Type::SetOf(s) => {
+            substitute_type_reference(&mut s.r#type, name, actual)
+        }
+        ASN1Type::Sequence(s) | ASN1Type::Set(s) => s
+            .all_members_mut()
+            .for_each(|m| substitute_type_reference(&mut m.r#type, name, actual)),
+        ASN1Type::Choice(c) => c
+            .options
+            .iter_mut()
+            .for_each(|o| substitute_type_reference(&mut o.r#type, name, actual)),
+        _ => (),
+    }
 }
 
 impl
@@ -259,6 +355,100 @@ impl
     }
 }
 
+impl ObjectSet {
+    /// Replaces references to top-level information objects and object sets by the objects
+    /// that they declare, and references to top-level values in the objects' fields by the values.
+    /// Returns `true` if all references were resolved.
+    pub fn link_references(&mut self, tlds: &BTreeMap<String, ToplevelDeclaration>) -> bool {
+        self.link_references_at_depth(tlds, 0)
+    }
+
+    fn link_references_at_depth(
+        &mut self,
+        tlds: &BTreeMap<String, ToplevelDeclaration>,
+        depth: usize,
+    ) -> bool {
+        // guards against circular object set references
+        if depth > tlds.len() {
+            return false;
+        }
+        let mut resolved = true;
+        let mut values = vec![];
+        let mut extensible = self.extensible;
+        for (index, value) in self.values.drain(..).enumerate() {
+            if self.extensible == Some(index) {
+                extensible = Some(values.len());
+            }
+            match value {
+                ObjectSetValue::Reference(id) => match tlds.get(&id) {
+                    Some(ToplevelDeclaration::Information(ToplevelInformationDeclaration {
+                        value: ASN1Information::Object(object),
+                        ..
+                    })) => values.push(ObjectSetValue::Inline(object.fields.clone())),
+                    Some(ToplevelDeclaration::Information(ToplevelInformationDeclaration {
+                        value: ASN1Information::ObjectSet(set),
+                        ..
+                    })) => {
+                        let mut set = set.clone();
+                        resolved &= set.link_references_at_depth(tlds, depth + 1);
+                        values.append(&mut set.values);
+                    }
+                    _ => {
+                        resolved = false;
+                        values.push(ObjectSetValue::Reference(id))
+                    }
+                },
+                inline => values.push(inline),
+            }
+        }
+        for value in values.iter_mut() {
+            if let ObjectSetValue::Inline(fields) = value {
+                resolved &= fields.link_value_references(tlds);
+            }
+        }
+        self.values = values;
+        self.extensible = extensible;
+        resolved
+    }
+}
+
+impl InformationObjectFields {
+    /// Replaces references to top-level values in the fields by the values.
+    /// Returns `true` if all value references were resolved.
+    fn link_value_references(&mut self, tlds: &BTreeMap<String, ToplevelDeclaration>) -> bool {
+        let values = match self {
+            InformationObjectFields::DefaultSyntax(fields) => fields
+                .iter_mut()
+                .filter_map(|f| match f {
+                    InformationObjectField::FixedValueField(f) => Some(&mut f.value),
+                    _ => None,
+                })
+                .collect::<Vec<&mut ASN1Value>>(),
+            InformationObjectFields::CustomSyntax(applications) => applications
+                .iter_mut()
+                .filter_map(|a| match a {
+                    SyntaxApplication::ValueReference(v) => Some(v),
+                    _ => None,
+                })
+                .collect(),
+        };
+        values.into_iter().fold(true, |resolved, value| {
+            let mut depth = 0;
+            while let ASN1Value::ElsewhereDeclaredValue(id) = value {
+                // guards against circular value references
+                depth += 1;
+                match tlds.get(id) {
+                    Some(ToplevelDeclaration::Value(v)) if depth <= tlds.len() => {
+                        *value = v.value.clone()
+                    }
+                    _ => return false,
+                }
+            }
+            resolved
+        })
+    }
+}
+
 #[derive(Debug, Clone, PartialEq)]
 pub enum InformationObjectField {
     TypeField(TypeField),
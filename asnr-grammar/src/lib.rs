@@ -10,10 +10,12 @@
 extern crate alloc;
 
 pub mod constraints;
+mod dependencies;
 pub mod encoding_rules;
 pub mod error;
 pub mod information_object;
 pub mod parameterization;
+mod renaming;
 pub mod types;
 pub mod utils;
 
@@ -26,11 +28,11 @@ use alloc::{
     vec,
     vec::Vec,
 };
-use constraints::Constraint;
+use constraints::{Constraint, Parameter};
 use error::{GrammarError, GrammarErrorType};
 use information_object::{
-    InformationObjectClass, InformationObjectFieldReference, ObjectFieldIdentifier,
-    ToplevelInformationDeclaration,
+    ASN1Information, ClassLink, InformationObjectClass, InformationObjectFieldReference,
+    ObjectFieldIdentifier, ToplevelInformationDeclaration,
 };
 use parameterization::Parameterization;
 use types::*;
@@ -95,6 +97,7 @@ pub const AUTOMATIC: &'static str = "AUTOMATIC";
 pub const EXPLICIT: &'static str = "EXPLICIT";
 pub const IMPLICIT: &'static str = "IMPLICIT";
 pub const IMPORTS: &'static str = "IMPORTS";
+pub const EXPORTS: &str = "EXPORTS";
 pub const FROM: &'static str = "FROM";
 pub const INSTRUCTIONS: &'static str = "INSTRUCTIONS";
 pub const TAGS: &'static str = "TAGS";
@@ -113,6 +116,7 @@ pub const DOT: char = '.';
 // Subtyping tokens
 pub const SIZE: &'static str = "SIZE";
 pub const CONSTRAINED_BY: &'static str = "CONSTRAINED BY";
+pub const ENCODED_BY: &str = "ENCODED BY";
 pub const PATTERN: &'static str = "PATTERN";
 pub const DEFAULT: &'static str = "DEFAULT";
 pub const OPTIONAL: &'static str = "OPTIONAL";
@@ -161,7 +165,7 @@ pub const TIME: &'static str = "TIME";
 pub const TIME_OF_DAY: &'static str = "TIME-OF-DAY";
 pub const TYPE_IDENTIFIER: &'static str = "TYPE-IDENTIFIER";
 
-pub const ASN1_KEYWORDS: [&str; 63] = [
+pub const ASN1_KEYWORDS: [&str; 64] = [
     ABSTRACT_SYNTAX,
     BIT,
     CHARACTER,
@@ -222,11 +226,26 @@ pub const ASN1_KEYWORDS: [&str; 63] = [
     EXPLICIT,
     IMPLICIT,
     IMPORTS,
+    EXPORTS,
     FROM,
     INSTRUCTIONS,
     TAGS,
 ];
 
+pub const CHARACTER_STRING_TYPES: [&str; 11] = [
+    IA5_STRING,
+    UTF8_STRING,
+    NUMERIC_STRING,
+    VISIBLE_STRING,
+    TELETEX_STRING,
+    VIDEOTEX_STRING,
+    GRAPHIC_STRING,
+    GENERAL_STRING,
+    UNIVERSAL_STRING,
+    BMP_STRING,
+    PRINTABLE_STRING,
+];
+
 #[derive(Debug, Clone, PartialEq)]
 pub struct EncodingReferenceDefault(pub String);
 
@@ -268,6 +287,27 @@ impl From<(Vec<&str>, (&str, ObjectIdentifierValue, Option<&str>))> for Import {
     }
 }
 
+impl Import {
+    /// Looks up the imported declarations in the module they are imported from.
+    /// The originating module is identified by its name or by its object identifier.
+    /// Returns an empty iterator if the originating module is not among `all_modules`.
+    /// * `all_modules` - the parsed modules, each with its top-level declarations
+    pub fn resolve<'a>(
+        &'a self,
+        all_modules: &'a [(ModuleReference, Vec<ToplevelDeclaration>)],
+    ) -> impl Iterator<Item = &'a ToplevelDeclaration> {
+        all_modules
+            .iter()
+            .find(|(module, _)| {
+                module.name == self.origin_name
+                    || module.module_identifier.as_ref() == Some(&self.origin_identifier)
+            })
+            .into_iter()
+            .flat_map(|(_, tlds)| tlds.iter())
+            .filter(|tld| self.types.contains(tld.name()))
+    }
+}
+
 #[derive(Debug, Clone, PartialEq)]
 pub struct ModuleReference {
     pub name: String,
@@ -276,6 +316,18 @@ pub struct ModuleReference {
     pub tagging_environment: TaggingEnvironment,
     pub extensibility_environment: ExtensibilityEnvironment,
     pub imports: Vec<Import>,
+    /// The names listed in the module's `EXPORTS` clause.
+    /// `None` if the module exports all of its declarations,
+    /// either by `EXPORTS ALL` or by omitting the `EXPORTS` clause.
+    pub exports: Option<Vec<String>>,
+}
+
+impl ModuleReference {
+    /// Returns the names of the declarations that the module exports explicitly,
+    /// or `None` if all declarations are exported
+    pub fn export_list(&self) -> Option<&[String]> {
+        self.exports.as_deref()
+    }
 }
 
 impl
@@ -287,6 +339,7 @@ impl
             TaggingEnvironment,
             ExtensibilityEnvironment,
         ),
+        Option<Option<Vec<&str>>>,
         Option<Vec<Import>>,
     )> for ModuleReference
 {
@@ -299,6 +352,7 @@ impl
                 TaggingEnvironment,
                 ExtensibilityEnvironment,
             ),
+            Option<Option<Vec<&str>>>,
             Option<Vec<Import>>,
         ),
     ) -> Self {
@@ -308,7 +362,11 @@ impl
             encoding_reference_default: value.2 .0,
             tagging_environment: value.2 .1,
             extensibility_environment: value.2 .2,
-            imports: value.3.unwrap_or(vec![]),
+            exports: value
+                .3
+                .flatten()
+                .map(|names| names.into_iter().map(String::from).collect()),
+            imports: value.4.unwrap_or(vec![]),
         }
     }
 }
@@ -355,6 +413,45 @@ impl From<(&str, u128)> for ObjectIdentifierArc {
     }
 }
 
+/// Returns the effective tag of a type in a module with the given tagging environment
+fn effective_tag(
+    tag: &Option<AsnTag>,
+    r#type: &ASN1Type,
+    environment: &TaggingEnvironment,
+) -> Option<AsnTag> {
+    tag.as_ref().map(|t| AsnTag {
+        environment: if matches!(r#type, ASN1Type::Choice(_)) {
+            TaggingEnvironment::Explicit
+        } else if t.environment == TaggingEnvironment::Automatic {
+            environment.clone()
+        } else {
+            t.environment.clone()
+        },
+        tag_class: t.tag_class,
+        id: t.id,
+    })
+}
+
+fn apply_tagging_environment_to_components(
+    r#type: &mut ASN1Type,
+    environment: &TaggingEnvironment,
+) {
+    match r#type {
+        ASN1Type::Sequence(s) | ASN1Type::Set(s) => s.all_members_mut().for_each(|m| {
+            m.tag = effective_tag(&m.tag, &m.r#type, environment);
+            apply_tagging_environment_to_components(&mut m.r#type, environment);
+        }),
+        ASN1Type::Choice(c) => c.options.iter_mut().for_each(|o| {
+            o.tag = effective_tag(&o.tag, &o.r#type, environment);
+            apply_tagging_environment_to_components(&mut o.r#type, environment);
+        }),
+        ASN1Type::SequenceOf(s) | ASN1Type::SetOf(s) => {
+            apply_tagging_environment_to_components(&mut s.r#type, environment)
+        }
+        _ => (),
+    }
+}
+
 #[derive(Debug, Clone, PartialEq)]
 pub enum ToplevelDeclaration {
     Type(ToplevelTypeDeclaration),
@@ -363,35 +460,14 @@ pub enum ToplevelDeclaration {
 }
 
 impl ToplevelDeclaration {
+    /// Applies the module's tagging environment to all tags of the declaration,
+    /// including the tags of its inline types, that do not specify `IMPLICIT` or `EXPLICIT` themselves.
+    /// Tags of inline CHOICE types are always explicit (see X.680 31.2.7).
+    /// Tags of references to CHOICE types are made explicit by the validator.
     pub fn apply_tagging_environment(&mut self, environment: &TaggingEnvironment) {
-        match (environment, self) {
-            (env, ToplevelDeclaration::Type(ty)) => {
-                ty.tag = ty.tag.as_ref().map(|t| AsnTag {
-                    environment: env.clone(),
-                    tag_class: t.tag_class,
-                    id: t.id,
-                });
-                match &mut ty.r#type {
-                    ASN1Type::Sequence(s) | ASN1Type::Set(s) => {
-                        s.members.iter_mut().for_each(|m| {
-                            m.tag = m.tag.as_ref().map(|t| AsnTag {
-                                environment: env.clone(),
-                                tag_class: t.tag_class,
-                                id: t.id,
-                            });
-                        })
-                    }
-                    ASN1Type::Choice(c) => c.options.iter_mut().for_each(|o| {
-                        o.tag = o.tag.as_ref().map(|t| AsnTag {
-                            environment: env.clone(),
-                            tag_class: t.tag_class,
-                            id: t.id,
-                        });
-                    }),
-                    _ => (),
-                }
-            }
-            _ => (),
+        if let ToplevelDeclaration::Type(ty) = self {
+            ty.tag = effective_tag(&ty.tag, &ty.r#type, environment);
+            apply_tagging_environment_to_components(&mut ty.r#type, environment);
         }
     }
 
@@ -453,7 +529,7 @@ impl ToplevelDeclaration {
         match self {
             ToplevelDeclaration::Type(t) => match &t.r#type {
                 ASN1Type::Sequence(s) | ASN1Type::Set(s) => {
-                    s.members.iter().fold(false, |acc, m| {
+                    s.all_members().fold(false, |acc, m| {
                         acc || m
                             .default_value
                             .as_ref()
@@ -472,7 +548,7 @@ impl ToplevelDeclaration {
         match self {
             ToplevelDeclaration::Type(t) => match &mut t.r#type {
                 ASN1Type::Sequence(s) | ASN1Type::Set(s) => {
-                    s.members.iter_mut().fold(false, |acc, m| {
+                    s.all_members_mut().fold(false, |acc, m| {
                         if let Some(default) = m.default_value.as_mut() {
                             let maybe_id = if let ASN1Value::ElsewhereDeclaredValue(id) = default {
                                 Some(id.clone())
@@ -516,22 +592,62 @@ impl ToplevelDeclaration {
         }
     }
 
-    /// Traverses a top-level declaration to check for references to other top-level declarations
-    /// in a constraint. An example would be the constraint of the `intercontinental` field in the
-    /// following example.
-    /// ```ignore
-    /// fifteen INTEGER = 15
-    ///
-    /// Departures ::= SEQUENCE {
-    ///   local SEQUENCE (SIZE(0..999)) OF Local,
-    ///   continental SEQUENCE (SIZE(0..99)) OF Continental,
-    ///   intercontinental SEQUENCE (SIZE(0..fifteen)) OF Intercontinental
-    /// }
-    /// ```
-    pub fn has_constraint_reference(&self) -> bool {
+    /// Checks whether a top-level information object set still refers to its class by name.
+    pub fn has_object_set_reference(&self) -> bool {
+        matches!(
+            self,
+            ToplevelDeclaration::Information(ToplevelInformationDeclaration {
+                class: Some(ClassLink::ByName(_) | ClassLink::ByParameterizedName(..)),
+                value: ASN1Information::ObjectSet(_),
+                ..
+            })
+        )
+    }
+
+    /// Links a top-level information object set to its class and inlines the information objects,
+    /// object sets, and values that it refers to by name.
+    /// If the set refers to an instance of a parameterized class, the class is linked with its
+    /// formal parameters substituted by the actual parameters.
+    /// Returns `true` if all references were resolved.
+    pub fn link_object_set_reference(
+        &mut self,
+        tlds: &BTreeMap<String, ToplevelDeclaration>,
+    ) -> bool {
         match self {
-            ToplevelDeclaration::Type(t) => t.r#type.contains_constraint_reference(),
-            // TODO: Cover constraint references in other types of top-level declarations
+            ToplevelDeclaration::Information(ToplevelInformationDeclaration {
+                class,
+                value: ASN1Information::ObjectSet(set),
+                ..
+            }) => {
+                let class_link = match class {
+                    Some(ClassLink::ByName(name)) => tlds
+                        .get(name)
+                        .and_then(|tld| tld.is_class_with_name(name))
+                        .map(|c| ClassLink::ByReference(c.clone())),
+                    Some(ClassLink::ByParameterizedName(name, actual_parameters)) => {
+                        match tlds.get(name) {
+                            Some(ToplevelDeclaration::Information(
+                                ToplevelInformationDeclaration {
+                                    value: ASN1Information::ObjectClass(c),
+                                    parameterization: Some(parameterization),
+                                    ..
+                                },
+                            )) => {
+                                let mut class = c.clone();
+                                class.substitute_parameters(parameterization, actual_parameters);
+                                Some(ClassLink::ByReference(class))
+                            }
+                            _ => None,
+                        }
+                    }
+                    _ => None,
+                };
+                let is_linked = class_link.is_some();
+                if is_linked {
+                    *class = class_link;
+                }
+                set.link_references(tlds) && is_linked
+            }
             _ => false,
         }
     }
@@ -551,15 +667,17 @@ impl ToplevelDeclaration {
     /// The method handles linking of multiple constraint references within a top-level declaration.
     /// ### Params
     ///  * `tlds` - vector of other top-level declarations that will be searched as the method resolves a reference
-    /// returns `true` if the reference was resolved successfully.
+    ///    returns `true` if the reference was resolved successfully.
+    ///    Returns an `UnexpectedType` error if a reference resolves to a declaration
+    ///    that cannot be used in the constraint, e.g. to a BOOLEAN value in a value range.
     pub fn link_constraint_reference(
         &mut self,
         tlds: &BTreeMap<String, ToplevelDeclaration>,
-    ) -> bool {
+    ) -> Result<bool, GrammarError> {
         match self {
             ToplevelDeclaration::Type(t) => t.r#type.link_constraint_reference(&t.name, tlds),
             // TODO: Cover constraint references in other types of top-level declarations
-            _ => false,
+            _ => Ok(false),
         }
     }
 }
@@ -635,7 +753,7 @@ pub enum ASN1Type {
     Sequence(SequenceOrSet),
     SequenceOf(SequenceOf),
     Set(SequenceOrSet),
-    // SetOf,
+    SetOf(SequenceOf),
     ObjectIdentifier(ObjectIdentifier),
     ElsewhereDeclaredType(DeclarationElsewhere),
     InformationObjectFieldReference(InformationObjectFieldReference),
@@ -652,7 +770,7 @@ impl ASN1Type {
             ASN1Type::Enumerated(e) => e.constraints.clone(),
             ASN1Type::Choice(c) => c.constraints.clone(),
             ASN1Type::Sequence(s) => s.constraints.clone(),
-            ASN1Type::SequenceOf(s) => s.constraints.clone(),
+            ASN1Type::SequenceOf(s) | ASN1Type::SetOf(s) => s.constraints.clone(),
             ASN1Type::ElsewhereDeclaredType(e) => e.constraints.clone(),
             _ => vec![],
         }
@@ -662,127 +780,44 @@ impl ASN1Type {
         &mut self,
         name: &String,
         tlds: &BTreeMap<String, ToplevelDeclaration>,
-    ) -> bool {
-        match self {
+    ) -> Result<bool, GrammarError> {
+        Ok(match self {
             ASN1Type::Null => false,
             ASN1Type::Boolean => false,
-            ASN1Type::ObjectIdentifier(i) => i
-                .constraints
-                .iter_mut()
-                .map(|c| c.link_cross_reference(name, tlds))
-                .fold(false, |acc, b| acc || b),
-                ASN1Type::Integer(i) => i
-                .constraints
-                .iter_mut()
-                .map(|c| c.link_cross_reference(name, tlds))
-                .fold(false, |acc, b| acc || b),
-            ASN1Type::BitString(b) => b
-                .constraints
-                .iter_mut()
-                .map(|c| c.link_cross_reference(name, tlds))
-                .fold(false, |acc, b| acc || b),
-            ASN1Type::OctetString(o) => o
-                .constraints
-                .iter_mut()
-                .map(|c| c.link_cross_reference(name, tlds))
-                .fold(false, |acc, b| acc || b),
-            ASN1Type::CharacterString(c) => c
-                .constraints
-                .iter_mut()
-                .map(|c| c.link_cross_reference(name, tlds))
-                .fold(false, |acc, b| acc || b),
-            ASN1Type::Enumerated(e) => e
-                .constraints
-                .iter_mut()
-                .map(|c| c.link_cross_reference(name, tlds))
-                .fold(false, |acc, b| acc || b),
+            ASN1Type::ObjectIdentifier(i) => link_cross_references(&mut i.constraints, name, tlds)?,
+            ASN1Type::Integer(i) => link_cross_references(&mut i.constraints, name, tlds)?,
+            ASN1Type::BitString(b) => link_cross_references(&mut b.constraints, name, tlds)?,
+            ASN1Type::OctetString(o) => link_cross_references(&mut o.constraints, name, tlds)?,
+            ASN1Type::CharacterString(c) => link_cross_references(&mut c.constraints, name, tlds)?,
+            ASN1Type::Enumerated(e) => link_cross_references(&mut e.constraints, name, tlds)?,
             ASN1Type::Choice(c) => {
-                c.constraints
-                    .iter_mut()
-                    .map(|c| c.link_cross_reference(name, tlds))
-                    .fold(false, |acc, b| acc || b)
-                    || c.options
-                        .iter_mut()
-                        .map(|o| {
-                            let b = o.r#type.link_constraint_reference(&o.name, tlds);
-                            let a = o
-                                .constraints
-                                .iter_mut()
-                                .map(|c| c.link_cross_reference(name, tlds))
-                                .fold(false, |acc, b| acc || b);
-                            a || b
-                        })
-                        .fold(false, |acc, b| acc || b)
+                let mut linked = link_cross_references(&mut c.constraints, name, tlds)?;
+                for o in c.options.iter_mut() {
+                    let b = o.r#type.link_constraint_reference(&o.name, tlds)?;
+                    let a = link_cross_references(&mut o.constraints, name, tlds)?;
+                    linked = linked || a || b;
+                }
+                linked
             }
             ASN1Type::Sequence(s) => {
-                s.constraints
-                    .iter_mut()
-                    .map(|c| c.link_cross_reference(name, tlds))
-                    .fold(false, |acc, b| acc || b)
-                    || s.members
-                        .iter_mut()
-                        .map(|o| {
-                            let b = o.r#type.link_constraint_reference(&o.name, tlds);
-                            let a = o
-                                .constraints
-                                .iter_mut()
-                                .map(|c| c.link_cross_reference(name, tlds))
-                                .fold(false, |acc, b| acc || b);
-                            a || b
-                        })
-                        .fold(false, |acc, b| acc || b)
+                let mut linked = link_cross_references(&mut s.constraints, name, tlds)?;
+                for m in s.all_members_mut() {
+                    let b = m.r#type.link_constraint_reference(&m.name, tlds)?;
+                    let a = link_cross_references(&mut m.constraints, name, tlds)?;
+                    linked = linked || a || b;
+                }
+                linked
             }
-            ASN1Type::SequenceOf(s) => {
-                let a = s
-                    .constraints
-                    .iter_mut()
-                    .map(|c| c.link_cross_reference(name, tlds))
-                    .fold(false, |acc, b| acc || b);
-                let b = s.r#type.link_constraint_reference(name, tlds);
+            ASN1Type::SequenceOf(s) | ASN1Type::SetOf(s) => {
+                let a = link_cross_references(&mut s.constraints, name, tlds)?;
+                let b = s.r#type.link_constraint_reference(name, tlds)?;
                 a || b
             }
-            ASN1Type::ElsewhereDeclaredType(e) => e
-                .constraints
-                .iter_mut()
-                .map(|c| c.link_cross_reference(&e.identifier, tlds))
-                .fold(false, |acc, b| acc || b),
-            _ => false,
-        }
-    }
-
-    pub fn contains_constraint_reference(&self) -> bool {
-        match self {
-            ASN1Type::Null => false,
-            ASN1Type::Boolean => false,
-            ASN1Type::Integer(i) => i.constraints.iter().any(|c| c.has_cross_reference()),
-            ASN1Type::ObjectIdentifier(i) => i.constraints.iter().any(|c| c.has_cross_reference()),
-            ASN1Type::BitString(b) => b.constraints.iter().any(|c| c.has_cross_reference()),
-            ASN1Type::OctetString(o) => o.constraints.iter().any(|c| c.has_cross_reference()),
-            ASN1Type::CharacterString(c) => c.constraints.iter().any(|c| c.has_cross_reference()),
-            ASN1Type::Enumerated(e) => e.constraints.iter().any(|c| c.has_cross_reference()),
-            ASN1Type::Choice(c) => {
-                c.constraints.iter().any(|c| c.has_cross_reference())
-                    || c.options.iter().any(|o| {
-                        o.r#type.contains_constraint_reference()
-                            || o.constraints.iter().any(|c| c.has_cross_reference())
-                    })
-            }
-            ASN1Type::Sequence(s) => {
-                s.constraints.iter().any(|c| c.has_cross_reference())
-                    || s.members.iter().any(|m| {
-                        m.r#type.contains_constraint_reference()
-                            || m.constraints.iter().any(|c| c.has_cross_reference())
-                    })
-            }
-            ASN1Type::SequenceOf(s) => {
-                s.constraints.iter().any(|c| c.has_cross_reference())
-                    || s.r#type.contains_constraint_reference()
-            }
             ASN1Type::ElsewhereDeclaredType(e) => {
-                e.constraints.iter().any(|c| c.has_cross_reference())
+                link_cross_references(&mut e.constraints, &e.identifier, tlds)?
             }
             _ => false,
-        }
+        })
     }
 
     pub fn contains_class_field_reference(&self) -> bool {
@@ -792,10 +827,11 @@ impl ASN1Type {
                 .iter()
                 .any(|o| o.r#type.contains_class_field_reference()),
             ASN1Type::Sequence(s) => s
-                .members
-                .iter()
+                .all_members()
                 .any(|m| m.r#type.contains_class_field_reference()),
-            ASN1Type::SequenceOf(so) => so.r#type.contains_class_field_reference(),
+            ASN1Type::SequenceOf(so) | ASN1Type::SetOf(so) => {
+                so.r#type.contains_class_field_reference()
+            }
             ASN1Type::InformationObjectFieldReference(io_ref) => {
                 if let Some(ObjectFieldIdentifier::SingleValue(_)) = io_ref.field_path.last() {
                     true
@@ -822,23 +858,18 @@ impl ASN1Type {
                         tag: option.tag,
                         r#type: option.r#type.resolve_class_field_reference(tlds),
                         constraints: vec![],
+                        is_recursive: false,
                     })
                     .collect(),
                 constraints: c.constraints,
             }),
-            ASN1Type::Sequence(s) => ASN1Type::Sequence(SequenceOrSet {
-                extensible: s.extensible,
-                constraints: s.constraints,
-                members: s
-                    .members
-                    .into_iter()
-                    .map(|mut member| {
-                        member.constraints = vec![];
-                        member.r#type = member.r#type.resolve_class_field_reference(tlds);
-                        member
-                    })
-                    .collect(),
-            }),
+            ASN1Type::Sequence(mut s) => {
+                s.all_members_mut().for_each(|member| {
+                    member.constraints = vec![];
+                    member.r#type = member.r#type.clone().resolve_class_field_reference(tlds);
+                });
+                ASN1Type::Sequence(s)
+            }
             ASN1Type::InformationObjectFieldReference(_) => self.reassign_type_for_ref(tlds),
             _ => self,
         }
@@ -862,6 +893,23 @@ impl ASN1Type {
         self
     }
 
+    /// Replaces the formal type parameters of a parameterized type by the actual types
+    /// that the type is instantiated with.
+    /// Formal parameters that do not denote types are left untouched.
+    /// * `parameterization` - the formal parameters of the type
+    /// * `actual_parameters` - the actual parameters in the order of the formal parameters
+    pub fn substitute_parameters(
+        &mut self,
+        parameterization: &Parameterization,
+        actual_parameters: &[Parameter],
+    ) {
+        for (formal, actual) in parameterization.parameters.iter().zip(actual_parameters) {
+            if let (None, Parameter::TypeParameter(actual)) = (&formal.name, actual) {
+                information_object::substitute_type_reference(self, &formal.r#type, actual);
+            }
+        }
+    }
+
     pub fn link_subtype_constraint(
         &mut self,
         tlds: &BTreeMap<String, ToplevelDeclaration>,
@@ -877,6 +925,109 @@ impl ASN1Type {
             _ => false,
         }
     }
+
+    /// Returns a compact, name-independent fingerprint of the type's structure.
+    /// Structurally identical types yield equal signatures regardless of the names
+    /// of the types and members involved, e.g. `INTEGER (0..100)` yields `int:0:100`
+    /// and `SEQUENCE { a BOOLEAN, b INTEGER }` yields `seq:[bool,int]`.
+    pub fn type_signature(&self) -> String {
+        let kind = match self {
+            ASN1Type::Null => "null".to_owned(),
+            ASN1Type::Boolean => "bool".to_owned(),
+            ASN1Type::Integer(_) => "int".to_owned(),
+            ASN1Type::Real(_) => "real".to_owned(),
+            ASN1Type::BitString(_) => "bits".to_owned(),
+            ASN1Type::OctetString(_) => "octets".to_owned(),
+            ASN1Type::CharacterString(c) => format!("{:?}", c.r#type).to_lowercase(),
+            ASN1Type::ObjectIdentifier(_) => "oid".to_owned(),
+            ASN1Type::Enumerated(e) => format!(
+                "enum:[{}]",
+                list_signature(
+                    e.members.iter().map(|m| m.index.to_string()).collect(),
+                    e.extensible
+                )
+            ),
+            ASN1Type::Choice(c) => format!(
+                "choice:[{}]",
+                list_signature(
+                    c.options.iter().map(|o| o.r#type.type_signature()).collect(),
+                    c.extensible
+                )
+            ),
+            ASN1Type::Sequence(s) => format!("seq:[{}]", members_signature(s)),
+            ASN1Type::Set(s) => format!("set:[{}]", members_signature(s)),
+            ASN1Type::SequenceOf(s) => format!("seqof:{}", s.r#type.type_signature()),
+            ASN1Type::SetOf(s) => format!("setof:{}", s.r#type.type_signature()),
+            ASN1Type::ElsewhereDeclaredType(e) => format!("ref:{}", e.identifier),
+            ASN1Type::InformationObjectFieldReference(r) => format!(
+                "field:{}.{}",
+                r.class,
+                r.field_path
+                    .iter()
+                    .map(|f| match f {
+                        ObjectFieldIdentifier::SingleValue(v)
+                        | ObjectFieldIdentifier::MultipleValue(v) => v.clone(),
+                    })
+                    .collect::<Vec<String>>()
+                    .join(".")
+            ),
+        };
+        self.constraints()
+            .iter()
+            .fold(kind, |acc, c| acc + ":" + &c.signature())
+    }
+}
+
+fn members_signature(sequence_or_set: &SequenceOrSet) -> String {
+    let member_signature = |m: &SequenceOrSetMember| {
+        let signature = m
+            .constraints
+            .iter()
+            .fold(m.r#type.type_signature(), |acc, c| acc + ":" + &c.signature());
+        if let Some(default) = &m.default_value {
+            signature + "=" + &default.signature()
+        } else if m.is_optional {
+            signature + "?"
+        } else {
+            signature
+        }
+    };
+    let mut signatures = sequence_or_set
+        .members
+        .iter()
+        .map(member_signature)
+        .collect::<Vec<String>>();
+    signatures.extend(sequence_or_set.extension_groups.iter().map(|g| {
+        format!(
+            "group@{}:[{}]",
+            g.position,
+            g.members
+                .iter()
+                .map(member_signature)
+                .collect::<Vec<String>>()
+                .join(",")
+        )
+    }));
+    list_signature(signatures, sequence_or_set.extensible)
+}
+
+/// Links the cross-references in a list of constraints.
+/// Returns `true` if any of the references was resolved.
+fn link_cross_references(
+    constraints: &mut [Constraint],
+    name: &String,
+    tlds: &BTreeMap<String, ToplevelDeclaration>,
+) -> Result<bool, GrammarError> {
+    constraints.iter_mut().try_fold(false, |linked, c| {
+        Ok(c.link_cross_reference(name, tlds)? || linked)
+    })
+}
+
+fn list_signature(mut items: Vec<String>, extensible: Option<usize>) -> String {
+    if let Some(index) = extensible {
+        items.insert(index.min(items.len()), "...".to_owned());
+    }
+    items.join(",")
 }
 
 impl ToString for ASN1Type {
@@ -895,6 +1046,7 @@ impl ToString for ASN1Type {
             ASN1Type::Sequence(_) => todo!(),
             ASN1Type::SequenceOf(_) => todo!(),
             ASN1Type::Set(_) => todo!(),
+            ASN1Type::SetOf(_) => todo!(),
             ASN1Type::ElsewhereDeclaredType(e) => e.identifier.clone(),
             ASN1Type::InformationObjectFieldReference(_) => todo!(),
         }
@@ -939,28 +1091,76 @@ impl CharacterStringType {
         }
     }
 
+    /// Returns the number of bits used to encode a single character
+    /// if the string type's characters are encoded by their code points,
+    /// i.e. if its character set is too large to be enumerated.
+    /// Strings that are not known-multiplier strings, such as an unrestricted UTF8String,
+    /// are encoded as their UTF-8 octets, so that their bit size is the size of an octet.
+    pub fn char_bit_size(&self) -> usize {
+        match self {
+            CharacterStringType::NumericString => 4,
+            CharacterStringType::VisibleString
+            | CharacterStringType::PrintableString
+            | CharacterStringType::IA5String => 7,
+            CharacterStringType::BMPString => 16,
+            CharacterStringType::UniversalString => 32,
+            _ => 8,
+        }
+    }
+
+    /// Returns whether the string type's character set is too large to be enumerated.
+    /// Characters of such types are encoded using their raw code points.
+    pub fn has_raw_character_set(&self) -> bool {
+        !matches!(
+            self,
+            CharacterStringType::NumericString
+                | CharacterStringType::VisibleString
+                | CharacterStringType::PrintableString
+                | CharacterStringType::IA5String
+        )
+    }
+
+    /// Returns the characters of the string type's character set indexed by their position.
+    /// For string types with character sets that are too large to be enumerated,
+    /// such as `BMPString` (65536 characters) and `UniversalString` (1114112 characters),
+    /// or that are not known-multiplier strings, such as `TeletexString`, `VideotexString`,
+    /// `GraphicString`, and `GeneralString`, an empty map is returned.
+    /// An empty map indicates that characters are encoded by their raw code points
+    /// (see `CharacterStringType::has_raw_character_set`).
     pub fn character_set(&self) -> BTreeMap<usize, char> {
         match self {
             CharacterStringType::NumericString => {
                 NUMERIC_STRING_CHARSET.into_iter().enumerate().collect()
             }
-            CharacterStringType::VisibleString | CharacterStringType::PrintableString => {
+            CharacterStringType::PrintableString => {
                 PRINTABLE_STRING_CHARSET.into_iter().enumerate().collect()
             }
-            CharacterStringType::IA5String => (0..128u32)
-                .into_iter()
+            CharacterStringType::VisibleString => (0x20..0x7Fu32)
                 .map(|i| char::from_u32(i).unwrap())
                 .enumerate()
                 .collect(),
-            _ => (0..u16::MAX as u32)
+            CharacterStringType::IA5String => (0..128u32)
                 .into_iter()
-                .filter_map(|i| char::from_u32(i))
+                .map(|i| char::from_u32(i).unwrap())
                 .enumerate()
                 .collect(),
+            _ => BTreeMap::new(),
         }
     }
 }
 
+/// Returns the index of a character in a character set.
+/// If the character set is empty, i.e. too large to be enumerated,
+/// the character's code point is returned.
+pub fn index_in_character_set(char_set: &BTreeMap<usize, char>, character: char) -> Option<usize> {
+    if char_set.is_empty() {
+        return Some(character as usize);
+    }
+    char_set
+        .iter()
+        .find_map(|(i, c)| (character == *c).then_some(*i))
+}
+
 impl From<&str> for CharacterStringType {
     fn from(value: &str) -> Self {
         match value {
@@ -997,9 +1197,52 @@ pub enum ASN1Value {
     },
     ElsewhereDeclaredValue(String),
     ObjectIdentifier(ObjectIdentifierValue),
+    /// Value of a SEQUENCE member, of a CHOICE option, or of a referenced type,
+    /// as linked to its type by the validator
+    LinkedNestedValue {
+        r#type: Box<ASN1Type>,
+        value: Box<ASN1Value>,
+    },
+    /// Value of an OPTIONAL SEQUENCE member as linked by the validator,
+    /// where `None` marks an absent member
+    Optional(Option<Box<ASN1Value>>),
 }
 
 impl ASN1Value {
+    pub(crate) fn signature(&self) -> String {
+        match self {
+            ASN1Value::All => "all".to_owned(),
+            ASN1Value::Null => "null".to_owned(),
+            ASN1Value::Boolean(b) => b.to_string(),
+            ASN1Value::Integer(i) => i.to_string(),
+            ASN1Value::Real(r) => r.to_string(),
+            ASN1Value::String(s) => format!("{s:?}"),
+            ASN1Value::BitString(b) => b.iter().map(|bit| if *bit { '1' } else { '0' }).collect(),
+            ASN1Value::EnumeratedValue { enumerable, .. } => enumerable.clone(),
+            ASN1Value::ElsewhereDeclaredValue(v) => v.clone(),
+            ASN1Value::LinkedNestedValue { value, .. } => value.signature(),
+            ASN1Value::Optional(v) => v.as_ref().map_or("absent".to_owned(), |v| v.signature()),
+            ASN1Value::Choice(option, value) => format!("{option}:{}", value.signature()),
+            ASN1Value::Sequence(members) => format!(
+                "{{{}}}",
+                members
+                    .iter()
+                    .map(|(_, v)| v.signature())
+                    .collect::<Vec<String>>()
+                    .join(",")
+            ),
+            ASN1Value::ObjectIdentifier(oid) => oid
+                .0
+                .iter()
+                .map(|arc| {
+                    arc.number
+                        .map_or_else(|| arc.name.clone().unwrap_or_default(), |n| n.to_string())
+                })
+                .collect::<Vec<String>>()
+                .join("."),
+        }
+    }
+
     pub fn max(
         &self,
         other: &ASN1Value,
@@ -1043,10 +1286,10 @@ impl ASN1Value {
                 let s_as_char = s.chars().next().unwrap();
                 let o_as_char = o.chars().next().unwrap();
                 match (
-                    set.iter().find(|(_, c)| s_as_char == **c),
-                    set.iter().find(|(_, c)| o_as_char == **c),
+                    index_in_character_set(set, s_as_char),
+                    index_in_character_set(set, o_as_char),
                 ) {
-                    (Some((self_i, _)), Some((other_i, _))) => {
+                    (Some(self_i), Some(other_i)) => {
                         let return_self = if getting_mininum {
                             self_i <= other_i
                         } else {
@@ -1110,24 +1353,27 @@ impl ASN1Value {
         }
     }
 
+    /// Replaces a reference to an elsewhere declared value or enumeral by the referenced value.
+    /// Returns `true` if the reference was resolved, and an `UnexpectedType` error
+    /// if the reference names a declaration that is not a value.
     pub fn link_elsewhere_declared(
         &mut self,
         identifier: &String,
         tlds: &BTreeMap<String, ToplevelDeclaration>,
-    ) -> bool {
+    ) -> Result<bool, GrammarError> {
         match self {
             Self::EnumeratedValue {
                 enumerated: _,
                 enumerable: e,
             }
             | Self::ElsewhereDeclaredValue(e) => {
-                if let Some(v) = find_tld_or_enum_value_by_name(identifier, &e, tlds) {
+                if let Some(v) = find_tld_or_enum_value_by_name(identifier, e, tlds)? {
                     *self = v;
-                    return true;
+                    return Ok(true);
                 }
-                false
+                Ok(false)
             }
-            _ => false,
+            _ => Ok(false),
         }
     }
 
@@ -1192,6 +1438,9 @@ impl ASN1Value {
                 to_rust_title_case(enumerable)
             )),
             ASN1Value::ElsewhereDeclaredValue(e) => Ok(to_rust_const_case(e)),
+            ASN1Value::LinkedNestedValue { value, .. } => value.value_as_string(type_name),
+            ASN1Value::Optional(Some(v)) => Ok(format!("Some({})", v.value_as_string(None)?)),
+            ASN1Value::Optional(None) => Ok("None".to_owned()),
             ASN1Value::ObjectIdentifier(oid) => Ok(format!(
                 "[{}]",
                 oid.0
@@ -1274,3 +1523,206 @@ impl From<(Option<&str>, u64)> for AsnTag {
         }
     }
 }
+
+impl AsnTag {
+    /// Returns the tag's identifier octets as specified in X.690 §8.1.2.
+    /// Tag numbers up to 30 are encoded in a single octet, higher tag numbers
+    /// use the multi-octet high-tag-number form.
+    /// ### Params
+    /// * `is_constructed` - whether the tagged value is encoded in constructed form
+    pub fn to_ber_bytes(&self, is_constructed: bool) -> Vec<u8> {
+        let class_bits: u8 = match self.tag_class {
+            TagClass::Universal => 0b00,
+            TagClass::Application => 0b01,
+            TagClass::ContextSpecific => 0b10,
+            TagClass::Private => 0b11,
+        };
+        let leading = (class_bits << 6) | ((is_constructed as u8) << 5);
+        if self.id <= 30 {
+            return vec![leading | self.id as u8];
+        }
+        let mut bytes = vec![(self.id & 0x7F) as u8];
+        let mut remaining = self.id >> 7;
+        while remaining > 0 {
+            bytes.push(0x80 | (remaining & 0x7F) as u8);
+            remaining >>= 7;
+        }
+        bytes.push(leading | 0x1F);
+        bytes.reverse();
+        bytes
+    }
+
+    /// Reads a tag from its identifier octets as specified in X.690 §8.1.2.
+    /// Returns the tag and whether the tagged value is encoded in constructed form.
+    /// ### Params
+    /// * `first` - the leading identifier octet
+    /// * `rest` - the octets following the leading identifier octet. Only needed for high tag numbers.
+    pub fn from_ber_byte(first: u8, rest: &[u8]) -> Result<(Self, bool), GrammarError> {
+        let tag_class = match first >> 6 {
+            0b00 => TagClass::Universal,
+            0b01 => TagClass::Application,
+            0b10 => TagClass::ContextSpecific,
+            _ => TagClass::Private,
+        };
+        let is_constructed = first & 0x20 != 0;
+        let id = if first & 0x1F != 0x1F {
+            (first & 0x1F) as u64
+        } else {
+            let mut id: u64 = 0;
+            let mut octets = rest.iter();
+            loop {
+                let octet = octets.next().ok_or(GrammarError {
+                    details: "Unterminated high tag number in identifier octets!".into(),
+                    kind: GrammarErrorType::TagEncodingError,
+                })?;
+                if id > u64::MAX >> 7 {
+                    return Err(GrammarError {
+                        details: "High tag number exceeds u64 range!".into(),
+                        kind: GrammarErrorType::TagEncodingError,
+                    });
+                }
+                id = (id << 7) | (octet & 0x7F) as u64;
+                if octet & 0x80 == 0 {
+                    break id;
+                }
+            }
+        };
+        Ok((
+            AsnTag {
+                environment: TaggingEnvironment::Automatic,
+                tag_class,
+                id,
+            },
+            is_constructed,
+        ))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use alloc::{string::String, vec};
+
+    use crate::*;
+
+    fn tag(tag_class: TagClass, id: u64) -> AsnTag {
+        AsnTag {
+            environment: TaggingEnvironment::Automatic,
+            tag_class,
+            id,
+        }
+    }
+
+    #[test]
+    fn encodes_tags_of_all_classes_to_ber() {
+        assert_eq!(tag(TagClass::Universal, 2).to_ber_bytes(false), vec![0x02]);
+        assert_eq!(tag(TagClass::Universal, 16).to_ber_bytes(true), vec![0x30]);
+        assert_eq!(tag(TagClass::Application, 1).to_ber_bytes(false), vec![0x41]);
+        assert_eq!(
+            tag(TagClass::ContextSpecific, 30).to_ber_bytes(true),
+            vec![0xBE]
+        );
+        assert_eq!(tag(TagClass::Private, 0).to_ber_bytes(false), vec![0xC0]);
+    }
+
+    #[test]
+    fn encodes_high_tag_numbers_to_ber() {
+        assert_eq!(
+            tag(TagClass::ContextSpecific, 31).to_ber_bytes(false),
+            vec![0x9F, 0x1F]
+        );
+        assert_eq!(
+            tag(TagClass::Application, 201).to_ber_bytes(true),
+            vec![0x7F, 0x81, 0x49]
+        );
+        assert_eq!(
+            tag(TagClass::Private, 16384).to_ber_bytes(false),
+            vec![0xDF, 0x81, 0x80, 0x00]
+        );
+    }
+
+    #[test]
+    fn decodes_tags_from_ber() {
+        assert_eq!(
+            AsnTag::from_ber_byte(0x30, &[]).unwrap(),
+            (tag(TagClass::Universal, 16), true)
+        );
+        assert_eq!(
+            AsnTag::from_ber_byte(0x41, &[]).unwrap(),
+            (tag(TagClass::Application, 1), false)
+        );
+        assert_eq!(
+            AsnTag::from_ber_byte(0xBE, &[]).unwrap(),
+            (tag(TagClass::ContextSpecific, 30), true)
+        );
+        assert_eq!(
+            AsnTag::from_ber_byte(0xDF, &[0x81, 0x80, 0x00]).unwrap(),
+            (tag(TagClass::Private, 16384), false)
+        );
+        assert!(AsnTag::from_ber_byte(0x9F, &[0x81]).is_err());
+    }
+
+    #[test]
+    fn round_trips_high_tag_numbers() {
+        for id in [31, 127, 128, 16383, 16384, u32::MAX as u64, u64::MAX] {
+            let bytes = tag(TagClass::ContextSpecific, id).to_ber_bytes(true);
+            assert_eq!(
+                AsnTag::from_ber_byte(bytes[0], &bytes[1..]).unwrap(),
+                (tag(TagClass::ContextSpecific, id), true)
+            );
+        }
+    }
+
+    fn module(name: &str, arc: u128, imports: Vec<Import>) -> ModuleReference {
+        ModuleReference {
+            name: name.into(),
+            module_identifier: Some(ObjectIdentifierValue(vec![arc.into()])),
+            encoding_reference_default: None,
+            tagging_environment: TaggingEnvironment::Automatic,
+            extensibility_environment: ExtensibilityEnvironment::Explicit,
+            imports,
+            exports: None,
+        }
+    }
+
+    fn boolean(name: &str) -> ToplevelDeclaration {
+        ToplevelDeclaration::Type(ToplevelTypeDeclaration {
+            parameterization: None,
+            comments: String::new(),
+            name: name.into(),
+            r#type: ASN1Type::Boolean,
+            tag: None,
+        })
+    }
+
+    #[test]
+    fn resolves_imported_declarations() {
+        let import = Import {
+            types: vec!["Flag".into()],
+            origin_name: "Common".into(),
+            origin_identifier: ObjectIdentifierValue(vec![1.into()]),
+            with_successors: false,
+        };
+        let modules = vec![
+            (module("Common", 1, vec![]), vec![boolean("Flag"), boolean("Other")]),
+            (module("Messages", 2, vec![import.clone()]), vec![boolean("Message")]),
+        ];
+        assert_eq!(
+            import.resolve(&modules).collect::<Vec<_>>(),
+            vec![&boolean("Flag")]
+        );
+        let by_identifier = Import {
+            origin_name: "Renamed".into(),
+            ..import.clone()
+        };
+        assert_eq!(
+            by_identifier.resolve(&modules).collect::<Vec<_>>(),
+            vec![&boolean("Flag")]
+        );
+        let unknown = Import {
+            origin_name: "Unknown".into(),
+            origin_identifier: ObjectIdentifierValue(vec![3.into()]),
+            ..import
+        };
+        assert_eq!(unknown.resolve(&modules).count(), 0);
+    }
+}
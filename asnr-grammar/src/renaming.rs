@@ -0,0 +1,322 @@
+//! The `renaming` module renames top-level declarations together with
+//! the references to them, e.g. the `Bar` in `Foo ::= SEQUENCE { bar Bar }`.
+use alloc::{boxed::Box, collections::BTreeMap, string::String, vec::Vec};
+
+use crate::{constraints::*, information_object::*, types::*, *};
+
+/// Traverses an ASN1 element and replaces the names of referenced top-level declarations
+/// with their new names in `names`
+pub(crate) trait RenameReferences {
+    fn rename_references(&mut self, names: &BTreeMap<String, String>);
+}
+
+impl<T: RenameReferences> RenameReferences for Option<T> {
+    fn rename_references(&mut self, names: &BTreeMap<String, String>) {
+        if let Some(inner) = self {
+            inner.rename_references(names)
+        }
+    }
+}
+
+impl<T: RenameReferences> RenameReferences for Vec<T> {
+    fn rename_references(&mut self, names: &BTreeMap<String, String>) {
+        self.iter_mut()
+            .for_each(|inner| inner.rename_references(names))
+    }
+}
+
+impl<T: RenameReferences> RenameReferences for Box<T> {
+    fn rename_references(&mut self, names: &BTreeMap<String, String>) {
+        self.as_mut().rename_references(names)
+    }
+}
+
+impl RenameReferences for String {
+    fn rename_references(&mut self, names: &BTreeMap<String, String>) {
+        if let Some(new_name) = names.get(self) {
+            *self = new_name.clone();
+        }
+    }
+}
+
+impl ToplevelDeclaration {
+    /// Renames the declaration and its references to other top-level declarations.
+    /// References in constraints, default values, and information objects are renamed as well.
+    /// * `names` - the new names keyed by the names of the declarations to be renamed.
+    ///   Declarations that are missing from `names` keep their name.
+    pub fn rename(&mut self, names: &BTreeMap<String, String>) {
+        match self {
+            ToplevelDeclaration::Type(t) => {
+                t.name.rename_references(names);
+                t.r#type.rename_references(names);
+            }
+            ToplevelDeclaration::Value(v) => {
+                v.name.rename_references(names);
+                v.type_name.rename_references(names);
+                v.value.rename_references(names);
+            }
+            ToplevelDeclaration::Information(i) => {
+                i.name.rename_references(names);
+                i.class.rename_references(names);
+                i.value.rename_references(names);
+            }
+        }
+    }
+}
+
+impl RenameReferences for ASN1Type {
+    fn rename_references(&mut self, names: &BTreeMap<String, String>) {
+        match self {
+            ASN1Type::Null | ASN1Type::Boolean => (),
+            ASN1Type::Integer(Integer { constraints, .. })
+            | ASN1Type::Real(Real { constraints, .. })
+            | ASN1Type::BitString(BitString { constraints, .. })
+            | ASN1Type::OctetString(OctetString { constraints, .. })
+            | ASN1Type::CharacterString(CharacterString { constraints, .. })
+            | ASN1Type::Enumerated(Enumerated { constraints, .. })
+            | ASN1Type::ObjectIdentifier(ObjectIdentifier { constraints, .. }) => {
+                constraints.rename_references(names)
+            }
+            ASN1Type::Choice(c) => {
+                c.constraints.rename_references(names);
+                for option in &mut c.options {
+                    option.r#type.rename_references(names);
+                    option.constraints.rename_references(names);
+                }
+            }
+            ASN1Type::Sequence(s) | ASN1Type::Set(s) => {
+                s.constraints.rename_references(names);
+                for member in s.all_members_mut() {
+                    member.r#type.rename_references(names);
+                    member.constraints.rename_references(names);
+                    member.default_value.rename_references(names);
+                }
+            }
+            ASN1Type::SequenceOf(s) | ASN1Type::SetOf(s) => {
+                s.constraints.rename_references(names);
+                s.r#type.rename_references(names);
+            }
+            ASN1Type::ElsewhereDeclaredType(e) => {
+                e.identifier.rename_references(names);
+                e.constraints.rename_references(names);
+            }
+            ASN1Type::InformationObjectFieldReference(r) => {
+                r.class.rename_references(names);
+                r.constraints.rename_references(names);
+            }
+        }
+    }
+}
+
+impl RenameReferences for ASN1Value {
+    fn rename_references(&mut self, names: &BTreeMap<String, String>) {
+        match self {
+            ASN1Value::ElsewhereDeclaredValue(name) => name.rename_references(names),
+            ASN1Value::EnumeratedValue { enumerated, .. } => enumerated.rename_references(names),
+            ASN1Value::Choice(_, value) => value.rename_references(names),
+            ASN1Value::Sequence(members) => members
+                .iter_mut()
+                .for_each(|(_, value)| value.rename_references(names)),
+            ASN1Value::LinkedNestedValue { r#type, value } => {
+                r#type.rename_references(names);
+                value.rename_references(names);
+            }
+            ASN1Value::Optional(value) => value.rename_references(names),
+            _ => (),
+        }
+    }
+}
+
+impl RenameReferences for Constraint {
+    fn rename_references(&mut self, names: &BTreeMap<String, String>) {
+        match self {
+            Constraint::SubtypeConstraint(s) => s.set.rename_references(names),
+            Constraint::TableConstraint(t) => t.object_set.rename_references(names),
+            Constraint::ContentConstraint(c) => c.containing.rename_references(names),
+            Constraint::Parameter(parameters) => parameters.rename_references(names),
+        }
+    }
+}
+
+impl RenameReferences for Parameter {
+    fn rename_references(&mut self, names: &BTreeMap<String, String>) {
+        match self {
+            Parameter::ValueParameter(v) => v.rename_references(names),
+            Parameter::TypeParameter(t) => t.rename_references(names),
+            Parameter::InformationObjectParameter(o) => o.rename_references(names),
+            Parameter::ObjectSetParameter(o) => o.rename_references(names),
+        }
+    }
+}
+
+impl RenameReferences for ElementOrSetOperation {
+    fn rename_references(&mut self, names: &BTreeMap<String, String>) {
+        match self {
+            ElementOrSetOperation::Element(e) => e.rename_references(names),
+            ElementOrSetOperation::SetOperation(s) => {
+                s.base.rename_references(names);
+                s.operant.rename_references(names);
+            }
+        }
+    }
+}
+
+impl RenameReferences for SubtypeElement {
+    fn rename_references(&mut self, names: &BTreeMap<String, String>) {
+        match self {
+            SubtypeElement::SingleValue { value, .. } => value.rename_references(names),
+            SubtypeElement::ContainedSubtype { subtype, .. } => subtype.rename_references(names),
+            SubtypeElement::ValueRange { min, max, .. } => {
+                min.rename_references(names);
+                max.rename_references(names);
+            }
+            SubtypeElement::PermittedAlphabet(e) | SubtypeElement::SizeConstraint(e) => {
+                e.rename_references(names)
+            }
+            SubtypeElement::TypeConstraint(t) => t.rename_references(names),
+            SubtypeElement::SingleTypeConstraint(c)
+            | SubtypeElement::MultipleTypeConstraints(c) => c
+                .constraints
+                .iter_mut()
+                .for_each(|component| component.constraints.rename_references(names)),
+            SubtypeElement::PatternConstraint(_)
+            | SubtypeElement::UserDefinedConstraint(_)
+            | SubtypeElement::PropertySettings(_) => (),
+        }
+    }
+}
+
+impl RenameReferences for ClassLink {
+    fn rename_references(&mut self, names: &BTreeMap<String, String>) {
+        match self {
+            ClassLink::ByName(name) => name.rename_references(names),
+            ClassLink::ByParameterizedName(name, parameters) => {
+                name.rename_references(names);
+                parameters.rename_references(names);
+            }
+            ClassLink::ByReference(class) => class.rename_references(names),
+        }
+    }
+}
+
+impl RenameReferences for InformationObjectClass {
+    fn rename_references(&mut self, names: &BTreeMap<String, String>) {
+        self.fields.iter_mut().for_each(|field| {
+            field.r#type.rename_references(names);
+            field.default.rename_references(names);
+        })
+    }
+}
+
+impl RenameReferences for ASN1Information {
+    fn rename_references(&mut self, names: &BTreeMap<String, String>) {
+        match self {
+            ASN1Information::ObjectClass(c) => c.rename_references(names),
+            ASN1Information::ObjectSet(s) => s.rename_references(names),
+            ASN1Information::Object(o) => {
+                o.supertype.rename_references(names);
+                o.fields.rename_references(names);
+            }
+        }
+    }
+}
+
+impl RenameReferences for ObjectSet {
+    fn rename_references(&mut self, names: &BTreeMap<String, String>) {
+        for value in &mut self.values {
+            match value {
+                ObjectSetValue::Reference(name) => name.rename_references(names),
+                ObjectSetValue::Inline(fields) => fields.rename_references(names),
+            }
+        }
+    }
+}
+
+impl RenameReferences for InformationObjectFields {
+    fn rename_references(&mut self, names: &BTreeMap<String, String>) {
+        match self {
+            InformationObjectFields::DefaultSyntax(fields) => {
+                for field in fields {
+                    match field {
+                        InformationObjectField::TypeField(t) => t.r#type.rename_references(names),
+                        InformationObjectField::FixedValueField(f) => {
+                            f.value.rename_references(names)
+                        }
+                        InformationObjectField::ObjectSetField(o) => {
+                            o.value.rename_references(names)
+                        }
+                    }
+                }
+            }
+            InformationObjectFields::CustomSyntax(applications) => {
+                for application in applications {
+                    match application {
+                        SyntaxApplication::ObjectSetDeclaration(o) => o.rename_references(names),
+                        SyntaxApplication::ValueReference(v) => v.rename_references(names),
+                        SyntaxApplication::TypeReference(t) => t.rename_references(names),
+                        SyntaxApplication::Comma | SyntaxApplication::Literal(_) => (),
+                    }
+                }
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use alloc::{
+        boxed::Box,
+        collections::{BTreeMap, BTreeSet},
+        string::String,
+        vec,
+    };
+
+    use crate::{types::*, *};
+
+    #[test]
+    fn renames_declarations_and_references() {
+        let mut foo = ToplevelDeclaration::Type(ToplevelTypeDeclaration {
+            comments: String::new(),
+            tag: None,
+            name: "Foo".into(),
+            r#type: ASN1Type::SequenceOf(SequenceOf {
+                constraints: vec![],
+                r#type: Box::new(ASN1Type::ElsewhereDeclaredType(DeclarationElsewhere {
+                    identifier: "Bar".into(),
+                    constraints: vec![],
+                })),
+            }),
+            parameterization: None,
+        });
+        let names = BTreeMap::from([
+            (String::from("Foo"), String::from("AsnFoo")),
+            (String::from("Bar"), String::from("AsnBar")),
+        ]);
+        foo.rename(&names);
+        assert_eq!(foo.name(), "AsnFoo");
+        assert_eq!(foo.dependencies(), BTreeSet::from([String::from("AsnBar")]));
+        let mut value = ToplevelDeclaration::Value(ToplevelValueDeclaration {
+            comments: String::new(),
+            name: "default-foo".into(),
+            type_name: "Foo".into(),
+            value: ASN1Value::EnumeratedValue {
+                enumerated: "Baz".into(),
+                enumerable: "Foo".into(),
+            },
+        });
+        value.rename(&names);
+        assert_eq!(value.name(), "default-foo");
+        assert_eq!(
+            value,
+            ToplevelDeclaration::Value(ToplevelValueDeclaration {
+                comments: String::new(),
+                name: "default-foo".into(),
+                type_name: "AsnFoo".into(),
+                value: ASN1Value::EnumeratedValue {
+                    enumerated: "Baz".into(),
+                    enumerable: "Foo".into(),
+                },
+            })
+        );
+    }
+}
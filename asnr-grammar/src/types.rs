@@ -2,7 +2,7 @@ use core::fmt::Debug;
 
 use alloc::{borrow::ToOwned, boxed::Box, vec};
 
-use crate::{constraints::*, *};
+use crate::{constraints::*, encoding_rules::per_visible::per_visible_range_constraints, *};
 
 /// Representation of an ASN1 INTEGER data element
 /// with corresponding constraints and distinguished values
@@ -13,33 +13,20 @@ pub struct Integer {
 }
 
 impl Integer {
+    /// Returns the narrowest Rust integer type that holds every value
+    /// permitted by the folded PER-visible range constraints.
+    /// Falls back to `i128` for unconstrained, semi-constrained, and extensible integers.
     pub fn type_token(&self) -> String {
-        let (min, max) =
-            self.constraints
-                .iter()
-                .try_fold((i128::MAX, i128::MIN), |(mut min, mut max), c| {
-                    if let Ok((cmin, cmax, extensible)) = c.unpack_as_value_range() {
-                        if extensible {
-                            return Err("_");
-                        }
-                        if let Some(ASN1Value::Integer(i)) = cmin {
-                            min = (*i).min(min);
-                        };
-                        if let Some(ASN1Value::Integer(i)) = cmax {
-                            max = (*i).max(max);
-                        };
-                    };
-                    Ok((min, max))
-                }).unwrap_or((1,0));
-        if min > max {
-            "i128".to_owned()
-        } else {
-            int_type_token(min, max).to_owned()
+        match per_visible_range_constraints(true, &self.constraints) {
+            Ok(range) if !range.is_extensible() => match (range.min(), range.max()) {
+                (Some(min), Some(max)) if min <= max => int_type_token(min, max).to_owned(),
+                _ => "i128".to_owned(),
+            },
+            _ => "i128".to_owned(),
         }
     }
 }
 
-
 impl Default for Integer {
     fn default() -> Self {
         Self {
@@ -168,7 +155,7 @@ impl From<(&str, Option<Vec<Constraint>>)> for CharacterString {
     }
 }
 
-/// Representation of an ASN1 SEQUENCE OF data element
+/// Representation of an ASN1 SEQUENCE OF or SET OF data element
 /// with corresponding constraints and element type info
 #[derive(Debug, Clone, PartialEq)]
 pub struct SequenceOf {
@@ -191,15 +178,28 @@ impl From<(Option<Vec<Constraint>>, ASN1Type)> for SequenceOf {
 pub struct SequenceOrSet {
     pub extensible: Option<usize>,
     pub constraints: Vec<Constraint>,
+    /// The root components followed by the extension additions
+    /// that are not part of an extension addition group
     pub members: Vec<SequenceOrSetMember>,
+    pub extension_groups: Vec<ExtensionAdditionGroup>,
 }
 
+/// The extension additions of a SEQUENCE or SET, i.e. the single members and
+/// the extension addition groups following the first extension marker, and the
+/// root components that follow a second extension marker.
+/// The positions of the groups are relative to the single extension additions.
+pub type ExtensionAdditions = (
+    Vec<SequenceOrSetMember>,
+    Vec<ExtensionAdditionGroup>,
+    Vec<SequenceOrSetMember>,
+);
+
 impl
     From<(
         (
             Vec<SequenceOrSetMember>,
             Option<ExtensionMarker>,
-            Option<Vec<SequenceOrSetMember>>,
+            Option<ExtensionAdditions>,
         ),
         Option<Vec<Constraint>>,
     )> for SequenceOrSet
@@ -209,17 +209,94 @@ impl
             (
                 Vec<SequenceOrSetMember>,
                 Option<ExtensionMarker>,
-                Option<Vec<SequenceOrSetMember>>,
+                Option<ExtensionAdditions>,
             ),
             Option<Vec<Constraint>>,
         ),
     ) -> Self {
+        let (mut additions, mut extension_groups, mut root_components) =
+            value.0 .2.unwrap_or_default();
+        value.0 .0.append(&mut root_components);
         let index_of_first_extension = value.0 .0.len();
-        value.0 .0.append(&mut value.0 .2.unwrap_or(vec![]));
+        value.0 .0.append(&mut additions);
+        extension_groups
+            .iter_mut()
+            .for_each(|group| group.position += index_of_first_extension);
         SequenceOrSet {
             constraints: value.1.unwrap_or(vec![]),
             extensible: value.0 .1.map(|_| index_of_first_extension),
             members: value.0 .0,
+            extension_groups,
+        }
+    }
+}
+
+impl SequenceOrSet {
+    /// Iterates over the members, including the members of the extension addition groups
+    pub fn all_members(&self) -> impl Iterator<Item = &SequenceOrSetMember> {
+        self.members
+            .iter()
+            .chain(self.extension_groups.iter().flat_map(|g| g.members.iter()))
+    }
+
+    /// Mutably iterates over the members, including the members of the extension addition groups
+    pub fn all_members_mut(&mut self) -> impl Iterator<Item = &mut SequenceOrSetMember> {
+        self.members.iter_mut().chain(
+            self.extension_groups
+                .iter_mut()
+                .flat_map(|g| g.members.iter_mut()),
+        )
+    }
+
+    /// Represents the extension addition groups as members at their positions,
+    /// see [ExtensionAdditionGroup::into_member]
+    pub fn inline_extension_groups(&mut self) {
+        let groups = core::mem::take(&mut self.extension_groups);
+        for (index, group) in groups.into_iter().enumerate().rev() {
+            let position = group.position.min(self.members.len());
+            self.members.insert(position, group.into_member(index + 1));
+        }
+    }
+}
+
+/// Representation of an ASN1 extension addition group `[[ ... ]]`
+/// with its optional version number
+#[derive(Debug, Clone, PartialEq)]
+pub struct ExtensionAdditionGroup {
+    pub version: Option<i128>,
+    /// The number of the SEQUENCE's `members` that precede the group
+    pub position: usize,
+    pub members: Vec<SequenceOrSetMember>,
+}
+
+impl From<(Option<i128>, Vec<SequenceOrSetMember>)> for ExtensionAdditionGroup {
+    fn from(value: (Option<i128>, Vec<SequenceOrSetMember>)) -> Self {
+        Self {
+            version: value.0,
+            position: 0,
+            members: value.1,
+        }
+    }
+}
+
+impl ExtensionAdditionGroup {
+    /// Represents the group as a member of type SEQUENCE named `ext_group_<index>`
+    /// * `index` - the one-based position of the group among the extension addition groups
+    /// of its SEQUENCE
+    pub fn into_member(self, index: usize) -> SequenceOrSetMember {
+        SequenceOrSetMember {
+            name: format!("ext_group_{index}"),
+            tag: None,
+            r#type: ASN1Type::Sequence(SequenceOrSet {
+                extensible: None,
+                constraints: vec![],
+                members: self.members,
+                extension_groups: vec![],
+            }),
+            default_value: None,
+            is_optional: false,
+            constraints: vec![],
+            is_recursive: false,
         }
     }
 }
@@ -228,11 +305,16 @@ impl
 #[derive(Debug, Clone, PartialEq)]
 pub struct SequenceOrSetMember {
     pub name: String,
+    /// The member's explicitly declared tag. Its environment records
+    /// whether the member was declared IMPLICIT or EXPLICIT
     pub tag: Option<AsnTag>,
     pub r#type: ASN1Type,
     pub default_value: Option<ASN1Value>,
     pub is_optional: bool,
     pub constraints: Vec<Constraint>,
+    /// Whether the member's type refers back to the SEQUENCE by value,
+    /// so that its Rust representation needs to be boxed
+    pub is_recursive: bool,
 }
 
 impl
@@ -262,6 +344,7 @@ impl
             is_optional: value.4.is_some() || value.5.is_some(),
             default_value: value.5,
             constraints: value.3.unwrap_or(vec![]),
+            is_recursive: false,
         }
     }
 }
@@ -294,24 +377,30 @@ pub struct Choice {
 
 impl
     From<(
-        Vec<ChoiceOption>,
-        Option<ExtensionMarker>,
-        Option<Vec<ChoiceOption>>,
+        (
+            Vec<ChoiceOption>,
+            Option<ExtensionMarker>,
+            Option<Vec<ChoiceOption>>,
+        ),
+        Option<Vec<Constraint>>,
     )> for Choice
 {
     fn from(
         mut value: (
-            Vec<ChoiceOption>,
-            Option<ExtensionMarker>,
-            Option<Vec<ChoiceOption>>,
+            (
+                Vec<ChoiceOption>,
+                Option<ExtensionMarker>,
+                Option<Vec<ChoiceOption>>,
+            ),
+            Option<Vec<Constraint>>,
         ),
     ) -> Self {
-        let index_of_first_extension = value.0.len();
-        value.0.append(&mut value.2.unwrap_or(vec![]));
+        let index_of_first_extension = value.0 .0.len();
+        value.0 .0.append(&mut value.0 .2.unwrap_or(vec![]));
         Choice {
-            extensible: value.1.map(|_| index_of_first_extension),
-            options: value.0,
-            constraints: vec![],
+            extensible: value.0 .1.map(|_| index_of_first_extension),
+            options: value.0 .0,
+            constraints: value.1.unwrap_or(vec![]),
         }
     }
 }
@@ -323,6 +412,9 @@ pub struct ChoiceOption {
     pub tag: Option<AsnTag>,
     pub r#type: ASN1Type,
     pub constraints: Vec<Constraint>,
+    /// Whether the option's type refers back to the CHOICE by value,
+    /// so that its Rust representation needs to be boxed
+    pub is_recursive: bool,
 }
 
 impl From<(&str, Option<AsnTag>, ASN1Type, Option<Vec<Constraint>>)> for ChoiceOption {
@@ -332,6 +424,7 @@ impl From<(&str, Option<AsnTag>, ASN1Type, Option<Vec<Constraint>>)> for ChoiceO
             tag: value.1,
             r#type: value.2,
             constraints: value.3.unwrap_or(vec![]),
+            is_recursive: false,
         }
     }
 }
@@ -394,3 +487,38 @@ impl From<(&str, i128)> for DistinguishedValue {
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use crate::{constraints::*, types::Integer, ASN1Value};
+    use alloc::vec;
+
+    #[test]
+    fn selects_narrowest_integer_type() {
+        assert_eq!(Integer::from((0, 255, false)).type_token(), "u8");
+        assert_eq!(Integer::from((-1, 4000, false)).type_token(), "i16");
+        assert_eq!(Integer::from((0, 5000000000, false)).type_token(), "u64");
+        assert_eq!(Integer::from((0, 255, true)).type_token(), "i128");
+        assert_eq!(Integer::default().type_token(), "i128");
+        let intersection = Integer {
+            constraints: vec![
+                Integer::from((0, 100000, false)).constraints.remove(0),
+                Integer::from((0, 100, false)).constraints.remove(0),
+            ],
+            distinguished_values: None,
+        };
+        assert_eq!(intersection.type_token(), "u8");
+        let semi_constrained = Integer {
+            constraints: vec![Constraint::SubtypeConstraint(ElementSet {
+                set: ElementOrSetOperation::Element(SubtypeElement::ValueRange {
+                    min: Some(ASN1Value::Integer(0)),
+                    max: None,
+                    extensible: false,
+                }),
+                extensible: false,
+            })],
+            distinguished_values: None,
+        };
+        assert_eq!(semi_constrained.type_token(), "i128");
+    }
+}
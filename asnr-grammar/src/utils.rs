@@ -1,6 +1,7 @@
-use alloc::{collections::BTreeMap, string::String, vec::Vec};
+use alloc::{collections::BTreeMap, format, string::String, vec::Vec};
 
 use crate::{
+    error::{GrammarError, GrammarErrorType},
     information_object::{InformationObjectClassField, ObjectFieldIdentifier},
     ASN1Value, ToplevelDeclaration,
 };
@@ -25,28 +26,42 @@ pub fn int_type_token<'a>(min: i128, max: i128) -> &'a str {
     }
 }
 
+/// Finds the value of a top-level value declaration, an enumeral, or a distinguished value by its name.
+/// Returns `None` if no value of that name is declared, and an `UnexpectedType` error
+/// if the name belongs to a top-level declaration that is not a value, such as a type.
 pub(crate) fn find_tld_or_enum_value_by_name(
     type_name: &String,
     name: &String,
     tlds: &BTreeMap<String, ToplevelDeclaration>,
-) -> Option<ASN1Value> {
+) -> Result<Option<ASN1Value>, GrammarError> {
     if let Some(ToplevelDeclaration::Value(v)) = tlds.get(name) {
-        return Some(v.value.clone());
+        return Ok(Some(v.value.clone()));
     } else {
         for (_, tld) in tlds.iter() {
             if let Some(value) = tld.get_distinguished_or_enum_value(Some(type_name), name) {
-                return Some(value);
+                return Ok(Some(value));
             }
         }
         // Make second attempt without requiring a matching type name
         // This is the current best shot at linking inner subtypes
         for (_, tld) in tlds.iter() {
             if let Some(value) = tld.get_distinguished_or_enum_value(None, name) {
-                return Some(value);
+                return Ok(Some(value));
             }
         }
     }
-    None
+    let found = match tlds.get(name) {
+        Some(ToplevelDeclaration::Type(_)) => "type",
+        Some(ToplevelDeclaration::Information(_)) => "information object",
+        _ => return Ok(None),
+    };
+    Err(GrammarError {
+        details: format!("Expected {name} referenced in {type_name} to be a value"),
+        kind: GrammarErrorType::UnexpectedType {
+            expected: "value".into(),
+            found: found.into(),
+        },
+    })
 }
 
 pub(crate) fn walk_object_field_ref_path<'a>(
@@ -73,11 +88,12 @@ pub(crate) fn walk_object_field_ref_path<'a>(
         .flatten()
 }
 
-const RUST_KEYWORDS: [&'static str; 38] = [
+const RUST_KEYWORDS: [&str; 51] = [
     "as", "async", "await", "break", "const", "continue", "crate", "dyn", "else", "enum", "extern",
     "false", "fn", "for", "if", "impl", "in", "let", "loop", "match", "mod", "move", "mut", "pub",
     "ref", "return", "self", "Self", "static", "struct", "super", "trait", "true", "type",
-    "unsafe", "use", "where", "while",
+    "unsafe", "use", "where", "while", "abstract", "become", "box", "do", "final", "macro",
+    "override", "priv", "try", "typeof", "unsized", "virtual", "yield",
 ];
 
 pub fn to_rust_snake_case(input: &String) -> String {
@@ -105,9 +121,68 @@ pub fn to_rust_const_case(input: &String) -> String {
     to_rust_snake_case(input).to_uppercase()
 }
 
+/// Well-known names of the root arcs of the OBJECT IDENTIFIER tree, see ITU-T X.660 Annex A
+const OID_ROOT_ARCS: [(&str, u128); 5] = [
+    ("itu-t", 0),
+    ("ccitt", 0),
+    ("iso", 1),
+    ("joint-iso-itu-t", 2),
+    ("joint-iso-ccitt", 2),
+];
+
+/// Well-known names of the arcs below the root arcs of the OBJECT IDENTIFIER tree
+/// as `(root arc, name, number)`, see ITU-T X.660 Annex A
+const OID_CHILD_ARCS: [(u128, &str, u128); 14] = [
+    (0, "recommendation", 0),
+    (0, "question", 1),
+    (0, "administration", 2),
+    (0, "network-operator", 3),
+    (0, "identified-organization", 4),
+    (1, "standard", 0),
+    (1, "registration-authority", 1),
+    (1, "member-body", 2),
+    (1, "identified-organization", 3),
+    (2, "asn1", 1),
+    (2, "ds", 5),
+    (2, "country", 16),
+    (2, "registration-procedures", 17),
+    (2, "uuid", 25),
+];
+
+/// Returns the number of a well-known OBJECT IDENTIFIER arc, e.g. `1` for `iso`.
+/// Only the root arcs and the arcs directly below them are known, since the names
+/// of arcs depend on their parent, e.g. `identified-organization` is arc `4` below `itu-t`
+/// and arc `3` below `iso`.
+/// * `root` - the number of the parent root arc, or `None` for a root arc
+/// * `name` - the name of the arc
+pub fn resolve_oid_arc_name(root: Option<u128>, name: &str) -> Option<u128> {
+    match root {
+        None => OID_ROOT_ARCS
+            .iter()
+            .find(|(arc_name, _)| *arc_name == name)
+            .map(|(_, number)| *number),
+        Some(root) => OID_CHILD_ARCS
+            .iter()
+            .find(|(parent, arc_name, _)| *parent == root && *arc_name == name)
+            .map(|(_, _, number)| *number),
+    }
+}
+
+/// Names of the prelude and framework types that generated code refers to.
+/// Generated types of the same name would shadow them.
+const RUST_RESERVED_TYPE_NAMES: [&str; 12] = [
+    "Self", "Option", "Some", "None", "Result", "Ok", "Err", "Box", "String", "Vec", "Default",
+    "Debug",
+];
+
+/// Converts an ASN1 identifier to a Rust type or variant identifier.
+/// Characters other than alphanumerics, `-`, and `_` are stripped,
+/// names starting with a digit are prefixed with `Asn`,
+/// and names that collide with keywords or prelude types are suffixed with `Type`.
 pub fn to_rust_title_case(input: &String) -> String {
     let mut input = input.replace("-", "_");
-    input.drain(..).fold(String::new(), |mut acc, c| {
+    input.retain(|c| c.is_alphanumeric() || c == '_');
+    let title_case = input.drain(..).fold(String::new(), |mut acc, c| {
         if acc.is_empty() && c.is_lowercase() {
             acc.push(c.to_ascii_uppercase());
         } else if acc.ends_with(|last: char| last == '_') && c.is_uppercase() {
@@ -120,12 +195,140 @@ pub fn to_rust_title_case(input: &String) -> String {
             acc.push(c);
         }
         acc
-    })
+    });
+    if title_case.starts_with(|c: char| c.is_ascii_digit()) {
+        String::from("Asn") + &title_case
+    } else if RUST_RESERVED_TYPE_NAMES.contains(&title_case.as_str()) {
+        title_case + "Type"
+    } else {
+        title_case
+    }
+}
+
+/// Conventions for converting the names of ASN1 enumerals to Rust identifiers
+#[derive(Debug, Clone, Copy, PartialEq, Default)]
+pub enum NamingStrategy {
+    /// Capitalizes every word and lowercases words that are all uppercase,
+    /// e.g. `SHA256` becomes `Sha256`
+    TitleCase,
+    /// Capitalizes every word but otherwise keeps the original casing,
+    /// e.g. `SHA256` stays `SHA256` and `sha-256` becomes `Sha256`
+    #[default]
+    PreserveCase,
+    /// Uppercases every letter and separates words by underscores,
+    /// e.g. `positiveOutOfRange` becomes `POSITIVE_OUT_OF_RANGE`.
+    /// Note that such identifiers trigger the `non_camel_case_types` lint.
+    ScreamingSnakeCase,
+}
+
+impl NamingStrategy {
+    /// Converts an ASN1 identifier to a Rust identifier following the naming strategy
+    pub fn apply(&self, input: &String) -> String {
+        match self {
+            NamingStrategy::PreserveCase => to_rust_title_case(input),
+            NamingStrategy::TitleCase => input
+                .split(['-', '_'])
+                .map(|word| {
+                    let mut chars = word.chars();
+                    let first = chars.next().map(|c| c.to_ascii_uppercase());
+                    let rest = chars.collect::<String>();
+                    let rest = if word.chars().any(char::is_lowercase) {
+                        rest
+                    } else {
+                        rest.to_lowercase()
+                    };
+                    first.into_iter().collect::<String>() + &rest
+                })
+                .collect(),
+            NamingStrategy::ScreamingSnakeCase => {
+                let mut output = String::new();
+                let mut previous: Option<char> = None;
+                for c in input.chars() {
+                    if c == '-' || c == '_' {
+                        output.push('_');
+                    } else {
+                        if c.is_uppercase()
+                            && previous.is_some_and(|p| p.is_lowercase() || p.is_ascii_digit())
+                        {
+                            output.push('_');
+                        }
+                        output.push(c.to_ascii_uppercase());
+                    }
+                    previous = Some(c);
+                }
+                output
+            }
+        }
+    }
 }
 
 #[cfg(test)]
 mod tests {
-    use crate::utils::int_type_token;
+    use alloc::string::String;
+
+    use crate::utils::{
+        int_type_token, resolve_oid_arc_name, to_rust_snake_case, to_rust_title_case,
+        NamingStrategy,
+    };
+
+    #[test]
+    fn resolves_well_known_oid_arc_names() {
+        assert_eq!(resolve_oid_arc_name(None, "iso"), Some(1));
+        assert_eq!(resolve_oid_arc_name(None, "joint-iso-itu-t"), Some(2));
+        assert_eq!(resolve_oid_arc_name(Some(1), "member-body"), Some(2));
+        assert_eq!(
+            resolve_oid_arc_name(Some(1), "identified-organization"),
+            Some(3)
+        );
+        assert_eq!(
+            resolve_oid_arc_name(Some(0), "identified-organization"),
+            Some(4)
+        );
+        assert_eq!(resolve_oid_arc_name(None, "etsi"), None);
+    }
+
+    #[test]
+    fn resolves_oid_arc_names_by_position() {
+        assert_eq!(resolve_oid_arc_name(None, "member-body"), None);
+        assert_eq!(resolve_oid_arc_name(Some(1), "itu-t"), None);
+        assert_eq!(resolve_oid_arc_name(Some(2), "member-body"), None);
+    }
+
+    #[test]
+    fn escapes_invalid_type_names() {
+        assert_eq!(to_rust_title_case(&"Option".into()), "OptionType");
+        assert_eq!(to_rust_title_case(&"Self".into()), "SelfType");
+        assert_eq!(to_rust_title_case(&"3D-Position".into()), "Asn3DPosition");
+        assert_eq!(to_rust_title_case(&"Cause.Code".into()), "CauseCode");
+        assert_eq!(to_rust_title_case(&"options".into()), "Options");
+        assert_eq!(to_rust_title_case(&"gnss-Status".into()), "GnssStatus");
+    }
+
+    #[test]
+    fn escapes_reserved_field_names() {
+        assert_eq!(to_rust_snake_case(&"box".into()), "r_box");
+        assert_eq!(to_rust_snake_case(&"type".into()), "r_type");
+        assert_eq!(to_rust_snake_case(&"boxed".into()), "boxed");
+    }
+
+    #[test]
+    fn applies_naming_strategies() {
+        let name = |s: &str| String::from(s);
+        assert_eq!(NamingStrategy::TitleCase.apply(&name("SHA256")), "Sha256");
+        assert_eq!(NamingStrategy::PreserveCase.apply(&name("SHA256")), "SHA256");
+        assert_eq!(NamingStrategy::ScreamingSnakeCase.apply(&name("SHA256")), "SHA256");
+        assert_eq!(
+            NamingStrategy::TitleCase.apply(&name("positiveOutOfRange")),
+            "PositiveOutOfRange"
+        );
+        assert_eq!(NamingStrategy::TitleCase.apply(&name("sha-RSA")), "ShaRsa");
+        assert_eq!(NamingStrategy::PreserveCase.apply(&name("sha-RSA")), "ShaRSA");
+        assert_eq!(
+            NamingStrategy::ScreamingSnakeCase.apply(&name("positiveOutOfRange")),
+            "POSITIVE_OUT_OF_RANGE"
+        );
+        assert_eq!(NamingStrategy::ScreamingSnakeCase.apply(&name("sha-256")), "SHA_256");
+    }
 
     #[test]
     fn determines_int_type() {
@@ -1,11 +1,10 @@
 use std::num::ParseIntError;
 
-use asnr_transcoder::uper::Uper;
+use asnr_transcoder::uper::{BitIn, Uper};
 use asnr_tests::asn1::v2x::{ItsPduHeader, CAM};
 use criterion::{BenchmarkId, criterion_group, criterion_main, Criterion};
 use asnr_compiler_derive::asn1;
 use bitvec::prelude::*;
-use bitvec_nom::BSlice;
 
 fn decode_hex(s: &str) -> Result<Vec<u8>, ParseIntError> {
   (0..s.len())
@@ -20,7 +19,7 @@ fn decode_small_sequence(c: &mut Criterion) {
   item-name IA5String (SIZE (3..10))OPTIONAL,
   ...,
   urgency ENUMERATED {normal, high} DEFAULT normal }");
-  let input = BSlice::from(bits![static u8, Msb0; 
+  let input = BitIn::from(bits![static u8, Msb0; 
     1,
     1,
     0,0,0,1,1,0,1,1,
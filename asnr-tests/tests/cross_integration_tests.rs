@@ -40,7 +40,7 @@ fn encodes_octet_sequence() {
     .unwrap();
     let encoded = encoder.bitstring_output();
     let decoded = TestSequenceAsnr::decode::<asnr_transcoder::uper::Uper>(
-        bitvec_nom::BSlice::from(encoded.as_bitslice()),
+        asnr_transcoder::uper::BitIn::from(encoded.as_bitslice()),
     )
     .unwrap()
     .1;
@@ -70,4 +70,252 @@ fn xtest() {
     
     END
     "#);
-}
\ No newline at end of file
+}
+#[test]
+fn exposes_distinguished_values_as_constants() {
+    asn1!(
+        r#"AccelerationMagnitudeValue ::= INTEGER {
+        positiveOutOfRange(160),
+        unavailable(161)
+      } (0..161)"#
+    );
+
+    let unavailable = AccelerationMagnitudeValue::UNAVAILABLE;
+    assert_eq!(unavailable.0, 161);
+    assert!(unavailable.is_unavailable());
+    let encoded =
+        asnr_transcoder::uper::Uper::encode(AccelerationMagnitudeValue::POSITIVE_OUT_OF_RANGE)
+            .unwrap();
+    assert_eq!(
+        asnr_transcoder::uper::Uper::decode::<AccelerationMagnitudeValue>(&encoded).unwrap(),
+        AccelerationMagnitudeValue::POSITIVE_OUT_OF_RANGE
+    );
+}
+
+#[test]
+fn constructs_sequence_with_default_values() {
+    asn1!(
+        r#"Settings ::= SEQUENCE {
+        level INTEGER (0..10) DEFAULT 3,
+        enabled BOOLEAN DEFAULT TRUE,
+        id INTEGER (0..255)
+      }"#
+    );
+
+    let settings = Settings::new_with_defaults();
+    assert_eq!(settings.level, Some(InnerSettingsLevel(3)));
+    assert_eq!(settings.enabled, Some(Settings::default_enabled()));
+    assert_eq!(settings.id, InnerSettingsId(0));
+    assert_eq!(Settings::default().level, None);
+}
+
+#[test]
+fn applies_default_values_in_getters() {
+    asn1!(
+        r#"Mode ::= ENUMERATED { off, on }
+        Preferences ::= SEQUENCE {
+        world INTEGER (0..8) DEFAULT 8,
+        mode Mode DEFAULT on
+      }"#
+    );
+
+    let mut preferences = Preferences::default();
+    assert_eq!(preferences.world, None);
+    assert_eq!(preferences.world(), 8);
+    assert_eq!(preferences.mode(), Mode::On);
+    preferences.world = Some(InnerPreferencesWorld(2));
+    preferences.mode = Some(Mode::Off);
+    assert_eq!(preferences.world(), 2);
+    assert_eq!(preferences.mode(), Mode::Off);
+}
+
+#[test]
+fn accesses_named_bits() {
+    asn1!(r#"Permissions ::= BIT STRING { read(0), write(1), execute(4) } (SIZE(0..8))"#);
+
+    let mut permissions = Permissions(vec![true]);
+    assert!(permissions.read());
+    assert!(!permissions.execute());
+    permissions.set_execute(true);
+    assert_eq!(permissions.0.len(), Permissions::EXECUTE + 1);
+    assert!(permissions.execute() && !permissions.write());
+    permissions.set_read(false);
+    assert_eq!(permissions.0, vec![false, false, false, false, true]);
+}
+
+#[test]
+fn round_trips_recursive_structures() {
+    asn1!(
+        r#"Node ::= SEQUENCE {
+        value INTEGER (0..255),
+        next Node OPTIONAL
+      }
+      Expression ::= CHOICE {
+        literal INTEGER (0..255),
+        sum Sum
+      }
+      Sum ::= SEQUENCE {
+        left Expression,
+        right Expression
+      }"#
+    );
+
+    let list = Node {
+        value: InnerNodeValue(1),
+        next: Some(Box::new(Node {
+            value: InnerNodeValue(2),
+            next: Some(Box::new(Node {
+                value: InnerNodeValue(3),
+                next: None,
+            })),
+        })),
+    };
+    let encoded = asnr_transcoder::uper::Uper::encode(list.clone()).unwrap();
+    assert_eq!(
+        asnr_transcoder::uper::Uper::decode::<Node>(&encoded).unwrap(),
+        list
+    );
+    let expression = Expression::Sum(Box::new(Sum {
+        left: Expression::Literal(InnerExpressionLiteral(1)),
+        right: Expression::Sum(Box::new(Sum {
+            left: Expression::Literal(InnerExpressionLiteral(2)),
+            right: Expression::Literal(InnerExpressionLiteral(3)),
+        })),
+    }));
+    let encoded = asnr_transcoder::uper::Uper::encode(expression.clone()).unwrap();
+    assert_eq!(
+        asnr_transcoder::uper::Uper::decode::<Expression>(&encoded).unwrap(),
+        expression
+    );
+}
+
+#[test]
+fn constructs_sequences_through_generated_api() {
+    asn1!(
+        r#"Small ::= SEQUENCE {
+        name IA5String (SIZE (1..8)),
+        count INTEGER (0..255),
+        flag BOOLEAN OPTIONAL,
+        level INTEGER (0..7) DEFAULT 2
+      }
+      Large ::= SEQUENCE {
+        a INTEGER (0..255),
+        b IA5String (SIZE (1..8)),
+        c BOOLEAN OPTIONAL,
+        d INTEGER (0..7) DEFAULT 3,
+        e OCTET STRING (SIZE (2)),
+        f Small
+      }"#
+    );
+
+    let small = Small::new("asnr", 42);
+    assert_eq!(
+        small,
+        Small {
+            name: InnerSmallName("asnr".into()),
+            count: InnerSmallCount(42),
+            flag: None,
+            level: Some(InnerSmallLevel(2)),
+        }
+    );
+    let encoded = asnr_transcoder::uper::Uper::encode(small.clone()).unwrap();
+    assert_eq!(
+        asnr_transcoder::uper::Uper::decode::<Small>(&encoded).unwrap(),
+        small
+    );
+    assert!(Large::builder().a(1).build().is_none());
+    let large = Large::builder()
+        .a(1)
+        .b("large")
+        .c(true)
+        .e(vec![0xAB, 0xCD])
+        .f(small)
+        .build()
+        .unwrap();
+    assert_eq!(large.d, Some(InnerLargeD(3)));
+    let encoded = asnr_transcoder::uper::Uper::encode(large.clone()).unwrap();
+    assert_eq!(
+        asnr_transcoder::uper::Uper::decode::<Large>(&encoded).unwrap(),
+        large
+    );
+}
+
+#[test]
+fn converts_enumerated_values_to_and_from_strings() {
+    asn1!(
+        r#"Level ::= ENUMERATED {
+        positiveOutOfRange(160),
+        low,
+        ...,
+        extra
+      }"#
+    );
+
+    for level in [Level::PositiveOutOfRange, Level::Low, Level::Extra] {
+        assert_eq!(level.to_string().parse::<Level>().unwrap(), level);
+    }
+    assert_eq!(Level::PositiveOutOfRange.to_string(), "positiveOutOfRange");
+    assert_eq!("PositiveOutOfRange".parse::<Level>().unwrap(), Level::PositiveOutOfRange);
+    assert_eq!(Level::UnknownExtension.to_string(), "UnknownExtension");
+    assert!("positive-out-of-range".parse::<Level>().is_err());
+}
+
+#[test]
+fn round_trips_object_identifiers() {
+    asn1!(
+        r#"Signed ::= SEQUENCE {
+        algorithm OBJECT IDENTIFIER,
+        payload OCTET STRING (SIZE(0..8))
+      }"#
+    );
+
+    let signed = Signed {
+        algorithm: InnerSignedAlgorithm(vec![1, 37]),
+        payload: InnerSignedPayload(vec![0xAB]),
+    };
+    let encoded = asnr_transcoder::uper::Uper::encode(signed.clone()).unwrap();
+    assert_eq!(&encoded[..2], &[0x01, 0x4D]);
+    assert_eq!(
+        asnr_transcoder::uper::Uper::decode::<Signed>(&encoded).unwrap(),
+        signed
+    );
+}
+
+#[test]
+fn round_trips_sequence_of_sequences() {
+    asn1!(r#"List ::= SEQUENCE OF SEQUENCE { a INTEGER (0..10), b BOOLEAN OPTIONAL }"#);
+
+    let list = List(vec![
+        AnonymousList {
+            a: InnerAnonymousListA(3),
+            b: Some(InnerAnonymousListB(true)),
+        },
+        AnonymousList {
+            a: InnerAnonymousListA(10),
+            b: None,
+        },
+    ]);
+    let encoded = asnr_transcoder::uper::Uper::encode(list.clone()).unwrap();
+    assert_eq!(encoded, vec![0x02, 0x9D, 0x40]);
+    assert_eq!(
+        asnr_transcoder::uper::Uper::decode::<List>(&encoded).unwrap(),
+        list
+    );
+}
+
+#[test]
+fn round_trips_reals() {
+    asn1!(r#"Measurement ::= SEQUENCE { value REAL, offset REAL OPTIONAL }"#);
+
+    for value in [0.0, -0.0, 1.0, -0.75, 3.14159, 1e-300, f64::INFINITY] {
+        let measurement = Measurement {
+            value: InnerMeasurementValue(value),
+            offset: Some(InnerMeasurementOffset(-value)),
+        };
+        let encoded = asnr_transcoder::uper::Uper::encode(measurement.clone()).unwrap();
+        assert_eq!(
+            asnr_transcoder::uper::Uper::decode::<Measurement>(&encoded).unwrap(),
+            measurement
+        );
+    }
+}
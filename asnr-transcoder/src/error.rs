@@ -19,9 +19,11 @@ pub enum DecodingErrorType {
     InvalidEnumeratedIndex,
     InvalidChoiceIndex,
     InvalidSequenceMemberIndex,
+    InvalidObjectSetKey,
     GenericParsingError,
     ConstraintError,
     Unsupported,
+    SizeLimitExceeded,
     WrappedNomError(ErrorKind),
 }
 
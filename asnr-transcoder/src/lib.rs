@@ -34,9 +34,10 @@
 //! 
 //! fn encode_example_sequence() -> Vec<u8> {
 //!   let example_sequence = ExampleSequence {
-//!     // ASN1-built-in types are represented as new types within SEQUENCEs
-//!     member_1: InnerExampleSequenceMember1("Hello, World!".into()),
-//!     member_2: InnerExampleSequenceMember2(8),
+//!     // ASN1-built-in types are represented as new types within SEQUENCEs,
+//!     // which can be converted from and into the Rust types they wrap
+//!     member_1: "Hello, World!".into(),
+//!     member_2: 8.into(),
 //!     extension: None
 //!   };
 //!   Uper::encode(example_sequence).unwrap()
@@ -130,6 +131,12 @@ pub trait DecoderForKey<'a, I: AsBytes + Debug + 'a, T> {
 
 pub trait Decoder<'a, I: AsBytes + Debug + 'a> {
     fn decode_open_type(input: I) -> IResult<I, Vec<u8>>;
+    /// Decodes an open type whose contents are decoded by `decoder`,
+    /// e.g. an open type whose actual type is selected by an information object set
+    fn decode_open_type_with<T>(input: I, decoder: fn(I) -> IResult<I, T>) -> IResult<I, T>;
+    /// Decodes a complete value from `contents`,
+    /// e.g. from an OCTET STRING that is constrained to CONTAIN the value's type
+    fn decode_contents<T: Decode<'a, I>>(contents: &'a [u8]) -> Result<T, DecodingError<I>>;
     fn decode_integer<O>(
         integer: Integer,
     ) -> Result<Box<dyn Fn(I) -> IResult<I, O>>, DecodingError<I>>
@@ -143,6 +150,8 @@ pub trait Decoder<'a, I: AsBytes + Debug + 'a> {
     ) -> Result<Box<dyn Fn(I) -> IResult<I, O>>, DecodingError<I>>;
     fn decode_null<N: Default>(input: I) -> IResult<I, N>;
     fn decode_boolean(input: I) -> IResult<I, bool>;
+    fn decode_object_identifier(input: I) -> IResult<I, Vec<u32>>;
+    fn decode_real(input: I) -> IResult<I, f64>;
     fn decode_bit_string(
         bit_string: BitString,
     ) -> Result<Box<dyn Fn(I) -> IResult<I, Vec<bool>>>, DecodingError<I>>;
@@ -157,7 +166,7 @@ pub trait Decoder<'a, I: AsBytes + Debug + 'a> {
     ) -> Result<Box<dyn Fn(I) -> IResult<I, T>>, DecodingError<I>>;
     fn decode_sequence_of<T: Decode<'a, I> + 'a + Sized>(
         sequence_of: SequenceOf,
-        member_decoder: fn(I) -> IResult<I, T>,
+        member_decoder: impl Fn(I) -> IResult<I, T> + 'a,
     ) -> Result<Box<dyn Fn(I) -> IResult<I, Vec<T>> + 'a>, DecodingError<I>>;
     fn decode_unknown_extension(input: I) -> IResult<I, Vec<u8>>;
 }
@@ -169,6 +178,8 @@ pub trait Encoder<T, O: Extend<T> + Debug + 'static> {
     where
         I: num::Integer + num::ToPrimitive + num::FromPrimitive + Copy;
     fn encode_boolean(value: bool, output: O) -> Result<O, EncodingError>;
+    fn encode_object_identifier(arcs: &[u32], output: O) -> Result<O, EncodingError>;
+    fn encode_real(value: f64, output: O) -> Result<O, EncodingError>;
     fn encode_null(output: O) -> Result<O, EncodingError>;
     fn encode_bit_string(
         bit_string: BitString,
@@ -192,4 +203,13 @@ pub trait Encoder<T, O: Extend<T> + Debug + 'static> {
         sequence_of: SequenceOf,
     ) -> Result<Box<dyn Fn(Vec<M>, O) -> Result<O, EncodingError>>, EncodingError>;
     fn encode_open_type(input: &[u8], output: O) -> Result<O, EncodingError>;
+    /// Encodes `value` with `encoder` as the contents of an open type
+    fn encode_open_type_with<V>(
+        value: &V,
+        encoder: fn(&V, O) -> Result<O, EncodingError>,
+        output: O,
+    ) -> Result<O, EncodingError>;
+    /// Encodes `value` as a complete encoding,
+    /// e.g. as the contents of an OCTET STRING that is constrained to CONTAIN the value's type
+    fn encode_contents<V: Encode<T, O>>(value: V) -> Result<Vec<u8>, EncodingError>;
 }
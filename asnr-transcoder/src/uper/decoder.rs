@@ -1,4 +1,4 @@
-use alloc::{boxed::Box, string::String, vec, vec::Vec};
+use alloc::{boxed::Box, format, string::String, vec, vec::Vec};
 use asnr_grammar::{
     encoding_rules::per_visible::{
         per_visible_range_constraints, PerVisibleAlphabetConstraints, PerVisibleRangeConstraints,
@@ -6,7 +6,6 @@ use asnr_grammar::{
     types::SequenceOrSet,
 };
 use bitvec::{bitvec, field::BitField, prelude::Msb0, vec::BitVec};
-use bitvec_nom::BSlice;
 use nom::{bytes::complete::take, combinator::map, error::Error, AsBytes};
 use num::{FromPrimitive, Integer};
 
@@ -45,7 +44,7 @@ impl LengthDeterminant {
                         input: Some(input),
                         kind: DecodingErrorType::GenericParsingError,
                     })?),
-                    |res: BitIn| temp.extend_from_bitslice(res.0),
+                    |res: BitIn| temp.extend_from_bitslice(&res),
                 )(input)?
                 .0;
                 Ok((input, temp))
@@ -57,7 +56,7 @@ impl LengthDeterminant {
                         input: Some(input),
                         kind: DecodingErrorType::GenericParsingError,
                     })?),
-                    |res: BitIn| temp.extend_from_bitslice(res.0),
+                    |res: BitIn| temp.extend_from_bitslice(&res),
                 )(input)?
                 .0;
                 let (input, length_det) = decode_length_determinant(input)?;
@@ -80,6 +79,31 @@ impl<'a> Decoder<'a, BitIn<'a>> for Uper {
         }
     }
 
+    fn decode_contents<T: Decode<'a, BitIn<'a>>>(
+        contents: &'a [u8],
+    ) -> Result<T, DecodingError<BitIn<'a>>> {
+        Uper::decode(contents)
+    }
+
+    fn decode_open_type_with<T>(
+        input: BitIn<'a>,
+        decoder: fn(BitIn<'a>) -> IResult<BitIn<'a>, T>,
+    ) -> IResult<BitIn<'a>, T> {
+        let (input, ext_length) = decode_length_determinant(input)?;
+        match ext_length {
+            LengthDeterminant::Content(size) => {
+                let (input, contents) = take(size * 8)(input)?;
+                // the contents are padded to full octets, so the decoder may leave padding bits
+                decoder(contents).map(|(_, value)| (input, value))
+            }
+            LengthDeterminant::ContentFragment(_) => Err(DecodingError {
+                input: Some(input),
+                details: "Open type payloads larger than 65536 bits are not supported yet!".into(),
+                kind: DecodingErrorType::Unsupported,
+            }),
+        }
+    }
+
     fn decode_integer<O>(
         integer: asnr_grammar::types::Integer,
     ) -> Result<Box<dyn Fn(BitIn<'a>) -> IResult<BitIn<'a>, O>>, DecodingError<BitIn<'a>>>
@@ -120,21 +144,35 @@ impl<'a> Decoder<'a, BitIn<'a>> for Uper {
         for c in &enumerated.constraints {
             constraints += c.try_into()?
         }
+        // enumerals are encoded by their position in the list of enumerals sorted by index
+        let mut indices = enumerated
+            .members
+            .iter()
+            .map(|m| m.index)
+            .collect::<Vec<i128>>();
+        indices.sort();
         if constraints.is_extensible() {
             if let Some(bit_length) = constraints.bit_length() {
                 Ok(Box::new(move |input: BitIn| -> IResult<BitIn, O> {
                     let (input, is_extended) = read_bit(input)?;
                     if is_extended {
                         let (input, i) = decode_normally_small_number(input)?;
-                        let index = O::try_from((i + enumerated.extensible.unwrap()) as i128)
-                            .map_err(|_| DecodingError {
-                                details: "Failed to convert index to generic integer type.".into(),
+                        let position = i + enumerated.extensible.unwrap();
+                        if position < indices.len() {
+                            enum_index_into(&indices, position, input)
+                        } else {
+                            // unknown extension additions are converted with an index
+                            // beyond all known indices, yielding the unknown extension
+                            let unknown = indices.last().map_or(0, |i| i + 1) + position as i128;
+                            let index = O::try_from(unknown).map_err(|_| DecodingError {
+                                details: format!("Unknown extension at position {position}"),
                                 input: Some(input),
                                 kind: DecodingErrorType::GenericParsingError,
                             })?;
-                        Ok((input, index))
+                            Ok((input, index))
+                        }
                     } else {
-                        decode_enum_index_into(bit_length, input)
+                        decode_enum_index_into(bit_length, &indices, input)
                     }
                 }))
             } else {
@@ -143,7 +181,7 @@ impl<'a> Decoder<'a, BitIn<'a>> for Uper {
         } else {
             if let Some(bit_length) = constraints.bit_length() {
                 Ok(Box::new(move |input: BitIn| {
-                    decode_enum_index_into(bit_length, input)
+                    decode_enum_index_into(bit_length, &indices, input)
                 }))
             } else {
                 unreachable!()
@@ -166,8 +204,16 @@ impl<'a> Decoder<'a, BitIn<'a>> for Uper {
                         let mut index;
                         (input, index) = decode_normally_small_number(input)?;
                         index = index + choice.extensible.unwrap();
-                        let (mut inner_input, ext_length) =
-                            decode_varlength_integer::<usize>(input, Some(0))?;
+                        let (mut inner_input, ext_length) = match decode_length_determinant(input)? {
+                            (i, LengthDeterminant::Content(size)) => (i, size),
+                            (i, LengthDeterminant::ContentFragment(_)) => {
+                                return Err(DecodingError {
+                                    input: Some(i),
+                                    details: "Extension alternatives larger than 16383 octets are not supported yet!".into(),
+                                    kind: DecodingErrorType::Unsupported,
+                                })
+                            }
+                        };
                         (input, inner_input) =
                             take(usize::try_from(8 * ext_length).map_err(|_| DecodingError {
                                 details: "Failed to cast to usize.".into(),
@@ -219,13 +265,13 @@ impl<'a> Decoder<'a, BitIn<'a>> for Uper {
                 move |input: BitIn<'a>| -> IResult<BitIn<'a>, Vec<bool>> {
                     let (input, is_extended) = read_bit(input)?;
                     let (input, length_det) = size_length_det(is_extended, &constraints, input)?;
-                    n_times(input, read_bit, length_det)
+                    n_times(input, &read_bit, length_det)
                 },
             ))
         } else {
             Ok(Box::new(move |input| {
                 let (input, length_det) = size_length_det(false, &constraints, input)?;
-                n_times(input, read_bit, length_det)
+                n_times(input, &read_bit, length_det)
             }))
         }
     }
@@ -289,6 +335,7 @@ impl<'a> Decoder<'a, BitIn<'a>> for Uper {
         sequence: SequenceOrSet,
     ) -> Result<Box<dyn Fn(BitIn<'a>) -> IResult<BitIn, T>>, DecodingError<BitIn<'a>>> {
         if let Some(extension_index) = sequence.extensible {
+            let known_extensions = sequence.members.len().saturating_sub(extension_index);
             Ok(Box::new(move |input| {
                 let (input, is_extended) = read_bit(input)?;
                 let (mut input, mut instance) = decode_unextended_sequence::<T>(&sequence, input)?;
@@ -314,10 +361,17 @@ impl<'a> Decoder<'a, BitIn<'a>> for Uper {
                                                 kind: DecodingErrorType::GenericParsingError,
                                             }
                                         })?)(inner_input)?;
-                                    let _ = instance.decode_member_at_index::<Uper>(
-                                        index + extension_index,
-                                        inner_input,
-                                    )?;
+                                    if index < known_extensions {
+                                        let remaining = instance.decode_member_at_index::<Uper>(
+                                            index + extension_index,
+                                            inner_input,
+                                        )?;
+                                        // the open type content is padded to a full octet
+                                        skip_to_byte_alignment(remaining)?;
+                                    } else {
+                                        // skip extensions that are unknown to this version of the type
+                                        let _ = Self::decode_unknown_extension(inner_input)?;
+                                    }
                                 }
                                 LengthDeterminant::ContentFragment(_) => {
                                     todo!()
@@ -340,7 +394,7 @@ impl<'a> Decoder<'a, BitIn<'a>> for Uper {
 
     fn decode_sequence_of<T: Decode<'a, BitIn<'a>> + 'a>(
         sequence_of: asnr_grammar::types::SequenceOf,
-        member_decoder: fn(BitIn<'a>) -> IResult<BitIn<'a>, T>,
+        member_decoder: impl Fn(BitIn<'a>) -> IResult<BitIn<'a>, T> + 'a,
     ) -> Result<Box<dyn Fn(BitIn<'a>) -> IResult<BitIn<'a>, Vec<T>> + 'a>, DecodingError<BitIn<'a>>>
     {
         let constraints = per_visible_range_constraints(false, &sequence_of.constraints)?;
@@ -349,14 +403,14 @@ impl<'a> Decoder<'a, BitIn<'a>> for Uper {
                 move |input: BitIn<'a>| -> IResult<BitIn<'a>, Vec<T>> {
                     let (input, is_extended) = read_bit(input)?;
                     let (input, length_det) = size_length_det(is_extended, &constraints, input)?;
-                    n_times(input, member_decoder, length_det)
+                    n_times(input, &member_decoder, length_det)
                 },
             ))
         } else {
             Ok(Box::new(
                 move |input: BitIn<'a>| -> IResult<BitIn<'a>, Vec<T>> {
                     let (input, length_det) = size_length_det(false, &constraints, input)?;
-                    n_times(input, member_decoder, length_det)
+                    n_times(input, &member_decoder, length_det)
                 },
             ))
         }
@@ -365,15 +419,130 @@ impl<'a> Decoder<'a, BitIn<'a>> for Uper {
     fn decode_unknown_extension(input: BitIn<'a>) -> IResult<BitIn<'a>, Vec<u8>> {
         bitslice_to_bytes(input.len() / 8, input)
     }
+
+    fn decode_object_identifier(input: BitIn<'a>) -> IResult<BitIn<'a>, Vec<u32>> {
+        let (remaining, contents) = Self::decode_open_type(input)?;
+        match object_identifier_arcs(&contents) {
+            Some(arcs) => Ok((remaining, arcs)),
+            None => Err(DecodingError {
+                input: Some(input),
+                details: format!("Invalid OBJECT IDENTIFIER contents {contents:?}"),
+                kind: DecodingErrorType::GenericParsingError,
+            }),
+        }
+    }
+
+    fn decode_real(input: BitIn<'a>) -> IResult<BitIn<'a>, f64> {
+        let (remaining, contents) = Self::decode_open_type(input)?;
+        match real_value(&contents) {
+            Some(value) => Ok((remaining, value)),
+            None => Err(DecodingError {
+                input: Some(input),
+                details: format!("Invalid REAL contents {contents:?}"),
+                kind: DecodingErrorType::GenericParsingError,
+            }),
+        }
+    }
 }
 
-fn bitslice_to_bytes(
-    length_det: usize,
-    mut input: BSlice<'_, u8, Msb0>,
-) -> Result<(BSlice<'_, u8, Msb0>, Vec<u8>), DecodingError<BSlice<'_, u8, Msb0>>> {
+/// Decodes a REAL from its contents octets as specified in X.690 §8.5.
+/// Returns `None` if the contents are malformed.
+fn real_value(contents: &[u8]) -> Option<f64> {
+    let (first, rest) = match contents.split_first() {
+        Some(split) => split,
+        None => return Some(0.0),
+    };
+    match first >> 6 {
+        // Binary encoding
+        0b10 | 0b11 => {
+            let base_exponent = match (first >> 4) & 0b11 {
+                0b00 => 1,
+                0b01 => 3,
+                0b10 => 4,
+                _ => return None,
+            };
+            let scaling_factor = i32::from((first >> 2) & 0b11);
+            let (exponent_length, rest) = match first & 0b11 {
+                0b11 => (usize::from(*rest.first()?), rest.get(1..)?),
+                l => (usize::from(l) + 1, rest),
+            };
+            if exponent_length == 0 || exponent_length > 4 || rest.len() <= exponent_length {
+                return None;
+            }
+            let (exponent_octets, mantissa_octets) = rest.split_at(exponent_length);
+            let exponent = exponent_octets.iter().skip(1).fold(
+                i32::from(exponent_octets[0] as i8),
+                |exponent, octet| (exponent << 8) | i32::from(*octet),
+            );
+            let mantissa = mantissa_octets
+                .iter()
+                .fold(0.0, |mantissa, octet| mantissa * 256.0 + f64::from(*octet));
+            let value = scale_by_power_of_two(
+                mantissa,
+                exponent
+                    .checked_mul(base_exponent)?
+                    .checked_add(scaling_factor)?,
+            );
+            Some(if first & 0x40 == 0 { value } else { -value })
+        }
+        // Decimal encoding in one of the ISO 6093 number representations
+        0b00 => core::str::from_utf8(rest)
+            .ok()?
+            .trim()
+            .replace(',', ".")
+            .parse::<f64>()
+            .ok(),
+        // Special real values
+        _ => match first {
+            0x40 => Some(f64::INFINITY),
+            0x41 => Some(f64::NEG_INFINITY),
+            0x42 => Some(f64::NAN),
+            0x43 => Some(-0.0),
+            _ => None,
+        },
+    }
+}
+
+/// Multiplies `value` by 2^`exponent` in steps that stay within the range of normal f64 values
+fn scale_by_power_of_two(mut value: f64, mut exponent: i32) -> f64 {
+    while exponent != 0 && value != 0.0 && value.is_finite() {
+        let step = exponent.clamp(-1000, 1000);
+        value *= f64::from_bits(((1023 + step) as u64) << 52);
+        exponent -= step;
+    }
+    value
+}
+
+/// Decodes the arcs of an OBJECT IDENTIFIER from its contents octets as specified in X.690 §8.19.
+/// Returns `None` if the contents are malformed or an arc does not fit into u32.
+fn object_identifier_arcs(contents: &[u8]) -> Option<Vec<u32>> {
+    let mut subidentifiers = vec![];
+    let mut subidentifier: u64 = 0;
+    for byte in contents {
+        subidentifier = subidentifier.checked_mul(128)? | u64::from(byte & 0x7f);
+        if byte & 0x80 == 0 {
+            subidentifiers.push(subidentifier);
+            subidentifier = 0;
+        }
+    }
+    if contents.last().map_or(true, |byte| byte & 0x80 != 0) {
+        return None;
+    }
+    let (first, second) = match subidentifiers[0] {
+        s if s < 80 => (s / 40, s % 40),
+        s => (2, s - 80),
+    };
+    [first, second]
+        .into_iter()
+        .chain(subidentifiers.into_iter().skip(1))
+        .map(|arc| u32::try_from(arc).ok())
+        .collect()
+}
+
+fn bitslice_to_bytes(length_det: usize, mut input: BitIn) -> IResult<BitIn, Vec<u8>> {
     let mut bytes = vec![];
     for _ in 0..length_det {
-        let (new_input, byte) = map(take(8_usize), |bits: BitIn| bits.load_be::<u8>())(input)?;
+        let (new_input, byte) = read_byte(input)?;
         input = new_input;
         bytes.push(byte);
     }
@@ -435,14 +604,28 @@ fn decode_unextended_sequence<'a, T: DecodeMember<'a, BitIn<'a>> + Default>(
 
 fn decode_enum_index_into<'a, O: TryFrom<i128>>(
     bit_length: usize,
+    indices: &[i128],
     input: BitIn<'a>,
 ) -> IResult<BitIn<'a>, O> {
-    let (input, i) = read_int::<i128>(bit_length)(input)?;
-    let index = O::try_from(i).map_err(|_| DecodingError {
-        input: Some(input),
-        details: "Failed to convert index to generic integer type.".into(),
-        kind: DecodingErrorType::GenericParsingError,
-    })?;
+    let (input, i) = read_int::<usize>(bit_length)(input)?;
+    enum_index_into(indices, i, input)
+}
+
+/// Converts the position of an enumeral in the list of enumerals sorted by index
+/// into the enumeral's index
+fn enum_index_into<'a, O: TryFrom<i128>>(
+    indices: &[i128],
+    position: usize,
+    input: BitIn<'a>,
+) -> IResult<BitIn<'a>, O> {
+    let index = indices
+        .get(position)
+        .and_then(|i| O::try_from(*i).ok())
+        .ok_or_else(|| DecodingError {
+            input: Some(input),
+            details: format!("No enumeral at position {position} among the indices {indices:?}"),
+            kind: DecodingErrorType::GenericParsingError,
+        })?;
     Ok((input, index))
 }
 
@@ -502,7 +685,18 @@ fn decode_sized_string<'a>(
                 kind: DecodingErrorType::GenericParsingError,
             })?,
         )(input)?;
-    if permitted_alphabet.is_known_multiplier_string() {
+    if permitted_alphabet.is_raw_encoded() {
+        let mut char_vec = vec![];
+        while let Ok((new_buffer, i)) = read_int::<u32>(bit_size)(buffer) {
+            char_vec.push(char::from_u32(i).ok_or(DecodingError {
+                details: "Invalid code point in character string.".into(),
+                input: Some(input),
+                kind: DecodingErrorType::GenericParsingError,
+            })?);
+            buffer = new_buffer;
+        }
+        Ok((input, char_vec.into_iter().collect()))
+    } else if permitted_alphabet.is_known_multiplier_string() {
         let mut char_vec = vec![];
         while let Ok((new_buffer, i)) = read_int::<usize>(bit_size)(buffer) {
             char_vec.push(permitted_alphabet.get_char_by_index(i)?);
@@ -571,16 +765,34 @@ fn decode_length_determinant(input: BitIn) -> IResult<BitIn, LengthDeterminant>
         if longer_than_15999 {
             let (input, size_factor) = read_int::<usize>(6)(input)?;
             //TODO: Check that size factor is in range 1..=4
-            return Ok((
-                input,
-                LengthDeterminant::ContentFragment(16384 * size_factor),
-            ));
+            let size = check_length(16384 * size_factor, input)?;
+            return Ok((input, LengthDeterminant::ContentFragment(size)));
         }
         let (input, size) = read_int::<usize>(14)(input)?;
-        return Ok((input, LengthDeterminant::Content(size)));
+        return Ok((
+            input,
+            LengthDeterminant::Content(check_length(size, input)?),
+        ));
     }
     let (input, size) = read_int::<usize>(7)(input)?;
-    Ok((input, LengthDeterminant::Content(size)))
+    Ok((
+        input,
+        LengthDeterminant::Content(check_length(size, input)?),
+    ))
+}
+
+fn check_length(size: usize, input: BitIn) -> Result<usize, DecodingError<BitIn>> {
+    let max_length = input.max_length();
+    if size > max_length {
+        return Err(DecodingError {
+            details: format!(
+                "Length determinant {size} exceeds the configured maximum of {max_length}."
+            ),
+            input: Some(input),
+            kind: DecodingErrorType::SizeLimitExceeded,
+        });
+    }
+    Ok(size)
 }
 
 fn read_bit(input: BitIn) -> IResult<BitIn, bool> {
@@ -588,15 +800,29 @@ fn read_bit(input: BitIn) -> IResult<BitIn, bool> {
     Ok((input, bool_buffer[0]))
 }
 
+/// Consumes the padding that completes the content of an open type to a full octet.
+/// Fails if the content is followed by more than seven bits or if a padding bit is set.
+fn skip_to_byte_alignment(input: BitIn) -> IResult<BitIn, ()> {
+    if input.len() > 7 || input.any() {
+        return Err(DecodingError {
+            details: format!("Expected up to seven zero padding bits, found {}.", input.len()),
+            input: Some(input),
+            kind: DecodingErrorType::GenericParsingError,
+        });
+    }
+    let (input, _) = take(input.len())(input)?;
+    Ok((input, ()))
+}
+
 fn read_int<O>(bits: usize) -> impl FnMut(BitIn) -> IResult<BitIn, O>
 where
     O: Integer + FromPrimitive,
 {
     move |input| {
-        let (input, int_buffer) = take(bits)(input)?;
+        let (input, int) = read_bits_as_u64(bits, input)?;
         Ok((
             input,
-            O::from_u64(bits_to_int(int_buffer)).ok_or(DecodingError {
+            O::from_u64(int).ok_or(DecodingError {
                 details: "Failed to convert index to generic integer type.".into(),
                 input: Some(input),
                 kind: DecodingErrorType::GenericParsingError,
@@ -607,7 +833,7 @@ where
 
 fn n_times<'a, T>(
     input: BitIn<'a>,
-    parser: fn(BitIn<'a>) -> IResult<BitIn<'a>, T>,
+    parser: &impl Fn(BitIn<'a>) -> IResult<BitIn<'a>, T>,
     n: usize,
 ) -> IResult<BitIn<'a>, Vec<T>> {
     let mut vector = vec![];
@@ -620,15 +846,33 @@ fn n_times<'a, T>(
     Ok((input, vector))
 }
 
+/// Reads the next eight bits as a byte
+fn read_byte(input: BitIn) -> IResult<BitIn, u8> {
+    let (input, bits) = take(8_usize)(input)?;
+    Ok((input, bits.load_be::<u8>()))
+}
+
+/// Reads the next `n` bits as an unsigned integer. Supports up to 64 bits.
+fn read_bits_as_u64(n: usize, input: BitIn) -> IResult<BitIn, u64> {
+    if n > 64 {
+        return Err(DecodingError {
+            details: format!("Cannot read {n} bits into a 64-bit integer."),
+            input: Some(input),
+            kind: DecodingErrorType::Unsupported,
+        });
+    }
+    let (input, bits) = take(n)(input)?;
+    Ok((input, bits_to_int(bits)))
+}
+
+/// Loads up to 64 bits as a big-endian unsigned integer.
+/// An empty slice, e.g. of a single-valued constrained integer, loads as zero.
 fn bits_to_int(input: BitIn) -> u64 {
-    let mut int = 0;
-    for bit in input.0 {
-        int = int << 1;
-        if bit == true {
-            int += 1;
-        }
+    if input.is_empty() {
+        0
+    } else {
+        input.load_be::<u64>()
     }
-    return int;
 }
 
 macro_rules! int_from_bytes {
@@ -681,23 +925,72 @@ mod tests {
 
     use alloc::{format, vec};
     use bitvec::prelude::*;
-    use bitvec_nom::BSlice;
     use core::fmt::Debug;
 
     use crate::uper::decoder::*;
     use asnr_grammar::{constraints::*, types::Integer, *};
 
+    #[test]
+    fn decodes_object_identifier() {
+        let encoded = bitvec![u8, Msb0; 0, 0, 0, 0, 0, 0, 1, 0, 0, 1, 0, 0, 1, 1, 0, 1, 0, 0, 0, 0, 0, 0, 1, 1];
+        assert_eq!(
+            Uper::decode_object_identifier(BitIn::from(encoded.as_bitslice()))
+                .unwrap()
+                .1,
+            vec![1, 37, 3]
+        );
+        assert_eq!(object_identifier_arcs(&[0x88, 0x37]), Some(vec![2, 999]));
+        assert_eq!(object_identifier_arcs(&[0x88]), None);
+        assert_eq!(object_identifier_arcs(&[]), None);
+    }
+
+    #[test]
+    fn decodes_real() {
+        let encoded = bitvec![u8, Msb0; 0, 0, 0, 0, 0, 0, 1, 1, 1, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 1];
+        assert_eq!(
+            Uper::decode_real(BitIn::from(encoded.as_bitslice()))
+                .unwrap()
+                .1,
+            1.0
+        );
+        assert_eq!(real_value(&[0xC0, 0xFE, 0x03]), Some(-0.75));
+        assert_eq!(real_value(&[0x90, 0x01, 0x03]), Some(24.0));
+        assert_eq!(real_value(&[0x03, b'1', b'2', b',', b'5']), Some(12.5));
+        assert_eq!(real_value(&[]), Some(0.0));
+        assert_eq!(real_value(&[0x41]), Some(f64::NEG_INFINITY));
+        assert!(real_value(&[0x42]).unwrap().is_nan());
+        assert_eq!(real_value(&[0x80]), None);
+        assert_eq!(real_value(&[0x44]), None);
+    }
+
     #[test]
     fn bit_to_int() {
         let bits = bits![u8, Msb0; 1, 0, 1];
-        assert_eq!(5u64, bits_to_int(BSlice::from(bits)))
+        assert_eq!(5u64, bits_to_int(BitIn::from(bits)))
+    }
+
+    #[test]
+    fn reads_bytes_and_bits_as_integers() {
+        let bytes = [0b1010_0101, 0xff, 0x01];
+        let (input, byte) = read_byte(BitIn::from(bytes.view_bits::<Msb0>())).unwrap();
+        assert_eq!(byte, 0b1010_0101);
+        let (input, int) = read_bits_as_u64(12, input).unwrap();
+        assert_eq!(int, 0xff0);
+        let (input, int) = read_bits_as_u64(0, input).unwrap();
+        assert_eq!(int, 0);
+        assert_eq!(input.len(), 4);
+        assert!(read_byte(input).is_err());
+        let bytes = [0xff; 9];
+        let input = BitIn::from(bytes.view_bits::<Msb0>());
+        assert_eq!(read_bits_as_u64(64, input).unwrap().1, u64::MAX);
+        assert!(read_bits_as_u64(65, input).is_err());
     }
 
     #[test]
     fn decodes_varlength_integer() {
         assert_eq!(
             decode_varlength_integer::<i128>(
-                BSlice::from(bits![u8, Msb0; 0,0,0,0,0,0,1,0,0,0,0,1,0,0,0,0,0,0,0,0,0,0,0,0]),
+                BitIn::from(bits![u8, Msb0; 0,0,0,0,0,0,1,0,0,0,0,1,0,0,0,0,0,0,0,0,0,0,0,0]),
                 None
             )
             .unwrap()
@@ -706,7 +999,7 @@ mod tests {
         );
         assert_eq!(
             decode_varlength_integer::<i128>(
-                BSlice::from(bits![u8, Msb0; 0,0,0,0,0,0,0,1,0,1,1,1,1,1,1,1]),
+                BitIn::from(bits![u8, Msb0; 0,0,0,0,0,0,0,1,0,1,1,1,1,1,1,1]),
                 None
             )
             .unwrap()
@@ -715,7 +1008,7 @@ mod tests {
         );
         assert_eq!(
             decode_varlength_integer::<i128>(
-                BSlice::from(bits![u8, Msb0; 0,0,0,0,0,0,0,1,1,0,0,0,0,0,0,0]),
+                BitIn::from(bits![u8, Msb0; 0,0,0,0,0,0,0,1,1,0,0,0,0,0,0,0]),
                 None
             )
             .unwrap()
@@ -724,7 +1017,7 @@ mod tests {
         );
         assert_eq!(
             decode_varlength_integer::<i128>(
-                BSlice::from(bits![u8, Msb0; 0,0,0,0,0,0,1,0,0,0,0,0,0,0,0,0,1,0,0,0,0,0,0,0]),
+                BitIn::from(bits![u8, Msb0; 0,0,0,0,0,0,1,0,0,0,0,0,0,0,0,0,1,0,0,0,0,0,0,0]),
                 None
             )
             .unwrap()
@@ -733,7 +1026,7 @@ mod tests {
         );
         assert_eq!(
             decode_varlength_integer::<i128>(
-                BSlice::from(bits![u8, Msb0; 0,0,0,0,0,0,1,0,0,0,0,1,0,0,0,0,0,0,0,0,0,0,0,1]),
+                BitIn::from(bits![u8, Msb0; 0,0,0,0,0,0,1,0,0,0,0,1,0,0,0,0,0,0,0,0,0,0,0,1]),
                 Some(-1)
             )
             .unwrap()
@@ -742,7 +1035,7 @@ mod tests {
         );
         assert_eq!(
             decode_varlength_integer::<i128>(
-                BSlice::from(bits![u8, Msb0; 0,0,0,0,0,0,0,1,0,1,1,1,1,1,1,0]),
+                BitIn::from(bits![u8, Msb0; 0,0,0,0,0,0,0,1,0,1,1,1,1,1,1,0]),
                 Some(1)
             )
             .unwrap()
@@ -751,7 +1044,7 @@ mod tests {
         );
         assert_eq!(
             decode_varlength_integer::<i128>(
-                BSlice::from(bits![u8, Msb0; 0,0,0,0,0,0,0,1,1,0,0,0,0,0,0,0]),
+                BitIn::from(bits![u8, Msb0; 0,0,0,0,0,0,0,1,1,0,0,0,0,0,0,0]),
                 Some(0)
             )
             .unwrap()
@@ -775,27 +1068,19 @@ mod tests {
         })
         .unwrap();
         assert_eq!(
-            decoder(BSlice::from(bits![static u8, Msb0; 0,0]))
-                .unwrap()
-                .1,
+            decoder(BitIn::from(bits![static u8, Msb0; 0,0])).unwrap().1,
             3
         );
         assert_eq!(
-            decoder(BSlice::from(bits![static u8, Msb0; 0,1]))
-                .unwrap()
-                .1,
+            decoder(BitIn::from(bits![static u8, Msb0; 0,1])).unwrap().1,
             4
         );
         assert_eq!(
-            decoder(BSlice::from(bits![static u8, Msb0; 1,0]))
-                .unwrap()
-                .1,
+            decoder(BitIn::from(bits![static u8, Msb0; 1,0])).unwrap().1,
             5
         );
         assert_eq!(
-            decoder(BSlice::from(bits![static u8, Msb0; 1,1]))
-                .unwrap()
-                .1,
+            decoder(BitIn::from(bits![static u8, Msb0; 1,1])).unwrap().1,
             6
         );
         decoder = Uper::decode_integer::<i128>(Integer {
@@ -811,13 +1096,13 @@ mod tests {
         })
         .unwrap();
         assert_eq!(
-            decoder(BSlice::from(bits![static u8, Msb0; 0,0,0,0,0,0,1,0]))
+            decoder(BitIn::from(bits![static u8, Msb0; 0,0,0,0,0,0,1,0]))
                 .unwrap()
                 .1,
             4002
         );
         assert_eq!(
-            decoder(BSlice::from(bits![static u8, Msb0; 0,0,0,0,0,1,1,0]))
+            decoder(BitIn::from(bits![static u8, Msb0; 0,0,0,0,0,1,1,0]))
                 .unwrap()
                 .1,
             4006
@@ -835,7 +1120,7 @@ mod tests {
         })
         .unwrap();
         assert_eq!(
-            decoder(BSlice::from(
+            decoder(BitIn::from(
                 bits![static u8, Msb0; 1,0,0,0,0,0,0,0,0,0,0,0,0,0,0,0,1]
             ))
             .unwrap()
@@ -851,14 +1136,14 @@ mod tests {
         let decoder = TestInt::decoder::<Uper>().unwrap();
 
         assert_eq!(
-            decoder(BSlice::from(bits![static u8, Msb0; 0,0,1]))
+            decoder(BitIn::from(bits![static u8, Msb0; 0,0,1]))
                 .unwrap()
                 .1
                  .0,
             4
         );
         assert_eq!(
-            decoder(BSlice::from(
+            decoder(BitIn::from(
                 bits![static u8, Msb0; 1, 0,0,0,0,0,0,0,1, 0,0,0,0,0,1,1,1]
             ))
             .unwrap()
@@ -878,25 +1163,43 @@ mod tests {
 
         let decoder = TestEnum::decoder::<Uper>().unwrap();
         assert_eq!(
-            decoder(BSlice::from(bits![static u8, Msb0; 0,0]))
-                .unwrap()
-                .1,
+            decoder(BitIn::from(bits![static u8, Msb0; 0,0])).unwrap().1,
             TestEnum::One
         );
         assert_eq!(
-            decoder(BSlice::from(bits![static u8, Msb0; 0,1]))
-                .unwrap()
-                .1,
+            decoder(BitIn::from(bits![static u8, Msb0; 0,1])).unwrap().1,
             TestEnum::Two
         );
         assert_eq!(
-            decoder(BSlice::from(bits![static u8, Msb0; 1,0]))
-                .unwrap()
-                .1,
+            decoder(BitIn::from(bits![static u8, Msb0; 1,0])).unwrap().1,
             TestEnum::Three
         );
     }
 
+    #[test]
+    fn decodes_sparse_enum() {
+        asn1!(
+            "TestEnum ::= ENUMERATED { a(1000000), b(1), c(-20) }",
+            Framework::Asnr,
+            crate
+        );
+
+        let decoder = TestEnum::decoder::<Uper>().unwrap();
+        assert_eq!(
+            decoder(BitIn::from(bits![static u8, Msb0; 0,0])).unwrap().1,
+            TestEnum::C
+        );
+        assert_eq!(
+            decoder(BitIn::from(bits![static u8, Msb0; 0,1])).unwrap().1,
+            TestEnum::B
+        );
+        assert_eq!(
+            decoder(BitIn::from(bits![static u8, Msb0; 1,0])).unwrap().1,
+            TestEnum::A
+        );
+        assert!(decoder(BitIn::from(bits![static u8, Msb0; 1,1])).is_err());
+    }
+
     #[test]
     fn decodes_extended_enum() {
         asn1!(
@@ -906,19 +1209,19 @@ mod tests {
         );
         let decoder = TestEnumExt::decoder::<Uper>().unwrap();
         assert_eq!(
-            decoder(BSlice::from(bits![static u8, Msb0; 0,0,0]))
+            decoder(BitIn::from(bits![static u8, Msb0; 0,0,0]))
                 .unwrap()
                 .1,
             TestEnumExt::One
         );
         assert_eq!(
-            decoder(BSlice::from(bits![static u8, Msb0; 1,0,0,0,0,0,0,0]))
+            decoder(BitIn::from(bits![static u8, Msb0; 1,0,0,0,0,0,0,0]))
                 .unwrap()
                 .1,
             TestEnumExt::Three
         );
         assert_eq!(
-            decoder(BSlice::from(bits![static u8, Msb0; 1,0,0,0,0,0,1,1]))
+            decoder(BitIn::from(bits![static u8, Msb0; 1,0,0,0,0,0,1,1]))
                 .unwrap()
                 .1,
             TestEnumExt::UnknownExtension
@@ -938,7 +1241,7 @@ mod tests {
         );
 
         assert_eq!(
-            TestSequence::decode::<Uper>(BSlice::from(bits![static u8, Msb0; 
+            TestSequence::decode::<Uper>(BitIn::from(bits![static u8, Msb0; 
               1,0,
               0,0,0,1,1,0,1,1,
               0,1,1,
@@ -971,7 +1274,7 @@ mod tests {
         );
 
         assert_eq!(
-            TestSequence::decode::<Uper>(BSlice::from(bits![static u8, Msb0; 
+            TestSequence::decode::<Uper>(BitIn::from(bits![static u8, Msb0; 
             1,
             1,
             0,0,0,1,1,0,1,1,
@@ -1004,13 +1307,13 @@ mod tests {
             crate
         );
         assert_eq!(
-            ChoiceExample::decode::<Uper>(BSlice::from(bits![static u8, Msb0; 0,0]))
+            ChoiceExample::decode::<Uper>(BitIn::from(bits![static u8, Msb0; 0,0]))
                 .unwrap()
                 .1,
             ChoiceExample::Normal(InnerChoiceExampleNormal)
         );
         assert_eq!(
-            ChoiceExample::decode::<Uper>(BSlice::from(bits![
+            ChoiceExample::decode::<Uper>(BitIn::from(bits![
                     static u8, Msb0; 
                     1,
                     0,0,0,0,0,0,0,0,0,0,0,0,0,0,1,0,0,0,0,0,0,0,0]))
@@ -1020,6 +1323,27 @@ mod tests {
         )
     }
 
+    #[test]
+    fn decodes_unknown_choice_extension() {
+        asn1!(
+            "Choice-example ::= CHOICE {normal NULL, high NULL, ..., medium NULL }",
+            Framework::Asnr,
+            crate
+        );
+        assert_eq!(
+            ChoiceExample::decode::<Uper>(BitIn::from(bits![
+                    static u8, Msb0;
+                    1,
+                    0,0,0,0,0,0,1,
+                    0,0,0,0,0,0,1,0,
+                    1,0,1,0,1,0,1,1,
+                    1,1,0,0,1,1,0,1]))
+            .unwrap()
+            .1,
+            ChoiceExample::UnknownChoiceValue(vec![0xAB, 0xCD])
+        )
+    }
+
     #[test]
     fn decodes_fixed_size_bit_string() {
         asn1!(
@@ -1028,7 +1352,7 @@ mod tests {
             crate
         );
         assert_eq!(
-            BitStringExample::decode::<Uper>(BSlice::from(bits![static u8, Msb0; 0,0,1,0,1,1,0,0]))
+            BitStringExample::decode::<Uper>(BitIn::from(bits![static u8, Msb0; 0,0,1,0,1,1,0,0]))
                 .unwrap()
                 .1,
             BitStringExample(vec![false, false, true, false, true, true, false, false])
@@ -1043,7 +1367,7 @@ mod tests {
             crate
         );
         assert_eq!(
-            BitStringExample::decode::<Uper>(BSlice::from(bits![static u8, Msb0; 0,0,1,0,0,1,0,1]))
+            BitStringExample::decode::<Uper>(BitIn::from(bits![static u8, Msb0; 0,0,1,0,0,1,0,1]))
                 .unwrap()
                 .1,
             BitStringExample(vec![false, false, true, false, true])
@@ -1058,7 +1382,7 @@ mod tests {
             crate
         );
         assert_eq!(
-            BitStringExample::decode::<Uper>(BSlice::from(
+            BitStringExample::decode::<Uper>(BitIn::from(
                 bits![static u8, Msb0; 0,0,0,0,0,1,0,1, 0,0,1,0,1]
             ))
             .unwrap()
@@ -1075,7 +1399,7 @@ mod tests {
             crate
         );
         assert_eq!(
-            BitStringExample::decode::<Uper>(BSlice::from(bits![static u8, Msb0; 0, 1, 1]))
+            BitStringExample::decode::<Uper>(BitIn::from(bits![static u8, Msb0; 0, 1, 1]))
                 .unwrap()
                 .1,
             BitStringExample(vec![true])
@@ -1086,7 +1410,7 @@ mod tests {
     fn decodes_unconstrained_bit_string() {
         asn1!("BitStringExample ::= BIT STRING", Framework::Asnr, crate);
         assert_eq!(
-            BitStringExample::decode::<Uper>(BSlice::from(
+            BitStringExample::decode::<Uper>(BitIn::from(
                 bits![static u8, Msb0; 0,0,0,0,0,1,0,1, 0,0,1,0,1]
             ))
             .unwrap()
@@ -1103,7 +1427,7 @@ mod tests {
             crate
         );
         assert_eq!(
-            BitStringExample::decode::<Uper>(BSlice::from(
+            BitStringExample::decode::<Uper>(BitIn::from(
                 bits![static u8, Msb0; 0,0,0,1,0,1,1,0,0]
             ))
             .unwrap()
@@ -1111,7 +1435,7 @@ mod tests {
             BitStringExample(vec![false, false, true, false, true, true, false, false])
         );
         assert_eq!(
-            BitStringExample::decode::<Uper>(BSlice::from(
+            BitStringExample::decode::<Uper>(BitIn::from(
                 bits![static u8, Msb0; 1, 0,0,0,0,0,0,0,1, 0]
             ))
             .unwrap()
@@ -1128,7 +1452,7 @@ mod tests {
             crate
         );
         assert_eq!(
-            BitStringExample::decode::<Uper>(BSlice::from(
+            BitStringExample::decode::<Uper>(BitIn::from(
                 bits![static u8, Msb0; 0,0,0,1,0,0,1,0,1]
             ))
             .unwrap()
@@ -1136,7 +1460,7 @@ mod tests {
             BitStringExample(vec![false, false, true, false, true])
         );
         assert_eq!(
-            BitStringExample::decode::<Uper>(BSlice::from(
+            BitStringExample::decode::<Uper>(BitIn::from(
                 bits![static u8, Msb0; 1,0,0,0,0,0,0,0,1,0]
             ))
             .unwrap()
@@ -1153,7 +1477,7 @@ mod tests {
             crate
         );
         assert_eq!(
-            BitStringExample::decode::<Uper>(BSlice::from(
+            BitStringExample::decode::<Uper>(BitIn::from(
                 bits![static u8, Msb0; 0, 0,0,0,0,0,1,0,1, 0,0,1,0,1]
             ))
             .unwrap()
@@ -1161,7 +1485,7 @@ mod tests {
             BitStringExample(vec![false, false, true, false, true])
         );
         assert_eq!(
-            BitStringExample::decode::<Uper>(BSlice::from(
+            BitStringExample::decode::<Uper>(BitIn::from(
                 bits![static u8, Msb0; 1,0,0,0,0,0,0,0,1,0]
             ))
             .unwrap()
@@ -1179,7 +1503,7 @@ mod tests {
         );
 
         assert_eq!(
-            NumericStringExample::decode::<Uper>(BSlice::from(
+            NumericStringExample::decode::<Uper>(BitIn::from(
                 bits![static u8, Msb0; 0,0,1,1, 0,0,1,0, 1,0,0,0, 0,0,0,1, 0,0,1,1]
             ))
             .unwrap()
@@ -1197,7 +1521,7 @@ mod tests {
         );
 
         assert_eq!(
-            NumericStringExample::decode::<Uper>(BSlice::from(
+            NumericStringExample::decode::<Uper>(BitIn::from(
                 bits![static u8, Msb0; 0,0,1,1, 0,0, 1,0, 1,1, 0,1]
             ))
             .unwrap()
@@ -1215,13 +1539,28 @@ mod tests {
         );
 
         assert_eq!(
-            Digit::decode::<Uper>(BSlice::from(bits![static u8, Msb0; 1,0,0,0]))
+            Digit::decode::<Uper>(BitIn::from(bits![static u8, Msb0; 1,0,0,0]))
                 .unwrap()
                 .1,
             Digit("8".into())
         );
     }
 
+    #[test]
+    fn decodes_unrestricted_utf8_string_from_utf8_bytes() {
+        asn1!(r#"Currency ::= UTF8String"#, Framework::Asnr, crate);
+
+        assert_eq!(
+            Currency::decode::<Uper>(BitIn::from(bits![static u8, Msb0;
+                0,0,0,0,0,0,1,1,
+                1,1,1,0,0,0,1,0,1,0,0,0,0,0,1,0,1,0,1,0,1,1,0,0
+            ]))
+            .unwrap()
+            .1,
+            Currency("€".into())
+        );
+    }
+
     #[test]
     fn decodes_unconstrained_character_string_with_alphabet_constraint() {
         asn1!(
@@ -1231,7 +1570,7 @@ mod tests {
         );
 
         assert_eq!(
-            Greeting::decode::<Uper>(BSlice::from(
+            Greeting::decode::<Uper>(BitIn::from(
                 bits![static u8, Msb0; 0,0,0,0,0,1,0,1, 0,1, 0,0, 1,0, 1,0, 1,1]
             ))
             .unwrap()
@@ -1244,7 +1583,7 @@ mod tests {
     fn decodes_unconstrained_variable_size_character_string() {
         asn1!(r#"Greeting ::= GraphicString"#, Framework::Asnr, crate);
         assert_eq!(
-            Greeting::decode::<Uper>(BSlice::from(bits![static u8, Msb0;
+            Greeting::decode::<Uper>(BitIn::from(bits![static u8, Msb0;
             0,0,0,0,0,1,0,0,
             1,1,1,1,0,0,0,0,
             1,0,0,1,1,1,1,1,
@@ -1257,6 +1596,35 @@ mod tests {
         );
     }
 
+    #[test]
+    fn decodes_bmp_string_by_code_points() {
+        asn1!(r#"Greeting ::= BMPString"#, Framework::Asnr, crate);
+        assert_eq!(
+            Greeting::decode::<Uper>(BitIn::from(bits![static u8, Msb0;
+            0,0,0,0,0,0,1,0,
+            0,1,1,0,1,1,1,1,0,0,1,0,0,0,1,0,
+            0,1,0,1,1,0,1,1,0,1,0,1,0,1,1,1
+            ]))
+            .unwrap()
+            .1,
+            Greeting("漢字".into())
+        );
+    }
+
+    #[test]
+    fn decodes_universal_string_by_code_points() {
+        asn1!(r#"Greeting ::= UniversalString"#, Framework::Asnr, crate);
+        assert_eq!(
+            Greeting::decode::<Uper>(BitIn::from(bits![static u8, Msb0;
+            0,0,0,0,0,0,0,1,
+            0,0,0,0,0,0,0,0,0,0,0,0,0,0,0,1,1,1,1,1,1,0,0,1,1,0,0,0,0,0,0,0
+            ]))
+            .unwrap()
+            .1,
+            Greeting("🦀".into())
+        );
+    }
+
     #[test]
     fn decodes_extended_variable_size_character_string() {
         asn1!(
@@ -1265,7 +1633,7 @@ mod tests {
             crate
         );
         assert_eq!(
-            Greeting::decode::<Uper>(BSlice::from(bits![static u8, Msb0;
+            Greeting::decode::<Uper>(BitIn::from(bits![static u8, Msb0;
             0,0,0,0,0,1,0,0,
             1,1,1,1,0,0,0,0,
             1,0,0,1,1,1,1,1,
@@ -1286,7 +1654,7 @@ mod tests {
             crate
         );
         assert_eq!(
-            TestSequenceOf::decode::<Uper>(BSlice::from(bits![static u8, Msb0; 0,0,0,1,1,0]))
+            TestSequenceOf::decode::<Uper>(BitIn::from(bits![static u8, Msb0; 0,0,0,1,1,0]))
                 .unwrap()
                 .1,
             TestSequenceOf(vec![
@@ -1305,7 +1673,7 @@ mod tests {
             crate
         );
         assert_eq!(
-            TestSequenceOf::decode::<Uper>(BSlice::from(bits![u8, Msb0; 1,0,0,0,1]))
+            TestSequenceOf::decode::<Uper>(BitIn::from(bits![u8, Msb0; 1,0,0,0,1]))
                 .unwrap()
                 .1,
             TestSequenceOf(vec![AnonymousTestSequenceOf(1), AnonymousTestSequenceOf(2)]),
@@ -1320,7 +1688,7 @@ mod tests {
             crate
         );
         assert_eq!(
-            TestSequenceOf::decode::<Uper>(BSlice::from(
+            TestSequenceOf::decode::<Uper>(BitIn::from(
                 bits![u8, Msb0; 1, 0,0,0,0,0,0,1,1, 0,0, 0,1, 1,0]
             ))
             .unwrap()
@@ -1341,7 +1709,7 @@ mod tests {
             crate
         );
         assert_eq!(
-            TestSequenceOf::decode::<Uper>(BSlice::from(
+            TestSequenceOf::decode::<Uper>(BitIn::from(
                 bits![u8, Msb0; 0,0,0,0,0,0,1,1, 0,0, 0,1, 1,0]
             ))
             .unwrap()
@@ -1353,4 +1721,30 @@ mod tests {
             ]),
         );
     }
+
+    #[test]
+    fn decodes_sequence_of_with_sequence_members() {
+        asn1!(
+            r#"List ::= SEQUENCE OF SEQUENCE { a INTEGER (0..10), b BOOLEAN OPTIONAL }"#,
+            Framework::Asnr,
+            crate
+        );
+        assert_eq!(
+            List::decode::<Uper>(BitIn::from(
+                bits![u8, Msb0; 0,0,0,0,0,0,1,0, 1, 0,0,1,1, 1, 0, 1,0,1,0]
+            ))
+            .unwrap()
+            .1,
+            List(vec![
+                AnonymousList {
+                    a: InnerAnonymousListA(3),
+                    b: Some(InnerAnonymousListB(true))
+                },
+                AnonymousList {
+                    a: InnerAnonymousListA(10),
+                    b: None
+                }
+            ]),
+        );
+    }
 }
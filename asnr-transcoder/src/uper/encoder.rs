@@ -1,4 +1,4 @@
-use alloc::{boxed::Box, format, string::String, vec::Vec};
+use alloc::{boxed::Box, format, string::String, vec, vec::Vec};
 use asnr_grammar::{
     encoding_rules::{
         bit_length,
@@ -8,13 +8,23 @@ use asnr_grammar::{
         },
     },
     types::*,
+    utils::{to_rust_snake_case, to_rust_title_case},
 };
 use bitvec::{bitvec, prelude::Msb0, vec::BitVec, view::BitView};
 use core::fmt::Debug;
 
 use crate::{error::EncodingError, Encode, Encoder, EncoderForIndex, HasOptionalField};
 
-use super::{to_rust_camel_case, to_rust_title_case, BitOut, Uper};
+use super::{
+    per_common::{
+        align_back, encode_constrained_integer, encode_octets, encode_sized,
+        with_size_length_determinant, wrap_in_length_determinant, write_extended_bit,
+    },
+    BitOut, Uper,
+};
+
+/// UPER does not align any encodings on octet boundaries
+const NEEDS_ALIGNMENT: bool = false;
 
 impl Encoder<u8, BitOut> for Uper {
     fn encode_integer<I>(
@@ -123,33 +133,28 @@ impl Encoder<u8, BitOut> for Uper {
         Ok(output)
     }
 
+    fn encode_object_identifier(arcs: &[u32], output: BitOut) -> Result<BitOut, EncodingError> {
+        Self::encode_open_type(&object_identifier_contents(arcs)?, output)
+    }
+
+    fn encode_real(value: f64, output: BitOut) -> Result<BitOut, EncodingError> {
+        Self::encode_open_type(&real_contents(value), output)
+    }
+
     fn encode_bit_string(
         bit_string: BitString,
     ) -> Result<Box<dyn Fn(Vec<bool>, BitOut) -> Result<BitOut, EncodingError>>, EncodingError>
     {
         let constraints = per_visible_range_constraints(false, &bit_string.constraints)?;
-        if constraints.is_extensible() {
-            Ok(Box::new(
-                move |encodable: Vec<bool>, mut output: BitOut| -> Result<BitOut, EncodingError> {
-                    let actual_length = encodable.len();
-                    let _ = write_extended_bit(&constraints, actual_length, &mut output)?;
-                    let to_wrap = encodable.into_iter().fold(bitvec![u8, Msb0;], |acc, curr| {
-                        Self::encode_boolean(curr, acc).unwrap()
-                    });
-                    with_size_length_determinant(actual_length, &constraints, to_wrap, output)
-                },
-            ))
-        } else {
-            Ok(Box::new(
-                move |encodable: Vec<bool>, output: BitOut| -> Result<BitOut, EncodingError> {
-                    let actual_length = encodable.len();
-                    let to_wrap = encodable.into_iter().fold(bitvec![u8, Msb0;], |acc, curr| {
-                        Self::encode_boolean(curr, acc).unwrap()
-                    });
-                    with_size_length_determinant(actual_length, &constraints, to_wrap, output)
-                },
-            ))
-        }
+        Ok(Box::new(
+            move |encodable: Vec<bool>, output: BitOut| -> Result<BitOut, EncodingError> {
+                let actual_length = encodable.len();
+                let to_wrap = encodable.into_iter().fold(bitvec![u8, Msb0;], |acc, curr| {
+                    Self::encode_boolean(curr, acc).unwrap()
+                });
+                encode_sized(actual_length, &constraints, to_wrap, output, NEEDS_ALIGNMENT)
+            },
+        ))
     }
 
     fn encode_character_string(
@@ -166,11 +171,15 @@ impl Encoder<u8, BitOut> for Uper {
         permitted_alphabet.finalize();
         if constraints.is_extensible() && permitted_alphabet.is_known_multiplier_string() {
             Ok(Box::new(
-                move |encodable: &str, mut output: BitOut| -> Result<BitOut, EncodingError> {
-                    let actual_length = encodable.len();
-                    let _ = write_extended_bit(&constraints, actual_length, &mut output)?;
+                move |encodable: &str, output: BitOut| -> Result<BitOut, EncodingError> {
                     let to_wrap = encode_sized_string(&permitted_alphabet, encodable)?;
-                    with_size_length_determinant(actual_length, &constraints, to_wrap, output)
+                    encode_sized(
+                        encodable.chars().count(),
+                        &constraints,
+                        to_wrap,
+                        output,
+                        NEEDS_ALIGNMENT,
+                    )
                 },
             ))
         } else {
@@ -178,7 +187,18 @@ impl Encoder<u8, BitOut> for Uper {
                 move |encodable: &str, output: BitOut| -> Result<BitOut, EncodingError> {
                     let to_wrap: BitVec<u8, Msb0> =
                         encode_sized_string(&permitted_alphabet, encodable)?;
-                    with_size_length_determinant(encodable.len(), &constraints, to_wrap, output)
+                    let length = if permitted_alphabet.is_known_multiplier_string() {
+                        encodable.chars().count()
+                    } else {
+                        encodable.len()
+                    };
+                    with_size_length_determinant(
+                        length,
+                        &constraints,
+                        to_wrap,
+                        output,
+                        NEEDS_ALIGNMENT,
+                    )
                 },
             ))
         }
@@ -194,7 +214,7 @@ impl Encoder<u8, BitOut> for Uper {
             .map(|(i, m)| {
                 (
                     i,
-                    to_rust_camel_case(&m.name),
+                    to_rust_snake_case(&m.name),
                     (m.is_optional || i >= sequence.extensible.unwrap_or(usize::MAX)),
                 )
             })
@@ -446,23 +466,11 @@ impl Encoder<u8, BitOut> for Uper {
         octet_string: OctetString,
     ) -> Result<Box<dyn Fn(&[u8], BitOut) -> Result<BitOut, EncodingError>>, EncodingError> {
         let constraints = per_visible_range_constraints(false, &octet_string.constraints)?;
-        if constraints.is_extensible() {
-            Ok(Box::new(
-                move |encodable: &[u8], mut output: BitOut| -> Result<BitOut, EncodingError> {
-                    let actual_length = encodable.len();
-                    let _ = write_extended_bit(&constraints, actual_length, &mut output)?;
-                    let to_wrap = encodable.view_bits::<Msb0>().to_bitvec();
-                    with_size_length_determinant(actual_length, &constraints, to_wrap, output)
-                },
-            ))
-        } else {
-            Ok(Box::new(
-                move |encodable: &[u8], output: BitOut| -> Result<BitOut, EncodingError> {
-                    let to_wrap: BitVec<u8, Msb0> = encodable.view_bits::<Msb0>().to_bitvec();
-                    with_size_length_determinant(encodable.len(), &constraints, to_wrap, output)
-                },
-            ))
-        }
+        Ok(Box::new(
+            move |encodable: &[u8], output: BitOut| -> Result<BitOut, EncodingError> {
+                encode_octets(&constraints, encodable, output, NEEDS_ALIGNMENT)
+            },
+        ))
     }
 
     fn encode_sequence_of<M: Encode<u8, BitOut>>(
@@ -485,6 +493,7 @@ impl Encoder<u8, BitOut> for Uper {
                         &constraints,
                         encoded_members,
                         output,
+                        NEEDS_ALIGNMENT,
                     )
                 },
             ))
@@ -502,6 +511,7 @@ impl Encoder<u8, BitOut> for Uper {
                         &constraints,
                         encoded_members,
                         output,
+                        NEEDS_ALIGNMENT,
                     )
                 },
             ))
@@ -516,6 +526,91 @@ impl Encoder<u8, BitOut> for Uper {
             output,
         )
     }
+
+    fn encode_open_type_with<V>(
+        value: &V,
+        encoder: fn(&V, BitOut) -> Result<BitOut, EncodingError>,
+        output: BitOut,
+    ) -> Result<BitOut, EncodingError> {
+        let mut contents = encoder(value, bitvec![u8, Msb0;])?;
+        // like complete encodings, empty contents are replaced by a single zero octet
+        if contents.is_empty() {
+            contents.resize(8, false);
+        }
+        contents.set_uninitialized(false);
+        Self::encode_open_type(&contents.into_vec(), output)
+    }
+
+    fn encode_contents<V: Encode<u8, BitOut>>(value: V) -> Result<Vec<u8>, EncodingError> {
+        Uper::encode(value)
+    }
+}
+
+/// Encodes the arcs of an OBJECT IDENTIFIER as contents octets as specified in X.690 §8.19
+fn object_identifier_contents(arcs: &[u32]) -> Result<Vec<u8>, EncodingError> {
+    let first_subidentifier = match arcs {
+        [first @ 0..=1, second @ 0..=39, ..] | [first @ 2, second, ..] => {
+            u64::from(*first) * 40 + u64::from(*second)
+        }
+        _ => {
+            return Err(EncodingError {
+                details: format!("Invalid OBJECT IDENTIFIER arcs {arcs:?}"),
+            })
+        }
+    };
+    let mut contents = Vec::new();
+    for subidentifier in
+        core::iter::once(first_subidentifier).chain(arcs[2..].iter().map(|arc| u64::from(*arc)))
+    {
+        let mut octets = vec![(subidentifier & 0x7f) as u8];
+        let mut remainder = subidentifier >> 7;
+        while remainder > 0 {
+            octets.push((remainder & 0x7f) as u8 | 0x80);
+            remainder >>= 7;
+        }
+        contents.extend(octets.iter().rev());
+    }
+    Ok(contents)
+}
+
+/// Encodes a REAL as contents octets in the base 2 form that X.690 §11.3 prescribes for CER and DER
+fn real_contents(value: f64) -> Vec<u8> {
+    if value.is_nan() {
+        return vec![0x42];
+    } else if value.is_infinite() {
+        return vec![if value.is_sign_positive() { 0x40 } else { 0x41 }];
+    } else if value == 0.0 {
+        return if value.is_sign_positive() {
+            vec![]
+        } else {
+            vec![0x43]
+        };
+    }
+    let bits = value.to_bits();
+    let biased_exponent = ((bits >> 52) & 0x7ff) as i32;
+    let fraction = bits & ((1 << 52) - 1);
+    let (mut mantissa, mut exponent) = if biased_exponent == 0 {
+        (fraction, -1074)
+    } else {
+        (fraction | (1 << 52), biased_exponent - 1075)
+    };
+    while mantissa & 1 == 0 {
+        mantissa >>= 1;
+        exponent += 1;
+    }
+    let exponent_octets: &[u8] = &exponent.to_be_bytes();
+    let exponent_octets = if i8::try_from(exponent).is_ok() {
+        &exponent_octets[3..]
+    } else {
+        &exponent_octets[2..]
+    };
+    let mantissa_octets = mantissa.to_be_bytes();
+    let leading_zeros = mantissa_octets.iter().take_while(|octet| **octet == 0).count();
+    let sign = if value.is_sign_negative() { 0x40 } else { 0 };
+    let mut contents = vec![0x80 | sign | (exponent_octets.len() as u8 - 1)];
+    contents.extend_from_slice(exponent_octets);
+    contents.extend_from_slice(&mantissa_octets[leading_zeros..]);
+    contents
 }
 
 fn encode_sized_string(
@@ -526,16 +621,19 @@ fn encode_sized_string(
     if bit_length == 0 {
         return encode_sized_string(&permitted_alphabet.fall_back_to_standard_charset(), string);
     }
-    if permitted_alphabet.is_known_multiplier_string() {
+    if permitted_alphabet.is_raw_encoded() {
         let mut output = BitVec::new();
         for c in string.chars() {
-            let index =
-                permitted_alphabet
-                    .index_by_character_map()?
-                    .get(&c)
-                    .ok_or(EncodingError {
-                        details: format!("Character {c} is not part of permitted character set"),
-                    })?;
+            output = encode_constrained_integer(c as u32, bit_length, output)?;
+        }
+        Ok(output)
+    } else if permitted_alphabet.is_known_multiplier_string() {
+        let index_by_character = permitted_alphabet.index_by_character_map()?;
+        let mut output = BitVec::new();
+        for c in string.chars() {
+            let index = index_by_character.get(&c).ok_or(EncodingError {
+                details: format!("Character {c:?} is not part of permitted character set"),
+            })?;
             output = encode_constrained_integer(*index, bit_length, output)?;
         }
         Ok(output)
@@ -553,89 +651,6 @@ fn assert_byte_alignment(length: usize) -> Result<(), EncodingError> {
     Ok(())
 }
 
-fn write_extended_bit<I>(
-    constraints: &PerVisibleRangeConstraints,
-    encodable: I,
-    output: &mut BitOut,
-) -> Result<bool, EncodingError>
-where
-    I: num::Integer + num::ToPrimitive + num::FromPrimitive + Copy,
-{
-    let within_constraints = constraints.lies_within(&encodable)?;
-    if within_constraints {
-        output.push(false);
-    } else {
-        output.push(true);
-    }
-    Ok(within_constraints)
-}
-
-/// Wraps the provided buffer in a length determinant for size constraints
-/// ### Params
-/// * `actual_size` - number of counted items (i.e. size) of the encoded value. An _item_ can be an octet, a character, a member of a collection, depending on the ASN1 type that is encoded.
-/// * `constraints` - specification of the encoded type's constraints
-/// * `to_wrap` - BitVec containing the encoded value that should receive a size length determinant prefix
-/// * `output` - the output buffer that the sized value should be appended to
-/// ### Reference in ASN1 Complete (Larmouth 302)
-/// >* _With no PER-visible size constraint, or a constraint that allows counts
-/// in excess of 64K, we encode a general length determinant._
-/// >* _For abstract values outside the root, a general length determinant is again used._
-/// >* _With a size constraint that gives a fixed value for the count, there
-/// is no length determinant encoding._
-/// >* _Otherwise, we encode the count exactly like an integer with the equivalent constraint_
-fn with_size_length_determinant(
-    actual_size: usize,
-    constraints: &PerVisibleRangeConstraints,
-    mut to_wrap: BitOut,
-    output: BitOut,
-) -> Result<BitOut, EncodingError> {
-    if let (Some(bit_length), Some(Some(_)), true) = (
-        constraints.bit_length(),
-        constraints.range_width()?.map(|w| (w <= 65536).then(|| w)),
-        constraints.lies_within(&actual_size)?,
-    ) {
-        let mut output = encode_constrained_integer(
-            actual_size - constraints.min().unwrap_or(0),
-            bit_length,
-            output,
-        )?;
-        output.append(&mut to_wrap);
-        Ok(output)
-    } else {
-        wrap_in_length_determinant(actual_size, to_wrap, Some(0), output)
-    }
-}
-
-fn wrap_in_length_determinant<I>(
-    length_offset: usize,
-    mut to_wrap: BitOut,
-    min: Option<I>,
-    mut output: BitOut,
-) -> Result<BitOut, EncodingError> {
-    match length_offset {
-        x if x < 128 => {
-            let mut length_det = encode_constrained_integer(x, 8, output)?;
-            length_det.append(&mut to_wrap);
-            Ok(length_det)
-        }
-        x if x < 16384 => {
-            output.append(&mut bitvec![u8, Msb0; 1, 0]);
-            let mut length_det = encode_constrained_integer(x, 14, output)?;
-            length_det.append(&mut to_wrap);
-            Ok(length_det)
-        }
-        x => {
-            let (mut fragment, fragment_size) = match x {
-                s if s < 32768 => (bitvec![u8, Msb0; 1,1,0,0,0,0,1,0], 32768),
-                s if s < 49152 => (bitvec![u8, Msb0; 1,1,0,0,0,0,1,1], 49152),
-                _ => (bitvec![u8, Msb0; 1,1,0,0,0,1,0,0], 65536),
-            };
-            fragment.extend(to_wrap[..fragment_size].iter());
-            wrap_in_length_determinant(length_offset - fragment_size, fragment, min, output)
-        }
-    }
-}
-
 fn encode_varlength_integer<I>(integer: I, min: Option<I>) -> Result<BitOut, EncodingError>
 where
     I: num::Integer + num::ToPrimitive + Copy,
@@ -694,21 +709,6 @@ where
     }
 }
 
-fn encode_constrained_integer<I>(
-    integer: I,
-    bit_length: usize,
-    mut output: BitOut,
-) -> Result<BitOut, EncodingError>
-where
-    I: num::Integer + num::ToPrimitive + Copy,
-{
-    let as_u128 = integer.to_u128().ok_or(EncodingError {
-        details: "Failed to convert integer to u128!".into(),
-    })?;
-    output.extend((0..bit_length).rev().map(|n| (as_u128 >> n) & 1 != 0));
-    Ok(output)
-}
-
 fn pad(bytes: usize, mut output: BitOut) -> BitOut {
     let mut padding = bitvec![u8, Msb0; 0; bytes];
     padding.append(&mut output);
@@ -723,26 +723,48 @@ fn align(output: BitOut) -> BitOut {
     pad(missing_bits, output)
 }
 
-fn align_back(mut output: BitOut) -> BitOut {
-    let missing_bits = 8 - output.len() % 8;
-    if missing_bits == 8 {
-        return output;
-    }
-    for _ in 0..missing_bits {
-        output.push(false);
-    }
-    return output;
-}
-
 #[cfg(test)]
 mod tests {
-    use crate::uper::{
-        encoder::{align, encode_constrained_integer, pad},
-        Uper,
+    use crate::{
+        uper::{
+            encoder::{align, object_identifier_contents, pad, real_contents},
+            per_common::encode_constrained_integer,
+            Uper,
+        },
+        Encoder,
     };
+    use alloc::vec;
     use asnr_compiler_derive::asn1;
     use bitvec::{bitvec, prelude::Msb0};
 
+    #[test]
+    fn encodes_object_identifier() {
+        assert_eq!(
+            Uper::encode_object_identifier(&[1, 37, 3], bitvec![u8, Msb0;]).unwrap(),
+            bitvec![u8, Msb0; 0, 0, 0, 0, 0, 0, 1, 0, 0, 1, 0, 0, 1, 1, 0, 1, 0, 0, 0, 0, 0, 0, 1, 1]
+        );
+        assert_eq!(
+            object_identifier_contents(&[2, 999]).unwrap(),
+            vec![0x88, 0x37]
+        );
+        assert!(object_identifier_contents(&[1, 40]).is_err());
+        assert!(object_identifier_contents(&[1]).is_err());
+    }
+
+    #[test]
+    fn encodes_real() {
+        assert_eq!(
+            Uper::encode_real(1.0, bitvec![u8, Msb0;]).unwrap(),
+            bitvec![u8, Msb0; 0, 0, 0, 0, 0, 0, 1, 1, 1, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 1]
+        );
+        assert_eq!(real_contents(-0.75), vec![0xC0, 0xFE, 0x03]);
+        assert_eq!(real_contents(24.0), vec![0x80, 0x03, 0x03]);
+        assert_eq!(real_contents(0.0), vec![]);
+        assert_eq!(real_contents(-0.0), vec![0x43]);
+        assert_eq!(real_contents(f64::INFINITY), vec![0x40]);
+        assert_eq!(real_contents(f64::NAN), vec![0x42]);
+    }
+
     #[test]
     fn pads_bits() {
         let input = bitvec![u8, Msb0; 1, 1];
@@ -1021,6 +1043,66 @@ mod tests {
         );
     }
 
+    #[test]
+    fn encodes_unrestricted_utf8_string_by_utf8_bytes() {
+        asn1!(r#"TestString ::= UTF8String"#,
+            Framework::Asnr,
+            crate);
+        assert_eq!(
+            TestString::encode::<Uper>(TestString("€".into()), bitvec![u8, Msb0;]).unwrap(),
+            bitvec![u8, Msb0;
+            0,0,0,0,0,0,1,1,
+            1,1,1,0,0,0,1,0,1,0,0,0,0,0,1,0,1,0,1,0,1,1,0,0
+            ]
+        );
+    }
+
+    #[test]
+    fn encodes_bmp_string_by_code_points() {
+        asn1!(r#"TestString ::= BMPString"#,
+            Framework::Asnr,
+            crate);
+        assert_eq!(
+            TestString::encode::<Uper>(TestString("中".into()), bitvec![u8, Msb0;]).unwrap(),
+            bitvec![u8, Msb0;
+            0,0,0,0,0,0,0,1,
+            0,1,0,0,1,1,1,0,0,0,1,0,1,1,0,1
+            ]
+        );
+    }
+
+    #[test]
+    fn encodes_bmp_string_kanji_in_sixteen_bits() {
+        asn1!(r#"TestString ::= BMPString"#,
+            Framework::Asnr,
+            crate);
+        let encoded =
+            TestString::encode::<Uper>(TestString("漢字".into()), bitvec![u8, Msb0;]).unwrap();
+        assert_eq!(encoded.len(), 8 + 2 * 16);
+        assert_eq!(
+            encoded,
+            bitvec![u8, Msb0;
+            0,0,0,0,0,0,1,0,
+            0,1,1,0,1,1,1,1,0,0,1,0,0,0,1,0,
+            0,1,0,1,1,0,1,1,0,1,0,1,0,1,1,1
+            ]
+        );
+    }
+
+    #[test]
+    fn encodes_universal_string_by_code_points() {
+        asn1!(r#"TestString ::= UniversalString"#,
+            Framework::Asnr,
+            crate);
+        assert_eq!(
+            TestString::encode::<Uper>(TestString("🦀".into()), bitvec![u8, Msb0;]).unwrap(),
+            bitvec![u8, Msb0;
+            0,0,0,0,0,0,0,1,
+            0,0,0,0,0,0,0,0,0,0,0,0,0,0,0,1,1,1,1,1,1,0,0,1,1,0,0,0,0,0,0,0
+            ]
+        );
+    }
+
     #[test]
     fn encodes_constrained_extensible_character_string_with_permitted_alphabet() {
         asn1!(r#"TestString ::= NumericString (SIZE(1..4,...))"#,
@@ -1051,6 +1133,29 @@ mod tests {
         );
     }
 
+    #[test]
+    fn rejects_characters_outside_of_character_set() {
+        asn1!(
+            r#"Numeric ::= NumericString
+            Ia5 ::= IA5String
+            Printable ::= PrintableString
+            Visible ::= VisibleString (SIZE(1..8))
+            Restricted ::= VisibleString (FROM("ABC"))"#,
+            Framework::Asnr,
+            crate
+        );
+        assert!(Numeric::encode::<Uper>(Numeric("12 34".into()), bitvec![u8, Msb0;]).is_ok());
+        assert!(Numeric::encode::<Uper>(Numeric("12a".into()), bitvec![u8, Msb0;]).is_err());
+        assert!(Ia5::encode::<Uper>(Ia5("tab\t".into()), bitvec![u8, Msb0;]).is_ok());
+        assert!(Ia5::encode::<Uper>(Ia5("café".into()), bitvec![u8, Msb0;]).is_err());
+        assert!(Printable::encode::<Uper>(Printable("a-(1)".into()), bitvec![u8, Msb0;]).is_ok());
+        assert!(Printable::encode::<Uper>(Printable("a!".into()), bitvec![u8, Msb0;]).is_err());
+        assert!(Visible::encode::<Uper>(Visible("a!~".into()), bitvec![u8, Msb0;]).is_ok());
+        assert!(Visible::encode::<Uper>(Visible("a\n".into()), bitvec![u8, Msb0;]).is_err());
+        assert!(Restricted::encode::<Uper>(Restricted("CAB".into()), bitvec![u8, Msb0;]).is_ok());
+        assert!(Restricted::encode::<Uper>(Restricted("CAD".into()), bitvec![u8, Msb0;]).is_err());
+    }
+
     #[test]
     fn encodes_simple_enumerated() {
         asn1!(r#"TestEnum ::= ENUMERATED {m1, m2, m3}"#,
@@ -1089,6 +1194,21 @@ mod tests {
         );
     }
 
+    #[test]
+    fn encodes_sparse_enumerated() {
+        asn1!(r#"TestEnum ::= ENUMERATED {a(1), b(1000000)}"#,
+            Framework::Asnr,
+            crate);
+        assert_eq!(
+            TestEnum::encode::<Uper>(TestEnum::A, bitvec![u8, Msb0;]).unwrap(),
+            bitvec![u8, Msb0; 0]
+        );
+        assert_eq!(
+            TestEnum::encode::<Uper>(TestEnum::B, bitvec![u8, Msb0;]).unwrap(),
+            bitvec![u8, Msb0; 1]
+        );
+    }
+
     #[test]
     fn encodes_extended_enumerated() {
         asn1!(
@@ -1177,7 +1297,7 @@ mod tests {
         );
         assert_eq!(
             SymmetricEncryptionKey::encode::<Uper>(
-                SymmetricEncryptionKey::None(InnerSymmetricEncryptionKeyNone),
+                SymmetricEncryptionKey::NoneType(InnerSymmetricEncryptionKeyNone),
                 bitvec![u8, Msb0;]
             )
             .unwrap(),
@@ -1197,24 +1317,18 @@ mod tests {
             Framework::Asnr,
             crate
         );
+        let extended = ExtendedSequence {
+            item_code: InnerExtendedSequenceItemcode(5),
+            test_ext: Some(InnerExtendedSequenceTestext(false)),
+            ext_group_1: Some(InnerExtendedSequenceExtGroup1 {
+                alternate_item_code: InnerInnerExtendedSequenceExtGroup1Alternateitemcode(3),
+                and_another: None,
+            }),
+        };
+        assert_eq!(extended.alternate_item_code().map(|code| code.0), Some(3));
+        assert_eq!(extended.and_another(), None);
         assert_eq!(
-            ExtendedSequence::encode::<Uper>(
-                ExtendedSequence {
-                    item_code: InnerExtendedSequenceItemcode(5),
-                    test_ext: Some(InnerExtendedSequenceTestext(false)),
-                    ext_group_alternate_item_code: Some(
-                        InnerExtendedSequenceExtgroupalternateitemcode {
-                            alternate_item_code:
-                                InnerInnerExtendedSequenceExtgroupalternateitemcodeAlternateitemcode(
-                                    3
-                                ),
-                            and_another: None
-                        }
-                    ),
-                },
-                bitvec![u8, Msb0;]
-            )
-            .unwrap(),
+            ExtendedSequence::encode::<Uper>(extended, bitvec![u8, Msb0;]).unwrap(),
             bitvec![u8, Msb0;
             1, // is extended
             0,0,0,0,0,1,0,1, // value of item-code
@@ -1248,7 +1362,7 @@ mod tests {
                 ExtendedSequence {
                     item_code: InnerExtendedSequenceItemcode(5),
                     test_ext: Some(InnerExtendedSequenceTestext(false)),
-                    ext_group_alternate_item_code: None,
+                    ext_group_1: None,
                 },
                 bitvec![u8, Msb0;]
             )
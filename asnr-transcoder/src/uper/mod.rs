@@ -1,7 +1,9 @@
-use bitvec::{bitvec, prelude::Msb0, vec::BitVec, view::BitView};
+use bitvec::{bitvec, prelude::Msb0, slice::BitSlice, vec::BitVec, view::BitView};
 use bitvec_nom::BSlice;
+use nom::{AsBytes, InputIter, InputLength, InputTake, Needed, Offset};
 
 use alloc::{string::String, vec::Vec};
+use core::ops::Deref;
 
 use crate::{
     error::{DecodingError, EncodingError},
@@ -10,14 +12,55 @@ use crate::{
 
 mod decoder;
 mod encoder;
+mod per_common;
 
 pub struct Uper;
 
+/// Limits that the UPER decoder enforces on untrusted input
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct DecoderConfig {
+    /// Maximum length that a length determinant may announce.
+    /// Larger lengths are rejected before anything is allocated for them.
+    pub max_bytes: usize,
+}
+
+impl Default for DecoderConfig {
+    fn default() -> Self {
+        Self {
+            max_bytes: usize::MAX,
+        }
+    }
+}
+
 impl Uper {
     pub fn decode<'a, T: Decode<'a, BitIn<'a>>>(
         input: &'a [u8],
-    ) -> Result<T, DecodingError<BitIn>> {
-        T::decode::<Uper>(BitIn::from(input.view_bits::<Msb0>())).map(|(_, res)| res)
+    ) -> Result<T, DecodingError<BitIn<'a>>> {
+        Self::decode_with_config(input, &DecoderConfig::default())
+    }
+
+    /// Decodes a value from the start of `input` and returns it together with the
+    /// bytes that follow its encoding, e.g. to decode several concatenated messages.
+    /// Since complete UPER encodings are padded to full octets, the remaining bytes
+    /// start right after the padding of the decoded value.
+    pub fn decode_partial<'a, T: Decode<'a, BitIn<'a>>>(
+        input: &'a [u8],
+    ) -> Result<(T, &'a [u8]), DecodingError<BitIn<'a>>> {
+        T::decode::<Uper>(BitIn::from(input.view_bits::<Msb0>())).map(|(remaining, res)| {
+            let consumed = input.len() - remaining.len() / 8;
+            (res, &input[consumed..])
+        })
+    }
+
+    /// Decodes `input` while enforcing the limits of `config`.
+    /// The limits travel with the decoder's input, so concurrent decodings
+    /// do not affect each other.
+    pub fn decode_with_config<'a, T: Decode<'a, BitIn<'a>>>(
+        input: &'a [u8],
+        config: &DecoderConfig,
+    ) -> Result<T, DecodingError<BitIn<'a>>> {
+        T::decode::<Uper>(BitIn::with_config(input.view_bits::<Msb0>(), config))
+            .map(|(_, res)| res)
     }
 
     pub fn encode<'a, T: Encode<u8, BitOut>>(input: T) -> Result<Vec<u8>, EncodingError> {
@@ -26,62 +69,158 @@ impl Uper {
             bitvec.into_vec()
         })
     }
+
+    /// Encodes `input` and returns the encoding in an array of `N` bytes together with
+    /// the number of bytes that the encoding occupies, e.g. to store it in a fixed-size
+    /// field. The encoder still builds the encoding in a heap-allocated `BitVec` before
+    /// it is written into the array, so this does not avoid allocations.
+    /// Types whose encodings have a fixed size provide the matching buffer size in
+    /// bits as `ENCODED_SIZE_BITS`.
+    pub fn encode_into<const N: usize, T: Encode<u8, BitOut>>(
+        input: T,
+    ) -> Result<([u8; N], usize), EncodingError> {
+        let mut encoded = T::encode::<Uper>(input, bitvec![u8, Msb0;])?;
+        encoded.set_uninitialized(false);
+        let bytes = encoded.as_raw_slice();
+        if bytes.len() > N {
+            return Err(EncodingError {
+                details: "Buffer too small".into(),
+            });
+        }
+        let mut buffer = [0; N];
+        buffer[..bytes.len()].copy_from_slice(bytes);
+        Ok((buffer, bytes.len()))
+    }
 }
 
-pub type BitIn<'a> = BSlice<'a, u8, Msb0>;
-pub type BitOut = BitVec<u8, Msb0>;
+/// Input of the UPER decoder. Wraps the remaining bits together with the
+/// limits of the `DecoderConfig` that the decoding was started with.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct BitIn<'a> {
+    bits: BSlice<'a, u8, Msb0>,
+    max_length: usize,
+}
+
+impl<'a> BitIn<'a> {
+    pub fn with_config(bits: &'a BitSlice<u8, Msb0>, config: &DecoderConfig) -> Self {
+        Self {
+            bits: BSlice::from(bits),
+            max_length: config.max_bytes,
+        }
+    }
 
-const RUST_KEYWORDS: [&'static str; 38] = [
-    "as", "async", "await", "break", "const", "continue", "crate", "dyn", "else", "enum", "extern",
-    "false", "fn", "for", "if", "impl", "in", "let", "loop", "match", "mod", "move", "mut", "pub",
-    "ref", "return", "self", "Self", "static", "struct", "super", "trait", "true", "type",
-    "unsafe", "use", "where", "while",
-];
+    /// Maximum length that a length determinant in this input may announce
+    pub(crate) fn max_length(&self) -> usize {
+        self.max_length
+    }
 
-pub fn to_rust_camel_case(input: &String) -> String {
-    let mut input = input.replace("-", "_");
-    let input = input.drain(..).fold(String::new(), |mut acc, c| {
-        if acc.is_empty() && c.is_uppercase() {
-            acc.push(c.to_ascii_lowercase());
-        } else if acc.ends_with(|last: char| last.is_lowercase() || last == '_') && c.is_uppercase()
-        {
-            acc.push('_');
-            acc.push(c.to_ascii_lowercase());
-        } else {
-            acc.push(c);
+    fn with_bits(&self, bits: BSlice<'a, u8, Msb0>) -> Self {
+        Self {
+            bits,
+            max_length: self.max_length,
         }
-        acc
-    });
-    if RUST_KEYWORDS.contains(&input.as_str()) {
-        String::from("r_") + &input
-    } else {
-        input
     }
 }
 
+impl<'a> From<&'a BitSlice<u8, Msb0>> for BitIn<'a> {
+    fn from(bits: &'a BitSlice<u8, Msb0>) -> Self {
+        Self::with_config(bits, &DecoderConfig::default())
+    }
+}
+
+impl<'a> Deref for BitIn<'a> {
+    type Target = BitSlice<u8, Msb0>;
+
+    fn deref(&self) -> &Self::Target {
+        self.bits.0
+    }
+}
+
+impl<'a> AsBytes for BitIn<'a> {
+    fn as_bytes(&self) -> &[u8] {
+        self.bits.as_bytes()
+    }
+}
+
+impl<'a> InputLength for BitIn<'a> {
+    fn input_len(&self) -> usize {
+        self.bits.input_len()
+    }
+}
+
+impl<'a> InputTake for BitIn<'a> {
+    fn take(&self, count: usize) -> Self {
+        self.with_bits(self.bits.take(count))
+    }
+
+    fn take_split(&self, count: usize) -> (Self, Self) {
+        let (suffix, prefix) = self.bits.take_split(count);
+        (self.with_bits(suffix), self.with_bits(prefix))
+    }
+}
+
+impl<'a> InputIter for BitIn<'a> {
+    type Item = <BSlice<'a, u8, Msb0> as InputIter>::Item;
+    type Iter = <BSlice<'a, u8, Msb0> as InputIter>::Iter;
+    type IterElem = <BSlice<'a, u8, Msb0> as InputIter>::IterElem;
+
+    fn iter_indices(&self) -> Self::Iter {
+        self.bits.iter_indices()
+    }
+
+    fn iter_elements(&self) -> Self::IterElem {
+        self.bits.iter_elements()
+    }
+
+    fn position<P>(&self, predicate: P) -> Option<usize>
+    where
+        P: Fn(Self::Item) -> bool,
+    {
+        self.bits.position(predicate)
+    }
+
+    fn slice_index(&self, count: usize) -> Result<usize, Needed> {
+        self.bits.slice_index(count)
+    }
+}
+
+impl<'a> Offset for BitIn<'a> {
+    fn offset(&self, second: &Self) -> usize {
+        self.bits.offset(&second.bits)
+    }
+}
+
+pub type BitOut = BitVec<u8, Msb0>;
+
+#[deprecated(note = "use `asnr_grammar::utils::to_rust_snake_case` instead")]
+pub fn to_rust_camel_case(input: &String) -> String {
+    asnr_grammar::utils::to_rust_snake_case(input)
+}
+
+#[deprecated(note = "use `asnr_grammar::utils::to_rust_title_case` instead")]
 pub fn to_rust_title_case(input: &String) -> String {
-    let mut input = input.replace("-", "_");
-    input.drain(..).fold(String::new(), |mut acc, c| {
-        if acc.is_empty() && c.is_lowercase() {
-            acc.push(c.to_ascii_uppercase());
-        } else if acc.ends_with(|last: char| last == '_') && c.is_uppercase() {
-            acc.pop();
-            acc.push(c);
-        } else if acc.ends_with(|last: char| last == '_') {
-            acc.pop();
-            acc.push(c.to_ascii_uppercase());
-        } else {
-            acc.push(c);
-        }
-        acc
-    })
+    asnr_grammar::utils::to_rust_title_case(input)
 }
 
 #[cfg(test)]
 mod tests {
     use asnr_compiler_derive::asn1;
 
-    use crate::uper::Uper;
+    use crate::uper::{DecoderConfig, Uper};
+
+    #[test]
+    #[allow(deprecated)]
+    fn forwards_deprecated_case_conversions() {
+        let name = String::from("Self-Type");
+        assert_eq!(
+            super::to_rust_camel_case(&name),
+            asnr_grammar::utils::to_rust_snake_case(&name)
+        );
+        assert_eq!(
+            super::to_rust_title_case(&name),
+            asnr_grammar::utils::to_rust_title_case(&name)
+        );
+    }
 
     #[test]
     fn encodes_as_decodes_integer() {
@@ -186,6 +325,45 @@ mod tests {
         );
     }
 
+    #[test]
+    fn encodes_as_decodes_bit_string_with_named_bits() {
+        asn1!(
+            r#"Permissions ::= BIT STRING { read(0), write(1), execute(2) } (SIZE(3))"#,
+            Framework::Asnr,
+            crate
+        );
+
+        let mut permissions = Permissions(vec![false; 3]);
+        permissions.set_write(true);
+        let decoded = Uper::decode::<Permissions>(&Uper::encode(permissions).unwrap()).unwrap();
+        assert_eq!(decoded.0, vec![false, true, false]);
+        assert!(decoded.write() && !decoded.read());
+        assert_eq!(Permissions::EXECUTE, 2);
+    }
+
+    #[test]
+    fn encodes_as_decodes_instantiated_parameterized_sequence() {
+        asn1!(
+            r#"Container {ElementType} ::= SEQUENCE { id INTEGER (0..255), element ElementType }
+            Speed ::= INTEGER (0..100)
+            Heading ::= INTEGER (0..359)
+            Report ::= SEQUENCE { speed Container {Speed}, heading Container {Heading} }"#,
+            Framework::Asnr,
+            crate
+        );
+
+        let speed = ContainerOfSpeed::new(7, Speed(100));
+        assert_eq!(
+            Uper::encode(speed.clone()).unwrap(),
+            vec![0x07, 0b1100_1000]
+        );
+        let report = Report::new(speed, ContainerOfHeading::new(8, Heading(359)));
+        assert_eq!(
+            report,
+            Uper::decode::<Report>(&Uper::encode(report.clone()).unwrap()).unwrap()
+        );
+    }
+
     #[test]
     fn encodes_as_decodes_octet_string() {
         asn1!(
@@ -573,14 +751,20 @@ mod tests {
             },
             Uper::decode::<ExampleSequence>(
                 &Uper::encode(ExampleSequence {
-                    member_1: InnerExampleSequenceMember1("Hello, World!".into()),
-                    member_2: InnerExampleSequenceMember2(8),
+                    member_1: "Hello, World!".into(),
+                    member_2: 8.into(),
                     extension: None
                 })
                 .unwrap()
             )
             .unwrap()
         );
+        let member_1 = InnerExampleSequenceMember1::from("Hello, World!");
+        assert_eq!(member_1.as_ref(), "Hello, World!");
+        assert_eq!(member_1.len(), 13);
+        let member_2: u8 = InnerExampleSequenceMember2(8).into();
+        assert_eq!(member_2, 8);
+        assert_eq!(*InnerExampleSequenceMember2::from(8), 8);
     }
 
     #[test]
@@ -610,4 +794,378 @@ mod tests {
             .unwrap()
         );
     }
+
+    #[test]
+    fn encodes_as_decodes_sequence_with_odd_bit_extensions() {
+        asn1!(
+            r#"OddBits ::= SEQUENCE {
+            base INTEGER (0..7),
+            ...,
+            flag BOOLEAN,
+            count INTEGER (0..7)
+          }"#,
+            Framework::Asnr,
+            crate
+        );
+
+        let value = OddBits {
+            base: InnerOddBitsBase(5),
+            flag: Some(InnerOddBitsFlag(true)),
+            count: Some(InnerOddBitsCount(3)),
+        };
+        let mut encoded = Uper::encode(value.clone()).unwrap();
+        assert_eq!(value, Uper::decode::<OddBits>(&encoded).unwrap());
+        // the flag is the first of the eight bits of its open type content, which follows
+        // the preamble, the extension bitmap, and the length determinant
+        encoded[2] |= 0b0000_0010;
+        assert!(Uper::decode::<OddBits>(&encoded).is_err());
+    }
+
+    #[test]
+    fn decodes_sequence_with_unknown_extensions() {
+        let encoded = {
+            asn1!(
+                r#"Extended ::= SEQUENCE {
+                base INTEGER (0..7),
+                ...,
+                first BOOLEAN,
+                second INTEGER (0..255),
+                third IA5String
+              }"#,
+                Framework::Asnr,
+                crate
+            );
+            Uper::encode(Extended {
+                base: InnerExtendedBase(3),
+                first: Some(InnerExtendedFirst(true)),
+                second: Some(InnerExtendedSecond(42)),
+                third: Some(InnerExtendedThird("unknown".into())),
+            })
+            .unwrap()
+        };
+        asn1!(
+            r#"Extended ::= SEQUENCE {
+            base INTEGER (0..7),
+            ...,
+            first BOOLEAN,
+            second INTEGER (0..255)
+          }"#,
+            Framework::Asnr,
+            crate
+        );
+        assert_eq!(
+            Extended {
+                base: InnerExtendedBase(3),
+                first: Some(InnerExtendedFirst(true)),
+                second: Some(InnerExtendedSecond(42)),
+            },
+            Uper::decode::<Extended>(&encoded).unwrap()
+        );
+    }
+
+    #[test]
+    fn encodes_as_decodes_sequence_with_multiple_extension_versions() {
+        asn1!(
+            r#"Versioned ::= SEQUENCE {
+            base INTEGER,
+            ...,
+            [[ v1 BOOLEAN ]],
+            [[ 2: v2 IA5String ]],
+            ...,
+            trailer BOOLEAN
+          }"#,
+            Framework::Asnr,
+            crate
+        );
+
+        let first_version = Versioned {
+            base: InnerVersionedBase(1),
+            trailer: InnerVersionedTrailer(true),
+            ext_group_1: Some(InnerVersionedExtGroup1 {
+                v1: InnerInnerVersionedExtGroup1V1(true),
+            }),
+            ext_group_2: None,
+        };
+        assert_eq!(
+            first_version,
+            Uper::decode::<Versioned>(&Uper::encode(first_version.clone()).unwrap()).unwrap()
+        );
+        let second_version = Versioned {
+            base: InnerVersionedBase(2),
+            trailer: InnerVersionedTrailer(false),
+            ext_group_1: None,
+            ext_group_2: Some(InnerVersionedExtGroup2 {
+                v2: InnerInnerVersionedExtGroup2V2("v2".into()),
+            }),
+        };
+        assert_eq!(
+            second_version,
+            Uper::decode::<Versioned>(&Uper::encode(second_version.clone()).unwrap()).unwrap()
+        );
+    }
+
+    #[test]
+    fn encodes_as_decodes_sequence_with_object_set_lookup() {
+        asn1!(
+            r#"CONTAINER ::= CLASS {
+            &id INTEGER UNIQUE,
+            &Type
+          } WITH SYNTAX { &Type IDENTIFIED BY &id }
+
+          FirstContainer ::= SEQUENCE { a INTEGER (0..255), b BOOLEAN }
+          SecondContainer ::= IA5String (SIZE(1..8))
+
+          Containers CONTAINER ::= {
+            {FirstContainer IDENTIFIED BY 1} |
+            {SecondContainer IDENTIFIED BY 2},
+            ...
+          }
+
+          WrappedContainer ::= SEQUENCE {
+            containerId CONTAINER.&id ({Containers}),
+            data CONTAINER.&Type ({Containers}{@containerId})
+          }"#,
+            Framework::Asnr,
+            crate
+        );
+
+        let first = WrappedContainer::new(
+            1,
+            Containers::FirstContainer(FirstContainer::new(42, true)),
+        );
+        assert_eq!(
+            first,
+            Uper::decode::<WrappedContainer>(&Uper::encode(first.clone()).unwrap()).unwrap()
+        );
+        let second = WrappedContainer::new(
+            2,
+            Containers::SecondContainer(SecondContainer("second".into())),
+        );
+        assert_eq!(
+            second,
+            Uper::decode::<WrappedContainer>(&Uper::encode(second.clone()).unwrap()).unwrap()
+        );
+        let unknown = WrappedContainer::new(3, Containers::UnknownClassImplementation(vec![0xAB]));
+        assert_eq!(
+            unknown,
+            Uper::decode::<WrappedContainer>(&Uper::encode(unknown.clone()).unwrap()).unwrap()
+        );
+        assert!(Uper::encode(WrappedContainer::new(
+            2,
+            Containers::FirstContainer(FirstContainer::new(42, true)),
+        ))
+        .is_err());
+    }
+
+    #[test]
+    fn encodes_as_decodes_contents() {
+        asn1!(
+            r#"Data ::= SEQUENCE { id INTEGER (0..255), flag BOOLEAN }"#,
+            Framework::Asnr,
+            crate
+        );
+        use super::{BitIn, BitOut};
+
+        let contents = <Uper as Encoder<u8, BitOut>>::encode_contents(Data::new(7, true)).unwrap();
+        assert_eq!(contents, Uper::encode(Data::new(7, true)).unwrap());
+        assert_eq!(
+            <Uper as Decoder<BitIn>>::decode_contents::<Data>(&contents).unwrap(),
+            Data::new(7, true)
+        );
+    }
+
+    #[test]
+    fn converts_object_set_members_into_object_set() {
+        asn1!(
+            r#"CONTAINER ::= CLASS {
+            &id INTEGER UNIQUE,
+            &Type
+          } WITH SYNTAX { &Type IDENTIFIED BY &id }
+
+          FirstContainer ::= SEQUENCE { a INTEGER (0..255), b BOOLEAN }
+          SecondContainer ::= IA5String (SIZE(1..8))
+
+          Containers CONTAINER ::= {
+            {FirstContainer IDENTIFIED BY 1} |
+            {SecondContainer IDENTIFIED BY 2} |
+            {SecondContainer IDENTIFIED BY 3}
+          }
+
+          WrappedContainer ::= SEQUENCE {
+            containerId CONTAINER.&id ({Containers}),
+            data CONTAINER.&Type ({Containers}{@containerId})
+          }"#,
+            Framework::Asnr,
+            crate
+        );
+
+        fn wrap<T: ContainersMember>(id: i128, member: T) -> WrappedContainer {
+            WrappedContainer::new(id, member.into())
+        }
+
+        let first = wrap(1, FirstContainer::new(42, true));
+        assert_eq!(
+            first.data,
+            Containers::FirstContainer(FirstContainer::new(42, true))
+        );
+        assert_eq!(
+            first,
+            Uper::decode::<WrappedContainer>(&Uper::encode(first.clone()).unwrap()).unwrap()
+        );
+    }
+
+    #[test]
+    fn encodes_as_decodes_distinguished_values() {
+        asn1!(
+            r#"SpeedValue ::= INTEGER { standstill(0), unavailable(16383) } (0..16383)"#,
+            Framework::Asnr,
+            crate
+        );
+
+        let decoded =
+            Uper::decode::<SpeedValue>(&Uper::encode(SpeedValue::UNAVAILABLE).unwrap()).unwrap();
+        assert_eq!(decoded, SpeedValue::UNAVAILABLE);
+        assert_eq!(decoded.0, 16383);
+        assert!(decoded.is_unavailable());
+        assert!(!decoded.is_standstill());
+        assert!(SpeedValue::STANDSTILL.is_standstill());
+    }
+
+    #[test]
+    fn encodes_as_decodes_real() {
+        asn1!(
+            r#"Real-1 ::= REAL
+            Real-2 ::= REAL (-1.0..100.0)"#,
+            Framework::Asnr,
+            crate
+        );
+
+        // encoded in the base 2 form of CER and DER, as X.691 §15 requires
+        let encoded = Uper::encode(Real1(4.56789)).unwrap();
+        assert_eq!(
+            encoded,
+            alloc::vec![0x09, 0x80, 0xCF, 0x09, 0x22, 0xC2, 0x7A, 0x63, 0x73, 0x6D]
+        );
+        assert_eq!(4.56789, Uper::decode::<Real1>(&encoded).unwrap().0);
+        assert_eq!(
+            -0.5,
+            Uper::decode::<Real2>(&Uper::encode(Real2(-0.5)).unwrap())
+                .unwrap()
+                .0
+        );
+        for special in [f64::INFINITY, f64::NEG_INFINITY, 0.0, -0.0] {
+            let decoded = Uper::decode::<Real1>(&Uper::encode(Real1(special)).unwrap())
+                .unwrap()
+                .0;
+            assert_eq!(special.to_bits(), decoded.to_bits());
+        }
+        assert!(
+            Uper::decode::<Real1>(&Uper::encode(Real1(f64::NAN)).unwrap())
+                .unwrap()
+                .0
+                .is_nan()
+        );
+        // binary form with a separately encoded exponent length
+        assert_eq!(
+            -0.75,
+            Uper::decode::<Real1>(&[0x04, 0xC3, 0x01, 0xFE, 0x03])
+                .unwrap()
+                .0
+        );
+        // decimal form in the ISO 6093 NR3 representation
+        assert_eq!(
+            4.56789,
+            Uper::decode::<Real1>(&[
+                0x0B, 0x03, b'4', b'5', b'6', b'7', b'8', b'9', b'.', b'E', b'-', b'5'
+            ])
+            .unwrap()
+            .0
+        );
+    }
+
+    #[test]
+    fn rejects_lengths_exceeding_the_configured_limit() {
+        asn1!(r#"Payload ::= OCTET STRING"#, Framework::Asnr, crate);
+
+        let config = DecoderConfig { max_bytes: 1024 };
+        // fragmented length determinant announcing 4 * 16384 octets
+        let crafted = [0b1100_0100, 0xFF, 0xFF];
+        assert!(matches!(
+            Uper::decode_with_config::<Payload>(&crafted, &config)
+                .unwrap_err()
+                .kind,
+            DecodingErrorType::SizeLimitExceeded
+        ));
+        // long-form length determinant announcing 16383 octets
+        let crafted = [0b1011_1111, 0xFF, 0xFF];
+        assert!(matches!(
+            Uper::decode_with_config::<Payload>(&crafted, &config)
+                .unwrap_err()
+                .kind,
+            DecodingErrorType::SizeLimitExceeded
+        ));
+        assert!(!matches!(
+            Uper::decode::<Payload>(&crafted).unwrap_err().kind,
+            DecodingErrorType::SizeLimitExceeded
+        ));
+        let payload = Payload(alloc::vec![1, 2, 3]);
+        assert_eq!(
+            payload,
+            Uper::decode_with_config::<Payload>(&Uper::encode(payload.clone()).unwrap(), &config)
+                .unwrap()
+        );
+    }
+
+    #[test]
+    fn encodes_into_fixed_size_array() {
+        asn1!(
+            r#"Small ::= INTEGER (0..255)
+            Reading ::= SEQUENCE { sensor INTEGER (0..15), value INTEGER (0..65535) }"#,
+            Framework::Asnr,
+            crate
+        );
+
+        assert_eq!(Uper::encode_into::<1, _>(Small(42)).unwrap(), ([42], 1));
+        assert_eq!(
+            Uper::encode_into::<4, _>(Small(42)).unwrap(),
+            ([42, 0, 0, 0], 1)
+        );
+        assert_eq!(
+            Uper::encode_into::<0, _>(Small(42)).unwrap_err().details,
+            "Buffer too small"
+        );
+
+        const SIZE: usize = Reading::ENCODED_SIZE_BITS.div_ceil(8);
+        assert_eq!(SIZE, 3);
+        let reading = Reading::new(0xA, 0x1234);
+        let (buffer, length) = Uper::encode_into::<SIZE, _>(reading.clone()).unwrap();
+        assert_eq!(buffer, [0xA1, 0x23, 0x40]);
+        assert_eq!(length, SIZE);
+        assert_eq!(Uper::decode::<Reading>(&buffer).unwrap(), reading);
+    }
+
+    #[test]
+    fn decodes_concatenated_values() {
+        asn1!(
+            r#"Message ::= SEQUENCE {
+                id INTEGER (0..7),
+                flag BOOLEAN
+            }"#,
+            Framework::Asnr,
+            crate
+        );
+
+        let first = Message::new(5, true);
+        let second = Message::new(2, false);
+        let mut buffer = Uper::encode(first.clone()).unwrap();
+        assert_eq!(buffer.len(), 1);
+        buffer.extend(Uper::encode(second.clone()).unwrap());
+
+        let (decoded, remaining) = Uper::decode_partial::<Message>(&buffer).unwrap();
+        assert_eq!(decoded, first);
+        assert_eq!(remaining, [0b0100_0000]);
+        let (decoded, remaining) = Uper::decode_partial::<Message>(remaining).unwrap();
+        assert_eq!(decoded, second);
+        assert!(remaining.is_empty());
+    }
 }
@@ -0,0 +1,204 @@
+use asnr_grammar::encoding_rules::per_visible::PerVisibleRangeConstraints;
+use bitvec::{bitvec, prelude::Msb0, view::BitView};
+
+use crate::error::EncodingError;
+
+use super::BitOut;
+
+/// Encodes a value that is subject to a PER-visible size constraint.
+/// For extensible constraints, the extension bit is written first,
+/// followed by the size length determinant and the encoded value itself.
+/// ### Params
+/// * `actual_size` - number of counted items (i.e. size) of the encoded value
+/// * `constraints` - specification of the encoded type's constraints
+/// * `to_wrap` - BitVec containing the encoded value
+/// * `output` - the output buffer that the sized value should be appended to
+/// * `needs_alignment` - whether the encoded value has to be octet-aligned, as required by the aligned variant of PER
+pub(crate) fn encode_sized(
+    actual_size: usize,
+    constraints: &PerVisibleRangeConstraints,
+    to_wrap: BitOut,
+    mut output: BitOut,
+    needs_alignment: bool,
+) -> Result<BitOut, EncodingError> {
+    if constraints.is_extensible() {
+        let _ = write_extended_bit(constraints, actual_size, &mut output)?;
+    }
+    with_size_length_determinant(actual_size, constraints, to_wrap, output, needs_alignment)
+}
+
+/// Encodes an OCTET STRING value
+/// ### Params
+/// * `constraints` - the OCTET STRING's PER-visible size constraints
+/// * `octets` - the octets to encode
+/// * `output` - the output buffer that the encoded value should be appended to
+/// * `needs_alignment` - whether the octets have to be octet-aligned, as required by the aligned variant of PER
+pub(crate) fn encode_octets(
+    constraints: &PerVisibleRangeConstraints,
+    octets: &[u8],
+    output: BitOut,
+    needs_alignment: bool,
+) -> Result<BitOut, EncodingError> {
+    encode_sized(
+        octets.len(),
+        constraints,
+        octets.view_bits::<Msb0>().to_bitvec(),
+        output,
+        needs_alignment,
+    )
+}
+
+pub(crate) fn write_extended_bit<I>(
+    constraints: &PerVisibleRangeConstraints,
+    encodable: I,
+    output: &mut BitOut,
+) -> Result<bool, EncodingError>
+where
+    I: num::Integer + num::ToPrimitive + num::FromPrimitive + Copy,
+{
+    let within_constraints = constraints.lies_within(&encodable)?;
+    if within_constraints {
+        output.push(false);
+    } else {
+        output.push(true);
+    }
+    Ok(within_constraints)
+}
+
+/// Wraps the provided buffer in a length determinant for size constraints
+/// ### Params
+/// * `actual_size` - number of counted items (i.e. size) of the encoded value. An _item_ can be an octet, a character, a member of a collection, depending on the ASN1 type that is encoded.
+/// * `constraints` - specification of the encoded type's constraints
+/// * `to_wrap` - BitVec containing the encoded value that should receive a size length determinant prefix
+/// * `output` - the output buffer that the sized value should be appended to
+/// * `needs_alignment` - whether the encoded value has to be octet-aligned, as required by the aligned variant of PER
+/// ### Reference in ASN1 Complete (Larmouth 302)
+/// >* _With no PER-visible size constraint, or a constraint that allows counts
+/// in excess of 64K, we encode a general length determinant._
+/// >* _For abstract values outside the root, a general length determinant is again used._
+/// >* _With a size constraint that gives a fixed value for the count, there
+/// is no length determinant encoding._
+/// >* _Otherwise, we encode the count exactly like an integer with the equivalent constraint_
+pub(crate) fn with_size_length_determinant(
+    actual_size: usize,
+    constraints: &PerVisibleRangeConstraints,
+    mut to_wrap: BitOut,
+    output: BitOut,
+    needs_alignment: bool,
+) -> Result<BitOut, EncodingError> {
+    if let (Some(bit_length), Some(Some(width)), true) = (
+        constraints.bit_length(),
+        constraints.range_width()?.map(|w| (w <= 65536).then(|| w)),
+        constraints.lies_within(&actual_size)?,
+    ) {
+        let mut output = encode_constrained_integer(
+            actual_size - constraints.min().unwrap_or(0),
+            bit_length,
+            output,
+        )?;
+        // fixed-size values of up to two octets are never aligned (X.691 16.9, 17.6)
+        if needs_alignment && (width > 0 || to_wrap.len() > 16) {
+            output = align_back(output);
+        }
+        output.append(&mut to_wrap);
+        Ok(output)
+    } else if needs_alignment {
+        wrap_in_length_determinant(actual_size, to_wrap, Some(0), align_back(output))
+    } else {
+        wrap_in_length_determinant(actual_size, to_wrap, Some(0), output)
+    }
+}
+
+pub(crate) fn wrap_in_length_determinant<I>(
+    length_offset: usize,
+    mut to_wrap: BitOut,
+    min: Option<I>,
+    mut output: BitOut,
+) -> Result<BitOut, EncodingError> {
+    match length_offset {
+        x if x < 128 => {
+            let mut length_det = encode_constrained_integer(x, 8, output)?;
+            length_det.append(&mut to_wrap);
+            Ok(length_det)
+        }
+        x if x < 16384 => {
+            output.append(&mut bitvec![u8, Msb0; 1, 0]);
+            let mut length_det = encode_constrained_integer(x, 14, output)?;
+            length_det.append(&mut to_wrap);
+            Ok(length_det)
+        }
+        x => {
+            let (mut fragment, fragment_size) = match x {
+                s if s < 32768 => (bitvec![u8, Msb0; 1,1,0,0,0,0,1,0], 32768),
+                s if s < 49152 => (bitvec![u8, Msb0; 1,1,0,0,0,0,1,1], 49152),
+                _ => (bitvec![u8, Msb0; 1,1,0,0,0,1,0,0], 65536),
+            };
+            fragment.extend(to_wrap[..fragment_size].iter());
+            wrap_in_length_determinant(length_offset - fragment_size, fragment, min, output)
+        }
+    }
+}
+
+pub(crate) fn encode_constrained_integer<I>(
+    integer: I,
+    bit_length: usize,
+    mut output: BitOut,
+) -> Result<BitOut, EncodingError>
+where
+    I: num::Integer + num::ToPrimitive + Copy,
+{
+    let as_u128 = integer.to_u128().ok_or(EncodingError {
+        details: "Failed to convert integer to u128!".into(),
+    })?;
+    output.extend((0..bit_length).rev().map(|n| (as_u128 >> n) & 1 != 0));
+    Ok(output)
+}
+
+pub(crate) fn align_back(mut output: BitOut) -> BitOut {
+    let missing_bits = 8 - output.len() % 8;
+    if missing_bits == 8 {
+        return output;
+    }
+    for _ in 0..missing_bits {
+        output.push(false);
+    }
+    output
+}
+
+#[cfg(test)]
+mod tests {
+    use alloc::{boxed::Box, vec};
+    use asnr_grammar::{
+        constraints::*, encoding_rules::per_visible::per_visible_range_constraints, ASN1Value,
+    };
+    use bitvec::{bitvec, prelude::Msb0};
+
+    use super::encode_octets;
+
+    #[test]
+    fn aligns_octet_string_only_if_required() {
+        let constraints = per_visible_range_constraints(
+            false,
+            &vec![Constraint::SubtypeConstraint(ElementSet {
+                set: ElementOrSetOperation::Element(SubtypeElement::SizeConstraint(Box::new(
+                    ElementOrSetOperation::Element(SubtypeElement::ValueRange {
+                        min: Some(ASN1Value::Integer(1)),
+                        max: Some(ASN1Value::Integer(8)),
+                        extensible: false,
+                    }),
+                ))),
+                extensible: false,
+            })],
+        )
+        .unwrap();
+        let octets = [0xA2, 0xB3, 0xC4];
+        let unaligned =
+            encode_octets(&constraints, &octets, bitvec![u8, Msb0; 1], false).unwrap();
+        let aligned = encode_octets(&constraints, &octets, bitvec![u8, Msb0; 1], true).unwrap();
+        // 1 preceding bit + 3 bits length determinant + 24 bits content
+        assert_eq!(unaligned.len(), 28);
+        // 4 bits of padding after the length determinant
+        assert_eq!(aligned.len(), 32);
+        assert_eq!(&aligned[8..], &unaligned[4..]);
+    }
+}